@@ -0,0 +1,52 @@
+//! Compares the throughput of concurrent `eth_blockNumber` calls over pooled HTTP/1.1 connections and over a single
+//! multiplexed HTTP/2 connection. Requires an endpoint that supports HTTP/2:
+//!
+//! ```bash
+//! ETHRS_BENCH_URL=https://... cargo bench --bench http2 --features http2-alpn
+//! ```
+use ethrs::provider::{HttpVersion, Provider};
+
+use std::env;
+use std::thread;
+use std::time::Instant;
+
+const THREADS: usize = 32;
+const REQUESTS_PER_THREAD: usize = 50;
+
+fn throughput(provider: &Provider) -> f64 {
+    // warm up the connections before measuring
+    provider.block_number().unwrap();
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for _ in 0..REQUESTS_PER_THREAD {
+                    provider.block_number().unwrap();
+                }
+            });
+        }
+    });
+    (THREADS * REQUESTS_PER_THREAD) as f64 / start.elapsed().as_secs_f64()
+}
+
+fn main() {
+    let url = match env::var("ETHRS_BENCH_URL") {
+        Ok(url) if !url.is_empty() => url,
+        _ => {
+            println!("ETHRS_BENCH_URL is not set, skipping");
+            return;
+        }
+    };
+    for (name, version) in [
+        ("HTTP/1.1 pooled", HttpVersion::Http1Only),
+        ("HTTP/2 prior knowledge", HttpVersion::Http2PriorKnowledge),
+        ("auto (ALPN)", HttpVersion::Auto),
+    ] {
+        let provider = Provider::builder(&url)
+            .http_version(version)
+            .http2_adaptive_window(true)
+            .build()
+            .unwrap();
+        println!("{name:<24} {:>10.1} req/s", throughput(&provider));
+    }
+}
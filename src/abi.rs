@@ -0,0 +1,900 @@
+//!The abi module implements the Solidity contract ABI encoding used for calldata, storage slot derivation and message hashing,
+//!and the decoding of return data into Rust values.
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Address, H256};
+use crate::utils::{event_topic, function_selector};
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+///The `Token` enum represents a single Solidity ABI value. Signed integers are expected in their two's complement `U256` form.
+///## Example
+///```rust
+///use ethrs::abi::{encode, Token};
+///use ethrs::types::U256;
+///
+///let encoded = encode(&[Token::Uint(U256::from(1)), Token::Bool(true)]);
+///assert_eq!(encoded.len(), 64);
+///assert_eq!(encoded[31], 1);
+///assert_eq!(encoded[63], 1);
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Address(Address),
+    Uint(U256),
+    Int(U256),
+    Bool(bool),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<Token>),
+    FixedArray(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+impl Token {
+    ///Returns `true` if the token is encoded in the tail section of its enclosing tuple.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+            Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+                tokens.iter().any(Token::is_dynamic)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn pad_right(data: &[u8]) -> Vec<u8> {
+    let mut padded = data.to_vec();
+    padded.resize((data.len() + 31) / 32 * 32, 0);
+    padded
+}
+
+fn encode_token(token: &Token) -> Vec<u8> {
+    match token {
+        Token::Address(address) => {
+            let mut encoded = vec![0u8; 12];
+            encoded.extend_from_slice(address.as_bytes());
+            encoded
+        }
+        Token::Uint(value) | Token::Int(value) => word(*value).to_vec(),
+        Token::Bool(value) => word(U256::from(*value as u8)).to_vec(),
+        Token::FixedBytes(data) => {
+            let mut encoded = data.clone();
+            encoded.resize(32, 0);
+            encoded
+        }
+        Token::Bytes(data) => {
+            let mut encoded = word(U256::from(data.len())).to_vec();
+            encoded.extend(pad_right(data));
+            encoded
+        }
+        Token::String(data) => encode_token(&Token::Bytes(data.as_bytes().to_vec())),
+        Token::Array(tokens) => {
+            let mut encoded = word(U256::from(tokens.len())).to_vec();
+            encoded.extend(encode(tokens));
+            encoded
+        }
+        Token::FixedArray(tokens) | Token::Tuple(tokens) => encode(tokens),
+    }
+}
+
+///The `encode()` function ABI-encodes a list of tokens as if they were the members of a tuple, which is the format
+///used for function arguments and `abi.encode()`.
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let encoded: Vec<Vec<u8>> = tokens.iter().map(encode_token).collect();
+    let head_len: usize = tokens
+        .iter()
+        .zip(encoded.iter())
+        .map(|(token, data)| if token.is_dynamic() { 32 } else { data.len() })
+        .sum();
+
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+    for (token, data) in tokens.iter().zip(encoded) {
+        if token.is_dynamic() {
+            head.extend_from_slice(&word(U256::from(head_len + tail.len())));
+            tail.extend(data);
+        } else {
+            head.extend(data);
+        }
+    }
+    head.extend(tail);
+    head
+}
+
+///The `ParamType` enum describes the Solidity type of an ABI value, as required for decoding. It is parsed from
+///Solidity type names via `FromStr`.
+///## Example
+///```rust
+///use ethrs::abi::ParamType;
+///
+///let param: ParamType = "(address,uint256)[]".parse().unwrap();
+///assert_eq!(
+///    param,
+///    ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address, ParamType::Uint(256)])))
+///);
+///assert_eq!(param.to_string(), "(address,uint256)[]");
+///```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParamType {
+    Address,
+    Uint(usize),
+    Int(usize),
+    Bool,
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    ///Returns `true` if values of this type are encoded in the tail section of their enclosing tuple.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(param, _) => param.is_dynamic(),
+            ParamType::Tuple(params) => params.iter().any(ParamType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    ///Returns `true` if the token is a valid value of this type.
+    pub fn matches(&self, token: &Token) -> bool {
+        match (self, token) {
+            (ParamType::Address, Token::Address(_))
+            | (ParamType::Uint(_), Token::Uint(_))
+            | (ParamType::Int(_), Token::Int(_))
+            | (ParamType::Bool, Token::Bool(_))
+            | (ParamType::Bytes, Token::Bytes(_))
+            | (ParamType::String, Token::String(_)) => true,
+            (ParamType::FixedBytes(size), Token::FixedBytes(bytes)) => bytes.len() == *size,
+            (ParamType::Array(param), Token::Array(tokens)) => {
+                tokens.iter().all(|token| param.matches(token))
+            }
+            (ParamType::FixedArray(param, len), Token::FixedArray(tokens)) => {
+                tokens.len() == *len && tokens.iter().all(|token| param.matches(token))
+            }
+            (ParamType::Tuple(params), Token::Tuple(tokens)) => {
+                params.len() == tokens.len()
+                    && params
+                        .iter()
+                        .zip(tokens)
+                        .all(|(param, token)| param.matches(token))
+            }
+            _ => false,
+        }
+    }
+
+    // the size of the head of a value of this type
+    fn head_len(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => 32,
+            ParamType::FixedArray(param, len) => param.head_len() * len,
+            ParamType::Tuple(params) => params.iter().map(ParamType::head_len).sum(),
+            _ => 32,
+        }
+    }
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamType::Address => write!(f, "address"),
+            ParamType::Uint(size) => write!(f, "uint{size}"),
+            ParamType::Int(size) => write!(f, "int{size}"),
+            ParamType::Bool => write!(f, "bool"),
+            ParamType::FixedBytes(size) => write!(f, "bytes{size}"),
+            ParamType::Bytes => write!(f, "bytes"),
+            ParamType::String => write!(f, "string"),
+            ParamType::Array(param) => write!(f, "{param}[]"),
+            ParamType::FixedArray(param, len) => write!(f, "{param}[{len}]"),
+            ParamType::Tuple(params) => {
+                let params: Vec<String> = params.iter().map(ParamType::to_string).collect();
+                write!(f, "({})", params.join(","))
+            }
+        }
+    }
+}
+
+fn split_params(s: &str) -> Result<Vec<&str>, Box<dyn Error>> {
+    let mut params = vec![];
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1).ok_or("Unbalanced parentheses")?,
+            ',' if depth == 0 => {
+                params.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err("Unbalanced parentheses".into());
+    }
+    if !s.is_empty() {
+        params.push(&s[start..]);
+    }
+    Ok(params)
+}
+
+impl FromStr for ParamType {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(inner) = s.strip_suffix(']') {
+            let open = inner.rfind('[').ok_or("Invalid array type")?;
+            let param = Box::new(inner[..open].parse()?);
+            return match &inner[open + 1..] {
+                "" => Ok(ParamType::Array(param)),
+                len => Ok(ParamType::FixedArray(param, len.parse()?)),
+            };
+        }
+        if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+            return Ok(ParamType::Tuple(
+                split_params(inner)?
+                    .into_iter()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?,
+            ));
+        }
+        let sized = |prefix: &str, default: usize| -> Result<Option<usize>, Box<dyn Error>> {
+            match s.strip_prefix(prefix) {
+                Some("") => Ok(Some(default)),
+                Some(size) => Ok(Some(size.parse()?)),
+                None => Ok(None),
+            }
+        };
+        match s {
+            "address" => Ok(ParamType::Address),
+            "bool" => Ok(ParamType::Bool),
+            "bytes" => Ok(ParamType::Bytes),
+            "string" => Ok(ParamType::String),
+            _ => {
+                if let Some(size) = sized("uint", 256)? {
+                    Ok(ParamType::Uint(size))
+                } else if let Some(size) = sized("int", 256)? {
+                    Ok(ParamType::Int(size))
+                } else if let Some(size) = s.strip_prefix("bytes") {
+                    Ok(ParamType::FixedBytes(size.parse()?))
+                } else {
+                    Err(format!("Unknown type {s}").into())
+                }
+            }
+        }
+    }
+}
+
+fn read_word(data: &[u8], offset: usize) -> Result<&[u8], Box<dyn Error>> {
+    match offset.checked_add(32) {
+        Some(end) if end <= data.len() => Ok(&data[offset..end]),
+        _ => Err("ABI data is too short".into()),
+    }
+}
+
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, Box<dyn Error>> {
+    let value = U256::from_big_endian(read_word(data, offset)?);
+    match value <= U256::from(data.len()) {
+        true => Ok(value.as_usize()),
+        false => Err("ABI offset or length is out of bounds".into()),
+    }
+}
+
+fn decode_param(param: &ParamType, data: &[u8], offset: usize) -> Result<Token, Box<dyn Error>> {
+    // dynamic values are stored at an offset relative to the start of the enclosing tuple
+    let (data, offset) = match param.is_dynamic() {
+        true => (&data[read_usize(data, offset)?..], 0),
+        false => (data, offset),
+    };
+    match param {
+        ParamType::Address => {
+            let word = read_word(data, offset)?;
+            if word[..12].iter().any(|byte| *byte != 0) {
+                return Err("Invalid address".into());
+            }
+            let mut address = [0u8; 20];
+            address.copy_from_slice(&word[12..]);
+            Ok(Token::Address(Address(address)))
+        }
+        ParamType::Uint(_) => Ok(Token::Uint(U256::from_big_endian(read_word(data, offset)?))),
+        ParamType::Int(_) => Ok(Token::Int(U256::from_big_endian(read_word(data, offset)?))),
+        ParamType::Bool => {
+            let word = read_word(data, offset)?;
+            match word[..31].iter().all(|byte| *byte == 0) && word[31] <= 1 {
+                true => Ok(Token::Bool(word[31] == 1)),
+                false => Err("Invalid bool".into()),
+            }
+        }
+        ParamType::FixedBytes(size) if *size <= 32 => Ok(Token::FixedBytes(
+            read_word(data, offset)?[..*size].to_vec(),
+        )),
+        ParamType::FixedBytes(_) => Err("Invalid fixed bytes size".into()),
+        ParamType::Bytes | ParamType::String => {
+            let len = read_usize(data, offset)?;
+            let bytes = match (offset + 32).checked_add(len) {
+                Some(end) if end <= data.len() => data[offset + 32..end].to_vec(),
+                _ => return Err("ABI data is too short".into()),
+            };
+            match param {
+                ParamType::String => Ok(Token::String(String::from_utf8(bytes)?)),
+                _ => Ok(Token::Bytes(bytes)),
+            }
+        }
+        ParamType::Array(param) => {
+            let len = read_usize(data, offset)?;
+            let params = vec![param.as_ref().clone(); len];
+            Ok(Token::Array(decode_params(&params, &data[offset + 32..])?))
+        }
+        ParamType::FixedArray(param, len) => {
+            let params = vec![param.as_ref().clone(); *len];
+            let data = data.get(offset..).ok_or("ABI data is too short")?;
+            Ok(Token::FixedArray(decode_params(&params, data)?))
+        }
+        ParamType::Tuple(params) => {
+            let data = data.get(offset..).ok_or("ABI data is too short")?;
+            Ok(Token::Tuple(decode_params(params, data)?))
+        }
+    }
+}
+
+fn decode_params(params: &[ParamType], data: &[u8]) -> Result<Vec<Token>, Box<dyn Error>> {
+    let mut offset = 0;
+    let mut tokens = Vec::with_capacity(params.len());
+    for param in params {
+        tokens.push(decode_param(param, data, offset)?);
+        offset += param.head_len();
+    }
+    Ok(tokens)
+}
+
+///The `decode()` function takes a list of types and ABI-encoded data, such as the return data of a call, and attempts to
+///decode them as the members of a tuple as `Ok(Vec<Token>)`. Returns an `Err()` if the data is malformed.
+///## Example
+///```rust
+///use ethrs::abi::{decode, encode, ParamType, Token};
+///use ethrs::types::U256;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let tokens = vec![Token::Uint(U256::from(1)), Token::String("ethrs".to_owned())];
+///  let decoded = decode(&[ParamType::Uint(256), ParamType::String], &encode(&tokens))?;
+///  assert_eq!(decoded, tokens);
+///  Ok(())
+///}
+///```
+pub fn decode(params: &[ParamType], data: &[u8]) -> Result<Vec<Token>, Box<dyn Error>> {
+    decode_params(params, data)
+}
+
+///The `Tokenizable` trait converts Rust values from and into a single `Token`.
+pub trait Tokenizable: Sized {
+    ///Attempts to convert a token into the value, returning an `Err()` if the token has a different type.
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>>;
+    ///Converts the value into a token.
+    fn into_token(self) -> Token;
+}
+
+///The `Detokenize` trait converts a list of decoded tokens, such as the outputs of a function, into a Rust value. It is
+///implemented for every `Tokenizable` type: a single output converts into the type directly, while several outputs
+///convert into a tuple.
+pub trait Detokenize: Sized {
+    ///Attempts to convert the tokens into the value, returning an `Err()` on type mismatches.
+    fn from_tokens(tokens: Vec<Token>) -> Result<Self, Box<dyn Error>>;
+}
+
+impl<T: Tokenizable> Detokenize for T {
+    fn from_tokens(mut tokens: Vec<Token>) -> Result<Self, Box<dyn Error>> {
+        match tokens.len() {
+            1 => T::from_token(tokens.remove(0)),
+            _ => T::from_token(Token::Tuple(tokens)),
+        }
+    }
+}
+
+fn mismatch<T>(expected: &str, token: &Token) -> Result<T, Box<dyn Error>> {
+    Err(format!("Expected {expected}, found {token:?}").into())
+}
+
+impl Tokenizable for Token {
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+        Ok(token)
+    }
+
+    fn into_token(self) -> Token {
+        self
+    }
+}
+
+impl Tokenizable for Address {
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+        match token {
+            Token::Address(address) => Ok(address),
+            token => mismatch("address", &token),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::Address(self)
+    }
+}
+
+impl Tokenizable for U256 {
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+        match token {
+            Token::Uint(value) | Token::Int(value) => Ok(value),
+            token => mismatch("integer", &token),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::Uint(self)
+    }
+}
+
+impl Tokenizable for bool {
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+        match token {
+            Token::Bool(value) => Ok(value),
+            token => mismatch("bool", &token),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::Bool(self)
+    }
+}
+
+impl Tokenizable for String {
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+        match token {
+            Token::String(value) => Ok(value),
+            token => mismatch("string", &token),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::String(self)
+    }
+}
+
+impl Tokenizable for H256 {
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+        match token {
+            Token::FixedBytes(bytes) if bytes.len() == 32 => {
+                let mut hash = [0u8; 32];
+                hash.copy_from_slice(&bytes);
+                Ok(H256(hash))
+            }
+            token => mismatch("bytes32", &token),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::FixedBytes(self.0.to_vec())
+    }
+}
+
+impl Tokenizable for crate::types::Bytes {
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+        match token {
+            Token::Bytes(bytes) | Token::FixedBytes(bytes) => Ok(crate::types::Bytes(bytes)),
+            token => mismatch("bytes", &token),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::Bytes(self.0)
+    }
+}
+
+macro_rules! impl_tokenizable_uint {
+    ($($ty:ty),*) => {
+        $(
+            impl Tokenizable for $ty {
+                fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+                    let value = U256::from_token(token)?;
+                    match value <= U256::from(<$ty>::MAX) {
+                        true => Ok(value.as_u128() as $ty),
+                        false => Err(format!("Integer {value} overflows {}", stringify!($ty)).into()),
+                    }
+                }
+
+                fn into_token(self) -> Token {
+                    Token::Uint(U256::from(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_tokenizable_uint!(u8, u16, u32, u64, u128, usize);
+
+impl<T: Tokenizable> Tokenizable for Vec<T> {
+    fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+        match token {
+            Token::Array(tokens) | Token::FixedArray(tokens) => {
+                tokens.into_iter().map(T::from_token).collect()
+            }
+            token => mismatch("array", &token),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::Array(self.into_iter().map(T::into_token).collect())
+    }
+}
+
+macro_rules! impl_tokenizable_tuple {
+    ($len:expr => $($ty:ident),+) => {
+        impl<$($ty: Tokenizable),+> Tokenizable for ($($ty,)+) {
+            fn from_token(token: Token) -> Result<Self, Box<dyn Error>> {
+                match token {
+                    Token::Tuple(tokens) if tokens.len() == $len => {
+                        let mut tokens = tokens.into_iter();
+                        Ok(($($ty::from_token(tokens.next().unwrap())?,)+))
+                    }
+                    token => mismatch(concat!("tuple of ", $len), &token),
+                }
+            }
+
+            #[allow(non_snake_case)]
+            fn into_token(self) -> Token {
+                let ($($ty,)+) = self;
+                Token::Tuple(vec![$($ty.into_token()),+])
+            }
+        }
+    };
+}
+
+impl_tokenizable_tuple!(1 => A);
+impl_tokenizable_tuple!(2 => A, B);
+impl_tokenizable_tuple!(3 => A, B, C);
+impl_tokenizable_tuple!(4 => A, B, C, D);
+impl_tokenizable_tuple!(5 => A, B, C, D, E);
+impl_tokenizable_tuple!(6 => A, B, C, D, E, F);
+impl_tokenizable_tuple!(7 => A, B, C, D, E, F, G);
+impl_tokenizable_tuple!(8 => A, B, C, D, E, F, G, H);
+
+///The `Param` struct is a function, constructor, event or error parameter of a JSON ABI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Param {
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<Param>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internal_type: Option<String>,
+}
+
+impl Param {
+    ///The `param_type()` function attempts to return the `ParamType` of the parameter as `Ok(ParamType)`, resolving
+    ///`tuple` types from their components. Returns an `Err()` on unknown types.
+    pub fn param_type(&self) -> Result<ParamType, Box<dyn Error>> {
+        match self.kind.strip_prefix("tuple") {
+            Some(suffix) => {
+                let components = self
+                    .components
+                    .iter()
+                    .map(Param::param_type)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let components: Vec<String> = components.iter().map(ParamType::to_string).collect();
+                format!("({}){suffix}", components.join(",")).parse()
+            }
+            None => self.kind.parse(),
+        }
+    }
+
+    ///Returns `true` if the parameter is an indexed event parameter.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed.unwrap_or(false)
+    }
+}
+
+fn param_types(params: &[Param]) -> Result<Vec<ParamType>, Box<dyn Error>> {
+    params.iter().map(Param::param_type).collect()
+}
+
+fn signature(name: &str, params: &[Param]) -> Result<String, Box<dyn Error>> {
+    let types: Vec<String> = param_types(params)?
+        .iter()
+        .map(ParamType::to_string)
+        .collect();
+    Ok(format!("{name}({})", types.join(",")))
+}
+
+fn encode_params(params: &[Param], tokens: &[Token]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let types = param_types(params)?;
+    if types.len() != tokens.len() {
+        return Err(format!("Expected {} arguments, found {}", types.len(), tokens.len()).into());
+    }
+    match types
+        .iter()
+        .zip(tokens)
+        .find(|(param, token)| !param.matches(token))
+    {
+        Some((param, token)) => Err(format!("Expected {param}, found {token:?}").into()),
+        None => Ok(encode(tokens)),
+    }
+}
+
+///The `Function` struct is a function of a JSON ABI.
+///## Example
+///```rust
+///use ethrs::abi::{Abi, Token};
+///use ethrs::types::U256;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let abi: Abi = serde_json::from_str(r#"[{
+///      "type": "function",
+///      "name": "balanceOf",
+///      "inputs": [{"name": "owner", "type": "address"}],
+///      "outputs": [{"name": "", "type": "uint256"}],
+///      "stateMutability": "view"
+///  }]"#)?;
+///  let balance_of = abi.function("balanceOf").unwrap();
+///  assert_eq!(balance_of.signature()?, "balanceOf(address)");
+///  assert_eq!(balance_of.selector()?, [0x70, 0xa0, 0x82, 0x31]);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Function {
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<Param>,
+    #[serde(default)]
+    pub outputs: Vec<Param>,
+    #[serde(default)]
+    pub state_mutability: String,
+}
+
+impl Function {
+    ///Returns the canonical signature of the function, e.g. `transfer(address,uint256)`.
+    pub fn signature(&self) -> Result<String, Box<dyn Error>> {
+        signature(&self.name, &self.inputs)
+    }
+
+    ///Returns the 4-byte selector of the function.
+    pub fn selector(&self) -> Result<[u8; 4], Box<dyn Error>> {
+        Ok(function_selector(&self.signature()?))
+    }
+
+    ///The `encode_input()` function takes the arguments of the function and attempts to return the calldata of a call
+    ///as `Ok(Vec<u8>)`. Returns an `Err()` if the arguments do not match the inputs of the function.
+    pub fn encode_input(&self, tokens: &[Token]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut calldata = self.selector()?.to_vec();
+        calldata.extend(encode_params(&self.inputs, tokens)?);
+        Ok(calldata)
+    }
+
+    ///The `decode_input()` function takes the calldata of a call and attempts to decode its arguments according to the
+    ///inputs of the function as `Ok(Vec<Token>)`. Returns an `Err()` if the selector does not match or the data is
+    ///malformed.
+    pub fn decode_input(&self, calldata: &[u8]) -> Result<Vec<Token>, Box<dyn Error>> {
+        if calldata.len() < 4 || calldata[..4] != self.selector()? {
+            return Err(format!("Calldata is not a call to {}", self.signature()?).into());
+        }
+        decode(&param_types(&self.inputs)?, &calldata[4..])
+    }
+
+    ///The `decode_output()` function takes the return data of a call and attempts to decode it according to the outputs
+    ///of the function as `Ok(Vec<Token>)`. Returns an `Err()` if the data is malformed.
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<Token>, Box<dyn Error>> {
+        decode(&param_types(&self.outputs)?, data)
+    }
+}
+
+///The `Constructor` struct is the constructor of a JSON ABI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Constructor {
+    #[serde(default)]
+    pub inputs: Vec<Param>,
+    #[serde(default)]
+    pub state_mutability: String,
+}
+
+impl Constructor {
+    ///The `encode_input()` function takes the bytecode of a contract and the arguments of its constructor, and attempts
+    ///to return the creation code as `Ok(Vec<u8>)`. Returns an `Err()` if the arguments do not match the inputs of the
+    ///constructor.
+    pub fn encode_input(
+        &self,
+        bytecode: &[u8],
+        tokens: &[Token],
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut code = bytecode.to_vec();
+        code.extend(encode_params(&self.inputs, tokens)?);
+        Ok(code)
+    }
+}
+
+///The `Event` struct is an event of a JSON ABI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<Param>,
+    #[serde(default)]
+    pub anonymous: bool,
+}
+
+impl Event {
+    ///Returns the canonical signature of the event, e.g. `Transfer(address,address,uint256)`.
+    pub fn signature(&self) -> Result<String, Box<dyn Error>> {
+        signature(&self.name, &self.inputs)
+    }
+
+    ///Returns the topic of the event, i.e. the hash of its signature.
+    pub fn topic(&self) -> Result<H256, Box<dyn Error>> {
+        Ok(event_topic(&self.signature()?))
+    }
+
+    ///The `decode_log()` function takes the topics and data of a log and attempts to decode the parameters of the event,
+    ///in the order of its inputs, as `Ok(Vec<Token>)`. Indexed strings, bytes, arrays and tuples are only logged as
+    ///their hash, which is returned as `Token::FixedBytes`. Returns an `Err()` if the log is not an instance of the
+    ///event.
+    pub fn decode_log(&self, topics: &[H256], data: &[u8]) -> Result<Vec<Token>, Box<dyn Error>> {
+        let topics = match self.anonymous {
+            true => topics,
+            false => match topics.split_first() {
+                Some((topic, topics)) if *topic == self.topic()? => topics,
+                _ => return Err(format!("Log is not a {} event", self.name).into()),
+            },
+        };
+        let indexed: Vec<&Param> = self
+            .inputs
+            .iter()
+            .filter(|input| input.is_indexed())
+            .collect();
+        if indexed.len() != topics.len() {
+            return Err(format!(
+                "Expected {} indexed parameters, found {}",
+                indexed.len(),
+                topics.len()
+            )
+            .into());
+        }
+        let unindexed: Vec<&Param> = self
+            .inputs
+            .iter()
+            .filter(|input| !input.is_indexed())
+            .collect();
+        let types = unindexed
+            .iter()
+            .map(|input| input.param_type())
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut values = decode(&types, data)?.into_iter();
+        let mut topics = topics.iter();
+        self.inputs
+            .iter()
+            .map(|input| match input.is_indexed() {
+                true => {
+                    let topic = topics.next().expect("topics are counted above");
+                    match input.param_type()? {
+                        kind @ (ParamType::Address
+                        | ParamType::Uint(_)
+                        | ParamType::Int(_)
+                        | ParamType::Bool
+                        | ParamType::FixedBytes(_)) => {
+                            Ok(decode(&[kind], topic.as_bytes())?.remove(0))
+                        }
+                        _ => Ok(Token::FixedBytes(topic.as_bytes().to_vec())),
+                    }
+                }
+                false => Ok(values.next().expect("values are decoded above")),
+            })
+            .collect()
+    }
+}
+
+///The `AbiError` struct is a custom error of a JSON ABI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiError {
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<Param>,
+}
+
+impl AbiError {
+    ///Returns the canonical signature of the error, e.g. `InsufficientBalance(uint256,uint256)`.
+    pub fn signature(&self) -> Result<String, Box<dyn Error>> {
+        signature(&self.name, &self.inputs)
+    }
+
+    ///Returns the 4-byte selector of the error.
+    pub fn selector(&self) -> Result<[u8; 4], Box<dyn Error>> {
+        Ok(function_selector(&self.signature()?))
+    }
+}
+
+///The `AbiItem` enum is a single entry of a JSON ABI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AbiItem {
+    Function(Function),
+    Constructor(Constructor),
+    Event(Event),
+    Error(AbiError),
+    Fallback {
+        #[serde(default, rename = "stateMutability")]
+        state_mutability: String,
+    },
+    Receive {
+        #[serde(default, rename = "stateMutability")]
+        state_mutability: String,
+    },
+}
+
+///The `Abi` struct is a contract JSON ABI, as emitted by the Solidity compiler.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Abi(pub Vec<AbiItem>);
+
+impl Abi {
+    ///Returns the constructor of the contract, if it declares one.
+    pub fn constructor(&self) -> Option<&Constructor> {
+        self.0.iter().find_map(|item| match item {
+            AbiItem::Constructor(constructor) => Some(constructor),
+            _ => None,
+        })
+    }
+
+    ///Returns an iterator over the functions of the contract.
+    pub fn functions(&self) -> impl Iterator<Item = &Function> {
+        self.0.iter().filter_map(|item| match item {
+            AbiItem::Function(function) => Some(function),
+            _ => None,
+        })
+    }
+
+    ///Returns the first function with the given name. Use `functions()` to look up overloads.
+    pub fn function(&self, name: &str) -> Option<&Function> {
+        self.functions().find(|function| function.name == name)
+    }
+
+    ///Returns an iterator over the events of the contract.
+    pub fn events(&self) -> impl Iterator<Item = &Event> {
+        self.0.iter().filter_map(|item| match item {
+            AbiItem::Event(event) => Some(event),
+            _ => None,
+        })
+    }
+
+    ///Returns the first event with the given name.
+    pub fn event(&self, name: &str) -> Option<&Event> {
+        self.events().find(|event| event.name == name)
+    }
+
+    ///Returns an iterator over the custom errors of the contract.
+    pub fn errors(&self) -> impl Iterator<Item = &AbiError> {
+        self.0.iter().filter_map(|item| match item {
+            AbiItem::Error(error) => Some(error),
+            _ => None,
+        })
+    }
+}
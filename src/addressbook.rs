@@ -0,0 +1,170 @@
+//!The addressbook module provides a registry mapping addresses to human-readable labels, with reverse lookups, so that
+//!pretty-printers and trace decoders can annotate their output.
+use serde::{Deserialize, Serialize};
+
+use crate::arbitrum::{ARB_RETRYABLE_TX, NODE_INTERFACE};
+use crate::deployer::CREATE2_DEPLOYER;
+use crate::optimism::L2_TO_L1_MESSAGE_PASSER;
+use crate::provider::{BEACON_ROOTS_ADDRESS, HISTORY_STORAGE_ADDRESS};
+use crate::types::Address;
+
+use std::collections::{BTreeMap, HashMap};
+
+///The well-known contracts bundled with `AddressBook::with_well_known()`. Every entry is deployed at the same address on
+///all chains it exists on.
+pub const WELL_KNOWN: &[(&str, &str)] = &[
+    ("0x0000000000000000000000000000000000000000", "Zero"),
+    ("0xca11bde05977b3631167028862be2a173976ca11", "Multicall3"),
+    (CREATE2_DEPLOYER, "Create2Deployer"),
+    ("0x000000000022d473030f116ddee9f6b43ac78ba3", "Permit2"),
+    (
+        "0x5ff137d4b0fdcd49dca30c7cf57e578a026d2789",
+        "EntryPointV06",
+    ),
+    (
+        "0x0000000071727de22e5e9d8baf0edac6f37da032",
+        "EntryPointV07",
+    ),
+    (BEACON_ROOTS_ADDRESS, "BeaconRoots"),
+    (HISTORY_STORAGE_ADDRESS, "HistoryStorage"),
+    (L2_TO_L1_MESSAGE_PASSER, "L2ToL1MessagePasser"),
+    (NODE_INTERFACE, "NodeInterface"),
+    (ARB_RETRYABLE_TX, "ArbRetryableTx"),
+];
+
+///The `AddressBook` struct maps addresses to labels and labels back to addresses. Labels are unique: inserting a label
+///that is already in use moves it to the new address. It serializes to (and deserializes from) a JSON object of
+///checksummed addresses to labels.
+///## Example
+///```rust
+///use ethrs::addressbook::AddressBook;
+///use ethrs::types::Address;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let mut book = AddressBook::with_well_known();
+///  let deployer: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+///  book.insert(deployer, "Deployer");
+///  assert_eq!(book.label(&deployer), Some("Deployer"));
+///  assert_eq!(book.address("Multicall3"), Some("0xcA11bde05977b3631167028862bE2a173976CA11".parse()?));
+///  assert_eq!(book.annotate(&deployer), "Deployer (0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266)");
+///  let exported = serde_json::to_string(&book)?;
+///  assert_eq!(serde_json::from_str::<AddressBook>(&exported)?, book);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<Address, String>", into = "BTreeMap<Address, String>")]
+pub struct AddressBook {
+    labels: BTreeMap<Address, String>,
+    addresses: HashMap<String, Address>,
+}
+
+impl AddressBook {
+    ///The `new()` function returns an empty `AddressBook`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///The `with_well_known()` function returns an `AddressBook` pre-populated with the `WELL_KNOWN` contracts.
+    pub fn with_well_known() -> Self {
+        WELL_KNOWN
+            .iter()
+            .map(|(address, label)| {
+                (
+                    address.parse().expect("well-known addresses are valid"),
+                    label.to_string(),
+                )
+            })
+            .collect()
+    }
+
+    ///The `insert()` function labels an address, returning the label it previously had, if any.
+    pub fn insert(&mut self, address: Address, label: impl Into<String>) -> Option<String> {
+        let label = label.into();
+        if let Some(previous) = self.addresses.insert(label.clone(), address) {
+            if previous != address {
+                self.labels.remove(&previous);
+            }
+        }
+        let replaced = self.labels.insert(address, label.clone());
+        if let Some(replaced) = &replaced {
+            if *replaced != label {
+                self.addresses.remove(replaced);
+            }
+        }
+        replaced
+    }
+
+    ///The `remove()` function removes the label of an address, returning it if the address was labelled.
+    pub fn remove(&mut self, address: &Address) -> Option<String> {
+        let label = self.labels.remove(address)?;
+        self.addresses.remove(&label);
+        Some(label)
+    }
+
+    ///The `label()` function returns the label of an address, if any.
+    pub fn label(&self, address: &Address) -> Option<&str> {
+        self.labels.get(address).map(String::as_str)
+    }
+
+    ///The `address()` function returns the address with the given label, if any.
+    pub fn address(&self, label: &str) -> Option<Address> {
+        self.addresses.get(label).copied()
+    }
+
+    ///The `annotate()` function formats an address for display, as `Label (0x...)` if it is labelled and as the
+    ///checksummed address otherwise.
+    pub fn annotate(&self, address: &Address) -> String {
+        match self.label(address) {
+            Some(label) => format!("{label} ({address})"),
+            None => address.to_string(),
+        }
+    }
+
+    ///The `extend()` function merges another `AddressBook` into this one, with its labels taking precedence.
+    pub fn extend(&mut self, other: AddressBook) {
+        for (address, label) in other.labels {
+            self.insert(address, label);
+        }
+    }
+
+    ///Returns an iterator over the labelled addresses, ordered by address.
+    pub fn iter(&self) -> impl Iterator<Item = (&Address, &str)> {
+        self.labels
+            .iter()
+            .map(|(address, label)| (address, label.as_str()))
+    }
+
+    ///Returns the number of labelled addresses.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    ///Returns `true` if no address is labelled.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+impl FromIterator<(Address, String)> for AddressBook {
+    fn from_iter<I: IntoIterator<Item = (Address, String)>>(iter: I) -> Self {
+        let mut book = AddressBook::new();
+        for (address, label) in iter {
+            book.insert(address, label);
+        }
+        book
+    }
+}
+
+impl From<BTreeMap<Address, String>> for AddressBook {
+    fn from(labels: BTreeMap<Address, String>) -> Self {
+        labels.into_iter().collect()
+    }
+}
+
+impl From<AddressBook> for BTreeMap<Address, String> {
+    fn from(book: AddressBook) -> Self {
+        book.labels
+    }
+}
@@ -0,0 +1,188 @@
+//!The approvals module enumerates the outstanding ERC-20 approvals of a wallet, from its `Approval` logs and the current
+//!allowances, and builds the calls revoking them.
+use primitive_types::U256;
+
+use crate::abi::{encode, Token};
+use crate::provider::{CallInput, Filter, Provider, TransactionInput};
+use crate::types::{Address, BlockId, Bytes, H256};
+use crate::utils::keccak256;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+///The selector of `allowance(address,address)`.
+const ALLOWANCE: [u8; 4] = [0xdd, 0x62, 0xed, 0x3e];
+///The selector of `approve(address,uint256)`.
+const APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+///The `Approval` struct is an outstanding ERC-20 approval: the token, the spender allowed to transfer it and the
+///remaining allowance, along with the block of the last `Approval` log of the pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Approval {
+    pub token: Address,
+    pub spender: Address,
+    pub allowance: U256,
+    pub last_approved_block: u64,
+}
+
+impl Approval {
+    ///Returns `true` if the allowance is the maximum `uint256`, which many dapps request as an "unlimited" approval.
+    pub fn is_unlimited(&self) -> bool {
+        self.allowance == U256::MAX
+    }
+
+    ///Returns the calldata of `approve(spender, 0)`, which revokes the approval when sent to the token by the owner.
+    pub fn revoke_calldata(&self) -> Bytes {
+        Bytes(
+            [
+                &APPROVE[..],
+                &encode(&[Token::Address(self.spender), Token::Uint(U256::zero())]),
+            ]
+            .concat(),
+        )
+    }
+
+    ///Returns a transaction from the owner to the token revoking the approval, leaving gas, fees and nonce unset.
+    pub fn revoke_transaction(&self, owner: Address) -> TransactionInput {
+        TransactionInput {
+            from: owner,
+            to: Some(self.token),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(self.revoke_calldata().to_string()),
+            nonce: None,
+            access_list: None,
+        }
+    }
+}
+
+///The `ApprovalScanner` struct finds the approvals granted by a wallet in the `Approval` logs emitted since a starting
+///block, then checks the current allowance of every token and spender pair found, as approvals may since have been
+///spent or revoked. Only ERC-20 approvals are reported: ERC-721 `Approval` logs, which index the token id, are skipped.
+///## Example
+///```rust
+///use ethrs::approvals::ApprovalScanner;
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///# let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  let owner = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+///  let head = provider.block_number()?;
+///  for approval in ApprovalScanner::new(provider).with_from_block(head - 1000).scan(owner)? {
+///      println!("{} may spend {} of {}", approval.spender, approval.allowance, approval.token);
+///  }
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct ApprovalScanner {
+    provider: Provider,
+    tokens: Vec<Address>,
+    from_block: u64,
+    batch_size: u64,
+}
+
+impl ApprovalScanner {
+    ///The `ApprovalScanner::new()` associated function takes a `Provider` and returns a scanner searching the logs of
+    ///every token from the genesis block, 10000 blocks per `eth_getLogs` request.
+    pub fn new(provider: Provider) -> ApprovalScanner {
+        ApprovalScanner {
+            provider,
+            tokens: vec![],
+            from_block: 0,
+            batch_size: 10000,
+        }
+    }
+
+    ///Restricts the scan to the given tokens.
+    pub fn with_tokens(mut self, tokens: &[Address]) -> ApprovalScanner {
+        self.tokens = tokens.to_vec();
+        self
+    }
+
+    ///Sets the first block searched for `Approval` logs, e.g. the block the wallet was first used in.
+    pub fn with_from_block(mut self, from_block: u64) -> ApprovalScanner {
+        self.from_block = from_block;
+        self
+    }
+
+    ///Sets the maximum number of blocks searched per `eth_getLogs` request.
+    pub fn with_batch_size(mut self, batch_size: u64) -> ApprovalScanner {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    ///The `scan()` function takes the owner address and attempts to return its approvals with a non-zero allowance as
+    ///`Ok(Vec<Approval>)`, ordered by token and spender. Returns an `Err()` on JSON-RPC errors.
+    pub fn scan(&self, owner: Address) -> Result<Vec<Approval>, Box<dyn Error>> {
+        let topic = H256(keccak256(b"Approval(address,address,uint256)"));
+        let head = self.provider.block_number()?;
+        let mut pairs: BTreeMap<(Address, Address), u64> = BTreeMap::new();
+        let mut next = self.from_block;
+        while next <= head {
+            let to_block = head.min(next + self.batch_size - 1);
+            let logs = self.provider.get_logs(&Filter {
+                from_block: Some(BlockId::Number(next)),
+                to_block: Some(BlockId::Number(to_block)),
+                block_hash: None,
+                address: self.tokens.clone(),
+                topics: vec![Some(vec![topic]), Some(vec![address_topic(owner)])],
+            })?;
+            for log in logs
+                .iter()
+                .filter(|log| log.topics.len() == 3 && !log.removed)
+            {
+                let mut spender = [0u8; 20];
+                spender.copy_from_slice(&log.topics[2].0[12..]);
+                pairs.insert((log.address, Address(spender)), log.block_number);
+            }
+            next = to_block + 1;
+        }
+
+        let mut approvals = vec![];
+        for ((token, spender), last_approved_block) in pairs {
+            let allowance = self.allowance(token, owner, spender, head)?;
+            if !allowance.is_zero() {
+                approvals.push(Approval {
+                    token,
+                    spender,
+                    allowance,
+                    last_approved_block,
+                });
+            }
+        }
+        Ok(approvals)
+    }
+
+    fn allowance(
+        &self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+        block: u64,
+    ) -> Result<U256, Box<dyn Error>> {
+        let data = [
+            &ALLOWANCE[..],
+            &encode(&[Token::Address(owner), Token::Address(spender)]),
+        ]
+        .concat();
+        let tx = CallInput::to(token).data(data);
+        let output: Bytes = self
+            .provider
+            .call(tx, Some(BlockId::Number(block)))?
+            .parse()?;
+        match output.0.len() >= 32 {
+            true => Ok(U256::from_big_endian(&output.0[..32])),
+            false => Err(format!("Invalid allowance returned by {token}").into()),
+        }
+    }
+}
+
+fn address_topic(address: Address) -> H256 {
+    let mut topic = [0u8; 32];
+    topic[12..].copy_from_slice(&address.0);
+    H256(topic)
+}
@@ -0,0 +1,223 @@
+//!The arbitrum module provides helpers for creating and tracking Arbitrum retryable tickets, the mechanism used to send
+//!L1→L2 messages through the canonical `Inbox`.
+use primitive_types::U256;
+use serde_json::json;
+
+use crate::abi::{encode, Token};
+use crate::provider::{CallInput, Provider};
+use crate::types::{Address, H256};
+use crate::utils::keccak256;
+
+use std::error::Error;
+
+///The address of the `NodeInterface` virtual contract, which is only reachable via `eth_call` and `eth_estimateGas`.
+pub const NODE_INTERFACE: &str = "0x00000000000000000000000000000000000000C8";
+
+///The address of the `ArbRetryableTx` precompile that emits the retryable ticket lifecycle events.
+pub const ARB_RETRYABLE_TX: &str = "0x000000000000000000000000000000000000006E";
+
+///The `RetryableTicket` struct describes an L1→L2 message, i.e. the arguments of `Inbox.createRetryableTicket()` that do
+///not depend on gas pricing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryableTicket {
+    pub from: Address,
+    pub to: Address,
+    pub l2_call_value: U256,
+    pub excess_fee_refund_address: Address,
+    pub call_value_refund_address: Address,
+    pub data: Vec<u8>,
+}
+
+///The `RetryableGasParams` struct contains the gas pricing of a retryable ticket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryableGasParams {
+    pub max_submission_cost: U256,
+    pub gas_limit: U256,
+    pub max_fee_per_gas: U256,
+}
+
+///The `RetryableStatus` enum describes the lifecycle of a retryable ticket as observed on L2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryableStatus {
+    ///The ticket creation transaction has not been executed on L2 yet.
+    NotYetCreated,
+    ///The ticket creation transaction reverted, usually because the submission cost was too low.
+    CreationFailed,
+    ///The ticket exists but its auto-redeem failed; it has to be redeemed manually before it expires.
+    FundsDeposited,
+    ///The ticket was redeemed by the given L2 transaction.
+    Redeemed(H256),
+}
+
+fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn parse_quantity(value: &str) -> Result<U256, Box<dyn Error>> {
+    match U256::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16) {
+        Ok(quantity) => Ok(quantity),
+        Err(_) => Err("Invalid quantity".into()),
+    }
+}
+
+impl RetryableTicket {
+    ///The `deposit()` function returns the L1 call value required to create the ticket, which covers the submission
+    ///cost, the L2 call value and the L2 execution gas.
+    pub fn deposit(&self, gas_params: &RetryableGasParams) -> U256 {
+        gas_params.max_submission_cost
+            + self.l2_call_value
+            + gas_params.gas_limit * gas_params.max_fee_per_gas
+    }
+
+    ///The `calldata()` function returns the ABI-encoded `Inbox.createRetryableTicket()` call for the ticket.
+    ///## Example
+    ///```rust
+    ///use ethrs::arbitrum::{RetryableGasParams, RetryableTicket};
+    ///use ethrs::types::{Address, U256};
+    ///
+    ///let ticket = RetryableTicket {
+    ///    from: Address::ZERO,
+    ///    to: Address::ZERO,
+    ///    l2_call_value: U256::zero(),
+    ///    excess_fee_refund_address: Address::ZERO,
+    ///    call_value_refund_address: Address::ZERO,
+    ///    data: vec![],
+    ///};
+    ///let gas_params = RetryableGasParams {
+    ///    max_submission_cost: U256::from(1000),
+    ///    gas_limit: U256::from(100000),
+    ///    max_fee_per_gas: U256::from(100000000),
+    ///};
+    ///assert_eq!(ticket.calldata(&gas_params)[..4], [0x67, 0x9b, 0x6d, 0xed]);
+    ///```
+    pub fn calldata(&self, gas_params: &RetryableGasParams) -> Vec<u8> {
+        let mut calldata = selector(
+            "createRetryableTicket(address,uint256,uint256,address,address,uint256,uint256,bytes)",
+        )
+        .to_vec();
+        calldata.extend(encode(&[
+            Token::Address(self.to),
+            Token::Uint(self.l2_call_value),
+            Token::Uint(gas_params.max_submission_cost),
+            Token::Address(self.excess_fee_refund_address),
+            Token::Address(self.call_value_refund_address),
+            Token::Uint(gas_params.gas_limit),
+            Token::Uint(gas_params.max_fee_per_gas),
+            Token::Bytes(self.data.clone()),
+        ]));
+        calldata
+    }
+}
+
+///The `submission_fee()` function takes an L1 provider, the `Inbox` address, the L2 calldata length and an L1 base fee,
+///and attempts to return the minimum submission cost as `Ok(U256)` via `Inbox.calculateRetryableSubmissionFee()`.
+///Returns an `Err()` on JSON-RPC errors.
+pub fn submission_fee(
+    l1_provider: &Provider,
+    inbox: Address,
+    data_length: usize,
+    l1_base_fee: U256,
+) -> Result<U256, Box<dyn Error>> {
+    let mut calldata = selector("calculateRetryableSubmissionFee(uint256,uint256)").to_vec();
+    calldata.extend(encode(&[
+        Token::Uint(U256::from(data_length)),
+        Token::Uint(l1_base_fee),
+    ]));
+    let result = l1_provider.call(
+        CallInput {
+            from: None,
+            to: inbox,
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(format!("0x{}", hex::encode(calldata))),
+        },
+        None,
+    )?;
+    parse_quantity(&result)
+}
+
+///The `estimate_gas_limit()` function takes an L2 provider, a ticket and the deposit used for the estimation, and attempts
+///to return the L2 gas limit of the ticket as `Ok(U256)` by estimating `NodeInterface.estimateRetryableTicket()`. The
+///deposit only has to cover the L2 call value and gas during estimation. Returns an `Err()` on JSON-RPC errors.
+pub fn estimate_gas_limit(
+    l2_provider: &Provider,
+    ticket: &RetryableTicket,
+    deposit: U256,
+) -> Result<U256, Box<dyn Error>> {
+    let mut calldata =
+        selector("estimateRetryableTicket(address,uint256,address,uint256,address,address,bytes)")
+            .to_vec();
+    calldata.extend(encode(&[
+        Token::Address(ticket.from),
+        Token::Uint(deposit),
+        Token::Address(ticket.to),
+        Token::Uint(ticket.l2_call_value),
+        Token::Address(ticket.excess_fee_refund_address),
+        Token::Address(ticket.call_value_refund_address),
+        Token::Bytes(ticket.data.clone()),
+    ]));
+    let gas: String = l2_provider.request(
+        "eth_estimateGas",
+        json!([{
+            "from": ticket.from,
+            "to": NODE_INTERFACE,
+            "data": format!("0x{}", hex::encode(calldata)),
+        }]),
+    )?;
+    parse_quantity(&gas)
+}
+
+///The `ticket_status()` function takes an L2 provider and a ticket id (the L2 hash of the ticket creation transaction),
+///and attempts to return the redemption status as `Ok(RetryableStatus)` from the L2 receipts. Returns an `Err()` on
+///JSON-RPC errors.
+///## Example
+///```rust,no_run
+///use ethrs::arbitrum::{ticket_status, RetryableStatus};
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://sepolia-rollup.arbitrum.io/rpc");
+///  let status = ticket_status(&provider, "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff".parse()?)?;
+///  assert_eq!(status, RetryableStatus::NotYetCreated);
+///  Ok(())
+///}
+///```
+pub fn ticket_status(
+    l2_provider: &Provider,
+    ticket_id: H256,
+) -> Result<RetryableStatus, Box<dyn Error>> {
+    let receipt = match l2_provider.get_transaction_receipt(ticket_id)? {
+        Some(receipt) => receipt,
+        None => return Ok(RetryableStatus::NotYetCreated),
+    };
+    if !receipt.status.is_success() {
+        return Ok(RetryableStatus::CreationFailed);
+    }
+
+    let arb_retryable_tx: Address = ARB_RETRYABLE_TX.parse()?;
+    let redeem_scheduled = H256(keccak256(
+        b"RedeemScheduled(bytes32,bytes32,uint64,uint64,address,uint256,uint256)",
+    ));
+    let retry_tx_hash = receipt
+        .logs
+        .iter()
+        .find(|log| {
+            log.address == arb_retryable_tx
+                && log.topics.first() == Some(&redeem_scheduled)
+                && log.topics.get(1) == Some(&ticket_id)
+        })
+        .and_then(|log| log.topics.get(2).copied());
+
+    match retry_tx_hash {
+        Some(retry_tx_hash) => match l2_provider.get_transaction_receipt(retry_tx_hash)? {
+            Some(retry_receipt) if retry_receipt.status.is_success() => {
+                Ok(RetryableStatus::Redeemed(retry_tx_hash))
+            }
+            _ => Ok(RetryableStatus::FundsDeposited),
+        },
+        None => Ok(RetryableStatus::FundsDeposited),
+    }
+}
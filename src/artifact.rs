@@ -0,0 +1,257 @@
+//!The artifact module loads the build outputs of Solidity toolchains, i.e. Foundry `out/*.json` files and Hardhat
+//!artifacts, into a common `Artifact` struct that can be fed to a `ContractFactory`.
+use serde::{Deserialize, Serialize};
+
+use crate::abi::Abi;
+use crate::types::{Address, Bytes};
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+///The `LinkOffset` struct locates a library address placeholder in unlinked bytecode, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkOffset {
+    pub start: usize,
+    pub length: usize,
+}
+
+///The link references of unlinked bytecode, keyed by source file and then by library name.
+pub type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<LinkOffset>>>;
+
+///The `Artifact` struct contains the parts of a compiled contract needed to deploy and interact with it. Bytecode is
+///kept as a `0x`-prefixed hexstring since unlinked bytecode contains library placeholders that are not valid hex.
+///## Example
+///```rust
+///use ethrs::artifact::Artifact;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let artifact = Artifact::from_json(r#"{
+///      "abi": [],
+///      "bytecode": {"object": "0x6080604052", "linkReferences": {}},
+///      "deployedBytecode": {"object": "0x6080", "linkReferences": {}}
+///  }"#)?;
+///  assert!(artifact.is_linked());
+///  assert_eq!(artifact.bytecode_bytes()?.as_ref(), &[0x60, 0x80, 0x60, 0x40, 0x52]);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Artifact {
+    pub contract_name: Option<String>,
+    pub abi: Abi,
+    pub bytecode: String,
+    pub deployed_bytecode: String,
+    pub link_references: LinkReferences,
+    pub deployed_link_references: LinkReferences,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundryArtifact {
+    abi: Abi,
+    bytecode: FoundryBytecode,
+    deployed_bytecode: FoundryBytecode,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundryBytecode {
+    object: String,
+    #[serde(default)]
+    link_references: LinkReferences,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HardhatArtifact {
+    contract_name: Option<String>,
+    abi: Abi,
+    bytecode: String,
+    deployed_bytecode: String,
+    #[serde(default)]
+    link_references: LinkReferences,
+    #[serde(default)]
+    deployed_link_references: LinkReferences,
+}
+
+fn prefixed(bytecode: String) -> String {
+    match bytecode.starts_with("0x") {
+        true => bytecode,
+        false => format!("0x{bytecode}"),
+    }
+}
+
+// Writes the address over every placeholder of the library, given as its name or fully qualified `<file>:<name>`,
+// and removes the library from the link references. Returns `true` if the library was referenced.
+fn link_bytecode(
+    bytecode: &mut String,
+    references: &mut LinkReferences,
+    library: &str,
+    address: &Address,
+) -> Result<bool, Box<dyn Error>> {
+    let replacement = hex::encode(address.as_bytes());
+    let mut linked = false;
+    for (file, libraries) in references.iter_mut() {
+        let names: Vec<String> = libraries
+            .keys()
+            .filter(|name| *name == library || format!("{file}:{name}") == library)
+            .cloned()
+            .collect();
+        for name in names {
+            for offset in libraries.remove(&name).unwrap_or_default() {
+                let start = 2 + offset.start * 2;
+                if offset.length != 20 || bytecode.get(start..start + 40).is_none() {
+                    return Err(format!("Invalid link reference for library {library}").into());
+                }
+                bytecode.replace_range(start..start + 40, &replacement);
+            }
+            linked = true;
+        }
+    }
+    references.retain(|_, libraries| !libraries.is_empty());
+    Ok(linked)
+}
+
+fn to_bytes(bytecode: &str) -> Result<Bytes, Box<dyn Error>> {
+    match bytecode.contains("__") {
+        true => Err("Bytecode has unlinked library references".into()),
+        false => Ok(bytecode.parse()?),
+    }
+}
+
+impl Artifact {
+    ///The `from_foundry_json()` function takes the contents of a Foundry `out/<File>.sol/<Contract>.json` file and
+    ///attempts to return the artifact as `Ok(Artifact)`. Returns an `Err()` if the JSON is not a Foundry artifact.
+    pub fn from_foundry_json(json: &str) -> Result<Artifact, Box<dyn Error>> {
+        let artifact: FoundryArtifact = serde_json::from_str(json)?;
+        Ok(Artifact {
+            contract_name: None,
+            abi: artifact.abi,
+            bytecode: prefixed(artifact.bytecode.object),
+            deployed_bytecode: prefixed(artifact.deployed_bytecode.object),
+            link_references: artifact.bytecode.link_references,
+            deployed_link_references: artifact.deployed_bytecode.link_references,
+        })
+    }
+
+    ///The `from_hardhat_json()` function takes the contents of a Hardhat `artifacts/<File>.sol/<Contract>.json` file
+    ///and attempts to return the artifact as `Ok(Artifact)`. Returns an `Err()` if the JSON is not a Hardhat artifact.
+    pub fn from_hardhat_json(json: &str) -> Result<Artifact, Box<dyn Error>> {
+        let artifact: HardhatArtifact = serde_json::from_str(json)?;
+        Ok(Artifact {
+            contract_name: artifact.contract_name,
+            abi: artifact.abi,
+            bytecode: prefixed(artifact.bytecode),
+            deployed_bytecode: prefixed(artifact.deployed_bytecode),
+            link_references: artifact.link_references,
+            deployed_link_references: artifact.deployed_link_references,
+        })
+    }
+
+    ///The `from_json()` function takes the contents of a Foundry or Hardhat artifact, detecting the format, and attempts
+    ///to return the artifact as `Ok(Artifact)`. Returns an `Err()` if the JSON is in neither format.
+    pub fn from_json(json: &str) -> Result<Artifact, Box<dyn Error>> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        match value["bytecode"].is_object() {
+            true => Artifact::from_foundry_json(json),
+            false => Artifact::from_hardhat_json(json),
+        }
+    }
+
+    ///The `load()` function takes the path of a Foundry or Hardhat artifact and attempts to return the artifact as
+    ///`Ok(Artifact)`, naming it after the file if the artifact does not contain a contract name. Returns an `Err()` if
+    ///the file cannot be read or is in neither format.
+    ///## Example
+    ///```rust,no_run
+    ///use ethrs::artifact::Artifact;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let artifact = Artifact::load("out/Counter.sol/Counter.json")?;
+    ///  assert_eq!(artifact.contract_name.as_deref(), Some("Counter"));
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn load(path: impl AsRef<Path>) -> Result<Artifact, Box<dyn Error>> {
+        let path = path.as_ref();
+        let mut artifact = Artifact::from_json(&fs::read_to_string(path)?)?;
+        if artifact.contract_name.is_none() {
+            artifact.contract_name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned());
+        }
+        Ok(artifact)
+    }
+
+    ///Returns the fully qualified names, i.e. `<file>:<name>`, of the libraries the creation bytecode still has to be
+    ///linked against.
+    pub fn libraries(&self) -> Vec<String> {
+        self.link_references
+            .iter()
+            .flat_map(|(file, libraries)| {
+                libraries.keys().map(move |name| format!("{file}:{name}"))
+            })
+            .collect()
+    }
+
+    ///The `link()` function takes a library, by name or fully qualified as `<file>:<name>`, and its deployed address,
+    ///and attempts to substitute the address for the library placeholders in the creation and runtime bytecode as
+    ///`Ok(())`. Returns an `Err()` if the bytecode does not reference the library.
+    ///## Example
+    ///```rust
+    ///use ethrs::artifact::Artifact;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let mut artifact = Artifact::from_json(r#"{
+    ///      "abi": [],
+    ///      "bytecode": "0x73__$1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e$__63",
+    ///      "deployedBytecode": "0x",
+    ///      "linkReferences": {"contracts/Math.sol": {"Math": [{"start": 1, "length": 20}]}}
+    ///  }"#)?;
+    ///  assert_eq!(artifact.libraries(), vec!["contracts/Math.sol:Math"]);
+    ///  artifact.link("Math", "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?)?;
+    ///  assert!(artifact.is_linked());
+    ///  assert_eq!(artifact.bytecode, "0x735fbdb2315678afecb367f032d93f642f64180aa363");
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn link(&mut self, library: &str, address: Address) -> Result<(), Box<dyn Error>> {
+        let linked = link_bytecode(
+            &mut self.bytecode,
+            &mut self.link_references,
+            library,
+            &address,
+        )?;
+        let deployed_linked = link_bytecode(
+            &mut self.deployed_bytecode,
+            &mut self.deployed_link_references,
+            library,
+            &address,
+        )?;
+        match linked || deployed_linked {
+            true => Ok(()),
+            false => Err(format!("Bytecode does not reference library {library}").into()),
+        }
+    }
+
+    ///Returns `true` if the creation bytecode has no unlinked library references.
+    pub fn is_linked(&self) -> bool {
+        self.link_references.is_empty() && !self.bytecode.contains("__")
+    }
+
+    ///The `bytecode_bytes()` function attempts to return the creation bytecode as `Ok(Bytes)`. Returns an `Err()` if the
+    ///bytecode has unlinked library references.
+    pub fn bytecode_bytes(&self) -> Result<Bytes, Box<dyn Error>> {
+        to_bytes(&self.bytecode)
+    }
+
+    ///The `deployed_bytecode_bytes()` function attempts to return the runtime bytecode as `Ok(Bytes)`. Returns an
+    ///`Err()` if the bytecode has unlinked library references.
+    pub fn deployed_bytecode_bytes(&self) -> Result<Bytes, Box<dyn Error>> {
+        to_bytes(&self.deployed_bytecode)
+    }
+}
@@ -0,0 +1,181 @@
+//!The balancer module provides the `LoadBalancedProvider`, which spreads requests over several endpoints according to
+//!their weights while capping the number of requests in flight on each of them.
+use crate::provider::{Provider, Transport};
+
+use std::error::Error;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+///The `Endpoint` struct describes an endpoint of a `LoadBalancedProvider`: its `Provider`, its share of the requests
+///relative to the other endpoints and the maximum number of requests it is sent concurrently, if any.
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    pub provider: Provider,
+    pub weight: u32,
+    pub max_concurrency: Option<usize>,
+}
+
+impl Endpoint {
+    ///The `Endpoint::new()` associated function takes a `Provider` and returns an endpoint with a weight of 1 and no
+    ///concurrency limit.
+    pub fn new(provider: Provider) -> Endpoint {
+        Endpoint {
+            provider,
+            weight: 1,
+            max_concurrency: None,
+        }
+    }
+
+    ///Sets the share of the requests sent to the endpoint relative to the other endpoints. Endpoints with a weight of 0
+    ///receive no requests.
+    pub fn with_weight(mut self, weight: u32) -> Endpoint {
+        self.weight = weight;
+        self
+    }
+
+    ///Sets the maximum number of requests sent to the endpoint concurrently, at least 1.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Endpoint {
+        self.max_concurrency = Some(max_concurrency.max(1));
+        self
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    current: Vec<i64>,
+    in_flight: Vec<usize>,
+}
+
+///The `LoadBalancedProvider` struct sends every request to one of its endpoints, picked by smooth weighted round-robin:
+///over any window of requests, each endpoint receives a share proportional to its weight, without bursts to the same
+///endpoint. Endpoints serving as many requests as their concurrency limit are skipped, and when every endpoint is at
+///its limit, requests wait for one to complete. Failed requests are not retried on another endpoint: combine with a
+///`FallbackProvider` per endpoint for that. Clones share the rotation and the requests in flight.
+///
+///A `LoadBalancedProvider` is a `Transport`: `provider()` returns a `Provider` exposing every JSON-RPC method through
+///it.
+///## Example
+///```rust
+///use ethrs::balancer::{Endpoint, LoadBalancedProvider};
+///use ethrs::provider::Provider;
+///
+///let balancer = LoadBalancedProvider::from_endpoints(vec![
+///    Endpoint::new(Provider::new("https://rpc.sepolia.org"))
+///        .with_weight(3)
+///        .with_max_concurrency(8),
+///    Endpoint::new(Provider::new("https://ethereum-sepolia-rpc.publicnode.com")).with_max_concurrency(2),
+///]);
+///let provider = balancer.provider();
+///if let Ok(block_number) = provider.block_number() {
+///    println!("latest block: {block_number}");
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct LoadBalancedProvider {
+    endpoints: Arc<Vec<Endpoint>>,
+    state: Arc<(Mutex<State>, Condvar)>,
+}
+
+impl LoadBalancedProvider {
+    ///The `LoadBalancedProvider::new()` associated function takes a list of HTTP(S) JSON-RPC URLs and returns a
+    ///`LoadBalancedProvider` sending them an equal share of the requests, without concurrency limits. Panics if the list
+    ///is empty.
+    pub fn new(urls: &[&str]) -> LoadBalancedProvider {
+        LoadBalancedProvider::from_endpoints(
+            urls.iter()
+                .map(|url| Endpoint::new(Provider::new(url)))
+                .collect(),
+        )
+    }
+
+    ///The `LoadBalancedProvider::from_endpoints()` associated function takes a list of endpoints and returns a
+    ///`LoadBalancedProvider` over them. Panics if no endpoint has a weight above 0.
+    pub fn from_endpoints(endpoints: Vec<Endpoint>) -> LoadBalancedProvider {
+        assert!(
+            endpoints.iter().any(|endpoint| endpoint.weight > 0),
+            "LoadBalancedProvider needs an endpoint with a weight above 0"
+        );
+        let state = State {
+            current: vec![0; endpoints.len()],
+            in_flight: vec![0; endpoints.len()],
+        };
+        LoadBalancedProvider {
+            endpoints: Arc::new(endpoints),
+            state: Arc::new((Mutex::new(state), Condvar::new())),
+        }
+    }
+
+    ///Returns a `Provider` sending its requests through the `LoadBalancedProvider`.
+    pub fn provider(&self) -> Provider {
+        Provider::new(self.endpoints[0].provider.url()).with_transport(self.clone())
+    }
+
+    ///Returns the endpoints, in order.
+    pub fn endpoints(&self) -> &[Endpoint] {
+        &self.endpoints
+    }
+
+    ///Returns the number of requests in flight on every endpoint, in order.
+    pub fn in_flight(&self) -> Vec<usize> {
+        self.lock().in_flight.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn acquire(&self) -> usize {
+        let mut state = self.lock();
+        loop {
+            let available: Vec<usize> = (0..self.endpoints.len())
+                .filter(|&index| {
+                    let endpoint = &self.endpoints[index];
+                    endpoint.weight > 0
+                        && endpoint
+                            .max_concurrency
+                            .map_or(true, |max| state.in_flight[index] < max)
+                })
+                .collect();
+            if !available.is_empty() {
+                // smooth weighted round-robin among the endpoints below their concurrency limit
+                let total: i64 = available
+                    .iter()
+                    .map(|&index| i64::from(self.endpoints[index].weight))
+                    .sum();
+                for &index in &available {
+                    state.current[index] += i64::from(self.endpoints[index].weight);
+                }
+                let picked = available
+                    .iter()
+                    .copied()
+                    .fold(available[0], |picked, index| {
+                        match state.current[index] > state.current[picked] {
+                            true => index,
+                            false => picked,
+                        }
+                    });
+                state.current[picked] -= total;
+                state.in_flight[picked] += 1;
+                return picked;
+            }
+            state = self
+                .state
+                .1
+                .wait(state)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+    }
+
+    fn release(&self, index: usize) {
+        self.lock().in_flight[index] -= 1;
+        self.state.1.notify_one();
+    }
+}
+
+impl Transport for LoadBalancedProvider {
+    fn send(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>> {
+        let index = self.acquire();
+        let result = self.endpoints[index].provider.send(payload);
+        self.release(index);
+        result
+    }
+}
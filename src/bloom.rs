@@ -0,0 +1,145 @@
+//!The bloom module provides the 2048-bit `Bloom` filter blocks and receipts summarize their logs with, so that blocks
+//!which cannot contain logs matching a filter can be skipped without asking the node for their logs.
+use crate::provider::Filter;
+use crate::types::{Address, FromHexError, H256};
+use crate::utils::keccak256;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+///The `Bloom` struct is the 256-byte log bloom of a block or receipt, in which the address and topics of every log are
+///set. A bloom can give false positives but no false negatives: if `contains_input()` returns `false`, no log of the
+///block has the input as its address or one of its topics.
+///## Example
+///```rust
+///use ethrs::bloom::Bloom;
+///use ethrs::provider::Filter;
+///use ethrs::utils::event_topic;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let token = "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?;
+///  let transfer = event_topic("Transfer(address,address,uint256)");
+///  let mut bloom = Bloom::default();
+///  bloom.accrue_address(&token);
+///  bloom.accrue_topic(&transfer);
+///  assert!(bloom.matches(&Filter {
+///      address: vec![token],
+///      topics: vec![Some(vec![transfer])],
+///      ..Default::default()
+///  }));
+///  assert!(!bloom.contains_topic(&event_topic("Approval(address,address,uint256)")));
+///  Ok(())
+///}
+///```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bloom(pub [u8; 256]);
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Bloom([0; 256])
+    }
+}
+
+impl Bloom {
+    ///Returns the three bits of the bloom set by the input, as byte indices and masks.
+    fn bits(input: &[u8]) -> [(usize, u8); 3] {
+        let hash = keccak256(input);
+        [0, 2, 4].map(|i| {
+            let bit = (usize::from(hash[i]) << 8 | usize::from(hash[i + 1])) & 2047;
+            (255 - bit / 8, 1 << (bit % 8))
+        })
+    }
+
+    ///Sets the bits of the input in the bloom.
+    pub fn accrue(&mut self, input: &[u8]) {
+        for (byte, mask) in Bloom::bits(input) {
+            self.0[byte] |= mask;
+        }
+    }
+
+    ///Sets the bits of a log address in the bloom.
+    pub fn accrue_address(&mut self, address: &Address) {
+        self.accrue(&address.0)
+    }
+
+    ///Sets the bits of a log topic in the bloom.
+    pub fn accrue_topic(&mut self, topic: &H256) {
+        self.accrue(&topic.0)
+    }
+
+    ///Returns whether the bits of the input are all set in the bloom, i.e. whether it may have been accrued.
+    pub fn contains_input(&self, input: &[u8]) -> bool {
+        Bloom::bits(input)
+            .iter()
+            .all(|(byte, mask)| self.0[*byte] & mask == *mask)
+    }
+
+    ///Returns whether a log of the given address may be summarized by the bloom.
+    pub fn contains_address(&self, address: &Address) -> bool {
+        self.contains_input(&address.0)
+    }
+
+    ///Returns whether a log with the given topic may be summarized by the bloom.
+    pub fn contains_topic(&self, topic: &H256) -> bool {
+        self.contains_input(&topic.0)
+    }
+
+    ///Returns whether logs matching the address and topics of a filter may be summarized by the bloom: one of the
+    ///addresses of the filter must be set, if it has any, along with one of the topics of every position it restricts.
+    ///The block range of the filter is ignored.
+    pub fn matches(&self, filter: &Filter) -> bool {
+        let address = filter.address.is_empty()
+            || filter
+                .address
+                .iter()
+                .any(|address| self.contains_address(address));
+        address
+            && filter.topics.iter().flatten().all(|topics| {
+                topics.is_empty() || topics.iter().any(|topic| self.contains_topic(topic))
+            })
+    }
+}
+
+impl fmt::Debug for Bloom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bloom({self})")
+    }
+}
+
+impl fmt::Display for Bloom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl FromStr for Bloom {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let stripped = s.strip_prefix("0x").unwrap_or(s);
+        if stripped.len() != 512 {
+            return Err(FromHexError::InvalidLength {
+                expected: 512,
+                found: stripped.len(),
+            });
+        }
+        let mut bloom = Bloom::default();
+        hex::decode_to_slice(stripped, &mut bloom.0).map_err(|_| FromHexError::InvalidCharacter)?;
+        Ok(bloom)
+    }
+}
+
+impl Serialize for Bloom {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Bloom {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
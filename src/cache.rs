@@ -0,0 +1,254 @@
+//!The cache module provides the `ResponseCache`, an in-memory cache of JSON-RPC responses a `Provider` answers
+//!repeated requests from, with a time to live per class of request.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+///The `CacheClass` enum classifies cacheable requests by how long their responses stay valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheClass {
+    ///Requests whose response never changes: `eth_chainId`, lookups by block or transaction hash, once found and mined,
+    ///and requests at a block hash or the earliest block.
+    Immutable,
+    ///Requests at an explicit block number, which only change if the block is reorganized.
+    Historical,
+    ///Requests for the latest state, e.g. `eth_blockNumber`, `eth_gasPrice` or calls at the latest block.
+    Volatile,
+}
+
+///Returns the position of the block parameter of methods taking one.
+fn block_param(method: &str) -> Option<usize> {
+    match method {
+        "eth_getBlockByNumber"
+        | "eth_getBlockReceipts"
+        | "eth_getTransactionByBlockNumberAndIndex"
+        | "eth_getBlockTransactionCountByNumber"
+        | "eth_getUncleCountByBlockNumber"
+        | "eth_getUncleByBlockNumberAndIndex" => Some(0),
+        "eth_getBalance"
+        | "eth_getCode"
+        | "eth_getTransactionCount"
+        | "eth_call"
+        | "eth_estimateGas" => Some(1),
+        "eth_getStorageAt" | "eth_getProof" => Some(2),
+        _ => None,
+    }
+}
+
+///Classifies a block parameter, `None` meaning it must not be cached.
+fn block_class(block: &serde_json::Value) -> Option<CacheClass> {
+    match block {
+        serde_json::Value::Null => Some(CacheClass::Volatile),
+        serde_json::Value::Object(block) if block.contains_key("blockHash") => {
+            Some(CacheClass::Immutable)
+        }
+        serde_json::Value::String(block) => match block.as_str() {
+            "pending" => None,
+            "earliest" => Some(CacheClass::Immutable),
+            "latest" | "safe" | "finalized" => Some(CacheClass::Volatile),
+            // block hashes are 32 bytes, block numbers are quantities
+            hash if hash.len() == 66 => Some(CacheClass::Immutable),
+            _ => Some(CacheClass::Historical),
+        },
+        _ => None,
+    }
+}
+
+///The `classify()` function takes a JSON-RPC method and its params and returns the class of the request, or `None` if
+///its response must not be cached, e.g. for transactions, filters, signing or requests for the pending block.
+pub fn classify(method: &str, params: &serde_json::Value) -> Option<CacheClass> {
+    if let Some(position) = block_param(method) {
+        return block_class(&params[position]);
+    }
+    match method {
+        "eth_chainId" | "net_version" => Some(CacheClass::Immutable),
+        "eth_getBlockByHash"
+        | "eth_getBlockTransactionCountByHash"
+        | "eth_getUncleCountByBlockHash"
+        | "eth_getUncleByBlockHashAndIndex"
+        | "eth_getTransactionByHash"
+        | "eth_getTransactionByBlockHashAndIndex"
+        | "eth_getTransactionReceipt" => Some(CacheClass::Immutable),
+        "eth_getLogs" => {
+            let filter = &params[0];
+            if filter["blockHash"].is_string() {
+                return Some(CacheClass::Immutable);
+            }
+            match (
+                block_class(&filter["fromBlock"])?,
+                block_class(&filter["toBlock"])?,
+            ) {
+                (CacheClass::Volatile, _) | (_, CacheClass::Volatile) => Some(CacheClass::Volatile),
+                _ => Some(CacheClass::Historical),
+            }
+        }
+        "eth_blockNumber"
+        | "eth_gasPrice"
+        | "eth_maxPriorityFeePerGas"
+        | "eth_blobBaseFee"
+        | "eth_feeHistory" => Some(CacheClass::Volatile),
+        _ => None,
+    }
+}
+
+///Returns `true` if the request only reads the chain, i.e. if it is cacheable or for the pending block.
+pub(crate) fn is_read(method: &str, params: &serde_json::Value) -> bool {
+    block_param(method).is_some() || classify(method, params).is_some()
+}
+
+#[derive(Debug)]
+struct Entry {
+    response: serde_json::Value,
+    expires: Option<Instant>,
+}
+
+///The `ResponseCache` struct caches successful JSON-RPC responses by method and params, once added to a `Provider`
+///with `Provider::with_cache()`. By default, immutable responses are cached forever, historical ones for a minute and
+///volatile ones for two seconds. Unknown methods are never cached, nor are `null` results and pending transactions of
+///hash lookups, which may not be known yet. Clones share the cached responses.
+///## Example
+///```rust
+///use ethrs::cache::{CacheClass, ResponseCache};
+///use ethrs::provider::Provider;
+///use std::time::Duration;
+///
+///let cache = ResponseCache::new()
+///    .with_ttl(CacheClass::Historical, None)
+///    .with_ttl(CacheClass::Volatile, Some(Duration::from_secs(12)));
+///let provider = Provider::new("https://rpc.sepolia.org").with_cache(cache);
+///```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    ttls: HashMap<CacheClass, Option<Duration>>,
+    max_entries: usize,
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        ResponseCache {
+            ttls: HashMap::from([
+                (CacheClass::Immutable, None),
+                (CacheClass::Historical, Some(Duration::from_secs(60))),
+                (CacheClass::Volatile, Some(Duration::from_secs(2))),
+            ]),
+            max_entries: 10000,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl ResponseCache {
+    ///The `ResponseCache::new()` associated function returns an empty cache with the default time to live of every
+    ///class, holding up to 10000 responses.
+    pub fn new() -> ResponseCache {
+        ResponseCache::default()
+    }
+
+    ///Sets how long the responses of a class are cached, `None` meaning forever. A zero duration disables caching for
+    ///the class.
+    pub fn with_ttl(mut self, class: CacheClass, ttl: Option<Duration>) -> ResponseCache {
+        self.ttls.insert(class, ttl);
+        self
+    }
+
+    ///Sets the maximum number of cached responses. Once reached, expired responses are evicted first, then the ones
+    ///expiring soonest.
+    pub fn with_max_entries(mut self, max_entries: usize) -> ResponseCache {
+        self.max_entries = max_entries;
+        self
+    }
+
+    ///Returns the number of cached responses, expired ones included until they are evicted.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    ///Returns `true` if no response is cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    ///Evicts every cached response.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn key(method: &str, params: &serde_json::Value) -> String {
+        format!("{method}{params}")
+    }
+
+    fn ttl(&self, method: &str, params: &serde_json::Value) -> Option<Option<Duration>> {
+        let ttl = *self.ttls.get(&classify(method, params)?)?;
+        match ttl {
+            Some(ttl) if ttl.is_zero() => None,
+            ttl => Some(ttl),
+        }
+    }
+
+    ///Returns the cached response to a request, if it has not expired.
+    pub(crate) fn get(
+        &self,
+        method: &str,
+        params: &serde_json::Value,
+    ) -> Option<serde_json::Value> {
+        self.ttl(method, params)?;
+        let key = ResponseCache::key(method, params);
+        let mut entries = self.lock();
+        let entry = entries.get(&key)?;
+        if entry
+            .expires
+            .map_or(false, |expires| expires <= Instant::now())
+        {
+            entries.remove(&key);
+            return None;
+        }
+        Some(entry.response.clone())
+    }
+
+    ///Caches the response to a request if it is cacheable.
+    pub(crate) fn insert(
+        &self,
+        method: &str,
+        params: &serde_json::Value,
+        response: &serde_json::Value,
+    ) {
+        let Some(ttl) = self.ttl(method, params) else {
+            return;
+        };
+        let result = &response["result"];
+        // unknown blocks and transactions may show up later, and pending transactions get mined
+        if !response["error"].is_null()
+            || result.is_null()
+            || (method.starts_with("eth_getTransactionBy") && result["blockHash"].is_null())
+            || self.max_entries == 0
+        {
+            return;
+        }
+        let now = Instant::now();
+        let mut entries = self.lock();
+        if entries.len() >= self.max_entries {
+            entries.retain(|_, entry| entry.expires.map_or(true, |expires| expires > now));
+        }
+        if entries.len() >= self.max_entries {
+            let soonest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires.map_or((1, now), |expires| (0, expires)))
+                .map(|(key, _)| key.clone());
+            if let Some(soonest) = soonest {
+                entries.remove(&soonest);
+            }
+        }
+        entries.insert(
+            ResponseCache::key(method, params),
+            Entry {
+                response: response.clone(),
+                expires: ttl.and_then(|ttl| now.checked_add(ttl)),
+            },
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
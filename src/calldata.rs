@@ -0,0 +1,150 @@
+//!The calldata module provides the `FunctionRegistry`, which decodes transaction calldata against every function ABI
+//!registered with it, including the calls batched by multicall-style functions, e.g. for human-readable transaction
+//!displays.
+use crate::abi::{Abi, Function, Token};
+
+use std::collections::HashMap;
+use std::error::Error;
+
+///The `DecodedCall` struct is calldata decoded as a call to one of the registered functions, with its arguments in the
+///order of the function inputs. Calls nested in `bytes` arguments, e.g. the calls of `multicall(bytes[])` or
+///`aggregate3((address,bool,bytes)[])`, are decoded into `calls` when they match a registered function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCall {
+    pub name: String,
+    pub signature: String,
+    pub params: Vec<(String, Token)>,
+    pub calls: Vec<DecodedCall>,
+}
+
+impl DecodedCall {
+    ///Returns the value of the named argument, if the function has it.
+    pub fn param(&self, name: &str) -> Option<&Token> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, token)| token)
+    }
+}
+
+///The `FunctionRegistry` struct holds function ABIs by selector. Functions with colliding selectors can both be
+///registered: the first one the calldata decodes with is used.
+///## Example
+///```rust
+///use ethrs::abi::{Abi, Token};
+///use ethrs::calldata::FunctionRegistry;
+///use ethrs::types::Bytes;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let abi: Abi = serde_json::from_str(r#"[{
+///      "type": "function",
+///      "name": "transfer",
+///      "inputs": [{"name": "to", "type": "address"}, {"name": "value", "type": "uint256"}],
+///      "outputs": [{"name": "", "type": "bool"}],
+///      "stateMutability": "nonpayable"
+///  }, {
+///      "type": "function",
+///      "name": "multicall",
+///      "inputs": [{"name": "data", "type": "bytes[]"}],
+///      "outputs": [{"name": "results", "type": "bytes[]"}],
+///      "stateMutability": "payable"
+///  }]"#)?;
+///  let mut registry = FunctionRegistry::new();
+///  registry.register_abi(&abi)?;
+///
+///  let transfer = abi.function("transfer").unwrap().encode_input(&[
+///      Token::Address("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?),
+///      Token::Uint(42.into()),
+///  ])?;
+///  let input = abi.function("multicall").unwrap().encode_input(&[Token::Array(vec![Token::Bytes(transfer)])])?;
+///  let tx_input = Bytes(input).to_string(); // e.g. the `input` of a transaction
+///
+///  let call = registry.decode_input(&tx_input.parse::<Bytes>()?).unwrap();
+///  assert_eq!(call.name, "multicall");
+///  assert_eq!(call.calls[0].signature, "transfer(address,uint256)");
+///  assert_eq!(call.calls[0].param("value"), Some(&Token::Uint(42.into())));
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default)]
+pub struct FunctionRegistry {
+    functions: HashMap<[u8; 4], Vec<Function>>,
+}
+
+impl FunctionRegistry {
+    ///The `new()` function returns an empty `FunctionRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///The `register()` function attempts to register a function as `Ok(())`, ignoring functions that are already
+    ///registered. Returns an `Err()` if the function has unknown parameter types.
+    pub fn register(&mut self, function: Function) -> Result<(), Box<dyn Error>> {
+        let functions = self.functions.entry(function.selector()?).or_default();
+        if !functions.contains(&function) {
+            functions.push(function);
+        }
+        Ok(())
+    }
+
+    ///The `register_abi()` function attempts to register every function of a JSON ABI as `Ok(())`. Returns an `Err()`
+    ///if a function has unknown parameter types.
+    pub fn register_abi(&mut self, abi: &Abi) -> Result<(), Box<dyn Error>> {
+        for function in abi.functions() {
+            self.register(function.clone())?;
+        }
+        Ok(())
+    }
+
+    ///Returns the functions registered under a selector.
+    pub fn functions(&self, selector: &[u8; 4]) -> &[Function] {
+        self.functions.get(selector).map_or(&[], Vec::as_slice)
+    }
+
+    ///The `decode_input()` function takes the calldata of a transaction or call and returns it decoded as a call to the
+    ///first registered function it matches, or `None` if it matches none.
+    pub fn decode_input(&self, input: &[u8]) -> Option<DecodedCall> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        self.functions(&selector).iter().find_map(|function| {
+            let tokens = function.decode_input(input).ok()?;
+            let mut calls = vec![];
+            for token in &tokens {
+                self.decode_nested(token, &mut calls);
+            }
+            Some(DecodedCall {
+                name: function.name.clone(),
+                signature: function.signature().ok()?,
+                params: function
+                    .inputs
+                    .iter()
+                    .map(|input| input.name.clone())
+                    .zip(tokens)
+                    .collect(),
+                calls,
+            })
+        })
+    }
+
+    fn decode_nested(&self, token: &Token, calls: &mut Vec<DecodedCall>) {
+        match token {
+            Token::Bytes(data) => calls.extend(self.decode_input(data)),
+            Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+                for token in tokens {
+                    self.decode_nested(token, calls);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ///Returns the number of registered functions.
+    pub fn len(&self) -> usize {
+        self.functions.values().map(Vec::len).sum()
+    }
+
+    ///Returns `true` if no function is registered.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
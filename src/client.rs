@@ -0,0 +1,597 @@
+//!The client module contains the `JsonRpcClient` trait, which abstracts the JSON-RPC methods of a `Provider` so that
+//!code built on them can be generic, e.g. to run against a mock in tests.
+use crate::abi::{decode, Detokenize, ParamType};
+use crate::provider::{
+    AccessListResult, AccountProof, Block, BlockWithTx, CallInput, Filter, FilterChanges,
+    FilterHandle, Log, Provider, Proxy, SimulatedBlock, SimulationInput, StateOverride,
+    Transaction, TransactionInput, TransactionReceipt,
+};
+use crate::types::{Address, BlockId, Bytes, Wei, H256};
+
+use std::error::Error;
+use std::time::Duration;
+
+///The `JsonRpcClient` trait exposes the JSON-RPC methods of a `Provider`, which implements it by calling its own
+///methods of the same name. Code generic over `JsonRpcClient` can run against any other implementation, e.g. one
+///serving canned responses in tests.
+///## Example
+///```rust
+///use ethrs::client::JsonRpcClient;
+///use ethrs::provider::Provider;
+///use ethrs::types::Address;
+///use std::error::Error;
+///
+///fn is_contract(client: &impl JsonRpcClient, address: Address) -> Result<bool, Box<dyn Error>> {
+///  Ok(client.get_code(address, None)? != "0x")
+///}
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///# let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  assert!(!is_contract(&provider, "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?)?);
+///  Ok(())
+///}
+///```
+pub trait JsonRpcClient {
+    ///See `Provider::block_number()`.
+    fn block_number(&self) -> Result<u64, Box<dyn Error>>;
+
+    ///See `Provider::gas_price()`.
+    fn gas_price(&self) -> Result<u128, Box<dyn Error>>;
+
+    ///See `Provider::gas_price_wei()`.
+    fn gas_price_wei(&self) -> Result<Wei, Box<dyn Error>>;
+
+    ///See `Provider::chain_id()`.
+    fn chain_id(&self) -> Result<u64, Box<dyn Error>>;
+
+    ///See `Provider::net_version()`.
+    fn net_version(&self) -> Result<String, Box<dyn Error>>;
+
+    ///See `Provider::net_peer_count()`.
+    fn net_peer_count(&self) -> Result<u128, Box<dyn Error>>;
+
+    ///See `Provider::client_version()`.
+    fn client_version(&self) -> Result<String, Box<dyn Error>>;
+
+    ///See `Provider::web3_sha3()`.
+    fn web3_sha3(&self, data: &[u8]) -> Result<H256, Box<dyn Error>>;
+
+    ///See `Provider::get_balance()`.
+    fn get_balance(&self, address: Address, block: Option<BlockId>)
+        -> Result<u128, Box<dyn Error>>;
+
+    ///See `Provider::get_balance_wei()`.
+    fn get_balance_wei(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<Wei, Box<dyn Error>>;
+
+    ///See `Provider::get_storage_at()`.
+    fn get_storage_at(
+        &self,
+        address: Address,
+        slot: &str,
+        block: Option<BlockId>,
+    ) -> Result<String, Box<dyn Error>>;
+
+    ///See `Provider::get_code()`.
+    fn get_code(&self, address: Address, block: Option<BlockId>) -> Result<String, Box<dyn Error>>;
+
+    ///See `Provider::get_transaction_count()`.
+    fn get_transaction_count(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<u128, Box<dyn Error>>;
+
+    ///See `Provider::get_proof()`.
+    fn get_proof(
+        &self,
+        address: Address,
+        storage_keys: &[H256],
+        block: Option<BlockId>,
+    ) -> Result<AccountProof, Box<dyn Error>>;
+
+    ///See `Provider::get_block_transaction_count_by_hash()`.
+    fn get_block_transaction_count_by_hash(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<u128>, Box<dyn Error>>;
+
+    ///See `Provider::get_block_by_hash()`.
+    fn get_block_by_hash(&self, block_hash: H256) -> Result<Option<Block>, Box<dyn Error>>;
+
+    ///See `Provider::get_block_by_hash_with_tx()`.
+    fn get_block_by_hash_with_tx(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<BlockWithTx>, Box<dyn Error>>;
+
+    ///See `Provider::get_block_by_number()`.
+    fn get_block_by_number(&self, block: Option<BlockId>) -> Result<Option<Block>, Box<dyn Error>>;
+
+    ///See `Provider::get_block_by_number_with_tx()`.
+    fn get_block_by_number_with_tx(
+        &self,
+        block: Option<BlockId>,
+    ) -> Result<Option<BlockWithTx>, Box<dyn Error>>;
+
+    ///See `Provider::get_uncle_by_block_hash_and_index()`.
+    fn get_uncle_by_block_hash_and_index(
+        &self,
+        block_hash: H256,
+        idx: u64,
+    ) -> Result<Option<Block>, Box<dyn Error>>;
+
+    ///See `Provider::get_uncle_by_block_number_and_index()`.
+    fn get_uncle_by_block_number_and_index(
+        &self,
+        block: Option<BlockId>,
+        idx: u64,
+    ) -> Result<Option<Block>, Box<dyn Error>>;
+
+    ///See `Provider::get_uncle_count_by_block_hash()`.
+    fn get_uncle_count_by_block_hash(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<u128>, Box<dyn Error>>;
+
+    ///See `Provider::get_uncle_count_by_block_number()`.
+    fn get_uncle_count_by_block_number(
+        &self,
+        block: Option<BlockId>,
+    ) -> Result<Option<u128>, Box<dyn Error>>;
+
+    ///See `Provider::get_transaction_by_hash()`.
+    fn get_transaction_by_hash(&self, txhash: H256) -> Result<Option<Transaction>, Box<dyn Error>>;
+
+    ///See `Provider::get_transaction_by_block_hash_and_index()`.
+    fn get_transaction_by_block_hash_and_index(
+        &self,
+        block_hash: H256,
+        idx: u64,
+    ) -> Result<Option<Transaction>, Box<dyn Error>>;
+
+    ///See `Provider::get_transaction_by_block_number_and_index()`.
+    fn get_transaction_by_block_number_and_index(
+        &self,
+        block_number: u64,
+        idx: u64,
+    ) -> Result<Option<Transaction>, Box<dyn Error>>;
+
+    ///See `Provider::get_transaction_receipt()`.
+    fn get_transaction_receipt(
+        &self,
+        txhash: H256,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>>;
+
+    ///See `Provider::get_block_receipts()`.
+    fn get_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> Result<Option<Vec<TransactionReceipt>>, Box<dyn Error>>;
+
+    ///See `Provider::wait_for_transaction()`.
+    fn wait_for_transaction(
+        &self,
+        txhash: H256,
+        confirmations: u64,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>>;
+
+    ///See `Provider::beacon_root_at()`.
+    fn beacon_root_at(
+        &self,
+        timestamp: u64,
+        block: Option<BlockId>,
+    ) -> Result<Option<H256>, Box<dyn Error>>;
+
+    ///See `Provider::block_hash_at()`.
+    fn block_hash_at(&self, block_number: u64) -> Result<Option<H256>, Box<dyn Error>>;
+
+    ///See `Provider::supports_interface()`.
+    fn supports_interface(
+        &self,
+        address: Address,
+        interface_id: [u8; 4],
+    ) -> Result<bool, Box<dyn Error>>;
+
+    ///See `Provider::resolve_proxy()`.
+    fn resolve_proxy(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<Option<Proxy>, Box<dyn Error>>;
+
+    ///See `Provider::get_logs()`.
+    fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, Box<dyn Error>>;
+
+    ///See `Provider::get_logs_paginated()`.
+    fn get_logs_paginated(
+        &self,
+        filter: &Filter,
+        chunk_size: u64,
+    ) -> Result<Vec<Log>, Box<dyn Error>>;
+
+    ///See `Provider::new_filter()`.
+    fn new_filter(&self, filter: &Filter) -> Result<FilterHandle, Box<dyn Error>>;
+
+    ///See `Provider::new_block_filter()`.
+    fn new_block_filter(&self) -> Result<FilterHandle, Box<dyn Error>>;
+
+    ///See `Provider::new_pending_transaction_filter()`.
+    fn new_pending_transaction_filter(&self) -> Result<FilterHandle, Box<dyn Error>>;
+
+    ///See `Provider::get_filter_changes()`.
+    fn get_filter_changes(&self, filter: &FilterHandle) -> Result<FilterChanges, Box<dyn Error>>;
+
+    ///See `Provider::uninstall_filter()`.
+    fn uninstall_filter(&self, filter: &FilterHandle) -> Result<bool, Box<dyn Error>>;
+
+    ///See `Provider::send_transaction()`.
+    fn send_transaction(&self, tx: TransactionInput) -> Result<H256, Box<dyn Error>>;
+
+    ///See `Provider::send_raw_transaction()`.
+    fn send_raw_transaction(&self, raw: &[u8]) -> Result<H256, Box<dyn Error>>;
+
+    ///See `Provider::sign_typed_data()`.
+    fn sign_typed_data(
+        &self,
+        address: Address,
+        typed_data: &serde_json::Value,
+    ) -> Result<Bytes, Box<dyn Error>>;
+
+    ///See `Provider::call()`.
+    fn call(&self, tx: CallInput, block: Option<BlockId>) -> Result<String, Box<dyn Error>>;
+
+    ///See `Provider::call_with_overrides()`.
+    fn call_with_overrides(
+        &self,
+        tx: CallInput,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<String, Box<dyn Error>>;
+
+    ///See `Provider::estimate_gas()`.
+    fn estimate_gas(&self, tx: CallInput, block: Option<BlockId>) -> Result<u128, Box<dyn Error>>;
+
+    ///See `Provider::estimate_gas_with_overrides()`.
+    fn estimate_gas_with_overrides(
+        &self,
+        tx: CallInput,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<u128, Box<dyn Error>>;
+
+    ///See `Provider::simulate()`.
+    fn simulate(
+        &self,
+        input: &SimulationInput,
+        block: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Box<dyn Error>>;
+
+    ///See `Provider::create_access_list()`.
+    fn create_access_list(
+        &self,
+        tx: CallInput,
+        block: Option<BlockId>,
+    ) -> Result<AccessListResult, Box<dyn Error>>;
+
+    ///See `Provider::call_decoded()`.
+    fn call_decoded<T: Detokenize>(
+        &self,
+        tx: CallInput,
+        output_types: &[ParamType],
+        block: Option<BlockId>,
+    ) -> Result<T, Box<dyn Error>>
+    where
+        Self: Sized,
+    {
+        let data: Bytes = self.call(tx, block)?.parse()?;
+        T::from_tokens(decode(output_types, &data)?)
+    }
+}
+
+impl JsonRpcClient for Provider {
+    fn block_number(&self) -> Result<u64, Box<dyn Error>> {
+        Provider::block_number(self)
+    }
+
+    fn gas_price(&self) -> Result<u128, Box<dyn Error>> {
+        Provider::gas_price(self)
+    }
+
+    fn gas_price_wei(&self) -> Result<Wei, Box<dyn Error>> {
+        Provider::gas_price_wei(self)
+    }
+
+    fn chain_id(&self) -> Result<u64, Box<dyn Error>> {
+        Provider::chain_id(self)
+    }
+
+    fn net_version(&self) -> Result<String, Box<dyn Error>> {
+        Provider::net_version(self)
+    }
+
+    fn net_peer_count(&self) -> Result<u128, Box<dyn Error>> {
+        Provider::net_peer_count(self)
+    }
+
+    fn client_version(&self) -> Result<String, Box<dyn Error>> {
+        Provider::client_version(self)
+    }
+
+    fn web3_sha3(&self, data: &[u8]) -> Result<H256, Box<dyn Error>> {
+        Provider::web3_sha3(self, data)
+    }
+
+    fn get_balance(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<u128, Box<dyn Error>> {
+        Provider::get_balance(self, address, block)
+    }
+
+    fn get_balance_wei(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<Wei, Box<dyn Error>> {
+        Provider::get_balance_wei(self, address, block)
+    }
+
+    fn get_storage_at(
+        &self,
+        address: Address,
+        slot: &str,
+        block: Option<BlockId>,
+    ) -> Result<String, Box<dyn Error>> {
+        Provider::get_storage_at(self, address, slot, block)
+    }
+
+    fn get_code(&self, address: Address, block: Option<BlockId>) -> Result<String, Box<dyn Error>> {
+        Provider::get_code(self, address, block)
+    }
+
+    fn get_transaction_count(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<u128, Box<dyn Error>> {
+        Provider::get_transaction_count(self, address, block)
+    }
+
+    fn get_proof(
+        &self,
+        address: Address,
+        storage_keys: &[H256],
+        block: Option<BlockId>,
+    ) -> Result<AccountProof, Box<dyn Error>> {
+        Provider::get_proof(self, address, storage_keys, block)
+    }
+
+    fn get_block_transaction_count_by_hash(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<u128>, Box<dyn Error>> {
+        Provider::get_block_transaction_count_by_hash(self, block_hash)
+    }
+
+    fn get_block_by_hash(&self, block_hash: H256) -> Result<Option<Block>, Box<dyn Error>> {
+        Provider::get_block_by_hash(self, block_hash)
+    }
+
+    fn get_block_by_hash_with_tx(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<BlockWithTx>, Box<dyn Error>> {
+        Provider::get_block_by_hash_with_tx(self, block_hash)
+    }
+
+    fn get_block_by_number(&self, block: Option<BlockId>) -> Result<Option<Block>, Box<dyn Error>> {
+        Provider::get_block_by_number(self, block)
+    }
+
+    fn get_block_by_number_with_tx(
+        &self,
+        block: Option<BlockId>,
+    ) -> Result<Option<BlockWithTx>, Box<dyn Error>> {
+        Provider::get_block_by_number_with_tx(self, block)
+    }
+
+    fn get_uncle_by_block_hash_and_index(
+        &self,
+        block_hash: H256,
+        idx: u64,
+    ) -> Result<Option<Block>, Box<dyn Error>> {
+        Provider::get_uncle_by_block_hash_and_index(self, block_hash, idx)
+    }
+
+    fn get_uncle_by_block_number_and_index(
+        &self,
+        block: Option<BlockId>,
+        idx: u64,
+    ) -> Result<Option<Block>, Box<dyn Error>> {
+        Provider::get_uncle_by_block_number_and_index(self, block, idx)
+    }
+
+    fn get_uncle_count_by_block_hash(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<u128>, Box<dyn Error>> {
+        Provider::get_uncle_count_by_block_hash(self, block_hash)
+    }
+
+    fn get_uncle_count_by_block_number(
+        &self,
+        block: Option<BlockId>,
+    ) -> Result<Option<u128>, Box<dyn Error>> {
+        Provider::get_uncle_count_by_block_number(self, block)
+    }
+
+    fn get_transaction_by_hash(&self, txhash: H256) -> Result<Option<Transaction>, Box<dyn Error>> {
+        Provider::get_transaction_by_hash(self, txhash)
+    }
+
+    fn get_transaction_by_block_hash_and_index(
+        &self,
+        block_hash: H256,
+        idx: u64,
+    ) -> Result<Option<Transaction>, Box<dyn Error>> {
+        Provider::get_transaction_by_block_hash_and_index(self, block_hash, idx)
+    }
+
+    fn get_transaction_by_block_number_and_index(
+        &self,
+        block_number: u64,
+        idx: u64,
+    ) -> Result<Option<Transaction>, Box<dyn Error>> {
+        Provider::get_transaction_by_block_number_and_index(self, block_number, idx)
+    }
+
+    fn get_transaction_receipt(
+        &self,
+        txhash: H256,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>> {
+        Provider::get_transaction_receipt(self, txhash)
+    }
+
+    fn get_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> Result<Option<Vec<TransactionReceipt>>, Box<dyn Error>> {
+        Provider::get_block_receipts(self, block)
+    }
+
+    fn wait_for_transaction(
+        &self,
+        txhash: H256,
+        confirmations: u64,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>> {
+        Provider::wait_for_transaction(self, txhash, confirmations, timeout, poll_interval)
+    }
+
+    fn beacon_root_at(
+        &self,
+        timestamp: u64,
+        block: Option<BlockId>,
+    ) -> Result<Option<H256>, Box<dyn Error>> {
+        Provider::beacon_root_at(self, timestamp, block)
+    }
+
+    fn block_hash_at(&self, block_number: u64) -> Result<Option<H256>, Box<dyn Error>> {
+        Provider::block_hash_at(self, block_number)
+    }
+
+    fn supports_interface(
+        &self,
+        address: Address,
+        interface_id: [u8; 4],
+    ) -> Result<bool, Box<dyn Error>> {
+        Provider::supports_interface(self, address, interface_id)
+    }
+
+    fn resolve_proxy(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<Option<Proxy>, Box<dyn Error>> {
+        Provider::resolve_proxy(self, address, block)
+    }
+
+    fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, Box<dyn Error>> {
+        Provider::get_logs(self, filter)
+    }
+
+    fn get_logs_paginated(
+        &self,
+        filter: &Filter,
+        chunk_size: u64,
+    ) -> Result<Vec<Log>, Box<dyn Error>> {
+        Provider::get_logs_paginated(self, filter, chunk_size)
+    }
+
+    fn new_filter(&self, filter: &Filter) -> Result<FilterHandle, Box<dyn Error>> {
+        Provider::new_filter(self, filter)
+    }
+
+    fn new_block_filter(&self) -> Result<FilterHandle, Box<dyn Error>> {
+        Provider::new_block_filter(self)
+    }
+
+    fn new_pending_transaction_filter(&self) -> Result<FilterHandle, Box<dyn Error>> {
+        Provider::new_pending_transaction_filter(self)
+    }
+
+    fn get_filter_changes(&self, filter: &FilterHandle) -> Result<FilterChanges, Box<dyn Error>> {
+        Provider::get_filter_changes(self, filter)
+    }
+
+    fn uninstall_filter(&self, filter: &FilterHandle) -> Result<bool, Box<dyn Error>> {
+        Provider::uninstall_filter(self, filter)
+    }
+
+    fn send_transaction(&self, tx: TransactionInput) -> Result<H256, Box<dyn Error>> {
+        Provider::send_transaction(self, tx)
+    }
+
+    fn send_raw_transaction(&self, raw: &[u8]) -> Result<H256, Box<dyn Error>> {
+        Provider::send_raw_transaction(self, raw)
+    }
+
+    fn sign_typed_data(
+        &self,
+        address: Address,
+        typed_data: &serde_json::Value,
+    ) -> Result<Bytes, Box<dyn Error>> {
+        Provider::sign_typed_data(self, address, typed_data)
+    }
+
+    fn call(&self, tx: CallInput, block: Option<BlockId>) -> Result<String, Box<dyn Error>> {
+        Provider::call(self, tx, block)
+    }
+
+    fn call_with_overrides(
+        &self,
+        tx: CallInput,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<String, Box<dyn Error>> {
+        Provider::call_with_overrides(self, tx, block, overrides)
+    }
+
+    fn estimate_gas(&self, tx: CallInput, block: Option<BlockId>) -> Result<u128, Box<dyn Error>> {
+        Provider::estimate_gas(self, tx, block)
+    }
+
+    fn estimate_gas_with_overrides(
+        &self,
+        tx: CallInput,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<u128, Box<dyn Error>> {
+        Provider::estimate_gas_with_overrides(self, tx, block, overrides)
+    }
+
+    fn simulate(
+        &self,
+        input: &SimulationInput,
+        block: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Box<dyn Error>> {
+        Provider::simulate(self, input, block)
+    }
+
+    fn create_access_list(
+        &self,
+        tx: CallInput,
+        block: Option<BlockId>,
+    ) -> Result<AccessListResult, Box<dyn Error>> {
+        Provider::create_access_list(self, tx, block)
+    }
+}
@@ -0,0 +1,367 @@
+//!The contract module provides the `ContractFactory` struct for deploying contracts from their ABI and bytecode, and
+//!the `Contract` struct for calling deployed contracts through their ABI.
+use crate::abi::{Abi, Detokenize, Function, Token};
+use crate::artifact::Artifact;
+use crate::provider::{CallInput, Provider, TransactionInput, TxStatus};
+use crate::types::{Address, BlockId, Bytes, H256};
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::Duration;
+
+const LIBRARY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+///The `ContractFactory` struct deploys instances of a contract through a `Provider`, encoding the constructor arguments
+///according to the ABI of the contract.
+///## Example
+///```rust,no_run
+///use ethrs::abi::Token;
+///use ethrs::artifact::Artifact;
+///use ethrs::contract::ContractFactory;
+///use ethrs::provider::Provider;
+///use ethrs::types::U256;
+///use std::error::Error;
+///use std::time::Duration;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let artifact = Artifact::load("out/Counter.sol/Counter.json")?;
+///  let factory = ContractFactory::from_artifact(&artifact, provider.clone())?;
+///  let txhash = factory.deploy(
+///      "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?,
+///      &[Token::Uint(U256::from(42))],
+///  )?;
+///  let receipt = provider
+///      .wait_for_transaction(txhash, 1, Duration::from_secs(30), Duration::from_secs(1))?
+///      .unwrap();
+///  println!("Deployed at {:?}", receipt.contract_address);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct ContractFactory {
+    abi: Abi,
+    bytecode: Bytes,
+    provider: Provider,
+}
+
+impl ContractFactory {
+    ///The `ContractFactory::new()` associated function takes the ABI and creation bytecode of a contract and a
+    ///`Provider`, and returns a `ContractFactory` instance.
+    pub fn new(abi: Abi, bytecode: Bytes, provider: Provider) -> ContractFactory {
+        ContractFactory {
+            abi,
+            bytecode,
+            provider,
+        }
+    }
+
+    ///The `ContractFactory::from_artifact()` associated function takes a compiler artifact and a `Provider`, and
+    ///attempts to return a `ContractFactory` instance as `Ok(ContractFactory)`. Returns an `Err()` if the bytecode has
+    ///unlinked library references.
+    pub fn from_artifact(
+        artifact: &Artifact,
+        provider: Provider,
+    ) -> Result<ContractFactory, Box<dyn Error>> {
+        Ok(ContractFactory::new(
+            artifact.abi.clone(),
+            artifact.bytecode_bytes()?,
+            provider,
+        ))
+    }
+
+    ///The `ContractFactory::from_linked_artifact()` associated function takes a compiler artifact, the addresses of the
+    ///libraries it references, by name or fully qualified as `<file>:<name>`, and a `Provider`, and attempts to return a
+    ///`ContractFactory` instance for the linked bytecode as `Ok(ContractFactory)`. Returns an `Err()` if a library is not
+    ///referenced by the bytecode or if references remain unlinked.
+    pub fn from_linked_artifact(
+        artifact: &Artifact,
+        libraries: &[(&str, Address)],
+        provider: Provider,
+    ) -> Result<ContractFactory, Box<dyn Error>> {
+        let mut artifact = artifact.clone();
+        for (library, address) in libraries {
+            artifact.link(library, *address)?;
+        }
+        ContractFactory::from_artifact(&artifact, provider)
+    }
+
+    ///The `ContractFactory::deploy_libraries()` associated function takes a compiler artifact, the artifacts of the
+    ///libraries it may reference, the deployer address, a `Provider` and a timeout per deployment. It deploys every
+    ///library the artifact references, libraries referenced by those first, waiting for each deployment to be mined, and
+    ///attempts to return a `ContractFactory` instance for the linked bytecode along with the deployed libraries by their
+    ///fully qualified names as `Ok((ContractFactory, BTreeMap<String, Address>))`. Libraries are matched by contract
+    ///name, so Foundry artifacts should be loaded with `Artifact::load()`. Returns an `Err()` on JSON-RPC errors, if a
+    ///library artifact is missing or if a deployment fails or times out.
+    ///## Example
+    ///```rust,no_run
+    ///use ethrs::abi::Token;
+    ///use ethrs::artifact::Artifact;
+    ///use ethrs::contract::ContractFactory;
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///use std::time::Duration;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("http://localhost:8545");
+    ///  let deployer = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?;
+    ///  let (factory, libraries) = ContractFactory::deploy_libraries(
+    ///      &Artifact::load("out/Vault.sol/Vault.json")?,
+    ///      &[Artifact::load("out/Math.sol/Math.json")?],
+    ///      deployer,
+    ///      provider,
+    ///      Duration::from_secs(30),
+    ///  )?;
+    ///  println!("Math deployed at {:?}", libraries["src/Math.sol:Math"]);
+    ///  factory.deploy(deployer, &[])?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn deploy_libraries(
+        artifact: &Artifact,
+        libraries: &[Artifact],
+        from: Address,
+        provider: Provider,
+        timeout: Duration,
+    ) -> Result<(ContractFactory, BTreeMap<String, Address>), Box<dyn Error>> {
+        let mut deployed = BTreeMap::new();
+        let mut artifact = artifact.clone();
+        for library in artifact.libraries() {
+            let address =
+                deploy_library(&library, libraries, from, &provider, timeout, &mut deployed)?;
+            artifact.link(&library, address)?;
+        }
+        Ok((
+            ContractFactory::from_artifact(&artifact, provider)?,
+            deployed,
+        ))
+    }
+
+    ///Returns the ABI of the contract.
+    pub fn abi(&self) -> &Abi {
+        &self.abi
+    }
+
+    ///Returns the creation bytecode of the contract, without constructor arguments.
+    pub fn bytecode(&self) -> &Bytes {
+        &self.bytecode
+    }
+
+    ///The `deploy_data()` function takes the constructor arguments and attempts to return the creation code of the
+    ///contract, i.e. the bytecode followed by the encoded arguments, as `Ok(Bytes)`. Returns an `Err()` if the arguments
+    ///do not match the constructor.
+    pub fn deploy_data(&self, args: &[Token]) -> Result<Bytes, Box<dyn Error>> {
+        match self.abi.constructor() {
+            Some(constructor) => Ok(Bytes(constructor.encode_input(&self.bytecode, args)?)),
+            None if args.is_empty() => Ok(self.bytecode.clone()),
+            None => Err("Contract has no constructor arguments".into()),
+        }
+    }
+
+    ///Returns a `Contract` for an instance of the contract deployed at the given address.
+    pub fn at(&self, address: Address) -> Contract {
+        Contract::new(address, self.abi.clone(), self.provider.clone())
+    }
+
+    ///The `deploy()` function takes the deployer address and the constructor arguments, sends the deployment
+    ///transaction and attempts to return its transaction hash as `Ok(H256)`. The deployed address is available as the
+    ///`contract_address` of the receipt. Returns an `Err()` on JSON-RPC errors and if the arguments do not match the
+    ///constructor.
+    pub fn deploy(&self, from: Address, args: &[Token]) -> Result<H256, Box<dyn Error>> {
+        self.provider.send_transaction(TransactionInput {
+            from,
+            to: None,
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(self.deploy_data(args)?.to_string()),
+            nonce: None,
+            access_list: None,
+        })
+    }
+}
+
+///The `Contract` struct is a deployed contract, which encodes calls to its functions and decodes their results according
+///to its ABI. Functions are looked up by name, or by signature, e.g. `transfer(address,uint256)`, to pick an overload.
+///## Example
+///```rust
+///use ethrs::abi::{Abi, Token};
+///use ethrs::contract::Contract;
+///use ethrs::provider::Provider;
+///use ethrs::types::{Address, U256};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  let abi: Abi = serde_json::from_str(r#"[{
+///      "type": "function",
+///      "name": "balanceOf",
+///      "inputs": [{"name": "owner", "type": "address"}],
+///      "outputs": [{"name": "", "type": "uint256"}],
+///      "stateMutability": "view"
+///  }]"#)?;
+///  let weth = Contract::new("0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?, abi, provider);
+///  let owner: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+///  let balance: U256 = weth.call_fn("balanceOf", &[Token::Address(owner)])?;
+///  println!("{balance}");
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Contract {
+    address: Address,
+    abi: Abi,
+    provider: Provider,
+}
+
+impl Contract {
+    ///The `Contract::new()` associated function takes the address and ABI of a deployed contract and a `Provider`, and
+    ///returns a `Contract` instance.
+    pub fn new(address: Address, abi: Abi, provider: Provider) -> Contract {
+        Contract {
+            address,
+            abi,
+            provider,
+        }
+    }
+
+    ///Returns the address of the contract.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    ///Returns the ABI of the contract.
+    pub fn abi(&self) -> &Abi {
+        &self.abi
+    }
+
+    ///The `function()` function takes a function name or signature and attempts to return the function of the ABI as
+    ///`Ok(&Function)`. A name matches the first overload. Returns an `Err()` if the ABI has no such function.
+    pub fn function(&self, name: &str) -> Result<&Function, Box<dyn Error>> {
+        let function = match name.contains('(') {
+            true => self.abi.functions().find(|function| {
+                function
+                    .signature()
+                    .map_or(false, |signature| signature == name)
+            }),
+            false => self.abi.function(name),
+        };
+        function.ok_or_else(|| format!("Function {name} not found in the ABI").into())
+    }
+
+    ///The `encode_fn()` function takes a function name or signature and its arguments, and attempts to return the
+    ///calldata of a call as `Ok(Bytes)`. Returns an `Err()` if the function is not found or the arguments do not match
+    ///its inputs.
+    pub fn encode_fn(&self, name: &str, args: &[Token]) -> Result<Bytes, Box<dyn Error>> {
+        Ok(Bytes(self.function(name)?.encode_input(args)?))
+    }
+
+    ///The `call_fn()` function takes a function name or signature and its arguments, calls the function at the latest
+    ///block and attempts to return its outputs as `Ok(T)`, a single output converting into `T` directly and several
+    ///outputs into a tuple. Returns an `Err()` on JSON-RPC errors, reverts included, if the function is not found, or
+    ///if the arguments or outputs do not match it.
+    pub fn call_fn<T: Detokenize>(&self, name: &str, args: &[Token]) -> Result<T, Box<dyn Error>> {
+        self.call_fn_at(name, args, None)
+    }
+
+    ///Like `call_fn()`, but calls the function at the given block.
+    pub fn call_fn_at<T: Detokenize>(
+        &self,
+        name: &str,
+        args: &[Token],
+        block: Option<BlockId>,
+    ) -> Result<T, Box<dyn Error>> {
+        let function = self.function(name)?;
+        let data: Bytes = self
+            .provider
+            .call(
+                CallInput::to(self.address).data(function.encode_input(args)?),
+                block,
+            )?
+            .parse()?;
+        T::from_tokens(function.decode_output(&data)?)
+    }
+
+    ///The `encode_tx()` function takes the sender address, a function name or signature and its arguments, and
+    ///attempts to return a transaction calling the function as `Ok(TransactionInput)`, to be completed, e.g. with a
+    ///value, and sent with `Provider::send_transaction()`. Returns an `Err()` if the function is not found or the
+    ///arguments do not match its inputs.
+    ///## Example
+    ///```rust,no_run
+    ///use ethrs::abi::{Abi, Token};
+    ///use ethrs::contract::Contract;
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("http://localhost:8545");
+    ///  let abi: Abi = serde_json::from_str(&std::fs::read_to_string("abi/ERC20.json")?)?;
+    ///  let token = Contract::new("0x5FbDB2315678afecb367f032d93F642f64180aa3".parse()?, abi, provider.clone());
+    ///  let tx = token.encode_tx(
+    ///      "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?,
+    ///      "transfer",
+    ///      &[Token::Address("0x70997970C51812dc3A010C7d01b50e0d17dc79C8".parse()?), Token::Uint(U256::from(1))],
+    ///  )?;
+    ///  provider.send_transaction(tx)?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn encode_tx(
+        &self,
+        from: Address,
+        name: &str,
+        args: &[Token],
+    ) -> Result<TransactionInput, Box<dyn Error>> {
+        Ok(TransactionInput {
+            from,
+            to: Some(self.address),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(self.encode_fn(name, args)?.to_string()),
+            nonce: None,
+            access_list: None,
+        })
+    }
+}
+
+// Deploys a library after the libraries it references, reusing the ones that were already deployed.
+fn deploy_library(
+    library: &str,
+    artifacts: &[Artifact],
+    from: Address,
+    provider: &Provider,
+    timeout: Duration,
+    deployed: &mut BTreeMap<String, Address>,
+) -> Result<Address, Box<dyn Error>> {
+    if let Some(address) = deployed.get(library) {
+        return Ok(*address);
+    }
+    let name = library.rsplit(':').next().unwrap_or(library);
+    let mut artifact = artifacts
+        .iter()
+        .find(|artifact| artifact.contract_name.as_deref() == Some(name))
+        .ok_or(format!("Missing artifact for library {library}"))?
+        .clone();
+    for dependency in artifact.libraries() {
+        let address = deploy_library(&dependency, artifacts, from, provider, timeout, deployed)?;
+        artifact.link(&dependency, address)?;
+    }
+    let txhash = ContractFactory::from_artifact(&artifact, provider.clone())?.deploy(from, &[])?;
+    let receipt = provider
+        .wait_for_transaction(txhash, 1, timeout, LIBRARY_POLL_INTERVAL)?
+        .ok_or(format!("Timed out deploying library {library}"))?;
+    let address = match (receipt.status, receipt.contract_address) {
+        (TxStatus::Failed, _) => {
+            return Err(format!("Deployment of library {library} reverted").into())
+        }
+        (_, Some(address)) => address,
+        (_, None) => {
+            return Err(format!("Deployment of library {library} has no contract address").into())
+        }
+    };
+    deployed.insert(library.to_owned(), address);
+    Ok(address)
+}
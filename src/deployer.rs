@@ -0,0 +1,307 @@
+//!The deployer module makes deployment scripts reproducible: a `Deployer` records the deployed contracts of every chain
+//!in a manifest file, skips contracts that are already deployed and supports deterministic CREATE2 deployments through
+//!the canonical deployer.
+use serde::{Deserialize, Serialize};
+
+use crate::abi::Token;
+use crate::contract::ContractFactory;
+use crate::provider::{Provider, TransactionInput, TransactionReceipt};
+use crate::types::{Address, Bytes, H256};
+use crate::utils::keccak256;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+///The address of the canonical CREATE2 deployer, which deploys the creation code following a 32-byte salt.
+pub const CREATE2_DEPLOYER: &str = "0x4e59b44847b379578588920ca78fbf26c0b4956c";
+
+///The `Deployment` struct records a deployed contract. The transaction hash is not set for CREATE2 deployments that
+///were found already deployed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deployment {
+    pub address: Address,
+    pub transaction_hash: Option<H256>,
+    pub code_hash: H256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<H256>,
+}
+
+///The `Manifest` struct holds the deployments of every chain, keyed by chain id and then by contract name. It
+///serializes to a JSON object of the same shape.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Manifest(pub BTreeMap<u64, BTreeMap<String, Deployment>>);
+
+impl Manifest {
+    ///The `load()` function takes the path of a manifest file and attempts to return the manifest as `Ok(Manifest)`,
+    ///or an empty manifest if the file does not exist. Returns an `Err()` if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Manifest, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    ///The `save()` function takes a path and attempts to write the manifest to it as pretty-printed JSON as `Ok(())`.
+    ///Returns an `Err()` if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    ///Returns the deployment of a contract on a chain, if any.
+    pub fn get(&self, chain_id: u64, name: &str) -> Option<&Deployment> {
+        self.0.get(&chain_id)?.get(name)
+    }
+
+    ///The `insert()` function records the deployment of a contract on a chain, returning the deployment it replaces, if
+    ///any.
+    pub fn insert(
+        &mut self,
+        chain_id: u64,
+        name: impl Into<String>,
+        deployment: Deployment,
+    ) -> Option<Deployment> {
+        self.0
+            .entry(chain_id)
+            .or_default()
+            .insert(name.into(), deployment)
+    }
+}
+
+///The `create2_address()` function takes the address of a CREATE2 deployer, a salt and the creation code of a contract,
+///and returns the address the contract is deployed at.
+pub fn create2_address(deployer: Address, salt: H256, init_code: &[u8]) -> Address {
+    let mut preimage = Vec::with_capacity(85);
+    preimage.push(0xff);
+    preimage.extend_from_slice(deployer.as_bytes());
+    preimage.extend_from_slice(salt.as_bytes());
+    preimage.extend_from_slice(&keccak256(init_code));
+    let hash = keccak256(&preimage);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Address::from(address)
+}
+
+///The `Deployer` struct deploys contracts from a single account and records them in a `Manifest`, saving it after every
+///deployment if it was loaded from a file. Contracts that are recorded for the current chain are not deployed again as
+///long as their code hash matches the manifest.
+///## Example
+///```rust,no_run
+///use ethrs::abi::Token;
+///use ethrs::artifact::Artifact;
+///use ethrs::contract::ContractFactory;
+///use ethrs::deployer::Deployer;
+///use ethrs::provider::Provider;
+///use ethrs::types::{H256, U256};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let factory = ContractFactory::from_artifact(&Artifact::load("out/Counter.sol/Counter.json")?, provider.clone())?;
+///  let mut deployer = Deployer::new(provider, "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?)?
+///      .with_manifest("deployments.json")?;
+///  // running the script again skips both deployments
+///  let counter = deployer.deploy("Counter", &factory, &[Token::Uint(U256::from(42))])?;
+///  let deterministic = deployer.deploy_create2("CounterV2", &factory, &[Token::Uint(U256::from(42))], H256::ZERO)?;
+///  println!("Counter at {:?}, CounterV2 at {:?}", counter.address, deterministic.address);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Deployer {
+    provider: Provider,
+    from: Address,
+    chain_id: u64,
+    manifest: Manifest,
+    path: Option<PathBuf>,
+    timeout: Duration,
+    poll_interval: Duration,
+}
+
+impl Deployer {
+    ///The `Deployer::new()` associated function takes a `Provider` and the deployer address, and attempts to return a
+    ///`Deployer` with an empty manifest for the chain of the provider as `Ok(Deployer)`. Returns an `Err()` on JSON-RPC
+    ///errors.
+    pub fn new(provider: Provider, from: Address) -> Result<Deployer, Box<dyn Error>> {
+        Ok(Deployer {
+            chain_id: provider.chain_id()?,
+            provider,
+            from,
+            manifest: Manifest::default(),
+            path: None,
+            timeout: Duration::from_secs(120),
+            poll_interval: Duration::from_secs(1),
+        })
+    }
+
+    ///The `with_manifest()` function takes the path of a manifest file and attempts to load it, if it exists, as
+    ///`Ok(Deployer)`. New deployments are saved to the file. Returns an `Err()` if the file cannot be read or parsed.
+    pub fn with_manifest(mut self, path: impl Into<PathBuf>) -> Result<Deployer, Box<dyn Error>> {
+        let path = path.into();
+        self.manifest = Manifest::load(&path)?;
+        self.path = Some(path);
+        Ok(self)
+    }
+
+    ///The `with_timeout()` function sets how long to wait for each deployment to be mined, polling every
+    ///`poll_interval`. Defaults to two minutes, polled every second.
+    pub fn with_timeout(mut self, timeout: Duration, poll_interval: Duration) -> Deployer {
+        self.timeout = timeout;
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    ///Returns the chain id the deployments are recorded under.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    ///Returns the manifest of the deployer.
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    ///The `deploy()` function takes a contract name, a `ContractFactory` and the constructor arguments, and attempts to
+    ///return the deployment of the contract as `Ok(Deployment)`, deploying it with CREATE unless the manifest records
+    ///it for the current chain. A recorded contract without code, e.g. on a restarted devnet, is deployed again.
+    ///Returns an `Err()` on JSON-RPC errors, if the deployment fails or if the recorded code hash does not match.
+    pub fn deploy(
+        &mut self,
+        name: &str,
+        factory: &ContractFactory,
+        args: &[Token],
+    ) -> Result<Deployment, Box<dyn Error>> {
+        if let Some(deployment) = self.recorded(name)? {
+            return Ok(deployment);
+        }
+        let txhash = self.provider.send_transaction(TransactionInput {
+            from: self.from,
+            to: None,
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(factory.deploy_data(args)?.to_string()),
+            nonce: None,
+            access_list: None,
+        })?;
+        let address = self
+            .mined(name, txhash)?
+            .contract_address
+            .ok_or(format!("Deployment of {name} has no contract address"))?;
+        self.record(name, address, Some(txhash), None)
+    }
+
+    ///The `deploy_create2()` function takes a contract name, a `ContractFactory`, the constructor arguments and a salt,
+    ///and attempts to return the deployment of the contract as `Ok(Deployment)`, deploying it through the canonical
+    ///CREATE2 deployer unless the manifest records it for the current chain or it already exists at its deterministic
+    ///address. Returns an `Err()` on JSON-RPC errors, if the deployment fails or if the recorded code hash does not
+    ///match.
+    pub fn deploy_create2(
+        &mut self,
+        name: &str,
+        factory: &ContractFactory,
+        args: &[Token],
+        salt: H256,
+    ) -> Result<Deployment, Box<dyn Error>> {
+        if let Some(deployment) = self.recorded(name)? {
+            return Ok(deployment);
+        }
+        let deployer: Address = CREATE2_DEPLOYER.parse()?;
+        let init_code = factory.deploy_data(args)?;
+        let address = create2_address(deployer, salt, &init_code);
+        if !self.code(address)?.is_empty() {
+            return self.record(name, address, None, Some(salt));
+        }
+        if self.code(deployer)?.is_empty() {
+            return Err(format!(
+                "CREATE2 deployer is not deployed on chain {}",
+                self.chain_id
+            )
+            .into());
+        }
+        let mut data = salt.as_bytes().to_vec();
+        data.extend_from_slice(&init_code);
+        let txhash = self.provider.send_transaction(TransactionInput {
+            from: self.from,
+            to: Some(deployer),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(Bytes(data).to_string()),
+            nonce: None,
+            access_list: None,
+        })?;
+        self.mined(name, txhash)?;
+        self.record(name, address, Some(txhash), Some(salt))
+    }
+
+    fn code(&self, address: Address) -> Result<Bytes, Box<dyn Error>> {
+        self.provider
+            .get_code(address, None)?
+            .parse()
+            .map_err(Into::into)
+    }
+
+    // Returns the recorded deployment of a contract if its code is still deployed.
+    fn recorded(&self, name: &str) -> Result<Option<Deployment>, Box<dyn Error>> {
+        let deployment = match self.manifest.get(self.chain_id, name) {
+            Some(deployment) => deployment.clone(),
+            None => return Ok(None),
+        };
+        let code = self.code(deployment.address)?;
+        if code.is_empty() {
+            return Ok(None);
+        }
+        match H256(keccak256(&code)) == deployment.code_hash {
+            true => Ok(Some(deployment)),
+            false => Err(format!(
+                "Code of {name} at {} does not match the manifest",
+                deployment.address
+            )
+            .into()),
+        }
+    }
+
+    fn mined(&self, name: &str, txhash: H256) -> Result<TransactionReceipt, Box<dyn Error>> {
+        let receipt = self
+            .provider
+            .wait_for_transaction(txhash, 1, self.timeout, self.poll_interval)?
+            .ok_or(format!("Timed out deploying {name}"))?;
+        match receipt.status.is_failed() {
+            true => Err(format!("Deployment of {name} reverted").into()),
+            false => Ok(receipt),
+        }
+    }
+
+    fn record(
+        &mut self,
+        name: &str,
+        address: Address,
+        transaction_hash: Option<H256>,
+        salt: Option<H256>,
+    ) -> Result<Deployment, Box<dyn Error>> {
+        let code = self.code(address)?;
+        if code.is_empty() {
+            return Err(format!("Deployment of {name} left no code at {address}").into());
+        }
+        let deployment = Deployment {
+            address,
+            transaction_hash,
+            code_hash: H256(keccak256(&code)),
+            salt,
+        };
+        self.manifest
+            .insert(self.chain_id, name, deployment.clone());
+        if let Some(path) = &self.path {
+            self.manifest.save(path)?;
+        }
+        Ok(deployment)
+    }
+}
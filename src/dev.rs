@@ -0,0 +1,119 @@
+//!The dev module controls local development nodes such as Anvil and Hardhat, usually forks of a live chain run by
+//!integration tests: mining blocks, moving time forward, snapshotting and reverting the state, and editing accounts.
+//!These methods are only served by development nodes, every other node returns a JSON-RPC error.
+use serde_json::{json, Value};
+
+use crate::provider::Provider;
+use crate::types::{Address, Bytes, U256};
+
+use std::error::Error;
+
+///The `DevNode` struct wraps a `Provider` connected to a development node with the methods controlling it. Anvil
+///serves the `hardhat_*` methods as well, so every method works against both nodes.
+///## Example
+///```rust,no_run
+///use ethrs::dev::DevNode;
+///use ethrs::provider::Provider;
+///use ethrs::types::U256;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let node = DevNode::new(provider.clone());
+///  let whale = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+///  let snapshot = node.snapshot()?;
+///  node.set_balance(whale, U256::exp10(24))?;
+///  node.impersonate_account(whale)?;
+///  node.increase_time(86400)?;
+///  node.mine()?;
+///  assert_eq!(provider.get_balance(whale, None)?, U256::exp10(24).as_u128());
+///  assert!(node.revert(snapshot)?);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct DevNode {
+    provider: Provider,
+}
+
+impl DevNode {
+    ///The `DevNode::new()` associated function takes a `Provider` connected to a development node and returns a
+    ///`DevNode`.
+    pub fn new(provider: Provider) -> DevNode {
+        DevNode { provider }
+    }
+
+    ///Returns the provider of the node.
+    pub fn provider(&self) -> &Provider {
+        &self.provider
+    }
+
+    ///The `mine()` function attempts to mine a block with the pending transactions, returning `Ok(())`. Returns an
+    ///`Err()` on JSON-RPC errors.
+    pub fn mine(&self) -> Result<(), Box<dyn Error>> {
+        self.provider.request::<Value>("evm_mine", json!([]))?;
+        Ok(())
+    }
+
+    ///The `set_next_block_timestamp()` function takes a Unix timestamp in seconds and attempts to set it as the
+    ///timestamp of the next block, returning `Ok(())`. Returns an `Err()` on JSON-RPC errors, e.g. if the timestamp is
+    ///not after the latest block.
+    pub fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), Box<dyn Error>> {
+        self.provider
+            .request::<Value>("evm_setNextBlockTimestamp", json!([timestamp]))?;
+        Ok(())
+    }
+
+    ///The `increase_time()` function takes a number of seconds and attempts to add them to the timestamp of the next
+    ///blocks, returning `Ok(())`. Returns an `Err()` on JSON-RPC errors.
+    pub fn increase_time(&self, seconds: u64) -> Result<(), Box<dyn Error>> {
+        self.provider
+            .request::<Value>("evm_increaseTime", json!([seconds]))?;
+        Ok(())
+    }
+
+    ///The `snapshot()` function attempts to snapshot the state of the chain, returning the id to revert to as
+    ///`Ok(U256)`. Returns an `Err()` on JSON-RPC errors.
+    pub fn snapshot(&self) -> Result<U256, Box<dyn Error>> {
+        self.provider.request("evm_snapshot", json!([]))
+    }
+
+    ///The `revert()` function takes the id of a snapshot and attempts to revert the chain to its state, returning
+    ///whether the snapshot existed as `Ok(bool)`. Returns an `Err()` on JSON-RPC errors. A snapshot can only be
+    ///reverted to once, and reverting discards the snapshots taken after it.
+    pub fn revert(&self, snapshot: U256) -> Result<bool, Box<dyn Error>> {
+        self.provider.request("evm_revert", json!([snapshot]))
+    }
+
+    ///The `set_balance()` function takes an address and a balance in wei, and attempts to set the balance of the
+    ///address, returning `Ok(())`. Returns an `Err()` on JSON-RPC errors.
+    pub fn set_balance(&self, address: Address, balance: U256) -> Result<(), Box<dyn Error>> {
+        self.provider
+            .request::<Value>("anvil_setBalance", json!([address, balance]))?;
+        Ok(())
+    }
+
+    ///The `impersonate_account()` function takes an address and attempts to let transactions be sent from it
+    ///without its private key, with `eth_sendTransaction`, returning `Ok(())`. Returns an `Err()` on JSON-RPC errors.
+    pub fn impersonate_account(&self, address: Address) -> Result<(), Box<dyn Error>> {
+        self.provider
+            .request::<Value>("anvil_impersonateAccount", json!([address]))?;
+        Ok(())
+    }
+
+    ///The `stop_impersonating_account()` function takes an impersonated address and attempts to stop its
+    ///impersonation, returning `Ok(())`. Returns an `Err()` on JSON-RPC errors.
+    pub fn stop_impersonating_account(&self, address: Address) -> Result<(), Box<dyn Error>> {
+        self.provider
+            .request::<Value>("anvil_stopImpersonatingAccount", json!([address]))?;
+        Ok(())
+    }
+
+    ///The `set_code()` function takes an address and runtime bytecode, and attempts to set the code of the address,
+    ///returning `Ok(())`. Returns an `Err()` on JSON-RPC errors.
+    pub fn set_code(&self, address: Address, code: &Bytes) -> Result<(), Box<dyn Error>> {
+        self.provider
+            .request::<Value>("hardhat_setCode", json!([address, code]))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+//!The eip712 module hashes EIP-712 typed data, i.e. the structured messages of permits and off-chain orders, as signed
+//!by `Wallet::sign_typed_data()` locally or by the node with `Provider::sign_typed_data()`.
+use crate::types::{parse_u256, Address, Bytes, H256, U256};
+use crate::utils::keccak256;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+///The `Eip712Domain` struct is the domain of typed data, which binds its signatures to an application. Only the fields
+///that are set are hashed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip712Domain {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_chain_id"
+    )]
+    pub chain_id: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifying_contract: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<H256>,
+}
+
+// wallets send the chain id either as a JSON integer or as a string
+fn deserialize_chain_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<U256>, D::Error> {
+    crate::types::u256_quantity::deserialize(deserializer).map(Some)
+}
+
+impl Eip712Domain {
+    ///Returns the domain separator, i.e. the hash of the domain as an `EIP712Domain` struct of the fields that are set.
+    pub fn separator(&self) -> H256 {
+        let mut fields = vec![];
+        let mut data = vec![];
+        if let Some(name) = &self.name {
+            fields.push("string name");
+            data.extend(keccak256(name.as_bytes()));
+        }
+        if let Some(version) = &self.version {
+            fields.push("string version");
+            data.extend(keccak256(version.as_bytes()));
+        }
+        if let Some(chain_id) = self.chain_id {
+            fields.push("uint256 chainId");
+            data.extend(word(chain_id));
+        }
+        if let Some(verifying_contract) = self.verifying_contract {
+            fields.push("address verifyingContract");
+            data.extend(address_word(verifying_contract));
+        }
+        if let Some(salt) = self.salt {
+            fields.push("bytes32 salt");
+            data.extend(salt.0);
+        }
+        let encoded_type = format!("EIP712Domain({})", fields.join(","));
+        H256(keccak256(
+            &[&keccak256(encoded_type.as_bytes())[..], &data].concat(),
+        ))
+    }
+}
+
+///The `Eip712Field` struct is a member of a struct type of typed data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip712Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+}
+
+///The `TypedData` struct is an EIP-712 message, in the JSON format of `eth_signTypedData_v4`. Its `types` may include the
+///`EIP712Domain` type, which is ignored, as the domain is hashed from the fields that are set. Values are read from the
+///JSON `message` as `eth_signTypedData_v4` reads them, e.g. integers as JSON numbers or as decimal or hex strings.
+///## Example
+///```rust
+///use ethrs::eip712::TypedData;
+///use ethrs::wallet::Wallet;
+///use serde_json::json;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let typed_data: TypedData = serde_json::from_value(json!({
+///      "types": {
+///          "Person": [{"name": "name", "type": "string"}, {"name": "wallet", "type": "address"}],
+///          "Mail": [
+///              {"name": "from", "type": "Person"},
+///              {"name": "to", "type": "Person"},
+///              {"name": "contents", "type": "string"},
+///          ],
+///      },
+///      "primaryType": "Mail",
+///      "domain": {
+///          "name": "Ether Mail",
+///          "version": "1",
+///          "chainId": 1,
+///          "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+///      },
+///      "message": {
+///          "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+///          "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+///          "contents": "Hello, Bob!",
+///      },
+///  }))?;
+///  assert_eq!(
+///      typed_data.signing_hash()?,
+///      "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2".parse()?
+///  );
+///  let wallet = Wallet::random();
+///  let signature = wallet.sign_typed_data(&typed_data)?;
+///  assert!(signature.verify(typed_data.signing_hash()?, wallet.address()));
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<Eip712Field>>,
+    pub primary_type: String,
+    pub domain: Eip712Domain,
+    pub message: serde_json::Value,
+}
+
+impl TypedData {
+    ///The `encode_type()` function takes the name of a struct type and attempts to return its encoding as `Ok(String)`,
+    ///e.g. `Mail(Person from,Person to,string contents)Person(string name,address wallet)`, i.e. the type followed by
+    ///the struct types it references, sorted by name. Returns an `Err()` if the type is not defined.
+    pub fn encode_type(&self, type_name: &str) -> Result<String, Box<dyn Error>> {
+        let mut dependencies = BTreeSet::new();
+        self.collect_dependencies(type_name, &mut dependencies)?;
+        dependencies.remove(type_name);
+        let mut encoded = String::new();
+        for name in [type_name]
+            .into_iter()
+            .chain(dependencies.iter().map(String::as_str))
+        {
+            let fields = self.fields(name)?;
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|field| format!("{} {}", field.type_name, field.name))
+                .collect();
+            encoded.push_str(&format!("{name}({})", fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    fn fields(&self, type_name: &str) -> Result<&[Eip712Field], Box<dyn Error>> {
+        self.types
+            .get(type_name)
+            .map(Vec::as_slice)
+            .ok_or_else(|| unknown(type_name).into())
+    }
+
+    fn collect_dependencies(
+        &self,
+        type_name: &str,
+        dependencies: &mut BTreeSet<String>,
+    ) -> Result<(), Box<dyn Error>> {
+        if !dependencies.insert(type_name.to_owned()) {
+            return Ok(());
+        }
+        for field in self.fields(type_name)? {
+            let base = field.type_name.split('[').next().unwrap_or_default();
+            if self.types.contains_key(base) {
+                self.collect_dependencies(base, dependencies)?;
+            }
+        }
+        Ok(())
+    }
+
+    ///Returns the hash of the encoding of a struct type, as `encode_type()` does.
+    pub fn type_hash(&self, type_name: &str) -> Result<H256, Box<dyn Error>> {
+        Ok(H256(keccak256(self.encode_type(type_name)?.as_bytes())))
+    }
+
+    ///The `hash_struct()` function takes the name of a struct type and a JSON value of it, and attempts to return the
+    ///hash of the value as `Ok(H256)`. Returns an `Err()` if the type is not defined, or if a field of the value is
+    ///missing or does not match its type.
+    pub fn hash_struct(
+        &self,
+        type_name: &str,
+        value: &serde_json::Value,
+    ) -> Result<H256, Box<dyn Error>> {
+        let mut data = self.type_hash(type_name)?.0.to_vec();
+        for field in self.fields(type_name)? {
+            let value = value
+                .get(&field.name)
+                .ok_or_else(|| format!("Missing EIP-712 field {type_name}.{}", field.name))?;
+            data.extend(self.encode_value(&field.type_name, value)?);
+        }
+        Ok(H256(keccak256(&data)))
+    }
+
+    ///Returns the hash signed for the typed data, i.e. the Keccak-256 hash of `0x1901`, the domain separator and the
+    ///hash of the message. Returns an `Err()` as `hash_struct()` does.
+    pub fn signing_hash(&self) -> Result<H256, Box<dyn Error>> {
+        let message = self.hash_struct(&self.primary_type, &self.message)?;
+        let data = [&[0x19, 0x01][..], &self.domain.separator().0, &message.0].concat();
+        Ok(H256(keccak256(&data)))
+    }
+
+    ///Returns the typed data as JSON, e.g. for `Provider::sign_typed_data()`.
+    pub fn to_json(&self) -> serde_json::Value {
+        // all keys are strings and all values serializable
+        serde_json::to_value(self).expect("typed data JSON")
+    }
+
+    fn encode_value(
+        &self,
+        type_name: &str,
+        value: &serde_json::Value,
+    ) -> Result<[u8; 32], Box<dyn Error>> {
+        let mismatch = || format!("Invalid EIP-712 {type_name} value: {value}");
+        if let Some((item_type, length)) = type_name
+            .strip_suffix(']')
+            .and_then(|type_name| type_name.rsplit_once('['))
+        {
+            let items = value.as_array().ok_or_else(mismatch)?;
+            if !length.is_empty() && length.parse::<usize>().ok() != Some(items.len()) {
+                return Err(mismatch().into());
+            }
+            let mut data = vec![];
+            for item in items {
+                data.extend(self.encode_value(item_type, item)?);
+            }
+            return Ok(keccak256(&data));
+        }
+        if self.types.contains_key(type_name) {
+            return Ok(self.hash_struct(type_name, value)?.0);
+        }
+        let as_str = || value.as_str().ok_or_else(mismatch);
+        match type_name {
+            "string" => Ok(keccak256(as_str()?.as_bytes())),
+            "bytes" => Ok(keccak256(&as_str()?.parse::<Bytes>()?)),
+            "bool" => Ok(word(
+                U256::from(value.as_bool().ok_or_else(mismatch)? as u8),
+            )),
+            "address" => Ok(address_word(as_str()?.parse()?)),
+            _ => {
+                if let Some(size) = type_name.strip_prefix("bytes") {
+                    let bytes = as_str()?.parse::<Bytes>()?;
+                    let size: usize = size.parse().map_err(|_| unknown(type_name))?;
+                    if !(1..=32).contains(&size) || bytes.len() > size {
+                        return Err(mismatch().into());
+                    }
+                    let mut word = [0u8; 32];
+                    word[..bytes.len()].copy_from_slice(&bytes);
+                    return Ok(word);
+                }
+                let (signed, bits) = match type_name.strip_prefix("uint") {
+                    Some(bits) => (false, bits),
+                    None => (
+                        true,
+                        type_name
+                            .strip_prefix("int")
+                            .ok_or_else(|| unknown(type_name))?,
+                    ),
+                };
+                let bits: usize = bits.parse().map_err(|_| unknown(type_name))?;
+                if bits == 0 || bits > 256 || bits % 8 != 0 {
+                    return Err(unknown(type_name).into());
+                }
+                let (negative, magnitude) = match value {
+                    serde_json::Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+                        (Some(number), _) => (false, U256::from(number)),
+                        (None, Some(number)) => (true, U256::from(number.unsigned_abs())),
+                        _ => return Err(mismatch().into()),
+                    },
+                    serde_json::Value::String(number) => match number.strip_prefix('-') {
+                        Some(number) => (true, parse_u256(number)?),
+                        None => (false, parse_u256(number)?),
+                    },
+                    _ => return Err(mismatch().into()),
+                };
+                // the magnitude of negative values may reach 2^(bits - 1)
+                let limit = bits - usize::from(signed);
+                let fits = match negative {
+                    false => magnitude.bits() <= limit,
+                    true => {
+                        signed && (magnitude.bits() <= limit || magnitude == U256::one() << limit)
+                    }
+                };
+                if !fits {
+                    return Err(mismatch().into());
+                }
+                match negative {
+                    true => Ok(word((!magnitude).overflowing_add(U256::one()).0)),
+                    false => Ok(word(magnitude)),
+                }
+            }
+        }
+    }
+}
+
+fn unknown(type_name: &str) -> String {
+    format!("Unknown EIP-712 type {type_name}")
+}
+
+fn word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn address_word(address: Address) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(&address.0);
+    word
+}
@@ -0,0 +1,358 @@
+//!The engine module speaks the Engine API consensus clients drive execution clients with: payloads are executed with
+//!`engine_newPayloadVx`, the head of the chain is set and block building started with `engine_forkchoiceUpdatedVx`, and
+//!built payloads are retrieved with `engine_getPayloadVx`. The API is served on the authenticated port of the execution
+//!client, usually 8551, and every request carries a JWT signed with the secret shared by both clients.
+use hmac::{Hmac, Mac};
+use primitive_types::U256;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+
+use crate::bloom::Bloom;
+use crate::provider::{Provider, Withdrawal};
+use crate::signing::RequestSigner;
+use crate::types::{Address, Bytes, H256};
+
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+///The `JwtSecret` struct is the 32-byte secret shared by the consensus and execution clients, and the `RequestSigner`
+///sending the `Authorization: Bearer <jwt>` header of the Engine API. Every request gets a new HS256 token whose `iat`
+///claim is the current Unix time, as execution clients reject tokens issued more than 60 seconds away from their
+///clock. Its `Debug` output does not show the secret.
+#[derive(Clone, PartialEq, Eq)]
+pub struct JwtSecret([u8; 32]);
+
+impl JwtSecret {
+    ///The `JwtSecret::new()` associated function takes the secret and returns it as a `JwtSecret`.
+    pub fn new(secret: [u8; 32]) -> JwtSecret {
+        JwtSecret(secret)
+    }
+
+    ///The `JwtSecret::from_hex()` associated function takes a hex-encoded secret, with or without `0x` prefix, and
+    ///attempts to return it as `Ok(JwtSecret)`. Surrounding whitespace is ignored. Returns an `Err()` if it is not 32
+    ///bytes of valid hex.
+    pub fn from_hex(secret: &str) -> Result<JwtSecret, Box<dyn Error>> {
+        let secret = secret.trim();
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(secret.strip_prefix("0x").unwrap_or(secret), &mut bytes)
+            .map_err(|_| "JWT secret must be 32 hex-encoded bytes")?;
+        Ok(JwtSecret(bytes))
+    }
+
+    ///The `JwtSecret::from_file()` associated function takes the path of a file holding a hex-encoded secret, such as
+    ///the `jwt.hex` file generated by execution clients, and attempts to return it as `Ok(JwtSecret)`. Returns an
+    ///`Err()` if the file cannot be read or does not hold a valid secret.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<JwtSecret, Box<dyn Error>> {
+        JwtSecret::from_hex(&std::fs::read_to_string(path)?)
+    }
+
+    ///The `token()` function takes a Unix timestamp in seconds and returns the JWT issued at that time.
+    pub fn token(&self, iat: u64) -> String {
+        let header = base64url(br#"{"alg":"HS256","typ":"JWT"}"#);
+        let claims = base64url(format!(r#"{{"iat":{iat}}}"#).as_bytes());
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC key");
+        mac.update(format!("{header}.{claims}").as_bytes());
+        format!(
+            "{header}.{claims}.{}",
+            base64url(&mac.finalize().into_bytes())
+        )
+    }
+}
+
+impl RequestSigner for JwtSecret {
+    fn sign(&self, _body: &[u8]) -> Result<HeaderMap, Box<dyn Error>> {
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", self.token(iat)))?,
+        );
+        Ok(headers)
+    }
+}
+
+// the secret is kept out of logs and debug output
+impl fmt::Debug for JwtSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtSecret").finish_non_exhaustive()
+    }
+}
+
+///Encodes the input as unpadded base64url, as JWTs are.
+fn base64url(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut encoded = String::with_capacity((data.len() * 4 + 2) / 3);
+    for chunk in data.chunks(3) {
+        let buffer = chunk.iter().enumerate().fold(0u32, |buffer, (i, byte)| {
+            buffer | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            encoded.push(char::from(ALPHABET[(buffer >> (18 - 6 * i) & 63) as usize]));
+        }
+    }
+    encoded
+}
+
+///The `ExecutionPayload` struct is a block as exchanged over the Engine API. Withdrawals are set from Shanghai
+///(`V2`), and the blob gas fields from Cancun (`V3`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPayload {
+    pub parent_hash: H256,
+    pub fee_recipient: Address,
+    pub state_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Bloom,
+    pub prev_randao: H256,
+    #[serde(with = "crate::utils::quantity")]
+    pub block_number: u64,
+    #[serde(with = "crate::utils::quantity")]
+    pub gas_limit: u64,
+    #[serde(with = "crate::utils::quantity")]
+    pub gas_used: u64,
+    #[serde(with = "crate::utils::quantity")]
+    pub timestamp: u64,
+    pub extra_data: Bytes,
+    pub base_fee_per_gas: U256,
+    pub block_hash: H256,
+    pub transactions: Vec<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub blob_gas_used: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub excess_blob_gas: Option<u64>,
+}
+
+///The `PayloadValidity` enum is the verdict of the execution client on a payload or forkchoice update. `Syncing` and
+///`Accepted` mean the payload could not be validated yet, as the client is missing some of its ancestors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayloadValidity {
+    Valid,
+    Invalid,
+    Syncing,
+    Accepted,
+    InvalidBlockHash,
+}
+
+///The `PayloadStatus` struct is returned by `engine_newPayloadVx` and `engine_forkchoiceUpdatedVx`. For invalid
+///payloads, `latest_valid_hash` is the most recent valid ancestor and `validation_error` explains why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadStatus {
+    pub status: PayloadValidity,
+    #[serde(default)]
+    pub latest_valid_hash: Option<H256>,
+    #[serde(default)]
+    pub validation_error: Option<String>,
+}
+
+///The `ForkchoiceState` struct is the head, safe and finalized blocks chosen by the consensus client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceState {
+    pub head_block_hash: H256,
+    pub safe_block_hash: H256,
+    pub finalized_block_hash: H256,
+}
+
+///The `PayloadAttributes` struct asks the execution client to start building a payload on top of the new head.
+///Withdrawals are required from Shanghai (`V2`), and the parent beacon block root from Cancun (`V3`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadAttributes {
+    #[serde(with = "crate::utils::quantity")]
+    pub timestamp: u64,
+    pub prev_randao: H256,
+    pub suggested_fee_recipient: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<H256>,
+}
+
+///The `ForkchoiceUpdated` struct is returned by `engine_forkchoiceUpdatedVx`, with the id of the payload being built
+///if attributes were given and the new head is valid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceUpdated {
+    pub payload_status: PayloadStatus,
+    #[serde(default)]
+    pub payload_id: Option<Bytes>,
+}
+
+///The `BlobsBundle` struct holds the KZG commitments, proofs and blobs of the blob transactions of a built payload.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobsBundle {
+    pub commitments: Vec<Bytes>,
+    pub proofs: Vec<Bytes>,
+    pub blobs: Vec<Bytes>,
+}
+
+///The `BuiltPayload` struct is returned by `engine_getPayloadV2` and later: the payload, the fees it pays to the fee
+///recipient in wei and, from `V3`, its blobs and whether the consensus client should prefer a locally built block over
+///one from the builder network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuiltPayload {
+    pub execution_payload: ExecutionPayload,
+    pub block_value: U256,
+    #[serde(default)]
+    pub blobs_bundle: Option<BlobsBundle>,
+    #[serde(default)]
+    pub should_override_builder: Option<bool>,
+}
+
+///The `EngineClient` struct sends Engine API requests to an execution client, authenticated with a `JwtSecret`. The
+///version of every method matches the fork of the payload: `V1` for Paris, `V2` for Shanghai and `V3` for Cancun.
+///## Example
+///```rust,no_run
+///use ethrs::engine::{EngineClient, ForkchoiceState, JwtSecret, PayloadValidity};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let engine = EngineClient::new("http://localhost:8551", JwtSecret::from_file("jwt.hex")?)?;
+///  let payload = serde_json::from_str(&std::fs::read_to_string("payload.json")?)?;
+///  let status = engine.new_payload_v2(&payload)?;
+///  if status.status == PayloadValidity::Valid {
+///      let state = ForkchoiceState {
+///          head_block_hash: payload.block_hash,
+///          ..Default::default()
+///      };
+///      engine.forkchoice_updated_v2(&state, None)?;
+///  }
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct EngineClient {
+    provider: Provider,
+}
+
+impl EngineClient {
+    ///The `EngineClient::new()` associated function takes the URL of the authenticated port of the execution client
+    ///and the shared secret, and attempts to return a client as `Ok(EngineClient)`. Returns an `Err()` if the HTTP
+    ///client cannot be initialized.
+    pub fn new(url: &str, secret: JwtSecret) -> Result<EngineClient, Box<dyn Error>> {
+        let provider = Provider::builder(url).signer(secret).build()?;
+        Ok(EngineClient { provider })
+    }
+
+    ///The `exchange_capabilities()` function takes the Engine API methods supported by the caller and attempts to
+    ///return those supported by the execution client as `Ok(Vec<String>)`. Returns an `Err()` on JSON-RPC errors.
+    pub fn exchange_capabilities(&self, methods: &[&str]) -> Result<Vec<String>, Box<dyn Error>> {
+        self.provider
+            .request("engine_exchangeCapabilities", json!([methods]))
+    }
+
+    ///The `new_payload_v1()` function takes a Paris payload and attempts to return its status after the execution
+    ///client processed it as `Ok(PayloadStatus)`. Returns an `Err()` on JSON-RPC errors, not on invalid payloads.
+    pub fn new_payload_v1(
+        &self,
+        payload: &ExecutionPayload,
+    ) -> Result<PayloadStatus, Box<dyn Error>> {
+        self.provider
+            .request("engine_newPayloadV1", json!([payload]))
+    }
+
+    ///The `new_payload_v2()` function takes a Paris or Shanghai payload and attempts to return its status after the
+    ///execution client processed it as `Ok(PayloadStatus)`. Returns an `Err()` on JSON-RPC errors, not on invalid
+    ///payloads.
+    pub fn new_payload_v2(
+        &self,
+        payload: &ExecutionPayload,
+    ) -> Result<PayloadStatus, Box<dyn Error>> {
+        self.provider
+            .request("engine_newPayloadV2", json!([payload]))
+    }
+
+    ///The `new_payload_v3()` function takes a Cancun payload, the versioned hashes of its blobs in order and the root
+    ///of the parent beacon block, and attempts to return its status after the execution client processed it as
+    ///`Ok(PayloadStatus)`. Returns an `Err()` on JSON-RPC errors, not on invalid payloads.
+    pub fn new_payload_v3(
+        &self,
+        payload: &ExecutionPayload,
+        versioned_hashes: &[H256],
+        parent_beacon_block_root: H256,
+    ) -> Result<PayloadStatus, Box<dyn Error>> {
+        self.provider.request(
+            "engine_newPayloadV3",
+            json!([payload, versioned_hashes, parent_beacon_block_root]),
+        )
+    }
+
+    ///The `forkchoice_updated_v1()` function takes the new forkchoice state and optional Paris payload attributes,
+    ///and attempts to update the head of the execution client, returning the status of the head and the id of the
+    ///payload being built as `Ok(ForkchoiceUpdated)`. Returns an `Err()` on JSON-RPC errors, e.g. if the attributes
+    ///are invalid.
+    pub fn forkchoice_updated_v1(
+        &self,
+        state: &ForkchoiceState,
+        attributes: Option<&PayloadAttributes>,
+    ) -> Result<ForkchoiceUpdated, Box<dyn Error>> {
+        self.forkchoice_updated("engine_forkchoiceUpdatedV1", state, attributes)
+    }
+
+    ///The `forkchoice_updated_v2()` function is `forkchoice_updated_v1()` with Paris or Shanghai payload attributes.
+    pub fn forkchoice_updated_v2(
+        &self,
+        state: &ForkchoiceState,
+        attributes: Option<&PayloadAttributes>,
+    ) -> Result<ForkchoiceUpdated, Box<dyn Error>> {
+        self.forkchoice_updated("engine_forkchoiceUpdatedV2", state, attributes)
+    }
+
+    ///The `forkchoice_updated_v3()` function is `forkchoice_updated_v1()` with Cancun payload attributes.
+    pub fn forkchoice_updated_v3(
+        &self,
+        state: &ForkchoiceState,
+        attributes: Option<&PayloadAttributes>,
+    ) -> Result<ForkchoiceUpdated, Box<dyn Error>> {
+        self.forkchoice_updated("engine_forkchoiceUpdatedV3", state, attributes)
+    }
+
+    fn forkchoice_updated(
+        &self,
+        method: &str,
+        state: &ForkchoiceState,
+        attributes: Option<&PayloadAttributes>,
+    ) -> Result<ForkchoiceUpdated, Box<dyn Error>> {
+        self.provider.request(method, json!([state, attributes]))
+    }
+
+    ///The `get_payload_v1()` function takes the id of a payload being built and attempts to return the Paris payload
+    ///as `Ok(ExecutionPayload)`. Returns an `Err()` on JSON-RPC errors, e.g. if the payload is unknown.
+    pub fn get_payload_v1(&self, payload_id: &Bytes) -> Result<ExecutionPayload, Box<dyn Error>> {
+        self.provider
+            .request("engine_getPayloadV1", json!([payload_id]))
+    }
+
+    ///The `get_payload_v2()` function takes the id of a payload being built and attempts to return the Paris or
+    ///Shanghai payload and its value as `Ok(BuiltPayload)`. Returns an `Err()` on JSON-RPC errors, e.g. if the payload
+    ///is unknown.
+    pub fn get_payload_v2(&self, payload_id: &Bytes) -> Result<BuiltPayload, Box<dyn Error>> {
+        self.provider
+            .request("engine_getPayloadV2", json!([payload_id]))
+    }
+
+    ///The `get_payload_v3()` function takes the id of a payload being built and attempts to return the Cancun payload,
+    ///its value and its blobs as `Ok(BuiltPayload)`. Returns an `Err()` on JSON-RPC errors, e.g. if the payload is
+    ///unknown.
+    pub fn get_payload_v3(&self, payload_id: &Bytes) -> Result<BuiltPayload, Box<dyn Error>> {
+        self.provider
+            .request("engine_getPayloadV3", json!([payload_id]))
+    }
+}
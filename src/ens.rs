@@ -0,0 +1,297 @@
+//!The ens module resolves Ethereum Name Service names through the ENS registry: the address a name points to, its text
+//!records and its avatar, including the `eip155:` avatar URIs pointing to an NFT the owner of the name holds.
+use crate::abi::{decode, encode, ParamType, Token, Tokenizable};
+use crate::nft::{Erc1155, Erc721};
+use crate::provider::{CallInput, Provider};
+use crate::types::{Address, BlockId, Bytes, H256, U256};
+use crate::utils::{function_selector, keccak256};
+
+use serde_json::Value;
+use std::error::Error;
+
+///The address the ENS registry is deployed at on mainnet and its testnets.
+pub const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+///The gateway `ipfs://` URIs are resolved through by default.
+pub const IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+///The `namehash()` function takes an ENS name and returns its node, i.e. the recursive hash of its labels identifying
+///it in the registry and its resolver. The name is lowercased, but not otherwise normalized.
+///## Example
+///```rust
+///use ethrs::ens::namehash;
+///
+///assert_eq!(
+///  hex::encode(namehash("eth").0),
+///  "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+///);
+///```
+pub fn namehash(name: &str) -> H256 {
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return H256(node);
+    }
+    for label in name.to_lowercase().rsplit('.') {
+        node = keccak256(&[&node[..], &keccak256(label.as_bytes())].concat());
+    }
+    H256(node)
+}
+
+///The `Ens` struct resolves names through the ENS registry. Names are resolved at the latest block unless another block
+///is set with `with_block()`.
+///## Example
+///```rust
+///use ethrs::ens::Ens;
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///# let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  let ens = Ens::new(provider);
+///  println!("{:?}", ens.resolve_name("vitalik.eth")?);
+///  println!("{:?}", ens.get_text("vitalik.eth", "url")?);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Ens {
+    provider: Provider,
+    registry: Address,
+    block: Option<BlockId>,
+    ipfs_gateway: String,
+}
+
+impl Ens {
+    ///The `Ens::new()` associated function takes a `Provider` and returns an `Ens` resolving names through the registry
+    ///at its canonical address, at the latest block.
+    pub fn new(provider: Provider) -> Ens {
+        Ens {
+            provider,
+            // the constant is a valid address
+            registry: ENS_REGISTRY.parse().unwrap_or_default(),
+            block: None,
+            ipfs_gateway: IPFS_GATEWAY.to_owned(),
+        }
+    }
+
+    ///The `with_registry()` function sets the address of the ENS registry, for chains where it is not deployed at its
+    ///canonical address.
+    pub fn with_registry(mut self, registry: Address) -> Ens {
+        self.registry = registry;
+        self
+    }
+
+    ///The `with_block()` function sets the block names are resolved at.
+    pub fn with_block(mut self, block: BlockId) -> Ens {
+        self.block = Some(block);
+        self
+    }
+
+    ///The `with_ipfs_gateway()` function sets the gateway `ipfs://` URIs of avatars are resolved through, e.g.
+    ///`https://cloudflare-ipfs.com/ipfs/`.
+    pub fn with_ipfs_gateway(mut self, ipfs_gateway: &str) -> Ens {
+        self.ipfs_gateway = ipfs_gateway.to_owned();
+        self
+    }
+
+    ///The `resolver()` function takes a name and attempts to return the address of its resolver as
+    ///`Ok(Some(Address))`. Returns `Ok(None)` if the name has no resolver and returns an `Err()` on JSON-RPC errors.
+    pub fn resolver(&self, name: &str) -> Result<Option<Address>, Box<dyn Error>> {
+        let resolver = self.call(
+            self.registry,
+            "resolver(bytes32)",
+            &[Token::FixedBytes(namehash(name).0.to_vec())],
+            ParamType::Address,
+        )?;
+        Ok(Some(Address::from_token(resolver)?).filter(|resolver| *resolver != Address::ZERO))
+    }
+
+    ///The `resolve_name()` function takes a name and attempts to return the address it points to as
+    ///`Ok(Some(Address))`. Returns `Ok(None)` if the name has no resolver or address, and returns an `Err()` on JSON-RPC
+    ///errors.
+    pub fn resolve_name(&self, name: &str) -> Result<Option<Address>, Box<dyn Error>> {
+        let Some(resolver) = self.resolver(name)? else {
+            return Ok(None);
+        };
+        let address = self.call(
+            resolver,
+            "addr(bytes32)",
+            &[Token::FixedBytes(namehash(name).0.to_vec())],
+            ParamType::Address,
+        )?;
+        Ok(Some(Address::from_token(address)?).filter(|address| *address != Address::ZERO))
+    }
+
+    ///The `get_text()` function takes a name and the key of a text record, e.g. `url`, `com.twitter` or `avatar`, and
+    ///attempts to return the record as `Ok(Some(String))`. Returns `Ok(None)` if the name has no resolver or the record
+    ///is not set, and returns an `Err()` on JSON-RPC errors, e.g. when the resolver does not support text records.
+    pub fn get_text(&self, name: &str, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        let Some(resolver) = self.resolver(name)? else {
+            return Ok(None);
+        };
+        let text = self.call(
+            resolver,
+            "text(bytes32,string)",
+            &[
+                Token::FixedBytes(namehash(name).0.to_vec()),
+                Token::String(key.to_owned()),
+            ],
+            ParamType::String,
+        )?;
+        Ok(Some(String::from_token(text)?).filter(|text| !text.is_empty()))
+    }
+
+    ///The `get_avatar()` function takes a name and attempts to return the URL of its avatar image as
+    ///`Ok(Some(String))`, following ENSIP-12. An `eip155:<chain id>/erc721:<contract>/<token id>` or `erc1155` avatar
+    ///record is resolved to the image in the metadata of the NFT, if it is held by the address of the name. `ipfs://`
+    ///URIs are resolved through the IPFS gateway. Returns `Ok(None)` if the name has no avatar or does not hold the NFT,
+    ///and returns an `Err()` on JSON-RPC or HTTP errors, if the avatar record is malformed, or if the NFT is on another
+    ///chain than the provider.
+    ///## Example
+    ///```rust
+    ///use ethrs::ens::Ens;
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///# let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  if let Some(avatar) = Ens::new(provider).get_avatar("vitalik.eth")? {
+    ///      println!("Avatar at {avatar}");
+    ///  }
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_avatar(&self, name: &str) -> Result<Option<String>, Box<dyn Error>> {
+        let Some(avatar) = self.get_text(name, "avatar")? else {
+            return Ok(None);
+        };
+        if !avatar.to_lowercase().starts_with("eip155:") {
+            return Ok(Some(self.gateway_url(&avatar)));
+        }
+
+        let invalid = || format!("Invalid avatar record of {name}: {avatar}");
+        let mut parts = avatar["eip155:".len()..].split('/');
+        let (Some(chain_id), Some(asset), Some(id), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid().into());
+        };
+        let (standard, contract) = asset.split_once(':').ok_or_else(invalid)?;
+        let contract: Address = contract.parse().map_err(|_| invalid())?;
+        let id = U256::from_dec_str(id).map_err(|_| invalid())?;
+        let chain_id: u64 = chain_id.parse().map_err(|_| invalid())?;
+        if chain_id != self.provider.chain_id()? {
+            return Err(format!("Avatar of {name} is an NFT on chain {chain_id}").into());
+        }
+        let Some(owner) = self.resolve_name(name)? else {
+            return Ok(None);
+        };
+
+        let uri = match standard.to_lowercase().as_str() {
+            "erc721" => {
+                let collection = self.erc721(contract);
+                if collection.owner_of(id)? != owner {
+                    return Ok(None);
+                }
+                collection.token_uri(id)?
+            }
+            "erc1155" => {
+                let collection = self.erc1155(contract);
+                if collection.balance_of(owner, id)?.is_zero() {
+                    return Ok(None);
+                }
+                // ERC-1155 URIs substitute the id as 64 hex digits
+                collection.uri(id)?.replace("{id}", &format!("{id:064x}"))
+            }
+            _ => return Err(invalid().into()),
+        };
+        let metadata = self.metadata(&uri)?;
+        match metadata["image"]
+            .as_str()
+            .or(metadata["image_url"].as_str())
+        {
+            Some(image) => Ok(Some(self.gateway_url(image))),
+            None => Err(format!("No image in the metadata of {avatar}").into()),
+        }
+    }
+
+    fn erc721(&self, contract: Address) -> Erc721 {
+        let collection = Erc721::new(contract, self.provider.clone());
+        match self.block {
+            Some(block) => collection.with_block(block),
+            None => collection,
+        }
+    }
+
+    fn erc1155(&self, contract: Address) -> Erc1155 {
+        let collection = Erc1155::new(contract, self.provider.clone());
+        match self.block {
+            Some(block) => collection.with_block(block),
+            None => collection,
+        }
+    }
+
+    fn gateway_url(&self, uri: &str) -> String {
+        match uri.strip_prefix("ipfs://") {
+            Some(path) => {
+                let path = path.strip_prefix("ipfs/").unwrap_or(path);
+                format!("{}{path}", self.ipfs_gateway)
+            }
+            None => uri.to_owned(),
+        }
+    }
+
+    fn metadata(&self, uri: &str) -> Result<Value, Box<dyn Error>> {
+        if let Some(data) = uri.strip_prefix("data:") {
+            let (media_type, data) = data
+                .split_once(',')
+                .ok_or_else(|| format!("Invalid data URI: {uri}"))?;
+            return Ok(match media_type.ends_with(";base64") {
+                true => serde_json::from_slice(&decode_base64(data)?)?,
+                false => serde_json::from_str(data)?,
+            });
+        }
+        Ok(reqwest::blocking::get(self.gateway_url(uri))?
+            .error_for_status()?
+            .json()?)
+    }
+
+    fn call(
+        &self,
+        to: Address,
+        signature: &str,
+        args: &[Token],
+        output: ParamType,
+    ) -> Result<Token, Box<dyn Error>> {
+        let tx =
+            CallInput::to(to).data([&function_selector(signature)[..], &encode(args)].concat());
+        let data: Bytes = self.provider.call(tx, self.block)?.parse()?;
+        decode(&[output], &data.0)?
+            .pop()
+            .ok_or_else(|| format!("Invalid {signature} returned by {to}").into())
+    }
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut decoded = Vec::with_capacity(data.len() / 4 * 3);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for byte in data.bytes().filter(|byte| *byte != b'=') {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return Err("Invalid base64 in data URI".into()),
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(decoded)
+}
@@ -0,0 +1,164 @@
+//!The erc20 module provides the `Token` type, which reads the state of an ERC-20 token contract and builds the calldata
+//!of its transfers and approvals, without an ABI.
+use crate::abi::{self, decode, encode, ParamType};
+use crate::provider::{CallInput, Provider};
+use crate::types::{Address, BlockId, Bytes, U256};
+use crate::utils::function_selector;
+
+use std::error::Error;
+
+///The `Token` struct is an ERC-20 token contract. Its reads are made at the latest block unless another block is set
+///with `with_block()`. `name()` and `symbol()` also read the `bytes32` strings returned by some early tokens, e.g. MKR.
+///## Example
+///```rust
+///use ethrs::erc20::Token;
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///# let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  let weth = Token::new("0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?, provider);
+///  assert_eq!(weth.symbol()?, "WETH");
+///  assert_eq!(weth.decimals()?, 18);
+///  println!("{}", weth.balance_of("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?)?);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Token {
+    address: Address,
+    provider: Provider,
+    block: Option<BlockId>,
+}
+
+impl Token {
+    ///The `Token::new()` associated function takes the address of a token contract and a `Provider`, and returns a
+    ///`Token` reading it at the latest block.
+    pub fn new(address: Address, provider: Provider) -> Token {
+        Token {
+            address,
+            provider,
+            block: None,
+        }
+    }
+
+    ///The `with_block()` function sets the block the token is read at.
+    pub fn with_block(mut self, block: BlockId) -> Token {
+        self.block = Some(block);
+        self
+    }
+
+    ///Returns the address of the token.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    ///The `balance_of()` function takes an address and attempts to return its balance as `Ok(U256)`. Returns an `Err()`
+    ///on JSON-RPC errors or if the return data is malformed.
+    pub fn balance_of(&self, owner: Address) -> Result<U256, Box<dyn Error>> {
+        self.call_uint("balanceOf(address)", &[abi::Token::Address(owner)])
+    }
+
+    ///The `allowance()` function takes an owner and a spender address, and attempts to return the amount the spender
+    ///may transfer from the owner as `Ok(U256)`. Returns an `Err()` on JSON-RPC errors or if the return data is
+    ///malformed.
+    pub fn allowance(&self, owner: Address, spender: Address) -> Result<U256, Box<dyn Error>> {
+        self.call_uint(
+            "allowance(address,address)",
+            &[abi::Token::Address(owner), abi::Token::Address(spender)],
+        )
+    }
+
+    ///The `total_supply()` function attempts to return the total supply of the token as `Ok(U256)`. Returns an `Err()`
+    ///on JSON-RPC errors or if the return data is malformed.
+    pub fn total_supply(&self) -> Result<U256, Box<dyn Error>> {
+        self.call_uint("totalSupply()", &[])
+    }
+
+    ///The `name()` function attempts to return the name of the token as `Ok(String)`. Returns an `Err()` on JSON-RPC
+    ///errors, e.g. if the token does not implement the optional `name()`, or if the return data is malformed.
+    pub fn name(&self) -> Result<String, Box<dyn Error>> {
+        self.call_string("name()")
+    }
+
+    ///The `symbol()` function attempts to return the symbol of the token as `Ok(String)`. Returns an `Err()` on
+    ///JSON-RPC errors, e.g. if the token does not implement the optional `symbol()`, or if the return data is malformed.
+    pub fn symbol(&self) -> Result<String, Box<dyn Error>> {
+        self.call_string("symbol()")
+    }
+
+    ///The `decimals()` function attempts to return the number of decimals of the token as `Ok(u8)`. Returns an `Err()`
+    ///on JSON-RPC errors, e.g. if the token does not implement the optional `decimals()`, or if the return data is
+    ///malformed.
+    pub fn decimals(&self) -> Result<u8, Box<dyn Error>> {
+        let decimals = self.call_uint("decimals()", &[])?;
+        match decimals <= U256::from(u8::MAX) {
+            true => Ok(decimals.as_u32() as u8),
+            false => Err(format!("Invalid decimals returned by {}", self.address).into()),
+        }
+    }
+
+    ///Returns the calldata of `transfer(to, amount)`, which transfers the amount from the sender of the transaction.
+    ///## Example
+    ///```rust
+    ///use ethrs::erc20::Token;
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let token = Token::new("0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?, Provider::new("http://localhost:8545"));
+    ///  let calldata = token.transfer_calldata("0x70997970c51812dc3a010c7d01b50e0d17dc79c8".parse()?, U256::from(1));
+    ///  assert_eq!(calldata.0[..4], [0xa9, 0x05, 0x9c, 0xbb]);
+    ///  assert_eq!(calldata.0.len(), 68);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn transfer_calldata(&self, to: Address, amount: U256) -> Bytes {
+        calldata(
+            "transfer(address,uint256)",
+            &[abi::Token::Address(to), abi::Token::Uint(amount)],
+        )
+    }
+
+    ///Returns the calldata of `approve(spender, amount)`, which allows the spender to transfer up to the amount from the
+    ///sender of the transaction.
+    pub fn approve_calldata(&self, spender: Address, amount: U256) -> Bytes {
+        calldata(
+            "approve(address,uint256)",
+            &[abi::Token::Address(spender), abi::Token::Uint(amount)],
+        )
+    }
+
+    fn call(&self, signature: &str, args: &[abi::Token]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let tx = CallInput::to(self.address).data(calldata(signature, args));
+        let output: Bytes = self.provider.call(tx, self.block)?.parse()?;
+        Ok(output.0)
+    }
+
+    fn call_uint(&self, signature: &str, args: &[abi::Token]) -> Result<U256, Box<dyn Error>> {
+        let output = self.call(signature, args)?;
+        match output.len() >= 32 {
+            true => Ok(U256::from_big_endian(&output[..32])),
+            false => Err(format!("Invalid {signature} returned by {}", self.address).into()),
+        }
+    }
+
+    fn call_string(&self, signature: &str) -> Result<String, Box<dyn Error>> {
+        let output = self.call(signature, &[])?;
+        // early tokens return a null-padded bytes32
+        if output.len() == 32 {
+            let end = output.iter().position(|&byte| byte == 0).unwrap_or(32);
+            return Ok(String::from_utf8(output[..end].to_vec())?);
+        }
+        match decode(&[ParamType::String], &output)?.pop() {
+            Some(abi::Token::String(value)) => Ok(value),
+            _ => Err(format!("Invalid {signature} returned by {}", self.address).into()),
+        }
+    }
+}
+
+fn calldata(signature: &str, args: &[abi::Token]) -> Bytes {
+    Bytes([&function_selector(signature)[..], &encode(args)].concat())
+}
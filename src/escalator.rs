@@ -0,0 +1,216 @@
+//!The escalator module replaces stuck pending transactions: the `GasEscalator` rebroadcasts a transaction with the same
+//!nonce and raised fees until it is mined, so that a transaction sent while fees were low still lands.
+use crate::nonce::is_nonce_too_low;
+use crate::provider::{Provider, TransactionReceipt};
+use crate::transaction::{SignedTransaction, TypedTransaction};
+use crate::types::{H256, U256};
+use crate::wallet::Wallet;
+
+use std::error::Error;
+use std::thread;
+use std::time::{Duration, Instant};
+
+///The `GasEscalator` struct sends the transactions of a wallet and escalates their fees while they are pending: every
+///`interval`, a pending transaction is replaced by one with the same nonce and fees raised by `bump_percent`, until one
+///of them is mined or the timeout elapses. Fees are not raised above the max fee per gas, if one is set.
+///## Example
+///```rust,no_run
+///use ethrs::escalator::GasEscalator;
+///use ethrs::provider::Provider;
+///use ethrs::transaction::{Eip1559Transaction, TypedTransaction};
+///use ethrs::types::U256;
+///use ethrs::wallet::Wallet;
+///use std::error::Error;
+///use std::time::Duration;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let wallet: Wallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse()?;
+///  let escalator = GasEscalator::new(provider.clone(), wallet.clone())
+///      .with_bump_percent(20)
+///      .with_interval(Duration::from_secs(24))
+///      .with_max_fee_per_gas(U256::from(100_000_000_000u64));
+///  let receipt = escalator.send_transaction(TypedTransaction::from(Eip1559Transaction {
+///      chain_id: 31337,
+///      nonce: provider.get_transaction_count(wallet.address(), None)? as u64,
+///      max_fee_per_gas: U256::from(1_000_000_000u64),
+///      max_priority_fee_per_gas: U256::from(1_000_000u64),
+///      gas_limit: 21000,
+///      to: Some(wallet.address()),
+///      ..Default::default()
+///  }))?;
+///  println!("Mined in block {:?}", receipt.map(|receipt| receipt.block_number));
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct GasEscalator {
+    provider: Provider,
+    wallet: Wallet,
+    bump_percent: u64,
+    interval: Duration,
+    max_fee_per_gas: Option<U256>,
+    timeout: Duration,
+    poll_interval: Duration,
+}
+
+impl GasEscalator {
+    ///The `GasEscalator::new()` associated function takes a `Provider` and the `Wallet` signing the transactions, and
+    ///returns a `GasEscalator` raising fees by 10% every 30 seconds, without a max fee, for up to ten minutes.
+    pub fn new(provider: Provider, wallet: Wallet) -> GasEscalator {
+        GasEscalator {
+            provider,
+            wallet,
+            bump_percent: 10,
+            interval: Duration::from_secs(30),
+            max_fee_per_gas: None,
+            timeout: Duration::from_secs(600),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    ///The `with_bump_percent()` function sets the percentage fees are raised by on every replacement. Most nodes reject
+    ///replacements raising fees by less than 10%.
+    pub fn with_bump_percent(mut self, bump_percent: u64) -> GasEscalator {
+        self.bump_percent = bump_percent;
+        self
+    }
+
+    ///The `with_interval()` function sets how long a transaction stays pending before it is replaced.
+    pub fn with_interval(mut self, interval: Duration) -> GasEscalator {
+        self.interval = interval;
+        self
+    }
+
+    ///The `with_max_fee_per_gas()` function sets the highest gas price or max fee per gas replacements pay. Once a
+    ///replacement would exceed it, the last transaction is waited for without further replacements.
+    pub fn with_max_fee_per_gas(mut self, max_fee_per_gas: U256) -> GasEscalator {
+        self.max_fee_per_gas = Some(max_fee_per_gas);
+        self
+    }
+
+    ///The `with_timeout()` function sets how long to wait for a transaction to be mined, polling every
+    ///`poll_interval`. Defaults to ten minutes, polled every second.
+    pub fn with_timeout(mut self, timeout: Duration, poll_interval: Duration) -> GasEscalator {
+        self.timeout = timeout;
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    ///The `replace_transaction()` function takes the hash of a pending transaction of the wallet and a percentage, and
+    ///attempts to replace it with the same transaction paying fees raised by the percentage, returning the replacement
+    ///as `Ok(SignedTransaction)` once it is sent. Returns an `Err()` on JSON-RPC errors, e.g. when the node rejects the
+    ///replacement as underpriced, or if the transaction is unknown, already mined or not sent by the wallet.
+    ///## Example
+    ///```rust,no_run
+    ///use ethrs::escalator::GasEscalator;
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let escalator = GasEscalator::new(
+    ///      Provider::new("http://localhost:8545"),
+    ///      "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse()?,
+    ///  );
+    ///  let replacement = escalator.replace_transaction(
+    ///      "0x10e8caafb752c4b611c51dfa784168eebbf1b2819523ea6e8cdf7452552ef6c3".parse()?,
+    ///      25,
+    ///  )?;
+    ///  println!("Replaced by {:?}", replacement.hash);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn replace_transaction(
+        &self,
+        old_txhash: H256,
+        bump_percent: u64,
+    ) -> Result<SignedTransaction, Box<dyn Error>> {
+        let mut transaction = self.pending(old_txhash)?;
+        transaction.bump_fees(bump_percent);
+        self.send(transaction)
+    }
+
+    ///The `send_transaction()` function takes an unsigned transaction, signs it with the wallet and sends it, replacing
+    ///it while it is pending, and attempts to return the receipt of whichever transaction is mined as
+    ///`Ok(Some(TransactionReceipt))`. Returns `Ok(None)` if none is mined before the timeout elapses and returns an
+    ///`Err()` on JSON-RPC errors.
+    pub fn send_transaction(
+        &self,
+        transaction: TypedTransaction,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>> {
+        let signed = self.send(transaction)?;
+        self.wait(signed.transaction, signed.hash)
+    }
+
+    ///The `escalate()` function takes the hash of a pending transaction of the wallet, and replaces it while it is
+    ///pending as `send_transaction()` does. Returns an `Err()` on JSON-RPC errors, or if the transaction is unknown,
+    ///already mined or not sent by the wallet.
+    pub fn escalate(&self, txhash: H256) -> Result<Option<TransactionReceipt>, Box<dyn Error>> {
+        let transaction = self.pending(txhash)?;
+        self.wait(transaction, txhash)
+    }
+
+    fn wait(
+        &self,
+        mut transaction: TypedTransaction,
+        txhash: H256,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>> {
+        let deadline = Instant::now() + self.timeout;
+        let mut next_replacement = Instant::now() + self.interval;
+        // any of the transactions sent may be the one mined
+        let mut txhashes = vec![txhash];
+        loop {
+            for txhash in &txhashes {
+                if let Some(receipt) = self.provider.get_transaction_receipt(*txhash)? {
+                    return Ok(Some(receipt));
+                }
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            if now >= next_replacement {
+                next_replacement = now + self.interval;
+                let mut replacement = transaction.clone();
+                replacement.bump_fees(self.bump_percent);
+                let max_fee_per_gas = replacement.max_fee_per_gas();
+                if self
+                    .max_fee_per_gas
+                    .map_or(true, |max| max_fee_per_gas <= max)
+                {
+                    match self.send(replacement.clone()) {
+                        Ok(signed) => {
+                            txhashes.push(signed.hash);
+                            transaction = replacement;
+                        }
+                        // one of the transactions was mined since the receipts were polled
+                        Err(err) if is_nonce_too_low(err.as_ref()) => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+            thread::sleep(self.poll_interval.min(deadline - now));
+        }
+    }
+
+    fn send(&self, transaction: TypedTransaction) -> Result<SignedTransaction, Box<dyn Error>> {
+        let signed = self.wallet.sign_transaction(transaction)?;
+        self.provider.send_raw_transaction(&signed.encode()?)?;
+        Ok(signed)
+    }
+
+    fn pending(&self, txhash: H256) -> Result<TypedTransaction, Box<dyn Error>> {
+        let transaction = self
+            .provider
+            .get_transaction_by_hash(txhash)?
+            .ok_or(format!("Transaction {txhash:?} not found"))?;
+        if transaction.block_number.is_some() {
+            return Err(format!("Transaction {txhash:?} is already mined").into());
+        }
+        if transaction.from != self.wallet.address() {
+            return Err(format!("Transaction {txhash:?} is not sent by the wallet").into());
+        }
+        TypedTransaction::try_from(&transaction)
+    }
+}
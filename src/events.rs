@@ -0,0 +1,185 @@
+//!The events module provides the `EventRegistry`, which decodes raw logs from any source, e.g. receipts, filters or
+//!streams, against every event ABI registered with it, so that decoding is centralized across an application.
+use crate::abi::{Abi, Event, Token};
+use crate::provider::Log;
+use crate::types::{Bytes, H256};
+
+use std::collections::HashMap;
+use std::error::Error;
+
+///The `DecodedEvent` struct is a log decoded as one of the registered events, with its parameters in the order of the
+///event inputs.
+#[derive(Debug, Clone)]
+pub struct DecodedEvent {
+    pub name: String,
+    pub signature: String,
+    pub params: Vec<(String, Token)>,
+    pub log: Log,
+}
+
+impl DecodedEvent {
+    ///Returns the value of the named parameter, if the event has it.
+    pub fn param(&self, name: &str) -> Option<&Token> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, token)| token)
+    }
+}
+
+///The `EventLog` enum is a log decoded by an `EventRegistry`, or the raw log if it matches none of the registered
+///events.
+#[derive(Debug, Clone)]
+pub enum EventLog {
+    Known(DecodedEvent),
+    Unknown(Log),
+}
+
+impl EventLog {
+    ///Returns the name of the event, if the log is known.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            EventLog::Known(event) => Some(&event.name),
+            EventLog::Unknown(_) => None,
+        }
+    }
+
+    ///Returns the raw log.
+    pub fn log(&self) -> &Log {
+        match self {
+            EventLog::Known(event) => &event.log,
+            EventLog::Unknown(log) => log,
+        }
+    }
+}
+
+///The `EventRegistry` struct holds event ABIs by topic. Events sharing a signature but indexing different parameters,
+///e.g. ERC-20 and ERC-721 `Transfer`, can both be registered: the first one the log decodes with is used. Anonymous
+///events cannot be identified by their topic and are not supported.
+///## Example
+///```rust
+///use ethrs::abi::{Abi, Token};
+///use ethrs::events::{EventLog, EventRegistry};
+///use ethrs::provider::Log;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let erc20: Abi = serde_json::from_str(r#"[{
+///      "type": "event",
+///      "name": "Transfer",
+///      "inputs": [
+///          {"name": "from", "type": "address", "indexed": true},
+///          {"name": "to", "type": "address", "indexed": true},
+///          {"name": "value", "type": "uint256", "indexed": false}
+///      ],
+///      "anonymous": false
+///  }]"#)?;
+///  let mut registry = EventRegistry::new();
+///  registry.register_abi(&erc20)?;
+///
+///  let log: Log = serde_json::from_value(serde_json::json!({
+///      "removed": false,
+///      "logIndex": "0x0",
+///      "transactionIndex": "0x0",
+///      "transactionHash": "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8",
+///      "blockHash": "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6",
+///      "blockNumber": "0x1",
+///      "address": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+///      "data": "0x000000000000000000000000000000000000000000000000000000000000002a",
+///      "topics": [
+///          "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+///          "0x000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+///          "0x0000000000000000000000000000000000000000000000000000000000000000"
+///      ]
+///  }))?;
+///  match registry.decode(log) {
+///      EventLog::Known(event) => assert_eq!(event.param("value"), Some(&Token::Uint(42.into()))),
+///      EventLog::Unknown(_) => panic!("Transfer is registered"),
+///  }
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default)]
+pub struct EventRegistry {
+    events: HashMap<H256, Vec<Event>>,
+}
+
+impl EventRegistry {
+    ///The `new()` function returns an empty `EventRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///The `register()` function attempts to register an event as `Ok(())`, ignoring events that are already
+    ///registered. Returns an `Err()` if the event is anonymous or has unknown parameter types.
+    pub fn register(&mut self, event: Event) -> Result<(), Box<dyn Error>> {
+        if event.anonymous {
+            return Err(format!("Anonymous event {} cannot be registered", event.name).into());
+        }
+        let events = self.events.entry(event.topic()?).or_default();
+        if !events.contains(&event) {
+            events.push(event);
+        }
+        Ok(())
+    }
+
+    ///The `register_abi()` function attempts to register every event of a JSON ABI, except anonymous ones, as `Ok(())`.
+    ///Returns an `Err()` if an event has unknown parameter types.
+    pub fn register_abi(&mut self, abi: &Abi) -> Result<(), Box<dyn Error>> {
+        for event in abi.events().filter(|event| !event.anonymous) {
+            self.register(event.clone())?;
+        }
+        Ok(())
+    }
+
+    ///Returns the events registered under a topic.
+    pub fn events(&self, topic: &H256) -> &[Event] {
+        self.events.get(topic).map_or(&[], Vec::as_slice)
+    }
+
+    ///The `decode()` function takes a log and returns it decoded as the first registered event it is an instance of, or
+    ///as `EventLog::Unknown` otherwise.
+    pub fn decode(&self, log: Log) -> EventLog {
+        let data: Bytes = match log.data.parse() {
+            Ok(data) => data,
+            Err(_) => return EventLog::Unknown(log),
+        };
+        let events = match log.topics.first() {
+            Some(topic) => self.events(topic),
+            None => &[],
+        };
+        for event in events {
+            if let (Ok(tokens), Ok(signature)) =
+                (event.decode_log(&log.topics, &data), event.signature())
+            {
+                return EventLog::Known(DecodedEvent {
+                    name: event.name.clone(),
+                    signature,
+                    params: event
+                        .inputs
+                        .iter()
+                        .map(|input| input.name.clone())
+                        .zip(tokens)
+                        .collect(),
+                    log,
+                });
+            }
+        }
+        EventLog::Unknown(log)
+    }
+
+    ///The `decode_logs()` function takes any collection of logs and returns them decoded, in order.
+    pub fn decode_logs(&self, logs: impl IntoIterator<Item = Log>) -> Vec<EventLog> {
+        logs.into_iter().map(|log| self.decode(log)).collect()
+    }
+
+    ///Returns the number of registered events.
+    pub fn len(&self) -> usize {
+        self.events.values().map(Vec::len).sum()
+    }
+
+    ///Returns `true` if no event is registered.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
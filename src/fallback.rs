@@ -0,0 +1,250 @@
+//!The fallback module provides the `FallbackProvider`, which sends requests to the first healthy endpoint of an ordered
+//!list, failing over to the next one when an endpoint errors or times out, and `find_transaction()`, which looks a
+//!transaction up on several endpoints.
+use serde::{Deserialize, Serialize};
+
+use crate::provider::{Provider, Transaction, Transport};
+use crate::types::H256;
+
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+///The `EndpointHealth` struct tracks the outcomes of the requests sent to an endpoint. Only transport errors, e.g.
+///connection failures, timeouts or malformed responses, count as failures: JSON-RPC error responses do not.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub successes: u64,
+    pub failures: u64,
+    pub consecutive_failures: u64,
+    pub last_error: Option<String>,
+}
+
+impl EndpointHealth {
+    ///Returns `true` if the last request sent to the endpoint, if any, succeeded.
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_failures == 0
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    active: usize,
+    since: Instant,
+    health: Vec<EndpointHealth>,
+}
+
+///The `FallbackProvider` struct sends every request to its active endpoint, which is the first one initially. When the
+///active endpoint fails, the request is retried on the following endpoints in order, wrapping around, and the first
+///one to succeed becomes active. With `with_fail_back()`, requests go to the first endpoint again once the active one
+///has been active for the given duration. Clones share the active endpoint and health.
+///
+///A `FallbackProvider` is a `Transport`: `provider()` returns a `Provider` exposing every JSON-RPC method through it.
+///## Example
+///```rust
+///use ethrs::fallback::FallbackProvider;
+///use std::time::Duration;
+///
+///let fallback = FallbackProvider::new(&["https://rpc.sepolia.org", "https://ethereum-sepolia-rpc.publicnode.com"])
+///    .with_fail_back(Duration::from_secs(60));
+///let provider = fallback.provider();
+///if provider.block_number().is_ok() {
+///    println!("served by {}", fallback.active().url());
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct FallbackProvider {
+    endpoints: Arc<Vec<Provider>>,
+    fail_back: Option<Duration>,
+    state: Arc<Mutex<State>>,
+}
+
+impl FallbackProvider {
+    ///The `FallbackProvider::new()` associated function takes an ordered list of HTTP(S) JSON-RPC URLs and returns a
+    ///`FallbackProvider` over them. Panics if the list is empty.
+    pub fn new(urls: &[&str]) -> FallbackProvider {
+        FallbackProvider::from_providers(urls.iter().map(|url| Provider::new(url)).collect())
+    }
+
+    ///The `FallbackProvider::from_providers()` associated function takes an ordered list of providers, e.g. built with
+    ///timeouts or authentication, and returns a `FallbackProvider` over them. Panics if the list is empty.
+    pub fn from_providers(providers: Vec<Provider>) -> FallbackProvider {
+        assert!(!providers.is_empty(), "FallbackProvider needs an endpoint");
+        let health = providers
+            .iter()
+            .map(|provider| EndpointHealth {
+                url: provider.url().to_owned(),
+                ..Default::default()
+            })
+            .collect();
+        FallbackProvider {
+            endpoints: Arc::new(providers),
+            fail_back: None,
+            state: Arc::new(Mutex::new(State {
+                active: 0,
+                since: Instant::now(),
+                health,
+            })),
+        }
+    }
+
+    ///Sets how long a fallback endpoint stays active before requests go to the first endpoint again.
+    pub fn with_fail_back(mut self, after: Duration) -> FallbackProvider {
+        self.fail_back = Some(after);
+        self
+    }
+
+    ///Returns a `Provider` sending its requests through the `FallbackProvider`.
+    pub fn provider(&self) -> Provider {
+        Provider::new(self.endpoints[0].url()).with_transport(self.clone())
+    }
+
+    ///Returns the active endpoint.
+    pub fn active(&self) -> &Provider {
+        &self.endpoints[self.lock().active]
+    }
+
+    ///The `find_transaction()` function takes a transaction hash and attempts to find the transaction on any endpoint,
+    ///as the `find_transaction()` function of this module does, without searching transaction pools.
+    pub fn find_transaction(
+        &self,
+        txhash: H256,
+    ) -> Result<Option<FoundTransaction>, Box<dyn Error>> {
+        find_transaction(&self.endpoints, txhash, false)
+    }
+
+    ///Returns the health of every endpoint, in order.
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        self.lock().health.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Transport for FallbackProvider {
+    fn send(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>> {
+        let start = {
+            let state = self.lock();
+            match self.fail_back {
+                Some(after) if state.since.elapsed() >= after => 0,
+                _ => state.active,
+            }
+        };
+        let mut errors = vec![];
+        for index in (start..self.endpoints.len()).chain(0..start) {
+            // the lock is not held during requests, so that concurrent requests are not serialized
+            let result = self.endpoints[index].send(payload);
+            let mut state = self.lock();
+            let health = &mut state.health[index];
+            match result {
+                Ok(response) => {
+                    health.successes += 1;
+                    health.consecutive_failures = 0;
+                    // a failed attempt to fail back also restarts the fail back delay
+                    if state.active != index || index != start {
+                        state.active = index;
+                        state.since = Instant::now();
+                    }
+                    return Ok(response);
+                }
+                Err(err) => {
+                    health.failures += 1;
+                    health.consecutive_failures += 1;
+                    health.last_error = Some(err.to_string());
+                    errors.push(err.to_string());
+                }
+            }
+        }
+        Err(format!("All endpoints failed: {}", errors.join(", ")).into())
+    }
+}
+
+///The `FoundTransaction` struct is a transaction found by `find_transaction()`, along with the index of the provider
+///that knew it and whether it was found in its transaction pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FoundTransaction {
+    pub transaction: Transaction,
+    pub provider: usize,
+    pub from_txpool: bool,
+}
+
+///The `find_transaction()` function takes a list of providers and a transaction hash, and attempts to return the
+///transaction from the first provider that knows it as `Ok(Some(FoundTransaction))`, e.g. when it was broadcast through
+///another endpoint and has not reached the first one yet. The providers are asked with `eth_getTransactionByHash` in
+///order, then, if `search_txpool` is set, the pending and queued transactions of their `txpool_content` are searched,
+///as some nodes only return transactions they have mined or fully validated. Returns `Ok(None)` if no provider knows
+///the transaction, and an `Err()` if every provider failed.
+///## Example
+///```rust
+///use ethrs::fallback::find_transaction;
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let providers = [Provider::new("https://rpc.sepolia.org"), Provider::new("https://ethereum-sepolia-rpc.publicnode.com")];
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///#  let providers = [provider];
+///  let txhash = "0x6648b858a3d2b716d4c05c5d611844eb9827e2eea5bfc9db7a92187afd4d8c17".parse()?;
+///  if let Some(found) = find_transaction(&providers, txhash, true)? {
+///      println!("found by provider {}: {:?}", found.provider, found.transaction.block_number);
+///  }
+///  Ok(())
+///}
+///```
+pub fn find_transaction(
+    providers: &[Provider],
+    txhash: H256,
+    search_txpool: bool,
+) -> Result<Option<FoundTransaction>, Box<dyn Error>> {
+    let mut errors = vec![];
+    for (index, provider) in providers.iter().enumerate() {
+        match provider.get_transaction_by_hash(txhash) {
+            Ok(Some(transaction)) => {
+                return Ok(Some(FoundTransaction {
+                    transaction,
+                    provider: index,
+                    from_txpool: false,
+                }))
+            }
+            Ok(None) => {}
+            Err(err) => errors.push(format!("{}: {err}", provider.url())),
+        }
+    }
+    if search_txpool {
+        for (index, provider) in providers.iter().enumerate() {
+            // most public endpoints do not expose the txpool namespace
+            let Ok(content) =
+                provider.request::<serde_json::Value>("txpool_content", serde_json::json!([]))
+            else {
+                continue;
+            };
+            let transaction = ["pending", "queued"]
+                .iter()
+                .filter_map(|pool| content[pool].as_object())
+                .flat_map(|senders| senders.values())
+                .filter_map(|nonces| nonces.as_object())
+                .flat_map(|nonces| nonces.values())
+                .find(|transaction| {
+                    transaction["hash"]
+                        .as_str()
+                        .and_then(|hash| hash.parse::<H256>().ok())
+                        == Some(txhash)
+                });
+            if let Some(transaction) = transaction {
+                return Ok(Some(FoundTransaction {
+                    transaction: serde_json::from_value(transaction.clone())?,
+                    provider: index,
+                    from_txpool: true,
+                }));
+            }
+        }
+    }
+    match errors.len() == providers.len() && !providers.is_empty() {
+        true => Err(format!("All providers failed: {}", errors.join(", ")).into()),
+        false => Ok(None),
+    }
+}
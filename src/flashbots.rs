@@ -0,0 +1,219 @@
+//!The flashbots module submits transactions privately to the Flashbots relay, or to any builder exposing its API:
+//!bundles with `eth_sendBundle`, their simulation with `eth_callBundle` and single transactions with
+//!`eth_sendPrivateTransaction`. Every request is authenticated with the `X-Flashbots-Signature` header, signed by a local
+//!wallet which identifies the searcher to the relay and does not need to hold funds.
+use primitive_types::U256;
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::provider::Provider;
+use crate::signing::RequestSigner;
+use crate::types::{Address, BlockId, Bytes, H256};
+use crate::utils::keccak256;
+use crate::wallet::Wallet;
+
+use std::error::Error;
+
+///The URL of the Flashbots relay on mainnet.
+pub const FLASHBOTS_RELAY: &str = "https://relay.flashbots.net";
+
+///The `FlashbotsSigner` struct is the `RequestSigner` authenticating requests to the relay: the
+///`X-Flashbots-Signature` header is `<address>:<signature>`, the signature being the EIP-191 signature by the wallet of
+///the hex-encoded Keccak-256 hash of the body.
+#[derive(Debug, Clone)]
+pub struct FlashbotsSigner {
+    wallet: Wallet,
+}
+
+impl FlashbotsSigner {
+    ///The `FlashbotsSigner::new()` associated function takes the wallet identifying the searcher and returns a signer.
+    pub fn new(wallet: Wallet) -> FlashbotsSigner {
+        FlashbotsSigner { wallet }
+    }
+
+    ///The `signature()` function takes a request body and returns the value of the `X-Flashbots-Signature` header sent
+    ///with it.
+    pub fn signature(&self, body: &[u8]) -> String {
+        let digest = format!("0x{}", hex::encode(keccak256(body)));
+        let signature = self.wallet.sign_message(digest.as_bytes());
+        format!("{}:{signature}", self.wallet.address())
+    }
+}
+
+impl RequestSigner for FlashbotsSigner {
+    fn sign(&self, body: &[u8]) -> Result<HeaderMap, Box<dyn Error>> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-flashbots-signature",
+            HeaderValue::from_str(&self.signature(body))?,
+        );
+        Ok(headers)
+    }
+}
+
+///The `Bundle` struct is a bundle of signed transactions sent with `eth_sendBundle`, to be included atomically and in
+///order in the given block. Transactions listed in `reverting_tx_hashes` may revert without invalidating the bundle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    pub txs: Vec<Bytes>,
+    #[serde(with = "crate::utils::quantity")]
+    pub block_number: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reverting_tx_hashes: Vec<H256>,
+}
+
+///The `CallBundle` struct is a bundle simulated with `eth_callBundle` as if it were included in the given block, on top
+///of the state of `state_block_number`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundle {
+    pub txs: Vec<Bytes>,
+    #[serde(with = "crate::utils::quantity")]
+    pub block_number: u64,
+    pub state_block_number: BlockId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+///The `CallBundleResult` struct is the result of simulating a bundle with `eth_callBundle`. Amounts are in wei.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleResult {
+    pub bundle_hash: H256,
+    #[serde(with = "decimal")]
+    pub bundle_gas_price: U256,
+    #[serde(with = "decimal")]
+    pub coinbase_diff: U256,
+    #[serde(with = "decimal")]
+    pub eth_sent_to_coinbase: U256,
+    #[serde(with = "decimal")]
+    pub gas_fees: U256,
+    pub results: Vec<CallBundleTransaction>,
+    pub state_block_number: u64,
+    pub total_gas_used: u64,
+}
+
+///The `CallBundleTransaction` struct is the result of a transaction of a bundle simulated with `eth_callBundle`. The
+///`error` and `revert` reason are set if it reverted, and `value` holds its return data otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleTransaction {
+    pub tx_hash: H256,
+    pub from_address: Address,
+    pub to_address: Option<Address>,
+    #[serde(with = "decimal")]
+    pub coinbase_diff: U256,
+    #[serde(with = "decimal")]
+    pub eth_sent_to_coinbase: U256,
+    #[serde(with = "decimal")]
+    pub gas_fees: U256,
+    #[serde(with = "decimal")]
+    pub gas_price: U256,
+    pub gas_used: u64,
+    #[serde(default)]
+    pub value: Option<Bytes>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub revert: Option<String>,
+}
+
+///The `Flashbots` struct sends bundles and private transactions to a relay, signing every request with a
+///`FlashbotsSigner`.
+///## Example
+///```rust,no_run
+///use ethrs::flashbots::{Bundle, Flashbots, FLASHBOTS_RELAY};
+///use ethrs::provider::Provider;
+///use ethrs::wallet::Wallet;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let flashbots = Flashbots::new(FLASHBOTS_RELAY, Wallet::random())?;
+///  let signed_tx = std::fs::read_to_string("signed_tx.hex")?.trim().parse()?;
+///  let target = provider.block_number()? + 1;
+///  let bundle = Bundle {
+///      txs: vec![signed_tx],
+///      block_number: target,
+///      ..Default::default()
+///  };
+///  println!("Sent bundle {:?} for block {target}", flashbots.send_bundle(&bundle)?);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Flashbots {
+    relay: Provider,
+}
+
+impl Flashbots {
+    ///The `Flashbots::new()` associated function takes the URL of the relay and the wallet signing the requests, and
+    ///attempts to return a `Flashbots` client as `Ok(Flashbots)`. Returns an `Err()` if the HTTP client cannot be
+    ///initialized.
+    pub fn new(relay_url: &str, wallet: Wallet) -> Result<Flashbots, Box<dyn Error>> {
+        let relay = Provider::builder(relay_url)
+            .signer(FlashbotsSigner::new(wallet))
+            .build()?;
+        Ok(Flashbots { relay })
+    }
+
+    ///The `send_bundle()` function takes a bundle and attempts to submit it to the relay, returning its hash as
+    ///`Ok(H256)`. Returns an `Err()` on JSON-RPC errors, e.g. if the bundle is malformed. The bundle being accepted
+    ///does not mean it will be included.
+    pub fn send_bundle(&self, bundle: &Bundle) -> Result<H256, Box<dyn Error>> {
+        let result: serde_json::Value = self.relay.request("eth_sendBundle", json!([bundle]))?;
+        Ok(serde_json::from_value(result["bundleHash"].clone())?)
+    }
+
+    ///The `call_bundle()` function takes a bundle to simulate and attempts to return the result of simulating it as
+    ///`Ok(CallBundleResult)`. Returns an `Err()` on JSON-RPC errors, e.g. if a transaction is invalid, but not if a
+    ///transaction reverts.
+    pub fn call_bundle(&self, bundle: &CallBundle) -> Result<CallBundleResult, Box<dyn Error>> {
+        self.relay.request("eth_callBundle", json!([bundle]))
+    }
+
+    ///The `send_private_transaction()` function takes a signed transaction and the last block it may be included in,
+    ///defaulting to 25 blocks after the current one on the relay, and attempts to submit it privately to the builders,
+    ///returning its hash as `Ok(H256)`. Returns an `Err()` on JSON-RPC errors.
+    pub fn send_private_transaction(
+        &self,
+        tx: &Bytes,
+        max_block_number: Option<u64>,
+    ) -> Result<H256, Box<dyn Error>> {
+        let mut params = json!({ "tx": tx });
+        if let Some(max_block_number) = max_block_number {
+            params["maxBlockNumber"] = json!(format!("{max_block_number:#x}"));
+        }
+        self.relay
+            .request("eth_sendPrivateTransaction", json!([params]))
+    }
+
+    ///The `cancel_private_transaction()` function takes the hash of a private transaction and attempts to stop its
+    ///submission to the builders, returning whether it was cancelled as `Ok(bool)`. Returns an `Err()` on JSON-RPC
+    ///errors.
+    pub fn cancel_private_transaction(&self, txhash: H256) -> Result<bool, Box<dyn Error>> {
+        self.relay.request(
+            "eth_cancelPrivateTransaction",
+            json!([{ "txHash": txhash }]),
+        )
+    }
+}
+
+///Amounts in relay responses are decimal strings.
+mod decimal {
+    use primitive_types::U256;
+    use serde::{de, Deserialize, Deserializer};
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        U256::from_dec_str(&value).map_err(|_| de::Error::custom(format!("Invalid amount {value}")))
+    }
+}
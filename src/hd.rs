@@ -0,0 +1,180 @@
+//!The hd module derives keys from BIP-39 mnemonics along BIP-32 derivation paths, as HD wallets do, so that any number
+//!of accounts can be recovered from a single seed phrase. Ethereum accounts are derived along `m/44'/60'/0'/0/<index>`,
+//!as specified by BIP-44.
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use k256::{Scalar, SecretKey};
+use rand_core::{OsRng, RngCore};
+use sha2::Sha512;
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+///The bit set in the indices of hardened derivation steps.
+pub const HARDENED: u32 = 0x8000_0000;
+
+///The `DerivationPath` struct is a BIP-32 derivation path, e.g. `m/44'/60'/0'/0/0`. Hardened steps are parsed with a
+///trailing `'`, `h` or `H`, and displayed with a `'`.
+///## Example
+///```rust
+///use ethrs::hd::DerivationPath;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let path: DerivationPath = "m/44h/60h/0h/0/2".parse()?;
+///  assert_eq!(path, DerivationPath::ethereum(2));
+///  assert_eq!(path.to_string(), "m/44'/60'/0'/0/2");
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DerivationPath(pub Vec<u32>);
+
+impl DerivationPath {
+    ///The `DerivationPath::ethereum()` associated function takes an account index and returns its BIP-44 path, i.e.
+    ///`m/44'/60'/0'/0/<index>`, as used by MetaMask, Ledger Live and most other wallets.
+    pub fn ethereum(index: u32) -> DerivationPath {
+        DerivationPath(vec![44 | HARDENED, 60 | HARDENED, HARDENED, 0, index])
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid derivation path: {s:?}");
+        let mut steps = s.split('/');
+        if steps.next() != Some("m") {
+            return Err(invalid().into());
+        }
+        steps
+            .map(|step| {
+                let (index, hardened) = match step.strip_suffix(['\'', 'h', 'H']) {
+                    Some(index) => (index, HARDENED),
+                    None => (step, 0),
+                };
+                // u32 parsing would accept a leading '+'
+                if !index.bytes().all(|byte| byte.is_ascii_digit()) {
+                    return Err(invalid().into());
+                }
+                match index.parse::<u32>() {
+                    Ok(index) if index < HARDENED => Ok(index | hardened),
+                    _ => Err(invalid().into()),
+                }
+            })
+            .collect::<Result<_, Self::Err>>()
+            .map(DerivationPath)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+        for index in &self.0 {
+            match index & HARDENED {
+                0 => write!(f, "/{index}")?,
+                _ => write!(f, "/{}'", index & !HARDENED)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+///The `generate_mnemonic()` function takes a number of words, i.e. 12, 15, 18, 21 or 24, and attempts to return a new
+///English mnemonic with entropy from the OS random number generator as `Ok(String)`. Returns an `Err()` for other
+///numbers of words.
+///## Example
+///```rust
+///use ethrs::hd::{generate_mnemonic, mnemonic_to_seed};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let mnemonic = generate_mnemonic(12)?;
+///  assert_eq!(mnemonic.split(' ').count(), 12);
+///  assert!(mnemonic_to_seed(&mnemonic, "").is_ok());
+///  Ok(())
+///}
+///```
+pub fn generate_mnemonic(words: usize) -> Result<String, Box<dyn Error>> {
+    if !matches!(words, 12 | 15 | 18 | 21 | 24) {
+        return Err(format!("Invalid mnemonic length: {words} words").into());
+    }
+    let mut entropy = vec![0u8; words / 3 * 4];
+    OsRng.fill_bytes(&mut entropy);
+    Ok(Mnemonic::from_entropy(&entropy)?.to_string())
+}
+
+///The `mnemonic_to_seed()` function takes an English BIP-39 mnemonic and a passphrase, which is empty unless one was
+///chosen with the mnemonic, and attempts to return the 64-byte seed it encodes as `Ok([u8; 64])`. Returns an `Err()` if
+///a word is not in the English word list or if the checksum of the mnemonic does not match.
+pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> Result<[u8; 64], Box<dyn Error>> {
+    let mnemonic = Mnemonic::parse_in(bip39::Language::English, mnemonic)
+        .map_err(|err| format!("Invalid mnemonic: {err}"))?;
+    Ok(mnemonic.to_seed(passphrase))
+}
+
+///The `derive_key()` function takes a seed and a derivation path, and attempts to return the private key derived from
+///the seed along the path as `Ok([u8; 32])`, as specified by BIP-32. Returns an `Err()` if the seed is not between 16
+///and 64 bytes long, or in the astronomically unlikely case that a step derives an invalid key.
+///## Example
+///```rust
+///use ethrs::hd::{derive_key, mnemonic_to_seed, DerivationPath};
+///use ethrs::wallet::Wallet;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let seed = mnemonic_to_seed("test test test test test test test test test test test junk", "")?;
+///  let key = derive_key(&seed, &DerivationPath::ethereum(0))?;
+///  assert_eq!(Wallet::from_private_key(&key)?.address().to_string(), "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+///  Ok(())
+///}
+///```
+pub fn derive_key(seed: &[u8], path: &DerivationPath) -> Result<[u8; 32], Box<dyn Error>> {
+    if !(16..=64).contains(&seed.len()) {
+        return Err(format!("Invalid seed length: {} bytes", seed.len()).into());
+    }
+    let (mut key, mut chain_code) = split(hmac_sha512(b"Bitcoin seed", &[seed]));
+    let mut secret = secret_key(&key)?;
+    for &index in &path.0 {
+        let public_key;
+        let data: &[u8] = match index & HARDENED {
+            0 => {
+                public_key = secret.public_key().to_encoded_point(true);
+                public_key.as_bytes()
+            }
+            _ => &[&[0u8][..], &key].concat(),
+        };
+        let (tweak, child_chain_code) =
+            split(hmac_sha512(&chain_code, &[data, &index.to_be_bytes()]));
+        let tweak: Option<Scalar> = Scalar::from_repr(tweak.into()).into();
+        let child = tweak.ok_or("Invalid derived key")? + secret.to_nonzero_scalar().as_ref();
+        key = child.to_bytes().into();
+        secret = secret_key(&key)?;
+        chain_code = child_chain_code;
+    }
+    Ok(key)
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    // HMAC accepts keys of any length
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC key");
+    for data in data {
+        mac.update(data);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+fn split(bytes: [u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&bytes[..32]);
+    right.copy_from_slice(&bytes[32..]);
+    (left, right)
+}
+
+fn secret_key(key: &[u8; 32]) -> Result<SecretKey, Box<dyn Error>> {
+    Ok(SecretKey::from_slice(key).map_err(|_| "Invalid derived key")?)
+}
@@ -0,0 +1,192 @@
+//!The indexer module provides building blocks for log indexers, starting with the `Coordinator`, which backfills
+//!historical logs while tailing new blocks and hands over from one to the other without gaps or duplicates.
+use crate::provider::{Filter, Log, Provider};
+use crate::types::BlockId;
+
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+// The number of live batches buffered while the backfill catches up, after which live tailing waits for it.
+const LIVE_BUFFER: usize = 16;
+
+///The `Phase` enum tells whether a batch was fetched by the historical backfill or by live tailing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Backfill,
+    Live,
+}
+
+///The `Batch` struct holds the logs matching the filter of a `Coordinator` in an inclusive block range.
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub phase: Phase,
+    pub logs: Vec<Log>,
+}
+
+///The `Coordinator` struct indexes the logs matching a filter from a starting block onwards. On `run()`, it fixes a
+///boundary block at the (confirmed) chain head, then backfills up to the boundary while a second thread tails the
+///blocks after it. Batches are handed to the handler strictly in order: every batch starts at the block after the end
+///of the previous one, so the handoff at the boundary has neither gaps nor duplicates.
+///## Example
+///```rust,no_run
+///use ethrs::indexer::Coordinator;
+///use ethrs::provider::{Filter, Provider};
+///use std::error::Error;
+///use std::sync::atomic::AtomicBool;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let filter = Filter {
+///      address: vec!["0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?],
+///      ..Default::default()
+///  };
+///  let stop = AtomicBool::new(false);
+///  Coordinator::new(provider, filter, 17000000)
+///      .with_batch_size(500)
+///      .with_confirmations(2)
+///      .run(&stop, |batch| {
+///          println!("{:?} {}..={}: {} logs", batch.phase, batch.from_block, batch.to_block, batch.logs.len());
+///          Ok(())
+///      })
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Coordinator {
+    provider: Provider,
+    filter: Filter,
+    from_block: u64,
+    batch_size: u64,
+    confirmations: u64,
+    poll_interval: Duration,
+}
+
+impl Coordinator {
+    ///The `Coordinator::new()` associated function takes a `Provider`, the filter of the logs to index and the first
+    ///block to index, and returns a coordinator fetching up to 1000 blocks per batch, following the chain head and
+    ///polling every second. The block range of the filter is ignored.
+    pub fn new(provider: Provider, filter: Filter, from_block: u64) -> Coordinator {
+        Coordinator {
+            provider,
+            filter,
+            from_block,
+            batch_size: 1000,
+            confirmations: 0,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    ///Sets the maximum number of blocks fetched per `eth_getLogs` request.
+    pub fn with_batch_size(mut self, batch_size: u64) -> Coordinator {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    ///Sets how many blocks behind the chain head indexing stays, to avoid indexing blocks that may be reorganized.
+    pub fn with_confirmations(mut self, confirmations: u64) -> Coordinator {
+        self.confirmations = confirmations;
+        self
+    }
+
+    ///Sets how often live tailing polls for new blocks.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Coordinator {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    fn head(&self) -> Result<u64, Box<dyn Error>> {
+        let head = self.provider.block_number()?;
+        Ok(head.saturating_sub(self.confirmations))
+    }
+
+    fn fetch(&self, from_block: u64, to_block: u64, phase: Phase) -> Result<Batch, Box<dyn Error>> {
+        let logs = self.provider.get_logs(&Filter {
+            from_block: Some(BlockId::Number(from_block)),
+            to_block: Some(BlockId::Number(to_block)),
+            block_hash: None,
+            ..self.filter.clone()
+        })?;
+        Ok(Batch {
+            from_block,
+            to_block,
+            phase,
+            logs,
+        })
+    }
+
+    ///The `run()` function indexes logs until `stop` is set, handing every batch to the handler in block order, and
+    ///returns `Ok(())` once stopped. Returns an `Err()` on JSON-RPC errors or as soon as the handler fails. `stop` is
+    ///only read, so it can be shared with other coordinators.
+    pub fn run(
+        &self,
+        stop: &AtomicBool,
+        mut handler: impl FnMut(Batch) -> Result<(), Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let boundary = self.head()?;
+        let done = AtomicBool::new(false);
+        thread::scope(|scope| {
+            let (sender, receiver) = sync_channel::<Result<Batch, String>>(LIVE_BUFFER);
+            let done = &done;
+            // live tailing starts right after the boundary and buffers its batches until the backfill catches up
+            scope.spawn(move || {
+                let mut next = boundary.max(self.from_block.saturating_sub(1)) + 1;
+                while !stop.load(Ordering::Relaxed) && !done.load(Ordering::Relaxed) {
+                    let result = self.head().and_then(|head| match head >= next {
+                        true => {
+                            let to_block = head.min(next + self.batch_size - 1);
+                            self.fetch(next, to_block, Phase::Live).map(Some)
+                        }
+                        false => Ok(None),
+                    });
+                    match result {
+                        Ok(Some(batch)) => {
+                            next = batch.to_block + 1;
+                            if sender.send(Ok(batch)).is_err() {
+                                return;
+                            }
+                        }
+                        Ok(None) => thread::sleep(self.poll_interval),
+                        Err(err) => {
+                            let _ = sender.send(Err(err.to_string()));
+                            return;
+                        }
+                    }
+                }
+            });
+
+            let result = self.backfill(boundary, stop, &mut handler).and_then(|_| {
+                while !stop.load(Ordering::Relaxed) {
+                    match receiver.recv_timeout(self.poll_interval) {
+                        Ok(batch) => handler(batch?)?,
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                Ok(())
+            });
+            // makes the live thread exit even if the backfill or the handler failed, and unblocks it on a full buffer
+            done.store(true, Ordering::Relaxed);
+            drop(receiver);
+            result
+        })
+    }
+
+    fn backfill(
+        &self,
+        boundary: u64,
+        stop: &AtomicBool,
+        handler: &mut impl FnMut(Batch) -> Result<(), Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut next = self.from_block;
+        while next <= boundary && !stop.load(Ordering::Relaxed) {
+            let to_block = boundary.min(next + self.batch_size - 1);
+            handler(self.fetch(next, to_block, Phase::Backfill)?)?;
+            next = to_block + 1;
+        }
+        Ok(())
+    }
+}
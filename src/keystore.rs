@@ -0,0 +1,313 @@
+//!The keystore module reads and writes encrypted JSON keystore files, as specified by Web3 Secret Storage version 3 and
+//!written by Geth, Clef and most wallets, so that private keys can be kept out of configuration files.
+use crate::types::Address;
+use crate::utils::keccak256;
+use crate::wallet::Wallet;
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+///The length of the keys derived from passwords, of which the first half encrypts the private key and the second half
+///authenticates it.
+const DERIVED_KEY_LENGTH: usize = 32;
+
+///The `Kdf` enum is the key derivation function deriving the encryption key of a keystore from its password, with its
+///cost parameters. Higher costs make passwords slower to brute-force, and keystores slower to decrypt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    ///scrypt, with a cost of `2^log_n`, a block size of `r` and a parallelization of `p`.
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    ///PBKDF2 with HMAC-SHA256, with `c` iterations.
+    Pbkdf2 { c: u32 },
+}
+
+impl Kdf {
+    ///The scrypt parameters of Geth's standard keystores, which take about a second and 256 MB to decrypt.
+    pub const STANDARD: Kdf = Kdf::Scrypt {
+        log_n: 18,
+        r: 8,
+        p: 1,
+    };
+    ///The scrypt parameters of Geth's light keystores, which take a fraction of the time and memory to decrypt.
+    pub const LIGHT: Kdf = Kdf::Scrypt {
+        log_n: 12,
+        r: 8,
+        p: 6,
+    };
+}
+
+impl Default for Kdf {
+    fn default() -> Self {
+        Kdf::STANDARD
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KdfParams {
+    dklen: usize,
+    salt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    n: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    r: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    p: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    c: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prf: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Crypto {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: KdfParams,
+    mac: String,
+}
+
+///The `Keystore` struct is a private key encrypted with a password, in the JSON format of version 3 keystore files.
+///Keys are encrypted with AES-128-CTR, under a key derived from the password with scrypt or PBKDF2, and authenticated
+///with a Keccak-256 MAC, so that a wrong password is detected. Its `Debug` output only shows the id and address.
+///## Example
+///```rust
+///use ethrs::keystore::{Kdf, Keystore};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let private_key = [0x46; 32];
+///  let keystore = Keystore::encrypt(&private_key, "correct horse battery staple", Kdf::Pbkdf2 { c: 1024 })?;
+///  let json = keystore.to_string();
+///  assert_eq!(json.parse::<Keystore>()?.decrypt("correct horse battery staple")?, private_key);
+///  assert!(keystore.decrypt("hunter2").is_err());
+///  Ok(())
+///}
+///```
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    // Geth wrote the field capitalized before version 1.4
+    #[serde(alias = "Crypto")]
+    crypto: Crypto,
+    pub id: String,
+    pub version: u8,
+    ///The address of the key, as a hexstring without prefix, which some keystores omit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+}
+
+impl Keystore {
+    ///The `Keystore::encrypt()` associated function takes a private key, a password and a key derivation function, and
+    ///attempts to return the key encrypted with the password as `Ok(Keystore)`, with a random salt, IV and id. Returns an
+    ///`Err()` if the key is not a valid secp256k1 private key or if the KDF parameters are invalid.
+    pub fn encrypt(
+        private_key: &[u8; 32],
+        password: &str,
+        kdf: Kdf,
+    ) -> Result<Keystore, Box<dyn Error>> {
+        let address = Wallet::from_private_key(private_key)?.address();
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut iv);
+        OsRng.fill_bytes(&mut id);
+        let kdfparams = match kdf {
+            Kdf::Scrypt { log_n, r, p } => KdfParams {
+                dklen: DERIVED_KEY_LENGTH,
+                salt: hex::encode(salt),
+                n: Some(
+                    1u64.checked_shl(log_n.into())
+                        .ok_or("Invalid scrypt cost")?,
+                ),
+                r: Some(r),
+                p: Some(p),
+                c: None,
+                prf: None,
+            },
+            Kdf::Pbkdf2 { c } => KdfParams {
+                dklen: DERIVED_KEY_LENGTH,
+                salt: hex::encode(salt),
+                n: None,
+                r: None,
+                p: None,
+                c: Some(c),
+                prf: Some("hmac-sha256".to_owned()),
+            },
+        };
+        let key = derive_key(&kdf, password, &salt, DERIVED_KEY_LENGTH)?;
+        let mut ciphertext = private_key.to_vec();
+        Aes128Ctr::new(key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+        let mac = keccak256(&[&key[16..32], &ciphertext[..]].concat());
+        Ok(Keystore {
+            crypto: Crypto {
+                cipher: "aes-128-ctr".to_owned(),
+                cipherparams: CipherParams {
+                    iv: hex::encode(iv),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf: match kdf {
+                    Kdf::Scrypt { .. } => "scrypt".to_owned(),
+                    Kdf::Pbkdf2 { .. } => "pbkdf2".to_owned(),
+                },
+                kdfparams,
+                mac: hex::encode(mac),
+            },
+            id: uuid(id),
+            version: 3,
+            address: Some(hex::encode(address.0)),
+        })
+    }
+
+    ///Returns the key derivation function of the keystore. Returns an `Err()` if it is not supported.
+    pub fn kdf(&self) -> Result<Kdf, Box<dyn Error>> {
+        let params = &self.crypto.kdfparams;
+        let missing = |name: &str| format!("Missing {} parameter {name}", self.crypto.kdf);
+        match self.crypto.kdf.as_str() {
+            "scrypt" => {
+                let n = params.n.ok_or_else(|| missing("n"))?;
+                if !n.is_power_of_two() {
+                    return Err(format!("Invalid scrypt parameter n: {n}").into());
+                }
+                Ok(Kdf::Scrypt {
+                    log_n: n.trailing_zeros() as u8,
+                    r: params.r.ok_or_else(|| missing("r"))?,
+                    p: params.p.ok_or_else(|| missing("p"))?,
+                })
+            }
+            "pbkdf2" => match params.prf.as_deref() {
+                Some("hmac-sha256") => Ok(Kdf::Pbkdf2 {
+                    c: params.c.ok_or_else(|| missing("c"))?,
+                }),
+                prf => Err(format!("Unsupported PBKDF2 PRF: {}", prf.unwrap_or_default()).into()),
+            },
+            kdf => Err(format!("Unsupported keystore KDF: {kdf}").into()),
+        }
+    }
+
+    ///The `decrypt()` function takes the password of the keystore and attempts to return the private key as
+    ///`Ok([u8; 32])`. Returns an `Err()` if the password is wrong, if the keystore uses an unsupported cipher or KDF, or
+    ///if the key does not match the address of the keystore.
+    pub fn decrypt(&self, password: &str) -> Result<[u8; 32], Box<dyn Error>> {
+        if self.version != 3 {
+            return Err(format!("Unsupported keystore version {}", self.version).into());
+        }
+        if self.crypto.cipher != "aes-128-ctr" {
+            return Err(format!("Unsupported keystore cipher: {}", self.crypto.cipher).into());
+        }
+        let params = &self.crypto.kdfparams;
+        if params.dklen < DERIVED_KEY_LENGTH {
+            return Err(format!("Invalid keystore key length: {}", params.dklen).into());
+        }
+        let salt = hex::decode(&params.salt).map_err(|_| "Invalid keystore salt")?;
+        let key = derive_key(&self.kdf()?, password, &salt, params.dklen)?;
+        let mut ciphertext =
+            hex::decode(&self.crypto.ciphertext).map_err(|_| "Invalid keystore ciphertext")?;
+        let mac = keccak256(&[&key[16..32], &ciphertext[..]].concat());
+        if hex::encode(mac) != self.crypto.mac.to_lowercase() {
+            return Err("Invalid keystore password".into());
+        }
+        let iv: [u8; 16] = hex::decode(&self.crypto.cipherparams.iv)
+            .ok()
+            .and_then(|iv| iv.try_into().ok())
+            .ok_or("Invalid keystore IV")?;
+        Aes128Ctr::new(key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+        let private_key: [u8; 32] = ciphertext
+            .try_into()
+            .map_err(|_| "Invalid keystore ciphertext length")?;
+        if let Some(address) = &self.address {
+            let wallet = Wallet::from_private_key(&private_key)?;
+            if address.parse::<Address>()? != wallet.address() {
+                return Err(format!("Keystore key does not match its address {address}").into());
+            }
+        }
+        Ok(private_key)
+    }
+
+    ///The `Keystore::load()` associated function takes the path of a keystore file and attempts to return the keystore
+    ///as `Ok(Keystore)`. Returns an `Err()` if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Keystore, Box<dyn Error>> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    ///The `save()` function takes a path and attempts to write the keystore to it as `Ok(())`. Returns an `Err()` if the
+    ///file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+fn derive_key(
+    kdf: &Kdf,
+    password: &str,
+    salt: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut key = vec![0u8; length];
+    match *kdf {
+        Kdf::Scrypt { log_n, r, p } => {
+            let params = scrypt::Params::new(log_n, r, p, length)
+                .map_err(|_| "Invalid scrypt parameters")?;
+            scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+                .map_err(|_| "Invalid scrypt key length")?;
+        }
+        Kdf::Pbkdf2 { c } => pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, c, &mut key),
+    }
+    Ok(key)
+}
+
+///Formats random bytes as a version 4 UUID.
+fn uuid(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+impl FromStr for Keystore {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+impl fmt::Display for Keystore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl fmt::Debug for Keystore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keystore")
+            .field("id", &self.id)
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,19 +1,71 @@
-///## 🚀 Quick start
-///```rust
-///use ethrs::provider::Provider;
-///use ethrs::provider::Block;
-///use ethrs::provider::DefaultBlockParam;
-///use std::error::Error;
-
-///fn main() -> Result<(), Box<dyn Error>> {
-///    let provider = Provider::new("https://rpc.sepolia.org");
-///    // Get the latest block number
-///    print!("Latest block number: {}", provider.block_number().unwrap());
-///    // Or fetch a pending block
-///    let pending_block: Block = provider.get_block_by_number(Some(DefaultBlockParam::PENDING), None)?.unwrap();
-///    // More APIs available in the docs!
-///    Ok(())
-///}
-///```
+//!## 🚀 Quick start
+//!```rust
+//!use ethrs::provider::Provider;
+//!use ethrs::provider::Block;
+//!use ethrs::types::BlockId;
+//!use std::error::Error;
+//!
+//!fn main() -> Result<(), Box<dyn Error>> {
+//!    let provider = Provider::new("https://rpc.sepolia.org");
+//!#    let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+//!    // Get the latest block number
+//!    print!("Latest block number: {}", provider.block_number().unwrap());
+//!    // Or fetch a pending block
+//!    let pending_block: Block = provider.get_block_by_number(Some(BlockId::Pending))?.unwrap();
+//!    // More APIs available in the docs!
+//!    Ok(())
+//!}
+//!```
+pub mod abi;
+pub mod addressbook;
+pub mod approvals;
+pub mod arbitrum;
+pub mod artifact;
+pub mod balancer;
+pub mod bloom;
+pub mod cache;
+pub mod calldata;
+pub mod client;
+pub mod contract;
+pub mod deployer;
+pub mod dev;
+pub mod eip712;
+pub mod engine;
+pub mod ens;
+pub mod erc20;
+pub mod escalator;
+pub mod events;
+pub mod fallback;
+pub mod flashbots;
+pub mod hd;
+pub mod indexer;
+pub mod keystore;
+pub mod metrics;
+pub mod middleware;
+#[cfg(feature = "test-utils")]
+pub mod mock;
+pub mod multicall;
+pub mod nft;
+pub mod nonce;
+pub mod optimism;
+pub mod proof;
+#[cfg(feature = "protobuf")]
+pub mod proto;
 pub mod provider;
+pub mod quirks;
+pub mod quorum;
+pub mod rlp;
+pub mod signature;
+pub mod signing;
+pub mod sink;
+pub mod snapshot;
+pub mod stream;
+pub mod sync;
+pub mod testing;
+pub mod trace;
+pub mod transaction;
 pub mod types;
+pub mod units;
+pub mod utils;
+pub mod wallet;
+pub mod watchdog;
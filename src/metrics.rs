@@ -0,0 +1,183 @@
+//!The metrics module provides `ProviderMetrics`, the request counters and latency histograms every `Provider` keeps
+//!per JSON-RPC method, which can be exported in the Prometheus text format.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+///The upper bounds of the latency histogram buckets, in seconds, as the default buckets of the Prometheus clients.
+pub const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+///The `MethodMetrics` struct holds the metrics of a single JSON-RPC method. Errors count both transport failures and
+///JSON-RPC error responses, and every request is recorded in the latency histogram, errors included.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MethodMetrics {
+    pub requests: u64,
+    pub errors: u64,
+    ///The number of requests per bucket of `LATENCY_BUCKETS`, not cumulative, followed by the requests slower than the
+    ///last bucket.
+    pub buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    pub total_latency: Duration,
+}
+
+impl MethodMetrics {
+    fn record(&mut self, latency: Duration, error: bool) {
+        self.requests += 1;
+        self.errors += error as u64;
+        let seconds = latency.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.total_latency += latency;
+    }
+
+    ///The `quantile()` function takes a quantile between 0 and 1 and returns the latency estimated from the histogram
+    ///by linear interpolation within its bucket, as Prometheus' `histogram_quantile()` does, or `None` before the first
+    ///request. Latencies beyond the last bucket are estimated as the last bucket bound.
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        if self.requests == 0 {
+            return None;
+        }
+        let rank = quantile.clamp(0.0, 1.0) * self.requests as f64;
+        let mut count = 0;
+        for (bucket, requests) in self.buckets.iter().enumerate() {
+            let bound = match LATENCY_BUCKETS.get(bucket) {
+                Some(bound) => *bound,
+                None => break,
+            };
+            if *requests > 0 && (count + requests) as f64 >= rank {
+                let lower = bucket
+                    .checked_sub(1)
+                    .map_or(0.0, |lower| LATENCY_BUCKETS[lower]);
+                let fraction = (rank - count as f64) / *requests as f64;
+                return Some(Duration::from_secs_f64(lower + (bound - lower) * fraction));
+            }
+            count += requests;
+        }
+        Some(Duration::from_secs_f64(
+            LATENCY_BUCKETS[LATENCY_BUCKETS.len() - 1],
+        ))
+    }
+
+    ///Returns the estimated median latency.
+    pub fn p50(&self) -> Option<Duration> {
+        self.quantile(0.5)
+    }
+
+    ///Returns the estimated 95th percentile latency.
+    pub fn p95(&self) -> Option<Duration> {
+        self.quantile(0.95)
+    }
+}
+
+///The `ProviderMetrics` struct is a handle on the metrics of a `Provider`, returned by `Provider::metrics()`. It is
+///shared by all clones of the `Provider`, and reflects the requests made after it was taken. Its `Display`
+///implementation writes the metrics in the Prometheus text exposition format, as the `ethrs_rpc_requests_total` and
+///`ethrs_rpc_errors_total` counters and the `ethrs_rpc_request_duration_seconds` histogram, labelled by method.
+///## Example
+///```rust
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  let metrics = provider.metrics();
+///  provider.block_number()?;
+///  let block_number = &metrics.snapshot()["eth_blockNumber"];
+///  println!("{} requests, p95 {:?}", block_number.requests, block_number.p95());
+///  print!("{metrics}");
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default)]
+pub struct ProviderMetrics {
+    methods: Arc<Mutex<BTreeMap<String, MethodMetrics>>>,
+}
+
+impl ProviderMetrics {
+    ///Records a request of the method.
+    pub(crate) fn record(&self, method: &str, latency: Duration, error: bool) {
+        let mut methods = self.lock();
+        match methods.get_mut(method) {
+            Some(metrics) => metrics.record(latency, error),
+            None => {
+                let mut metrics = MethodMetrics::default();
+                metrics.record(latency, error);
+                methods.insert(method.to_owned(), metrics);
+            }
+        }
+    }
+
+    ///Returns the metrics of every method requested so far, by method name.
+    pub fn snapshot(&self) -> BTreeMap<String, MethodMetrics> {
+        self.lock().clone()
+    }
+
+    ///Returns the metrics of a method, or `None` if it was not requested yet.
+    pub fn method(&self, method: &str) -> Option<MethodMetrics> {
+        self.lock().get(method).cloned()
+    }
+
+    ///Resets every metric, e.g. after exporting them as deltas.
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, MethodMetrics>> {
+        self.methods.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl fmt::Display for ProviderMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let methods = self.snapshot();
+        writeln!(f, "# TYPE ethrs_rpc_requests_total counter")?;
+        for (method, metrics) in &methods {
+            writeln!(
+                f,
+                "ethrs_rpc_requests_total{{method={method:?}}} {}",
+                metrics.requests
+            )?;
+        }
+        writeln!(f, "# TYPE ethrs_rpc_errors_total counter")?;
+        for (method, metrics) in &methods {
+            writeln!(
+                f,
+                "ethrs_rpc_errors_total{{method={method:?}}} {}",
+                metrics.errors
+            )?;
+        }
+        writeln!(f, "# TYPE ethrs_rpc_request_duration_seconds histogram")?;
+        for (method, metrics) in &methods {
+            let mut count = 0;
+            for (bound, requests) in LATENCY_BUCKETS.iter().zip(metrics.buckets) {
+                count += requests;
+                writeln!(
+                    f,
+                    "ethrs_rpc_request_duration_seconds_bucket{{method={method:?},le=\"{bound}\"}} {count}"
+                )?;
+            }
+            writeln!(
+                f,
+                "ethrs_rpc_request_duration_seconds_bucket{{method={method:?},le=\"+Inf\"}} {}",
+                metrics.requests
+            )?;
+            writeln!(
+                f,
+                "ethrs_rpc_request_duration_seconds_sum{{method={method:?}}} {}",
+                metrics.total_latency.as_secs_f64()
+            )?;
+            writeln!(
+                f,
+                "ethrs_rpc_request_duration_seconds_count{{method={method:?}}} {}",
+                metrics.requests
+            )?;
+        }
+        Ok(())
+    }
+}
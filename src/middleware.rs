@@ -0,0 +1,112 @@
+//!The middleware module provides the `Middleware` trait, which intercepts the JSON-RPC requests of a `Provider` and
+//!their responses, for cross-cutting concerns such as logging, metrics, request rewriting or request signing.
+use reqwest::header::HeaderMap;
+use serde_json::json;
+
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+///The `RpcRequest` struct is a JSON-RPC request about to be sent by a `Provider`. Middlewares can change any of its
+///fields, and add HTTP headers sent along with it, e.g. a signature of the request body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcRequest {
+    pub method: String,
+    pub params: serde_json::Value,
+    pub id: serde_json::Value,
+    ///Headers sent in addition to the headers of the `Provider`. They are ignored when the `Provider` sends its requests
+    ///through a `Transport`.
+    pub headers: HeaderMap,
+}
+
+impl RpcRequest {
+    ///The `RpcRequest::from_payload()` associated function takes a JSON-RPC request payload and returns it as an
+    ///`RpcRequest` without additional headers.
+    pub fn from_payload(payload: &serde_json::Value) -> RpcRequest {
+        RpcRequest {
+            method: payload["method"].as_str().unwrap_or_default().to_owned(),
+            params: payload["params"].clone(),
+            id: payload["id"].clone(),
+            headers: HeaderMap::new(),
+        }
+    }
+
+    ///Returns the JSON-RPC payload of the request.
+    pub fn payload(&self) -> serde_json::Value {
+        json!({
+            "method": self.method,
+            "params": self.params,
+            "id": self.id,
+            "jsonrpc": "2.0",
+        })
+    }
+
+    ///Returns the HTTP body the request is sent with, i.e. the bytes a signature of the request has to cover.
+    pub fn body(&self) -> String {
+        self.payload().to_string()
+    }
+}
+
+///The `RpcResponse` struct is the raw JSON-RPC response to a request, including error responses, along with how long
+///the request took, middlewares included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcResponse {
+    pub body: serde_json::Value,
+    pub duration: Duration,
+}
+
+impl RpcResponse {
+    ///Returns the result of the response, or `None` for an error response.
+    pub fn result(&self) -> Option<&serde_json::Value> {
+        match self.error_message() {
+            Some(_) => None,
+            None => self.body.get("result"),
+        }
+    }
+
+    ///Returns the message of an error response.
+    pub fn error_message(&self) -> Option<&str> {
+        self.body["error"]["message"].as_str()
+    }
+}
+
+///The `Middleware` trait intercepts the requests of a `Provider` it is added to with `Provider::with_middleware()`.
+///Middlewares see requests in the order they were added, and responses in the reverse order, so that the first one
+///added wraps all the others. Every method has a default implementation doing nothing, so a middleware only implements
+///the ones it needs.
+///## Example
+///```rust
+///use ethrs::middleware::{Middleware, RpcRequest, RpcResponse};
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///
+///#[derive(Debug)]
+///struct Logger;
+///
+///impl Middleware for Logger {
+///    fn on_response(&self, request: &RpcRequest, response: &mut RpcResponse) {
+///        println!("{} took {:?}", request.method, response.duration);
+///    }
+///}
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org").with_middleware(Logger);
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///#  let provider = provider.with_middleware(Logger);
+///  provider.block_number()?;
+///  Ok(())
+///}
+///```
+pub trait Middleware: fmt::Debug + Send + Sync {
+    ///Called before the request is sent. Returning an `Err()` fails the request without sending it, and without
+    ///calling the following middlewares.
+    fn on_request(&self, _request: &mut RpcRequest) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    ///Called with the response to the request, which can be rewritten before it is processed.
+    fn on_response(&self, _request: &RpcRequest, _response: &mut RpcResponse) {}
+
+    ///Called when the request fails without a JSON-RPC response, e.g. on connection errors.
+    fn on_error(&self, _request: &RpcRequest, _error: &dyn Error) {}
+}
@@ -0,0 +1,151 @@
+//!The mock module provides the `MockProvider`, which answers JSON-RPC requests with scripted responses and records
+//!them, so that code built on a `Provider` can be tested without a node. It requires the `test-utils` feature.
+use crate::provider::{Provider, Transport};
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+///The `MockResponse` enum is a scripted answer of a `MockProvider`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockResponse {
+    ///A successful JSON-RPC response with the given result.
+    Result(serde_json::Value),
+    ///A JSON-RPC error response, e.g. a reverted call.
+    Error { code: i64, message: String },
+    ///A transport failure, e.g. a connection error, which is returned as an `Err()` without a JSON-RPC response.
+    Failure(String),
+}
+
+#[derive(Debug, Default)]
+struct State {
+    defaults: HashMap<String, MockResponse>,
+    queued: HashMap<String, VecDeque<MockResponse>>,
+    requests: Vec<serde_json::Value>,
+    latency: Duration,
+}
+
+///The `MockProvider` struct answers JSON-RPC requests with the responses scripted for their method: first the ones
+///queued with `once()`, in order, then the one set with `on()`. Requests for methods without a response get a "Method
+///not found" JSON-RPC error, as from a node. Every request payload is recorded, for assertions. Clones share the
+///scripted responses and recorded requests, so they can be changed after `provider()` is called.
+///
+///A `MockProvider` is a `Transport`: `provider()` returns a `Provider` exposing every JSON-RPC method through it.
+///## Example
+///```rust
+///use ethrs::mock::{MockProvider, MockResponse};
+///use serde_json::json;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let mock = MockProvider::new();
+///  mock.on("eth_blockNumber", MockResponse::Result(json!("0x10")))
+///      .once("eth_chainId", MockResponse::Failure("connection reset".to_owned()));
+///  let provider = mock.provider();
+///  assert_eq!(provider.block_number()?, 16);
+///  assert!(provider.chain_id().is_err());
+///  assert_eq!(mock.requests_for("eth_blockNumber").len(), 1);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default)]
+pub struct MockProvider {
+    state: Arc<Mutex<State>>,
+}
+
+impl MockProvider {
+    ///The `MockProvider::new()` associated function returns a `MockProvider` without scripted responses or latency.
+    pub fn new() -> MockProvider {
+        MockProvider::default()
+    }
+
+    ///Returns a `Provider` sending its requests to the `MockProvider`.
+    pub fn provider(&self) -> Provider {
+        Provider::new("http://mock.invalid").with_transport(self.clone())
+    }
+
+    ///Sets the response to every request for the method once its queued responses are used up.
+    pub fn on(&self, method: &str, response: MockResponse) -> &MockProvider {
+        self.lock().defaults.insert(method.to_owned(), response);
+        self
+    }
+
+    ///Queues a response to a single request for the method.
+    pub fn once(&self, method: &str, response: MockResponse) -> &MockProvider {
+        self.lock()
+            .queued
+            .entry(method.to_owned())
+            .or_default()
+            .push_back(response);
+        self
+    }
+
+    ///Sets how long every request takes before it is answered.
+    pub fn with_latency(&self, latency: Duration) -> &MockProvider {
+        self.lock().latency = latency;
+        self
+    }
+
+    ///Returns the payloads of every request received, in order.
+    pub fn requests(&self) -> Vec<serde_json::Value> {
+        self.lock().requests.clone()
+    }
+
+    ///Returns the params of every request received for the method, in order.
+    pub fn requests_for(&self, method: &str) -> Vec<serde_json::Value> {
+        self.lock()
+            .requests
+            .iter()
+            .filter(|request| request["method"] == method)
+            .map(|request| request["params"].clone())
+            .collect()
+    }
+
+    ///Forgets the requests received so far.
+    pub fn clear_requests(&self) {
+        self.lock().requests.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Transport for MockProvider {
+    fn send(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>> {
+        let method = payload["method"].as_str().unwrap_or_default();
+        let (response, latency) = {
+            let mut state = self.lock();
+            state.requests.push(payload.clone());
+            let queued = state
+                .queued
+                .get_mut(method)
+                .and_then(|queue| queue.pop_front());
+            let response = queued.or_else(|| state.defaults.get(method).cloned());
+            (response, state.latency)
+        };
+        // the lock is not held while sleeping, so that concurrent requests are delayed concurrently
+        if !latency.is_zero() {
+            thread::sleep(latency);
+        }
+        let id = payload["id"].clone();
+        match response {
+            Some(MockResponse::Result(result)) => {
+                Ok(serde_json::json!({"jsonrpc": "2.0", "id": id, "result": result}))
+            }
+            Some(MockResponse::Error { code, message }) => Ok(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": code, "message": message},
+            })),
+            Some(MockResponse::Failure(message)) => Err(message.into()),
+            None => Ok(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": -32601, "message": format!("Method not found: {method}")},
+            })),
+        }
+    }
+}
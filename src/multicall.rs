@@ -0,0 +1,245 @@
+//!The multicall module batches contract calls into a single `eth_call` of the Multicall3 contract, which is deployed at
+//!the same address on most chains, so that reading many values costs a single JSON-RPC request.
+use crate::abi::{decode, encode, Detokenize, Function, Param, ParamType, Token};
+use crate::provider::{CallInput, Provider};
+use crate::types::{Address, BlockId, Bytes};
+use crate::utils::function_selector;
+
+use std::error::Error;
+
+///The address Multicall3 is deployed at on most chains.
+pub const MULTICALL3: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+///The selector of `Error(string)`, which encodes the reason of `require()` and `revert()` failures.
+const ERROR_STRING: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+#[derive(Debug, Clone)]
+struct Call {
+    target: Address,
+    allow_failure: bool,
+    calldata: Bytes,
+    outputs: Vec<ParamType>,
+}
+
+///The `Multicall` struct collects contract calls and sends them as a single `aggregate3` call of Multicall3. Each call
+///either allows failure, in which case its result reports whether it succeeded, or makes the whole batch revert if it
+///fails.
+///## Example
+///```rust,no_run
+///use ethrs::abi::{Abi, Token};
+///use ethrs::multicall::Multicall;
+///use ethrs::provider::Provider;
+///use ethrs::types::{Address, U256};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let abi: Abi = serde_json::from_str(r#"[{
+///      "type": "function",
+///      "name": "balanceOf",
+///      "inputs": [{"name": "owner", "type": "address"}],
+///      "outputs": [{"name": "", "type": "uint256"}],
+///      "stateMutability": "view"
+///  }]"#)?;
+///  let balance_of = abi.function("balanceOf").unwrap();
+///  let weth: Address = "0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?;
+///  let holders: Vec<Address> = vec!["0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?; 500];
+///  let mut multicall = Multicall::new(Provider::new("https://rpc.sepolia.org"));
+///  for holder in &holders {
+///      multicall.add_call(weth, balance_of, &[Token::Address(*holder)], true)?;
+///  }
+///  for (holder, result) in holders.iter().zip(multicall.call()?) {
+///      let balance: U256 = result.decode()?;
+///      println!("{holder:?}: {balance}");
+///  }
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Multicall {
+    provider: Provider,
+    address: Address,
+    block: Option<BlockId>,
+    calls: Vec<Call>,
+}
+
+impl Multicall {
+    ///The `Multicall::new()` associated function takes a `Provider` and returns an empty `Multicall` calling Multicall3
+    ///at its canonical address, at the latest block.
+    pub fn new(provider: Provider) -> Multicall {
+        Multicall {
+            provider,
+            // the constant is a valid address
+            address: MULTICALL3.parse().unwrap_or_default(),
+            block: None,
+            calls: vec![],
+        }
+    }
+
+    ///The `with_address()` function sets the address of the Multicall3 contract, for chains where it is not deployed at
+    ///its canonical address.
+    pub fn with_address(mut self, address: Address) -> Multicall {
+        self.address = address;
+        self
+    }
+
+    ///The `with_block()` function sets the block the calls are made at.
+    pub fn with_block(mut self, block: BlockId) -> Multicall {
+        self.block = Some(block);
+        self
+    }
+
+    ///The `add_call()` function takes the address of a contract, one of its functions, the arguments and whether the
+    ///call may fail without reverting the batch, and attempts to add the call, returning the `Multicall` as
+    ///`Ok(&mut Multicall)`. Its result is decoded according to the outputs of the function. Returns an `Err()` if the
+    ///arguments do not match the inputs of the function.
+    pub fn add_call(
+        &mut self,
+        target: Address,
+        function: &Function,
+        args: &[Token],
+        allow_failure: bool,
+    ) -> Result<&mut Multicall, Box<dyn Error>> {
+        let outputs = function
+            .outputs
+            .iter()
+            .map(Param::param_type)
+            .collect::<Result<_, _>>()?;
+        self.calls.push(Call {
+            target,
+            allow_failure,
+            calldata: Bytes(function.encode_input(args)?),
+            outputs,
+        });
+        Ok(self)
+    }
+
+    ///The `add_raw_call()` function takes the address of a contract, the calldata of a call, the types of its outputs
+    ///and whether the call may fail without reverting the batch, and adds the call.
+    pub fn add_raw_call(
+        &mut self,
+        target: Address,
+        calldata: Bytes,
+        outputs: Vec<ParamType>,
+        allow_failure: bool,
+    ) -> &mut Multicall {
+        self.calls.push(Call {
+            target,
+            allow_failure,
+            calldata,
+            outputs,
+        });
+        self
+    }
+
+    ///Returns the number of calls added.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    ///Returns `true` if no call was added.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    ///Removes every call added, so that the `Multicall` can be reused.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+
+    ///Returns the calldata of the `aggregate3` call batching the calls added.
+    pub fn calldata(&self) -> Bytes {
+        let calls = self
+            .calls
+            .iter()
+            .map(|call| {
+                Token::Tuple(vec![
+                    Token::Address(call.target),
+                    Token::Bool(call.allow_failure),
+                    Token::Bytes(call.calldata.0.clone()),
+                ])
+            })
+            .collect();
+        let mut calldata = function_selector("aggregate3((address,bool,bytes)[])").to_vec();
+        calldata.extend(encode(&[Token::Array(calls)]));
+        Bytes(calldata)
+    }
+
+    ///The `call()` function sends the calls added as a single `eth_call` and attempts to return their results, in the
+    ///order they were added, as `Ok(Vec<CallResult>)`. Returns an `Err()` on JSON-RPC errors, e.g. when a call that
+    ///does not allow failure fails, or if the return data of Multicall3 is malformed.
+    pub fn call(&self) -> Result<Vec<CallResult>, Box<dyn Error>> {
+        if self.calls.is_empty() {
+            return Ok(vec![]);
+        }
+        let data: Bytes = self
+            .provider
+            .call(
+                CallInput::to(self.address).data(self.calldata()),
+                self.block,
+            )?
+            .parse()?;
+        let results: Vec<(bool, Bytes)> = Detokenize::from_tokens(decode(
+            &[ParamType::Array(Box::new(ParamType::Tuple(vec![
+                ParamType::Bool,
+                ParamType::Bytes,
+            ])))],
+            &data,
+        )?)?;
+        if results.len() != self.calls.len() {
+            return Err(format!(
+                "Expected {} Multicall3 results, found {}",
+                self.calls.len(),
+                results.len()
+            )
+            .into());
+        }
+        Ok(results
+            .into_iter()
+            .zip(&self.calls)
+            .map(|((success, return_data), call)| CallResult {
+                success,
+                return_data,
+                outputs: call.outputs.clone(),
+            })
+            .collect())
+    }
+}
+
+///The `CallResult` struct is the result of a call batched by a `Multicall`: whether it succeeded and the data it
+///returned, which is the revert data of failed calls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallResult {
+    pub success: bool,
+    pub return_data: Bytes,
+    outputs: Vec<ParamType>,
+}
+
+impl CallResult {
+    ///The `tokens()` function attempts to return the outputs of a successful call as `Ok(Vec<Token>)`. Returns an
+    ///`Err()` with the revert reason if the call failed, or if the return data does not match the outputs, e.g. when the
+    ///target is not a contract.
+    pub fn tokens(&self) -> Result<Vec<Token>, Box<dyn Error>> {
+        if !self.success {
+            let data = &self.return_data.0;
+            let reason = match data.get(..4) {
+                Some(selector) if selector == ERROR_STRING => {
+                    decode(&[ParamType::String], &data[4..]).ok()
+                }
+                _ => None,
+            };
+            return Err(match reason.as_deref() {
+                Some([Token::String(reason)]) => format!("Call reverted: {reason}"),
+                _ => format!("Call reverted: {}", self.return_data),
+            }
+            .into());
+        }
+        decode(&self.outputs, &self.return_data.0)
+    }
+
+    ///The `decode()` function attempts to return the outputs of a successful call as `Ok(T)`, a single output converting
+    ///into `T` directly and several outputs into a tuple. Returns an `Err()` as `tokens()` does, or if the outputs do
+    ///not convert into `T`.
+    pub fn decode<T: Detokenize>(&self) -> Result<T, Box<dyn Error>> {
+        T::from_tokens(self.tokens()?)
+    }
+}
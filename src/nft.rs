@@ -0,0 +1,674 @@
+//!The nft module reads ERC-721 and ERC-1155 collections, decodes their transfer logs and reconstructs their current
+//!holders, incrementally and from a checkpoint, on top of the `indexer::Coordinator`.
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::abi::{decode, encode, ParamType, Token, Tokenizable};
+use crate::indexer::{Batch, Coordinator};
+use crate::provider::{CallInput, Filter, Log, Provider};
+use crate::types::{Address, BlockId, Bytes, H256};
+use crate::utils::{function_selector, keccak256};
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+///The `Transfer` struct is a transfer of an amount of a token of a collection, as logged by an ERC-721 `Transfer`
+///(always an amount of 1) or an ERC-1155 `TransferSingle` or `TransferBatch` log. Mints are transfers from the zero
+///address and burns are transfers to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub collection: Address,
+    pub from: Address,
+    pub to: Address,
+    pub id: U256,
+    pub amount: U256,
+    pub block_number: u64,
+    pub transaction_hash: H256,
+}
+
+fn topics() -> [H256; 3] {
+    [
+        H256(keccak256(b"Transfer(address,address,uint256)")),
+        H256(keccak256(
+            b"TransferSingle(address,address,address,uint256,uint256)",
+        )),
+        H256(keccak256(
+            b"TransferBatch(address,address,address,uint256[],uint256[])",
+        )),
+    ]
+}
+
+fn topic_address(topic: &H256) -> Address {
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&topic.0[12..]);
+    Address(address)
+}
+
+///The `decode_transfers()` function takes a log and attempts to return the NFT transfers it records as
+///`Ok(Vec<Transfer>)`, which is empty for other logs, including ERC-20 `Transfer` logs and removed logs. Returns an
+///`Err()` if the log data does not match its event.
+///## Example
+///```rust
+///use ethrs::nft::decode_transfers;
+///use ethrs::provider::Log;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let log: Log = serde_json::from_value(serde_json::json!({
+///      "removed": false,
+///      "logIndex": "0x0",
+///      "transactionIndex": "0x0",
+///      "transactionHash": "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8",
+///      "blockHash": "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6",
+///      "blockNumber": "0x1",
+///      "address": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+///      "data": "0x",
+///      "topics": [
+///          "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+///          "0x0000000000000000000000000000000000000000000000000000000000000000",
+///          "0x000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+///          "0x0000000000000000000000000000000000000000000000000000000000000007",
+///      ],
+///  }))?;
+///  let transfers = decode_transfers(&log)?;
+///  assert_eq!(transfers[0].id, 7.into());
+///  Ok(())
+///}
+///```
+pub fn decode_transfers(log: &Log) -> Result<Vec<Transfer>, Box<dyn Error>> {
+    let [transfer, single, batch] = topics();
+    if log.removed || log.topics.is_empty() {
+        return Ok(vec![]);
+    }
+    let topic = log.topics[0];
+    let transfer_of = |from: &H256, to: &H256, id: U256, amount: U256| Transfer {
+        collection: log.address,
+        from: topic_address(from),
+        to: topic_address(to),
+        id,
+        amount,
+        block_number: log.block_number,
+        transaction_hash: log.transaction_hash,
+    };
+    if topic == transfer && log.topics.len() == 4 {
+        let id = U256::from_big_endian(&log.topics[3].0);
+        return Ok(vec![transfer_of(
+            &log.topics[1],
+            &log.topics[2],
+            id,
+            U256::one(),
+        )]);
+    }
+    if (topic != single && topic != batch) || log.topics.len() != 4 {
+        return Ok(vec![]);
+    }
+    let data: Bytes = log.data.parse()?;
+    let (ids, amounts) = match topic == single {
+        true => (ParamType::Uint(256), ParamType::Uint(256)),
+        false => (
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+        ),
+    };
+    let uints = |token: Token| match token {
+        Token::Uint(value) => Ok(vec![value]),
+        Token::Array(values) => values
+            .into_iter()
+            .map(|value| match value {
+                Token::Uint(value) => Ok(value),
+                _ => Err("Invalid transfer log data"),
+            })
+            .collect(),
+        _ => Err("Invalid transfer log data"),
+    };
+    let mut tokens = decode(&[ids, amounts], &data.0)?.into_iter();
+    let (ids, amounts) = match (tokens.next(), tokens.next()) {
+        (Some(ids), Some(amounts)) => (uints(ids)?, uints(amounts)?),
+        _ => return Err("Invalid transfer log data".into()),
+    };
+    if ids.len() != amounts.len() {
+        return Err("Mismatched ids and amounts in TransferBatch log".into());
+    }
+    Ok(ids
+        .into_iter()
+        .zip(amounts)
+        .map(|(id, amount)| transfer_of(&log.topics[2], &log.topics[3], id, amount))
+        .collect())
+}
+
+///The `Ownership` struct holds the current holders of the tokens of a collection, reconstructed from its transfers up
+///to a checkpoint block. It can be saved to a JSON file and loaded back to resume indexing from the checkpoint.
+///## Example
+///```rust
+///use ethrs::nft::{Ownership, Transfer};
+///use ethrs::types::{Address, H256};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let holder: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+///  let mut ownership = Ownership::new(0);
+///  ownership.apply(&Transfer {
+///      collection: "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?,
+///      from: Address::default(),
+///      to: holder,
+///      id: 7.into(),
+///      amount: 1.into(),
+///      block_number: 1,
+///      transaction_hash: H256::default(),
+///  });
+///  assert_eq!(ownership.owner_of(7.into()), Some(holder));
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ownership {
+    next_block: u64,
+    balances: BTreeMap<U256, BTreeMap<Address, U256>>,
+}
+
+impl Ownership {
+    ///The `Ownership::new()` associated function takes the first block to index, e.g. the deployment block of the
+    ///collection, and returns an `Ownership` without holders.
+    pub fn new(from_block: u64) -> Ownership {
+        Ownership {
+            next_block: from_block,
+            balances: BTreeMap::new(),
+        }
+    }
+
+    ///The `load()` function takes the path of a file saved with `save()` and attempts to return the ownership as
+    ///`Ok(Some(Ownership))`, or `Ok(None)` if the file does not exist. Returns an `Err()` if the file cannot be read or
+    ///parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Ownership>, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(ownership) => Ok(Some(serde_json::from_str(&ownership)?)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    ///The `save()` function takes a path and attempts to write the ownership to it as `Ok(())`. Returns an `Err()` if
+    ///the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    ///Returns the first block whose transfers are not applied yet, i.e. the block indexing resumes from.
+    pub fn next_block(&self) -> u64 {
+        self.next_block
+    }
+
+    ///Applies a transfer, without moving the checkpoint. Transfers from the zero address mint tokens and transfers
+    ///to it burn them.
+    pub fn apply(&mut self, transfer: &Transfer) {
+        let holders = self.balances.entry(transfer.id).or_default();
+        if transfer.from != Address::default() {
+            let balance = holders.entry(transfer.from).or_default();
+            *balance = balance.saturating_sub(transfer.amount);
+            if balance.is_zero() {
+                holders.remove(&transfer.from);
+            }
+        }
+        if transfer.to != Address::default() && !transfer.amount.is_zero() {
+            let balance = holders.entry(transfer.to).or_default();
+            *balance = balance.saturating_add(transfer.amount);
+        }
+        if holders.is_empty() {
+            self.balances.remove(&transfer.id);
+        }
+    }
+
+    ///The `apply_batch()` function takes a batch of logs from an `indexer::Coordinator` and attempts to apply their
+    ///transfers and move the checkpoint after the batch, returning the transfers as `Ok(Vec<Transfer>)`. Returns an
+    ///`Err()` without applying anything if the batch does not start at the checkpoint, so that no block is missed or
+    ///applied twice, or if a transfer log cannot be decoded.
+    pub fn apply_batch(&mut self, batch: &Batch) -> Result<Vec<Transfer>, Box<dyn Error>> {
+        if batch.from_block != self.next_block {
+            return Err(format!(
+                "Batch {}..={} does not start at checkpoint block {}",
+                batch.from_block, batch.to_block, self.next_block
+            )
+            .into());
+        }
+        let mut transfers = vec![];
+        for log in &batch.logs {
+            transfers.extend(decode_transfers(log)?);
+        }
+        transfers.iter().for_each(|transfer| self.apply(transfer));
+        self.next_block = batch.to_block + 1;
+        Ok(transfers)
+    }
+
+    ///Returns the holder of an ERC-721 token, or `None` if it is not minted, burned or held by several addresses, as
+    ///ERC-1155 tokens can be.
+    pub fn owner_of(&self, id: U256) -> Option<Address> {
+        let holders = self.balances.get(&id)?;
+        match holders.len() {
+            1 => holders.keys().next().copied(),
+            _ => None,
+        }
+    }
+
+    ///Returns the amount of a token held by an address.
+    pub fn balance_of(&self, holder: Address, id: U256) -> U256 {
+        self.balances
+            .get(&id)
+            .and_then(|holders| holders.get(&holder))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    ///Returns the holders of a token and their amounts, ordered by address.
+    pub fn holders(&self, id: U256) -> Vec<(Address, U256)> {
+        self.balances
+            .get(&id)
+            .map(|holders| {
+                holders
+                    .iter()
+                    .map(|(holder, amount)| (*holder, *amount))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    ///Returns the tokens held by an address and their amounts, ordered by id.
+    pub fn tokens_of(&self, holder: Address) -> Vec<(U256, U256)> {
+        self.balances
+            .iter()
+            .filter_map(|(id, holders)| holders.get(&holder).map(|amount| (*id, *amount)))
+            .collect()
+    }
+
+    ///Returns the ids of the tokens held by at least one address.
+    pub fn ids(&self) -> impl Iterator<Item = &U256> {
+        self.balances.keys()
+    }
+}
+
+///The `OwnershipUpdate` struct is handed to the handler of `NftIndexer::run()` after every batch: the transfers in the
+///batch, already applied to the `Ownership`, and the block range they were found in.
+#[derive(Debug)]
+pub struct OwnershipUpdate<'a> {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub transfers: Vec<Transfer>,
+    pub ownership: &'a Ownership,
+}
+
+///The `NftIndexer` struct keeps the `Ownership` of a collection up to date, backfilling its transfer logs from the
+///checkpoint and then tailing new blocks with an `indexer::Coordinator`. To stay safe from reorgs, it only indexes
+///blocks 12 confirmations deep by default, so that applied transfers never have to be rolled back.
+///## Example
+///```rust,no_run
+///use ethrs::nft::{NftIndexer, Ownership};
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///use std::sync::atomic::AtomicBool;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let collection = "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?;
+///  let ownership = Ownership::load("ownership.json")?.unwrap_or_else(|| Ownership::new(17000000));
+///  let stop = AtomicBool::new(false);
+///  NftIndexer::new(provider, collection, ownership).run(&stop, |update| {
+///      println!("{} transfers up to block {}", update.transfers.len(), update.to_block);
+///      update.ownership.save("ownership.json")
+///  })?;
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct NftIndexer {
+    provider: Provider,
+    collection: Address,
+    ownership: Ownership,
+    batch_size: u64,
+    confirmations: u64,
+    poll_interval: Duration,
+}
+
+impl NftIndexer {
+    ///The `NftIndexer::new()` associated function takes a `Provider`, the address of the collection and its ownership
+    ///at a checkpoint, and returns an indexer fetching up to 1000 blocks per batch, 12 blocks behind the chain head and
+    ///polling every second.
+    pub fn new(provider: Provider, collection: Address, ownership: Ownership) -> NftIndexer {
+        NftIndexer {
+            provider,
+            collection,
+            ownership,
+            batch_size: 1000,
+            confirmations: 12,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    ///Sets the maximum number of blocks fetched per `eth_getLogs` request.
+    pub fn with_batch_size(mut self, batch_size: u64) -> NftIndexer {
+        self.batch_size = batch_size;
+        self
+    }
+
+    ///Sets how many blocks behind the chain head indexing stays. Blocks indexed with fewer confirmations than the depth
+    ///of a reorg leave the ownership inconsistent.
+    pub fn with_confirmations(mut self, confirmations: u64) -> NftIndexer {
+        self.confirmations = confirmations;
+        self
+    }
+
+    ///Sets how often new blocks are polled for once the backfill is done.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> NftIndexer {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    ///Returns the current ownership of the collection.
+    pub fn ownership(&self) -> &Ownership {
+        &self.ownership
+    }
+
+    ///The `run()` function indexes the transfers of the collection until `stop` is set, handing an `OwnershipUpdate` to
+    ///the handler after every batch, and returns `Ok(())` once stopped. Returns an `Err()` on JSON-RPC errors, on
+    ///invalid transfer logs or as soon as the handler fails; the ownership is then left at the last batch applied.
+    pub fn run(
+        &mut self,
+        stop: &AtomicBool,
+        mut handler: impl FnMut(OwnershipUpdate) -> Result<(), Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let filter = Filter {
+            address: vec![self.collection],
+            topics: vec![Some(topics().to_vec())],
+            ..Default::default()
+        };
+        let coordinator =
+            Coordinator::new(self.provider.clone(), filter, self.ownership.next_block())
+                .with_batch_size(self.batch_size)
+                .with_confirmations(self.confirmations)
+                .with_poll_interval(self.poll_interval);
+        let ownership = &mut self.ownership;
+        coordinator.run(stop, |batch| {
+            let transfers = ownership.apply_batch(&batch)?;
+            handler(OwnershipUpdate {
+                from_block: batch.from_block,
+                to_block: batch.to_block,
+                transfers,
+                ownership,
+            })
+        })
+    }
+}
+
+///The `Erc721` struct is an ERC-721 collection contract, read at the latest block unless another block is set with
+///`with_block()`.
+///## Example
+///```rust,no_run
+///use ethrs::nft::Erc721;
+///use ethrs::provider::Provider;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let collection = Erc721::new("0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?, provider.clone());
+///  println!("#7 is held by {:?}: {}", collection.owner_of(7.into())?, collection.token_uri(7.into())?);
+///  let holder = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+///  for log in provider.get_logs(&collection.transfer_filter(None, Some(holder)))? {
+///      println!("Received in block {}", log.block_number);
+///  }
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Erc721 {
+    address: Address,
+    provider: Provider,
+    block: Option<BlockId>,
+}
+
+impl Erc721 {
+    ///The `Erc721::new()` associated function takes the address of a collection and a `Provider`, and returns an
+    ///`Erc721` reading it at the latest block.
+    pub fn new(address: Address, provider: Provider) -> Erc721 {
+        Erc721 {
+            address,
+            provider,
+            block: None,
+        }
+    }
+
+    ///The `with_block()` function sets the block the collection is read at.
+    pub fn with_block(mut self, block: BlockId) -> Erc721 {
+        self.block = Some(block);
+        self
+    }
+
+    ///Returns the address of the collection.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    ///The `owner_of()` function takes a token id and attempts to return the holder of the token as `Ok(Address)`.
+    ///Returns an `Err()` on JSON-RPC errors, e.g. when the token does not exist, or if the return data is malformed.
+    pub fn owner_of(&self, id: U256) -> Result<Address, Box<dyn Error>> {
+        let output = self.call("ownerOf(uint256)", &[Token::Uint(id)], ParamType::Address)?;
+        Address::from_token(output)
+    }
+
+    ///The `token_uri()` function takes a token id and attempts to return the URI of its metadata as `Ok(String)`.
+    ///Returns an `Err()` on JSON-RPC errors, e.g. when the collection does not implement the optional metadata
+    ///extension, or if the return data is malformed.
+    pub fn token_uri(&self, id: U256) -> Result<String, Box<dyn Error>> {
+        let output = self.call("tokenURI(uint256)", &[Token::Uint(id)], ParamType::String)?;
+        String::from_token(output)
+    }
+
+    ///The `balance_of()` function takes an address and attempts to return the number of tokens it holds as `Ok(U256)`.
+    ///Returns an `Err()` on JSON-RPC errors or if the return data is malformed.
+    pub fn balance_of(&self, owner: Address) -> Result<U256, Box<dyn Error>> {
+        let output = self.call(
+            "balanceOf(address)",
+            &[Token::Address(owner)],
+            ParamType::Uint(256),
+        )?;
+        U256::from_token(output)
+    }
+
+    ///The `is_approved_for_all()` function takes an owner and an operator address, and attempts to return whether the
+    ///operator may transfer every token of the owner as `Ok(bool)`. Returns an `Err()` on JSON-RPC errors or if the
+    ///return data is malformed.
+    pub fn is_approved_for_all(
+        &self,
+        owner: Address,
+        operator: Address,
+    ) -> Result<bool, Box<dyn Error>> {
+        let output = self.call(
+            "isApprovedForAll(address,address)",
+            &[Token::Address(owner), Token::Address(operator)],
+            ParamType::Bool,
+        )?;
+        bool::from_token(output)
+    }
+
+    ///Returns a filter matching the `Transfer` logs of the collection, from and to the given addresses if any, to be
+    ///completed with a block range and decoded with `decode_transfers()`.
+    pub fn transfer_filter(&self, from: Option<Address>, to: Option<Address>) -> Filter {
+        Filter {
+            address: vec![self.address],
+            topics: vec![
+                Some(vec![topics()[0]]),
+                from.map(|from| vec![address_topic(from)]),
+                to.map(|to| vec![address_topic(to)]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn call(
+        &self,
+        signature: &str,
+        args: &[Token],
+        output: ParamType,
+    ) -> Result<Token, Box<dyn Error>> {
+        call(
+            &self.provider,
+            self.address,
+            self.block,
+            signature,
+            args,
+            output,
+        )
+    }
+}
+
+///The `Erc1155` struct is an ERC-1155 collection contract, read at the latest block unless another block is set with
+///`with_block()`.
+///## Example
+///```rust,no_run
+///use ethrs::nft::Erc1155;
+///use ethrs::provider::Provider;
+///use ethrs::types::Address;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let collection = Erc1155::new("0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?, Provider::new("http://localhost:8545"));
+///  let holder: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+///  let balances = collection.balance_of_batch(&[holder, holder], &[1.into(), 2.into()])?;
+///  println!("{balances:?}, metadata at {}", collection.uri(1.into())?);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct Erc1155 {
+    address: Address,
+    provider: Provider,
+    block: Option<BlockId>,
+}
+
+impl Erc1155 {
+    ///The `Erc1155::new()` associated function takes the address of a collection and a `Provider`, and returns an
+    ///`Erc1155` reading it at the latest block.
+    pub fn new(address: Address, provider: Provider) -> Erc1155 {
+        Erc1155 {
+            address,
+            provider,
+            block: None,
+        }
+    }
+
+    ///The `with_block()` function sets the block the collection is read at.
+    pub fn with_block(mut self, block: BlockId) -> Erc1155 {
+        self.block = Some(block);
+        self
+    }
+
+    ///Returns the address of the collection.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    ///The `balance_of()` function takes an address and a token id, and attempts to return the amount of the token the
+    ///address holds as `Ok(U256)`. Returns an `Err()` on JSON-RPC errors or if the return data is malformed.
+    pub fn balance_of(&self, owner: Address, id: U256) -> Result<U256, Box<dyn Error>> {
+        let output = self.call(
+            "balanceOf(address,uint256)",
+            &[Token::Address(owner), Token::Uint(id)],
+            ParamType::Uint(256),
+        )?;
+        U256::from_token(output)
+    }
+
+    ///The `balance_of_batch()` function takes addresses and token ids, pairwise, and attempts to return the amount of
+    ///each token each address holds as `Ok(Vec<U256>)`, in a single call. Returns an `Err()` if the lists differ in
+    ///length, on JSON-RPC errors or if the return data is malformed.
+    pub fn balance_of_batch(
+        &self,
+        owners: &[Address],
+        ids: &[U256],
+    ) -> Result<Vec<U256>, Box<dyn Error>> {
+        if owners.len() != ids.len() {
+            return Err("Mismatched owners and ids".into());
+        }
+        let output = self.call(
+            "balanceOfBatch(address[],uint256[])",
+            &[
+                Token::Array(owners.iter().copied().map(Token::Address).collect()),
+                Token::Array(ids.iter().copied().map(Token::Uint).collect()),
+            ],
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+        )?;
+        let balances = Vec::<U256>::from_token(output)?;
+        match balances.len() == ids.len() {
+            true => Ok(balances),
+            false => Err(format!("Invalid balanceOfBatch returned by {}", self.address).into()),
+        }
+    }
+
+    ///The `uri()` function takes a token id and attempts to return the URI of its metadata as `Ok(String)`. Clients
+    ///are expected to replace `{id}` in the URI with the id as 64 lowercase hex digits. Returns an `Err()` on JSON-RPC
+    ///errors, e.g. when the collection does not implement the optional metadata extension, or if the return data is
+    ///malformed.
+    pub fn uri(&self, id: U256) -> Result<String, Box<dyn Error>> {
+        let output = self.call("uri(uint256)", &[Token::Uint(id)], ParamType::String)?;
+        String::from_token(output)
+    }
+
+    ///Returns a filter matching the `TransferSingle` and `TransferBatch` logs of the collection, from and to the given
+    ///addresses if any, to be completed with a block range and decoded with `decode_transfers()`.
+    pub fn transfer_filter(&self, from: Option<Address>, to: Option<Address>) -> Filter {
+        let [_, single, batch] = topics();
+        Filter {
+            address: vec![self.address],
+            topics: vec![
+                Some(vec![single, batch]),
+                // any operator
+                None,
+                from.map(|from| vec![address_topic(from)]),
+                to.map(|to| vec![address_topic(to)]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn call(
+        &self,
+        signature: &str,
+        args: &[Token],
+        output: ParamType,
+    ) -> Result<Token, Box<dyn Error>> {
+        call(
+            &self.provider,
+            self.address,
+            self.block,
+            signature,
+            args,
+            output,
+        )
+    }
+}
+
+fn address_topic(address: Address) -> H256 {
+    let mut topic = [0u8; 32];
+    topic[12..].copy_from_slice(&address.0);
+    H256(topic)
+}
+
+fn call(
+    provider: &Provider,
+    address: Address,
+    block: Option<BlockId>,
+    signature: &str,
+    args: &[Token],
+    output: ParamType,
+) -> Result<Token, Box<dyn Error>> {
+    let tx =
+        CallInput::to(address).data([&function_selector(signature)[..], &encode(args)].concat());
+    let data: Bytes = provider.call(tx, block)?.parse()?;
+    decode(&[output], &data.0)?
+        .pop()
+        .ok_or_else(|| format!("Invalid {signature} returned by {address}").into())
+}
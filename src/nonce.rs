@@ -0,0 +1,178 @@
+//!The nonce module provides the `NonceManager`, which hands out the nonces of an account locally so that transactions
+//!sent concurrently from it do not race on the nonce the node reports.
+use crate::provider::{Provider, TransactionInput};
+use crate::types::{Address, BlockId, H256, U256};
+
+use std::error::Error;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+///The `NonceManager` struct tracks the next nonce of an account. It is initialized from the pending transaction count
+///of the account on first use, then handed out sequentially under a lock, so that every caller gets a distinct nonce.
+///When the node rejects a transaction with "nonce too low", e.g. because another process sent from the account, the
+///nonce is resynced from the node and the transaction is sent again. Clones share the tracked nonce, so a clone can
+///be moved to each sending thread.
+///## Example
+///```rust,no_run
+///use ethrs::nonce::NonceManager;
+///use ethrs::provider::{Provider, TransactionInput};
+///use ethrs::types::U256;
+///use std::error::Error;
+///use std::thread;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let from = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?;
+///  let nonces = NonceManager::new(Provider::new("http://localhost:8545"), from);
+///  let senders: Vec<_> = (0..4)
+///      .map(|_| {
+///          let nonces = nonces.clone();
+///          thread::spawn(move || {
+///              nonces.send_transaction(TransactionInput {
+///                  from,
+///                  to: Some(from),
+///                  gas: None,
+///                  gas_price: None,
+///                  value: Some(U256::from(1)),
+///                  data: None,
+///                  nonce: None,
+///                  access_list: None,
+///              }).is_ok()
+///          })
+///      })
+///      .collect();
+///  for sender in senders {
+///      assert!(sender.join().unwrap());
+///  }
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct NonceManager {
+    provider: Provider,
+    address: Address,
+    next: Arc<Mutex<Option<u64>>>,
+}
+
+impl NonceManager {
+    ///The `NonceManager::new()` associated function takes a `Provider` and the address of the account to track the
+    ///nonce of, and returns a `NonceManager` fetching the nonce from the node on first use.
+    pub fn new(provider: Provider, address: Address) -> NonceManager {
+        NonceManager {
+            provider,
+            address,
+            next: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    ///Returns the address of the account.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    ///The `next_nonce()` function attempts to return the next nonce of the account as `Ok(u64)`, and counts it as used.
+    ///Returns an `Err()` on JSON-RPC errors while initializing the nonce.
+    pub fn next_nonce(&self) -> Result<u64, Box<dyn Error>> {
+        let mut next = self.lock();
+        let nonce = self.current(&mut next)?;
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    ///The `resync()` function attempts to reset the next nonce to the pending transaction count of the account and
+    ///return it as `Ok(u64)`. Returns an `Err()` on JSON-RPC errors.
+    pub fn resync(&self) -> Result<u64, Box<dyn Error>> {
+        let mut next = self.lock();
+        *next = None;
+        self.current(&mut next)
+    }
+
+    ///The `send_with()` function takes a closure sending a transaction with the nonce it is given, and attempts to
+    ///return its result as `Ok(T)`. The lock is held while the closure runs, so that transactions are sent in nonce
+    ///order. The nonce is only counted as used if the closure succeeds; on a "nonce too low" error the nonce is resynced
+    ///and the closure is called once more. Returns an `Err()` on JSON-RPC errors or if the closure fails.
+    ///## Example
+    ///```rust,no_run
+    ///use ethrs::nonce::NonceManager;
+    ///use ethrs::provider::Provider;
+    ///use ethrs::transaction::{Eip1559Transaction, TypedTransaction};
+    ///use ethrs::types::U256;
+    ///use ethrs::wallet::Wallet;
+    ///use serde_json::json;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("http://localhost:8545");
+    ///  let wallet: Wallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse()?;
+    ///  let nonces = NonceManager::new(provider.clone(), wallet.address());
+    ///  let txhash: String = nonces.send_with(|nonce| {
+    ///      let transaction = TypedTransaction::from(Eip1559Transaction {
+    ///          chain_id: 31337,
+    ///          nonce,
+    ///          gas_limit: 21000,
+    ///          max_fee_per_gas: U256::from(2_000_000_000u64),
+    ///          to: Some(wallet.address()),
+    ///          ..Default::default()
+    ///      });
+    ///      let raw = wallet.sign_transaction(transaction)?.encode()?;
+    ///      provider.request("eth_sendRawTransaction", json!([format!("0x{}", hex::encode(raw))]))
+    ///  })?;
+    ///  println!("Sent {txhash}");
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn send_with<T>(
+        &self,
+        mut send: impl FnMut(u64) -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        let mut next = self.lock();
+        let nonce = self.current(&mut next)?;
+        let result = match send(nonce) {
+            Err(err) if is_nonce_too_low(err.as_ref()) => {
+                *next = None;
+                let nonce = self.current(&mut next)?;
+                send(nonce).map(|result| (result, nonce))
+            }
+            result => result.map(|result| (result, nonce)),
+        };
+        let (result, nonce) = result?;
+        *next = Some(nonce + 1);
+        Ok(result)
+    }
+
+    ///The `send_transaction()` function takes a transaction input struct, sends it with the next nonce of the account as
+    ///`send_with()` does and attempts to return the transaction hash as `Ok(H256)`. The nonce of the transaction is
+    ///overwritten. Returns an `Err()` on JSON-RPC errors.
+    pub fn send_transaction(&self, tx: TransactionInput) -> Result<H256, Box<dyn Error>> {
+        self.send_with(|nonce| {
+            self.provider.send_transaction(TransactionInput {
+                nonce: Some(U256::from(nonce)),
+                ..tx.clone()
+            })
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<u64>> {
+        // the nonce is only written once a send succeeds, so it is consistent even if a closure panicked
+        self.next
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn current(&self, next: &mut Option<u64>) -> Result<u64, Box<dyn Error>> {
+        if let Some(nonce) = *next {
+            return Ok(nonce);
+        }
+        let count = self
+            .provider
+            .get_transaction_count(self.address, Some(BlockId::Pending))?;
+        let nonce = u64::try_from(count).map_err(|_| "Transaction count out of range")?;
+        *next = Some(nonce);
+        Ok(nonce)
+    }
+}
+
+///Returns whether the error is the rejection of a transaction whose nonce was already used, as reported by the common
+///clients.
+pub(crate) fn is_nonce_too_low(err: &dyn Error) -> bool {
+    let message = err.to_string().to_lowercase();
+    message.contains("nonce too low") || message.contains("oldnonce")
+}
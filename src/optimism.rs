@@ -0,0 +1,190 @@
+//!The optimism module provides helpers for proving OP-stack L2→L1 withdrawals on the canonical bridge, i.e. the inputs
+//!required by `OptimismPortal.proveWithdrawalTransaction()`.
+use primitive_types::U256;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::abi::{encode, Token};
+use crate::provider::Provider;
+use crate::types::{Address, BlockId, H256};
+use crate::utils::keccak256;
+
+use std::error::Error;
+
+///The address of the `L2ToL1MessagePasser` predeploy on every OP-stack chain.
+pub const L2_TO_L1_MESSAGE_PASSER: &str = "0x4200000000000000000000000000000000000016";
+
+///The `Withdrawal` struct mirrors the `Types.WithdrawalTransaction` struct of the OP-stack contracts, as emitted by the
+///`MessagePassed` event on L2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Withdrawal {
+    pub nonce: U256,
+    pub sender: Address,
+    pub target: Address,
+    pub value: U256,
+    pub gas_limit: U256,
+    pub data: Vec<u8>,
+}
+
+///The `OutputRootProof` struct contains the preimage of an L2 output root, as expected by `OptimismPortal`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputRootProof {
+    pub version: H256,
+    pub state_root: H256,
+    pub message_passer_storage_root: H256,
+    pub latest_blockhash: H256,
+}
+
+///The `WithdrawalProof` struct bundles everything needed to prove a withdrawal against an L2 output on L1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawalProof {
+    pub withdrawal_hash: H256,
+    pub l2_block_number: u64,
+    pub output_root: H256,
+    pub output_root_proof: OutputRootProof,
+    pub withdrawal_proof: Vec<Vec<u8>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct OutputResponse {
+    version: H256,
+    output_root: H256,
+    state_root: H256,
+    withdrawal_storage_root: H256,
+    block_ref: BlockRef,
+}
+
+#[derive(Deserialize, Debug)]
+struct BlockRef {
+    hash: H256,
+}
+
+impl Withdrawal {
+    ///The `hash()` function returns the withdrawal hash,
+    ///`keccak256(abi.encode(nonce, sender, target, value, gasLimit, data))`.
+    ///## Example
+    ///```rust
+    ///use ethrs::optimism::Withdrawal;
+    ///use ethrs::types::{Address, H256, U256};
+    ///
+    ///let withdrawal = Withdrawal {
+    ///    nonce: U256::zero(),
+    ///    sender: Address::ZERO,
+    ///    target: Address::ZERO,
+    ///    value: U256::zero(),
+    ///    gas_limit: U256::zero(),
+    ///    data: vec![],
+    ///};
+    ///assert_ne!(withdrawal.hash(), H256::ZERO);
+    ///```
+    pub fn hash(&self) -> H256 {
+        H256(keccak256(&encode(&[
+            Token::Uint(self.nonce),
+            Token::Address(self.sender),
+            Token::Address(self.target),
+            Token::Uint(self.value),
+            Token::Uint(self.gas_limit),
+            Token::Bytes(self.data.clone()),
+        ])))
+    }
+
+    ///The `storage_slot()` function returns the slot of `sentMessages[hash]` in the `L2ToL1MessagePasser` storage,
+    ///which is the key that has to be proven on L1.
+    pub fn storage_slot(&self) -> H256 {
+        H256(keccak256(&encode(&[
+            Token::FixedBytes(self.hash().0.to_vec()),
+            Token::Uint(U256::zero()),
+        ])))
+    }
+}
+
+impl OutputRootProof {
+    ///The `output_root()` function hashes the proof into the output root committed to by the L2 output oracle.
+    pub fn output_root(&self) -> H256 {
+        H256(keccak256(
+            &[
+                self.version.0,
+                self.state_root.0,
+                self.message_passer_storage_root.0,
+                self.latest_blockhash.0,
+            ]
+            .concat(),
+        ))
+    }
+}
+
+///The `output_at_block()` function takes a rollup node provider (exposing the `optimism_*` namespace) and an L2 block
+///number, and attempts to return the output root and its preimage as `Ok((H256, OutputRootProof))`. Returns an
+///`Err()` on JSON-RPC errors or when the returned preimage does not hash to the returned output root.
+pub fn output_at_block(
+    rollup_provider: &Provider,
+    l2_block_number: u64,
+) -> Result<(H256, OutputRootProof), Box<dyn Error>> {
+    let output: OutputResponse = rollup_provider.request(
+        "optimism_outputAtBlock",
+        json!([format!("0x{l2_block_number:x}")]),
+    )?;
+    let proof = OutputRootProof {
+        version: output.version,
+        state_root: output.state_root,
+        message_passer_storage_root: output.withdrawal_storage_root,
+        latest_blockhash: output.block_ref.hash,
+    };
+    match proof.output_root() == output.output_root {
+        true => Ok((output.output_root, proof)),
+        false => Err("Output root does not match its preimage".into()),
+    }
+}
+
+///The `prove_withdrawal()` function takes an L2 execution provider, a rollup node provider, a withdrawal and the L2 block
+///number of the output being proven against, and attempts to assemble a `WithdrawalProof` as `Ok(WithdrawalProof)`.
+///The L2 block must be at or after the block containing the withdrawal. Returns an `Err()` on JSON-RPC errors.
+///## Example
+///```rust,no_run
+///use ethrs::optimism::{prove_withdrawal, Withdrawal};
+///use ethrs::provider::Provider;
+///use ethrs::types::{Address, U256};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let l2 = Provider::new("https://sepolia.optimism.io");
+///  let rollup = Provider::new("http://localhost:9545");
+///  let withdrawal = Withdrawal {
+///      nonce: U256::from("0x0001000000000000000000000000000000000000000000000000000000000000"),
+///      sender: Address::ZERO,
+///      target: Address::ZERO,
+///      value: U256::zero(),
+///      gas_limit: U256::from(100000),
+///      data: vec![],
+///  };
+///  let proof = prove_withdrawal(&l2, &rollup, &withdrawal, 12000000)?;
+///  println!("{:?}", proof.output_root_proof);
+///  Ok(())
+///}
+///```
+pub fn prove_withdrawal(
+    l2_provider: &Provider,
+    rollup_provider: &Provider,
+    withdrawal: &Withdrawal,
+    l2_block_number: u64,
+) -> Result<WithdrawalProof, Box<dyn Error>> {
+    let (output_root, output_root_proof) = output_at_block(rollup_provider, l2_block_number)?;
+    let proof = l2_provider.get_proof(
+        L2_TO_L1_MESSAGE_PASSER.parse()?,
+        &[withdrawal.storage_slot()],
+        Some(BlockId::Number(l2_block_number)),
+    )?;
+    let withdrawal_proof = match proof.storage_proof.into_iter().next() {
+        Some(storage_proof) => storage_proof.proof.into_iter().map(Vec::from).collect(),
+        None => return Err("No storage proof returned".into()),
+    };
+
+    Ok(WithdrawalProof {
+        withdrawal_hash: withdrawal.hash(),
+        l2_block_number,
+        output_root,
+        output_root_proof,
+        withdrawal_proof,
+    })
+}
@@ -0,0 +1,351 @@
+//!The proof module verifies the Merkle-Patricia proofs returned by `eth_getProof` against a state root locally, so that
+//!account and storage values fetched from an untrusted RPC can be checked against a trusted block header. It also
+//!builds tries in memory with `trie_root()`, to recompute the transactions and receipts roots of blocks.
+use crate::provider::{AccountProof, StorageProof, Transaction, TransactionReceipt, TxStatus};
+use crate::rlp::{
+    decode_list, decode_string, encode, encode_list, encode_string, Encodable, RlpItem,
+};
+use crate::signature::Signature;
+use crate::transaction::TypedTransaction;
+use crate::types::{Bytes, H256};
+use crate::utils::keccak256;
+
+use std::error::Error;
+
+///The root hash of an empty trie, `keccak256(rlp(""))`.
+pub const EMPTY_ROOT: H256 = H256([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+///The code hash of accounts without code, `keccak256("")`.
+pub const EMPTY_CODE_HASH: H256 = H256([
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+]);
+
+///The `verify_proof()` function takes a trie root, a key and the proof nodes of the key, starting from the root, and
+///attempts to return the value stored under `keccak256(key)` as `Ok(Some(Vec<u8>))`. Returns `Ok(None)` if the proof
+///shows that the key is absent from the trie and returns an `Err()` if the proof is invalid.
+pub fn verify_proof(
+    root: H256,
+    key: &[u8],
+    proof: &[Bytes],
+) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    let path = to_nibbles(&keccak256(key));
+    let mut nodes = proof.iter();
+    let mut expected = NodeRef::Hash(root.0.to_vec());
+    let mut depth = 0;
+
+    loop {
+        let node = match expected {
+            NodeRef::Hash(hash) => match nodes.next() {
+                Some(node) if keccak256(node) == hash.as_slice() => node.as_ref(),
+                Some(_) => return Err("Proof node does not match its hash".into()),
+                None if root == EMPTY_ROOT && depth == 0 => return Ok(None),
+                None => return Err("Proof is incomplete".into()),
+            },
+            NodeRef::Inline(node) => node,
+        };
+        let items = decode_list(node)?;
+        match items.len() {
+            17 => {
+                if depth == path.len() {
+                    return Err("Branch node values are not used by state tries".into());
+                }
+                match child(items[path[depth] as usize])? {
+                    Some(next) => expected = next,
+                    None => return Ok(None),
+                }
+                depth += 1;
+            }
+            2 => {
+                let (is_leaf, node_path) = decode_path(decode_string(items[0])?)?;
+                let remaining = &path[depth..];
+                if is_leaf {
+                    return match remaining == node_path.as_slice() {
+                        true => Ok(Some(decode_string(items[1])?.to_vec())),
+                        false => Ok(None),
+                    };
+                }
+                if !remaining.starts_with(&node_path) {
+                    return Ok(None);
+                }
+                depth += node_path.len();
+                match child(items[1])? {
+                    Some(next) => expected = next,
+                    None => return Err("Extension node has no child".into()),
+                }
+            }
+            _ => return Err("Invalid trie node".into()),
+        }
+    }
+}
+
+///The `verify_storage_proof()` function takes the storage root of an account and a storage proof, and attempts to
+///verify that the slot holds the claimed value as `Ok(())`. Zero values must be proven absent. Returns an `Err()` if
+///the proof is invalid or does not match the claimed value.
+pub fn verify_storage_proof(
+    storage_root: H256,
+    proof: &StorageProof,
+) -> Result<(), Box<dyn Error>> {
+    let mut key = [0u8; 32];
+    proof.key.to_big_endian(&mut key);
+    let value = verify_proof(storage_root, &key, &proof.proof)?;
+    let expected = match proof.value.is_zero() {
+        true => None,
+        false => Some(encode(&proof.value)),
+    };
+    match value == expected {
+        true => Ok(()),
+        false => Err(format!(
+            "Storage proof does not match the value of slot {:#x}",
+            proof.key
+        )
+        .into()),
+    }
+}
+
+///The `verify_account_proof()` function takes a trusted state root and an `eth_getProof` response, and attempts to
+///verify the account fields and every storage proof it contains as `Ok(())`. Empty accounts must be proven absent.
+///Returns an `Err()` if any proof is invalid or does not match the claimed values.
+///## Example
+///```rust
+///use ethrs::proof::verify_account_proof;
+///use ethrs::provider::Provider;
+///use ethrs::types::{BlockId, H256};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  // the state root should come from a trusted source, e.g. a light client
+///  let block = provider.get_block_by_number(Some(BlockId::Number(3347000)))?.unwrap();
+///  let proof = provider.get_proof(
+///      "0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?,
+///      &[H256::ZERO],
+///      Some(BlockId::Number(3347000)),
+///  )?;
+///  verify_account_proof(block.state_root, &proof)?;
+///  Ok(())
+///}
+///```
+pub fn verify_account_proof(state_root: H256, proof: &AccountProof) -> Result<(), Box<dyn Error>> {
+    let account = verify_proof(state_root, proof.address.as_bytes(), &proof.account_proof)?;
+    let is_empty = proof.nonce.is_zero()
+        && proof.balance.is_zero()
+        && proof.storage_hash == EMPTY_ROOT
+        && proof.code_hash == EMPTY_CODE_HASH;
+    let expected = match is_empty {
+        true => None,
+        false => Some(encode_list(&[
+            encode(&proof.nonce),
+            encode(&proof.balance),
+            encode_string(proof.storage_hash.as_bytes()),
+            encode_string(proof.code_hash.as_bytes()),
+        ])),
+    };
+    if account != expected {
+        return Err(format!(
+            "Account proof does not match the state of {}",
+            proof.address
+        )
+        .into());
+    }
+    for storage_proof in &proof.storage_proof {
+        verify_storage_proof(proof.storage_hash, storage_proof)?;
+    }
+    Ok(())
+}
+
+///The `trie_root()` function takes key-value pairs and returns the root of the Merkle-Patricia trie holding them. Keys
+///are used as they are, unlike in state tries where they are hashed first, and must be distinct.
+///## Example
+///```rust
+///use ethrs::proof::{trie_root, EMPTY_ROOT};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  assert_eq!(trie_root(Vec::<(Vec<u8>, Vec<u8>)>::new()), EMPTY_ROOT);
+///  assert_eq!(
+///      trie_root([("doe", "reindeer"), ("dog", "puppy"), ("dogglesworth", "cat")]),
+///      "0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3".parse()?
+///  );
+///  Ok(())
+///}
+///```
+pub fn trie_root<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+    entries: impl IntoIterator<Item = (K, V)>,
+) -> H256 {
+    let mut entries: Vec<(Vec<u8>, Vec<u8>)> = entries
+        .into_iter()
+        .map(|(key, value)| (to_nibbles(key.as_ref()), value.as_ref().to_vec()))
+        .collect();
+    if entries.is_empty() {
+        return EMPTY_ROOT;
+    }
+    entries.sort();
+    // the root is referenced by its hash even when it is shorter than 32 bytes
+    H256(keccak256(&encode_node(&entries, 0)))
+}
+
+///The `ordered_trie_root()` function takes a list of values and returns the root of the trie holding every value under
+///the RLP encoding of its index, as the transactions, receipts and withdrawals of blocks are stored.
+pub fn ordered_trie_root<V: AsRef<[u8]>>(values: impl IntoIterator<Item = V>) -> H256 {
+    trie_root(
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (encode(&index), value)),
+    )
+}
+
+///The `transactions_root()` function takes the transactions of a block, in order, and attempts to return the root of
+///their trie as `Ok(H256)`, to be compared with the `transactions_root` of the block. Returns an `Err()` if a
+///transaction cannot be encoded or does not encode to its hash, e.g. if the node returned fields that were tampered
+///with or are missing.
+pub fn transactions_root(transactions: &[Transaction]) -> Result<H256, Box<dyn Error>> {
+    let encoded = transactions
+        .iter()
+        .map(|transaction| -> Result<Vec<u8>, Box<dyn Error>> {
+            let encoded = TypedTransaction::try_from(transaction)?
+                .encode_signed(&Signature::try_from(transaction)?)?;
+            match keccak256(&encoded) == transaction.hash.0 {
+                true => Ok(encoded),
+                false => Err(format!(
+                    "Transaction {} does not encode to its hash",
+                    transaction.hash
+                )
+                .into()),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ordered_trie_root(encoded))
+}
+
+///The `receipts_root()` function takes the receipts of every transaction of a block, in order, and attempts to return
+///the root of their trie as `Ok(H256)`, to be compared with the `receipts_root` of the block. Returns an `Err()` if the
+///log bloom of a receipt is malformed.
+pub fn receipts_root(receipts: &[TransactionReceipt]) -> Result<H256, Box<dyn Error>> {
+    let encoded = receipts
+        .iter()
+        .map(encode_receipt)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ordered_trie_root(encoded))
+}
+
+///Returns the consensus encoding of a receipt, prefixed with the type of its transaction unless it is legacy.
+fn encode_receipt(receipt: &TransactionReceipt) -> Result<Vec<u8>, Box<dyn Error>> {
+    let outcome = match &receipt.status {
+        TxStatus::Success => 1u8.to_rlp(),
+        TxStatus::Failed => 0u8.to_rlp(),
+        TxStatus::PreByzantiumRoot(root) => root.to_rlp(),
+    };
+    let logs_bloom: Bytes = receipt.logs_bloom.parse()?;
+    let mut encoded = match receipt.transaction_type.unwrap_or(0) {
+        0 => vec![],
+        tx_type => vec![u8::try_from(tx_type)?],
+    };
+    encoded.extend(
+        RlpItem::List(vec![
+            outcome,
+            receipt.cumulative_gas_used.to_rlp(),
+            logs_bloom.to_rlp(),
+            receipt.logs.to_rlp(),
+        ])
+        .encode(),
+    );
+    Ok(encoded)
+}
+
+///Returns the RLP encoding of the trie node holding the sorted entries, whose keys are nibbles, from the given depth.
+fn encode_node(entries: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+    if let [(key, value)] = entries {
+        return encode_list(&[
+            encode_string(&encode_path(&key[depth..], true)),
+            encode_string(value),
+        ]);
+    }
+    // the entries being sorted, the first and last keys share the shortest prefix
+    let (first, last) = (
+        &entries[0].0[depth..],
+        &entries[entries.len() - 1].0[depth..],
+    );
+    let shared = first.iter().zip(last).take_while(|(a, b)| a == b).count();
+    if shared > 0 {
+        return encode_list(&[
+            encode_string(&encode_path(&first[..shared], false)),
+            reference(encode_node(entries, depth + shared)),
+        ]);
+    }
+    let mut branch: Vec<Vec<u8>> = (0..16u8)
+        .map(|nibble| {
+            let children: Vec<_> = entries
+                .iter()
+                .filter(|(key, _)| key.get(depth) == Some(&nibble))
+                .cloned()
+                .collect();
+            match children.is_empty() {
+                true => encode_string(&[]),
+                false => reference(encode_node(&children, depth + 1)),
+            }
+        })
+        .collect();
+    let value = entries.iter().find(|(key, _)| key.len() == depth);
+    branch.push(encode_string(value.map_or(&[][..], |(_, value)| value)));
+    encode_list(&branch)
+}
+
+///Returns how a node is referenced by its parent: embedded if its encoding is shorter than 32 bytes, by hash otherwise.
+fn reference(node: Vec<u8>) -> Vec<u8> {
+    match node.len() < 32 {
+        true => node,
+        false => encode_string(&keccak256(&node)),
+    }
+}
+
+///Returns the hex-prefix encoding of a path of nibbles, flagging whether it ends in a leaf.
+fn encode_path(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let (mut encoded, rest) = match nibbles.len() % 2 {
+        1 => (vec![(flag + 1) << 4 | nibbles[0]], &nibbles[1..]),
+        _ => (vec![flag << 4], nibbles),
+    };
+    encoded.extend(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+    encoded
+}
+enum NodeRef<'a> {
+    Hash(Vec<u8>),
+    Inline(&'a [u8]),
+}
+
+fn child(item: &[u8]) -> Result<Option<NodeRef<'_>>, Box<dyn Error>> {
+    // nodes shorter than 32 bytes are embedded in their parent instead of being referenced by hash
+    if item.first().map_or(false, |prefix| *prefix >= 0xc0) {
+        return Ok(Some(NodeRef::Inline(item)));
+    }
+    let hash = decode_string(item)?;
+    match hash.len() {
+        0 => Ok(None),
+        32 => Ok(Some(NodeRef::Hash(hash.to_vec()))),
+        _ => Err("Invalid child reference".into()),
+    }
+}
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+fn decode_path(encoded: &[u8]) -> Result<(bool, Vec<u8>), Box<dyn Error>> {
+    let nibbles = to_nibbles(encoded);
+    match nibbles.first() {
+        Some(0) => Ok((false, nibbles[2..].to_vec())),
+        Some(1) => Ok((false, nibbles[1..].to_vec())),
+        Some(2) => Ok((true, nibbles[2..].to_vec())),
+        Some(3) => Ok((true, nibbles[1..].to_vec())),
+        _ => Err("Invalid node path".into()),
+    }
+}
@@ -0,0 +1,178 @@
+//!The proto module contains the protobuf messages of `proto/ethrs.proto`, which sink events are encoded as with
+//!`Encoding::Protobuf`, along with conversions from the provider types.
+use primitive_types::U256;
+
+use crate::provider;
+use crate::sink;
+
+fn quantity(value: U256) -> Vec<u8> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes[32 - (value.bits() + 7) / 8..].to_vec()
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Block {
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub hash: Option<Vec<u8>>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub parent_hash: Vec<u8>,
+    #[prost(uint64, optional, tag = "3")]
+    pub number: Option<u64>,
+    #[prost(uint64, tag = "4")]
+    pub timestamp: u64,
+    #[prost(bytes = "vec", optional, tag = "5")]
+    pub miner: Option<Vec<u8>>,
+    #[prost(uint64, tag = "6")]
+    pub gas_limit: u64,
+    #[prost(uint64, tag = "7")]
+    pub gas_used: u64,
+    #[prost(bytes = "vec", tag = "8")]
+    pub state_root: Vec<u8>,
+    #[prost(bytes = "vec", tag = "9")]
+    pub transactions_root: Vec<u8>,
+    #[prost(bytes = "vec", tag = "10")]
+    pub receipts_root: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "11")]
+    pub transactions: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Log {
+    #[prost(bytes = "vec", tag = "1")]
+    pub address: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub topics: Vec<Vec<u8>>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub data: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub block_hash: Vec<u8>,
+    #[prost(uint64, tag = "5")]
+    pub block_number: u64,
+    #[prost(bytes = "vec", tag = "6")]
+    pub transaction_hash: Vec<u8>,
+    #[prost(uint64, tag = "7")]
+    pub transaction_index: u64,
+    #[prost(uint64, tag = "8")]
+    pub log_index: u64,
+    #[prost(bool, tag = "9")]
+    pub removed: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Receipt {
+    #[prost(bytes = "vec", tag = "1")]
+    pub transaction_hash: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub transaction_index: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub block_hash: Vec<u8>,
+    #[prost(uint64, tag = "4")]
+    pub block_number: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub from: Vec<u8>,
+    #[prost(bytes = "vec", optional, tag = "6")]
+    pub to: Option<Vec<u8>>,
+    #[prost(bytes = "vec", optional, tag = "7")]
+    pub contract_address: Option<Vec<u8>>,
+    #[prost(uint64, tag = "8")]
+    pub gas_used: u64,
+    #[prost(bytes = "vec", tag = "9")]
+    pub effective_gas_price: Vec<u8>,
+    #[prost(bool, optional, tag = "10")]
+    pub status: Option<bool>,
+    #[prost(message, repeated, tag = "11")]
+    pub logs: Vec<Log>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Event {
+    #[prost(oneof = "event::Event", tags = "1, 2, 3")]
+    pub event: Option<event::Event>,
+}
+
+///The `event` module contains the `oneof` of the `Event` message.
+pub mod event {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag = "1")]
+        Block(super::Block),
+        #[prost(message, tag = "2")]
+        Log(super::Log),
+        #[prost(message, tag = "3")]
+        Confirmation(super::Receipt),
+    }
+}
+
+impl From<&provider::Block> for Block {
+    fn from(block: &provider::Block) -> Self {
+        Block {
+            hash: block.hash.map(|hash| hash.0.to_vec()),
+            parent_hash: block.parent_hash.0.to_vec(),
+            number: block.number,
+            timestamp: block.timestamp.low_u64(),
+            miner: block.miner.map(|miner| miner.0.to_vec()),
+            gas_limit: block.gas_limit.low_u64(),
+            gas_used: block.gas_used.low_u64(),
+            state_root: block.state_root.0.to_vec(),
+            transactions_root: block.transactions_root.0.to_vec(),
+            receipts_root: block.receipts_root.0.to_vec(),
+            transactions: block
+                .transactions
+                .iter()
+                .map(|hash| hash.0.to_vec())
+                .collect(),
+        }
+    }
+}
+
+impl From<&provider::Log> for Log {
+    fn from(log: &provider::Log) -> Self {
+        Log {
+            address: log.address.0.to_vec(),
+            topics: log.topics.iter().map(|topic| topic.0.to_vec()).collect(),
+            // logs returned by nodes always carry valid hex data
+            data: hex::decode(log.data.trim_start_matches("0x")).unwrap_or_default(),
+            block_hash: log.block_hash.0.to_vec(),
+            block_number: log.block_number,
+            transaction_hash: log.transaction_hash.0.to_vec(),
+            transaction_index: log.transaction_index.low_u64(),
+            log_index: log.log_index.low_u64(),
+            removed: log.removed,
+        }
+    }
+}
+
+impl From<&provider::TransactionReceipt> for Receipt {
+    fn from(receipt: &provider::TransactionReceipt) -> Self {
+        Receipt {
+            transaction_hash: receipt.transaction_hash.0.to_vec(),
+            transaction_index: receipt.transaction_index.low_u64(),
+            block_hash: receipt.block_hash.0.to_vec(),
+            block_number: receipt.block_number,
+            from: receipt.from.0.to_vec(),
+            to: receipt.to.map(|to| to.0.to_vec()),
+            contract_address: receipt.contract_address.map(|address| address.0.to_vec()),
+            gas_used: receipt.gas_used.low_u64(),
+            effective_gas_price: quantity(receipt.effective_gas_price),
+            status: match receipt.status {
+                provider::TxStatus::Success => Some(true),
+                provider::TxStatus::Failed => Some(false),
+                provider::TxStatus::PreByzantiumRoot(_) => None,
+            },
+            logs: receipt.logs.iter().map(Log::from).collect(),
+        }
+    }
+}
+
+impl From<&sink::Event> for Event {
+    fn from(event: &sink::Event) -> Self {
+        Event {
+            event: Some(match event {
+                sink::Event::Block(block) => event::Event::Block(block.into()),
+                sink::Event::Log(log) => event::Event::Log(log.into()),
+                sink::Event::Confirmation(receipt) => event::Event::Confirmation(receipt.into()),
+            }),
+        }
+    }
+}
@@ -4,77 +4,512 @@ use lazy_static::lazy_static;
 use primitive_types::U256;
 use regex::Regex;
 use reqwest;
-use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-
+use serde_json::json;
+
+use crate::abi::{decode, Detokenize, ParamType};
+use crate::bloom::Bloom;
+use crate::cache::{is_read, ResponseCache};
+use crate::metrics::ProviderMetrics;
+use crate::middleware::{Middleware, RpcRequest, RpcResponse};
+use crate::proof;
+use crate::quirks::{NodeClient, QuirkProfile};
+use crate::rlp::Header;
+use crate::signing::RequestSigner;
+use crate::stream::{BlockStream, LogStream};
+use crate::transaction::Authorization;
+use crate::types::{Address, BlockId, Bytes, FromHexError, Wei, H256};
+use crate::utils::{is_checksummed, parse_u64};
+
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
-use std::fmt::Write;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::ops::RangeInclusive;
+use std::path::Path;
 use std::string::String;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 ///The `Provider` struct simply contains the RPC url, a `reqwest` client and default headers.
+///
+///Cloning a `Provider` is cheap: clones share the connection pool of the underlying client and its configuration
+///behind `Arc`s, and `Provider` is `Send + Sync`. Worker threads should therefore clone (or borrow) a single
+///`Provider` instead of constructing their own.
 ///## Example
 ///```rust
 ///use ethrs::provider::Provider;
+///use std::thread;
 ///
 ///let provider = Provider::new("https://rpc.ankr.com/eth");
+///let worker = provider.clone();
+///thread::spawn(move || worker.block_number().is_ok());
 ///```
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
 pub struct Provider {
-    url: String,
+    config: Arc<ProviderConfig>,
     client: reqwest::blocking::Client,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ProviderConfig {
+    url: String,
     headers: HeaderMap,
+    field_aliases: Vec<(String, String)>,
+    response_hook: Option<fn(&str, &mut serde_json::Value)>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    timeout: Option<Duration>,
+    dump: Option<Arc<Mutex<File>>>,
+    auth: Option<Auth>,
+    pending_policy: PendingPolicy,
+    pending_is_latest: Arc<Mutex<Option<bool>>>,
+    transport: Option<Arc<dyn Transport>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    metrics: ProviderMetrics,
+    quantity_parsing: QuantityParsing,
+    cache: Option<ResponseCache>,
+    in_flight: Option<Arc<InFlight>>,
+    node: Option<NodeInfo>,
+    next_id: Arc<AtomicU64>,
+    signer: Option<Arc<dyn RequestSigner>>,
+    quirks: Option<QuirkProfile>,
+    require_checksums: bool,
 }
 
-pub enum DefaultBlockParam {
-    EARLIEST,
-    FINALIZED,
-    SAFE,
-    LATEST,
-    PENDING,
+///The number of times a request rejected with HTTP 429 is retried before the error is returned.
+const RATE_LIMIT_RETRIES: u32 = 3;
+///How long to wait before retrying a request rejected with HTTP 429 without a usable `Retry-After` header.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+///The longest `Retry-After` delay honored, so that a misconfigured endpoint cannot stall the caller indefinitely.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+///A token bucket shared by all clones of a `Provider`, refilled at a steady rate up to the burst size.
+#[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    burst: u32,
+    state: Mutex<(f64, Instant)>,
 }
 
-///The `RPCResponse` struct allows for deserialization of generic RPC requests that may either return an error or a single hash as a result.
-#[derive(Deserialize, Debug)]
-pub struct RPCResponse {
-    error: Option<RPCError>,
-    result: Option<String>,
+impl RateLimiter {
+    fn new(requests_per_second: u32, burst: u32) -> RateLimiter {
+        let burst = burst.max(1);
+        RateLimiter {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            burst,
+            state: Mutex::new((burst as f64, Instant::now())),
+        }
+    }
+
+    ///Blocks until a token is available and takes it.
+    fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+                let (tokens, last) = &mut *state;
+                let now = Instant::now();
+                *tokens = (*tokens
+                    + now.duration_since(*last).as_secs_f64() / self.interval.as_secs_f64())
+                .min(self.burst as f64);
+                *last = now;
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+                self.interval.mul_f64(1.0 - *tokens)
+            };
+            thread::sleep(wait);
+        }
+    }
 }
 
-#[derive(Deserialize, Debug)]
-pub struct RPCError {
-    message: String,
+///The requests in flight of a `Provider` coalescing identical requests, shared by all its clones.
+#[derive(Debug, Default)]
+struct InFlight {
+    requests: Mutex<HashMap<String, Arc<Flight>>>,
 }
 
-///The `BlockRPCResponse` struct allows for deserialization of JSON-RPC requests that may either return an error or return a block as a result.
-#[derive(Deserialize, Debug)]
-pub struct BlockRPCResponse {
-    error: Option<String>,
-    result: Option<Block>,
+///A request in flight, whose response is shared with the identical requests made meanwhile.
+#[derive(Debug, Default)]
+struct Flight {
+    response: Mutex<Option<Result<serde_json::Value, String>>>,
+    done: Condvar,
 }
 
-///The `TxRPCResponse` struct allows for deserialization of JSON-RPC requests that may either return an error or return a transaction as a result.
-#[derive(Deserialize, Debug)]
-pub struct TxRPCResponse {
-    error: Option<String>,
-    result: Option<Transaction>,
+impl InFlight {
+    ///Sends a request with `send`, unless an identical request is already in flight, in which case it waits for its
+    ///response instead.
+    fn coalesce(
+        &self,
+        key: String,
+        send: impl FnOnce() -> Result<serde_json::Value, Box<dyn Error>>,
+    ) -> Result<serde_json::Value, Box<dyn Error>> {
+        let (flight, leader) = {
+            let mut requests = self.requests.lock().unwrap_or_else(|err| err.into_inner());
+            match requests.get(&key) {
+                Some(flight) => (flight.clone(), false),
+                None => {
+                    let flight = Arc::new(Flight::default());
+                    requests.insert(key.clone(), flight.clone());
+                    (flight, true)
+                }
+            }
+        };
+        if !leader {
+            let mut response = flight
+                .response
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            while response.is_none() {
+                response = flight
+                    .done
+                    .wait(response)
+                    .unwrap_or_else(|err| err.into_inner());
+            }
+            return match response.as_ref() {
+                Some(Ok(response)) => Ok(response.clone()),
+                Some(Err(err)) => Err(err.clone().into()),
+                None => unreachable!(),
+            };
+        }
+        // completes the flight even if `send` panics, so that waiting requests do not hang
+        let mut landing = Landing {
+            in_flight: self,
+            key,
+            flight,
+            landed: false,
+        };
+        let response = send();
+        landing.land(match &response {
+            Ok(response) => Ok(response.clone()),
+            Err(err) => Err(err.to_string()),
+        });
+        response
+    }
+}
+
+struct Landing<'a> {
+    in_flight: &'a InFlight,
+    key: String,
+    flight: Arc<Flight>,
+    landed: bool,
+}
+
+impl Landing<'_> {
+    fn land(&mut self, response: Result<serde_json::Value, String>) {
+        self.landed = true;
+        self.in_flight
+            .requests
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(&self.key);
+        *self
+            .flight
+            .response
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = Some(response);
+        self.flight.done.notify_all();
+    }
+}
+
+impl Drop for Landing<'_> {
+    fn drop(&mut self) {
+        if !self.landed {
+            self.land(Err("Coalesced request panicked".to_owned()));
+        }
+    }
+}
+
+///The `HttpVersion` enum selects how a `Provider` negotiates the HTTP version with its endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HttpVersion {
+    ///HTTP/1.1 over plain connections, and HTTP/2 over TLS when negotiated via ALPN. ALPN requires the `http2-alpn`
+    ///feature.
+    #[default]
+    Auto,
+    ///Only use HTTP/1.1, with a pool of connections per host.
+    Http1Only,
+    ///Assume the endpoint speaks HTTP/2 without negotiation, multiplexing concurrent requests on a single connection.
+    ///Requests fail against endpoints that do not support HTTP/2.
+    Http2PriorKnowledge,
+}
+
+///The `Transport` trait sends JSON-RPC request payloads and returns the raw JSON-RPC responses, including error
+///responses. A `Provider` sends its requests over HTTP itself, or through a transport set with
+///`Provider::with_transport()`, e.g. a `FallbackProvider` spreading them over several endpoints. Transport errors, i.e.
+///`Err()`, are failures of the endpoint rather than of the request.
+pub trait Transport: fmt::Debug + Send + Sync {
+    fn send(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>>;
+}
+
+impl Transport for Provider {
+    fn send(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>> {
+        Provider::send(self, payload)
+    }
+}
+
+///The `PendingPolicy` enum sets how a `Provider` handles requests for the pending block when its endpoint serves the
+///latest block instead, as detected by `Provider::probe_pending()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum PendingPolicy {
+    ///Send requests for the pending block as is, without probing the endpoint.
+    #[default]
+    Trust,
+    ///Call the given function with a warning once if the endpoint serves the latest block, and send requests as is.
+    Warn(fn(&str)),
+    ///Fail requests for the pending block if the endpoint serves the latest block.
+    Error,
+    ///Compute pending nonces from the latest nonce and the transaction pool (`txpool_contentFrom`) if the endpoint serves
+    ///the latest block, and send other requests as is.
+    Fallback,
+}
+
+///The `QuantityParsing` enum sets how strictly a `Provider` parses the quantities in its responses, i.e. the integers
+///the JSON-RPC specification encodes as `0x`-prefixed hex strings without leading zeros.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuantityParsing {
+    ///Parse quantities as the specification encodes them.
+    #[default]
+    Strict,
+    ///Also accept quantities with leading zeros, decimal strings and JSON numbers, which some endpoints return, by
+    ///rewriting them as hex quantities before decoding. Only the results of methods returning a quantity and the known
+    ///quantity fields of objects, e.g. `gasUsed`, are rewritten.
+    Lenient,
+}
+
+///The `NodeInfo` struct describes the node behind a `Provider`, as recorded by `Provider::connect()`. The client
+///version is `None` if the endpoint does not expose `web3_clientVersion`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeInfo {
+    pub chain_id: u64,
+    pub client_version: Option<String>,
+}
+
+///The `Auth` enum holds the credentials a `Provider` authenticates every request to a managed RPC endpoint with.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Auth {
+    ///An API key sent in the given header, e.g. `x-api-key`.
+    Header { name: String, value: String },
+    ///A token sent as `Authorization: Bearer <token>`.
+    Bearer(String),
+    ///HTTP basic authentication, e.g. an Infura project id and secret.
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    ///An API key sent as the given URL query parameter.
+    Query { name: String, value: String },
+}
+
+// credentials are kept out of logs and debug output
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Header { name, .. } => write!(f, "Header({name})"),
+            Auth::Bearer(_) => write!(f, "Bearer"),
+            Auth::Basic { username, .. } => write!(f, "Basic({username})"),
+            Auth::Query { name, .. } => write!(f, "Query({name})"),
+        }
+    }
+}
+
+///The `ProviderBuilder` struct configures the HTTP client of a `Provider` before it is built.
+///## Example
+///```rust
+///use ethrs::provider::{HttpVersion, Provider};
+///use std::error::Error;
+///use std::time::Duration;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::builder("https://rpc.sepolia.org")
+///      .http_version(HttpVersion::Http2PriorKnowledge)
+///      .http2_adaptive_window(true)
+///      .timeout(Duration::from_secs(10))
+///      .connect_timeout(Duration::from_secs(3))
+///      .build()?;
+///  Ok(())
+///}
+///```
+pub struct ProviderBuilder {
+    url: String,
+    client: reqwest::blocking::ClientBuilder,
+    auth: Option<Auth>,
+    proxy: Option<String>,
+    proxy_auth: Option<(String, String)>,
+    signer: Option<Arc<dyn RequestSigner>>,
 }
 
-///The `TxReceiptRPCResponse` struct allows for deserialization of JSON-RPC requests that may either return an error or return a transaction receipt as a result.
+// URLs are redacted and proxy credentials left out, so that builders can be logged.
+impl fmt::Debug for ProviderBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProviderBuilder")
+            .field("url", &redact_url(&self.url))
+            .field("auth", &self.auth)
+            .field("proxy", &self.proxy.as_deref().map(redact_url))
+            .field("proxy_auth", &self.proxy_auth.is_some())
+            .field("signer", &self.signer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProviderBuilder {
+    ///The `http_version()` function sets how the HTTP version is negotiated, see `HttpVersion`.
+    pub fn http_version(mut self, version: HttpVersion) -> ProviderBuilder {
+        self.client = match version {
+            HttpVersion::Auto => self.client,
+            HttpVersion::Http1Only => self.client.http1_only(),
+            HttpVersion::Http2PriorKnowledge => self.client.http2_prior_knowledge(),
+        };
+        self
+    }
+
+    ///The `http2_adaptive_window()` function enables HTTP/2 flow control window sizing based on the measured
+    ///bandwidth-delay product, which improves throughput of large responses on high-latency connections.
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> ProviderBuilder {
+        self.client = self.client.http2_adaptive_window(enabled);
+        self
+    }
+
+    ///The `pool_max_idle_per_host()` function sets the maximum number of idle connections kept open per host.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> ProviderBuilder {
+        self.client = self.client.pool_max_idle_per_host(max);
+        self
+    }
+
+    ///The `timeout()` function sets the total time a request may take, from connecting until the response is read,
+    ///before it fails. Defaults to 30 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> ProviderBuilder {
+        self.client = self.client.timeout(timeout);
+        self
+    }
+
+    ///The `connect_timeout()` function sets the time connecting to the endpoint may take before a request fails. By
+    ///default, only the request timeout applies.
+    pub fn connect_timeout(mut self, timeout: Duration) -> ProviderBuilder {
+        self.client = self.client.connect_timeout(timeout);
+        self
+    }
+
+    ///The `auth()` function sets the credentials every request is authenticated with, see `Auth`.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::{Auth, Provider};
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::builder("https://mainnet.infura.io/v3/<project-id>")
+    ///      .auth(Auth::Basic {
+    ///          username: String::new(),
+    ///          password: Some("<project-secret>".to_owned()),
+    ///      })
+    ///      .build()?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn auth(mut self, auth: Auth) -> ProviderBuilder {
+        self.auth = Some(auth);
+        self
+    }
+
+    ///The `signer()` function sets the `RequestSigner` computing the signature headers of every request, e.g. an
+    ///`HmacSigner` configured with the secret of an authenticated gateway.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::signing::HmacSigner;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::builder("https://rpc.gateway.internal")
+    ///      .signer(HmacSigner::new(b"<secret>"))
+    ///      .build()?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn signer(mut self, signer: impl RequestSigner + 'static) -> ProviderBuilder {
+        self.signer = Some(Arc::new(signer));
+        self
+    }
+
+    ///The `proxy()` function sets a proxy all requests go through, instead of the proxies set by the `HTTP_PROXY`,
+    ///`HTTPS_PROXY` and `ALL_PROXY` environment variables. The URL scheme selects the kind of proxy: `http://`,
+    ///`https://`, or `socks5://` and `socks5h://` (which resolves hostnames through the proxy) with the `socks` feature.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::builder("https://rpc.sepolia.org")
+    ///      .proxy("http://proxy.internal:3128")
+    ///      .proxy_auth("user", "password")
+    ///      .build()?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn proxy(mut self, url: &str) -> ProviderBuilder {
+        self.proxy = Some(url.to_owned());
+        self
+    }
+
+    ///The `proxy_auth()` function sets the credentials to authenticate with the proxy set by `proxy()`.
+    pub fn proxy_auth(mut self, username: &str, password: &str) -> ProviderBuilder {
+        self.proxy_auth = Some((username.to_owned(), password.to_owned()));
+        self
+    }
+
+    ///The `build()` function attempts to return the configured `Provider` as `Ok(Provider)`. Returns an `Err()` if the
+    ///HTTP client cannot be initialized, e.g. when the TLS backend fails to load, if the proxy URL is invalid, or if
+    ///proxy credentials are set without a proxy.
+    pub fn build(mut self) -> Result<Provider, Box<dyn Error>> {
+        match (&self.proxy, &self.proxy_auth) {
+            (Some(url), auth) => {
+                let mut proxy = reqwest::Proxy::all(url)?;
+                if let Some((username, password)) = auth {
+                    proxy = proxy.basic_auth(username, password);
+                }
+                self.client = self.client.proxy(proxy);
+            }
+            (None, Some(_)) => return Err("proxy_auth() requires a proxy set with proxy()".into()),
+            (None, None) => {}
+        }
+        let mut provider = Provider::new(&self.url);
+        provider.client = self.client.build()?;
+        if let Some(Auth::Header { name, value }) = &self.auth {
+            let mut value = HeaderValue::from_str(value)?;
+            value.set_sensitive(true);
+            Arc::make_mut(&mut provider.config)
+                .headers
+                .insert(HeaderName::from_bytes(name.as_bytes())?, value);
+        }
+        let config = Arc::make_mut(&mut provider.config);
+        config.auth = self.auth;
+        config.signer = self.signer;
+        Ok(provider)
+    }
+}
+
+///The `JsonRPCResponse` struct allows for deserialization of JSON-RPC requests that may either return an error or an arbitrary JSON value as a result.
 #[derive(Deserialize, Debug)]
-pub struct TxReceiptRPCResponse {
-    error: Option<String>,
-    result: Option<TransactionReceipt>,
+pub struct JsonRPCResponse {
+    error: Option<RPCError>,
+    result: Option<serde_json::Value>,
 }
 
-///The `BlockWithTxRPCResponse` struct allows for deserialization of JSON-RPC requests that may either return an error or return a block with transactions as a result.
 #[derive(Deserialize, Debug)]
-pub struct BlockWithTxRPCResponse {
-    error: Option<String>,
-    result: Option<BlockWithTx>,
+pub struct RPCError {
+    message: String,
 }
 
-///The `Block` struct allows for returning successfully deserialized blocks from JSON-RPC requests.
+///The `Block` struct allows for returning successfully deserialized blocks from JSON-RPC requests. Its transactions are
+///`TX`: the transaction hashes by default, or the full transactions for a `BlockWithTx`.
 ///## Example
 ///```rust
 ///use ethrs::provider::Provider;
@@ -82,27 +517,29 @@ pub struct BlockWithTxRPCResponse {
 ///
 ///fn main() -> Result<(), Box<dyn Error>> {
 ///  let provider = Provider::new("https://rpc.sepolia.org");
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
 ///  assert!(provider
 ///    .get_block_by_number(
-///    None, None,
+///    None,
 ///    )?
 ///    .is_some());
 ///    Ok(())
 ///}
 ///```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Block {
-    pub number: Option<U256>,
-    pub hash: Option<String>,
-    pub parent_hash: String,
+pub struct Block<TX = H256> {
+    #[serde(default, with = "crate::utils::quantity_opt")]
+    pub number: Option<u64>,
+    pub hash: Option<H256>,
+    pub parent_hash: H256,
     pub nonce: Option<U256>,
-    pub sha3_uncles: String,
+    pub sha3_uncles: H256,
     pub logs_bloom: Option<String>,
-    pub transactions_root: String,
-    pub state_root: String,
-    pub receipts_root: String,
-    pub miner: Option<String>,
+    pub transactions_root: H256,
+    pub state_root: H256,
+    pub receipts_root: H256,
+    pub miner: Option<Address>,
     pub difficulty: U256,
     pub total_difficulty: Option<U256>,
     pub extra_data: String,
@@ -110,11 +547,187 @@ pub struct Block {
     pub gas_limit: U256,
     pub gas_used: U256,
     pub timestamp: U256,
-    pub transactions: Vec<String>,
-    pub uncles: Vec<String>,
+    // uncle headers have no transactions
+    #[serde(default = "Vec::new")]
+    pub transactions: Vec<TX>,
+    pub uncles: Vec<H256>,
+    ///The mix hash of proof-of-work blocks, which holds the `prevrandao` of the beacon chain after the merge.
+    #[serde(default, alias = "prevRandao", skip_serializing_if = "Option::is_none")]
+    pub mix_hash: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<H256>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub blob_gas_used: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub excess_blob_gas: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_hash: Option<H256>,
+}
+
+impl<TX> Block<TX> {
+    ///Returns the `prevrandao` of the block, i.e. the randomness of the beacon chain the block was built on, or `None`
+    ///for proof-of-work blocks, whose difficulty is not zero.
+    pub fn prevrandao(&self) -> Option<H256> {
+        self.mix_hash.filter(|_| self.difficulty.is_zero())
+    }
+
+    ///Returns the log bloom of the block, or `None` if the node did not return a well-formed one.
+    pub fn bloom(&self) -> Option<Bloom> {
+        self.logs_bloom.as_deref()?.parse().ok()
+    }
+
+    ///The `header()` function attempts to return the header of the block, built from its fields, as `Ok(Header)`.
+    ///Returns an `Err()` if a field of the header is missing, e.g. for pending blocks, or malformed.
+    pub fn header(&self) -> Result<Header, Box<dyn Error>> {
+        let missing = |field: &str| format!("Block has no {field}");
+        let nonce = self.nonce.ok_or_else(|| missing("nonce"))?;
+        if nonce.bits() > 64 {
+            return Err(format!("Block nonce {nonce:#x} does not fit in 8 bytes").into());
+        }
+        let mut nonce_bytes = [0u8; 32];
+        nonce.to_big_endian(&mut nonce_bytes);
+        Ok(Header {
+            parent_hash: self.parent_hash,
+            ommers_hash: self.sha3_uncles,
+            beneficiary: self.miner.ok_or_else(|| missing("miner"))?,
+            state_root: self.state_root,
+            transactions_root: self.transactions_root,
+            receipts_root: self.receipts_root,
+            logs_bloom: self
+                .logs_bloom
+                .as_deref()
+                .ok_or_else(|| missing("logs bloom"))?
+                .parse()?,
+            difficulty: self.difficulty,
+            number: self.number.ok_or_else(|| missing("number"))?,
+            gas_limit: header_u64(self.gas_limit, "gas limit")?,
+            gas_used: header_u64(self.gas_used, "gas used")?,
+            timestamp: header_u64(self.timestamp, "timestamp")?,
+            extra_data: self.extra_data.parse()?,
+            mix_hash: self.mix_hash.ok_or_else(|| missing("mix hash"))?,
+            nonce: Bytes(nonce_bytes[24..].to_vec()),
+            base_fee_per_gas: self.base_fee_per_gas,
+            withdrawals_root: self.withdrawals_root,
+            blob_gas_used: self.blob_gas_used,
+            excess_blob_gas: self.excess_blob_gas,
+            parent_beacon_block_root: self.parent_beacon_block_root,
+            requests_hash: self.requests_hash,
+        })
+    }
+
+    ///The `verify_hash()` function RLP-encodes the header of the block from its fields and checks that its hash is the
+    ///hash the block was returned with, to detect endpoints returning tampered or inconsistent blocks. Returns an
+    ///`Err()` if the hashes differ or if the header cannot be built, see `header()`.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::BlockId;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let block = provider.get_block_by_number(Some(BlockId::Latest))?.unwrap();
+    ///  block.verify_hash()?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn verify_hash(&self) -> Result<(), Box<dyn Error>> {
+        let reported = self.hash.ok_or("Block has no hash")?;
+        let computed = self.header()?.hash();
+        match computed == reported {
+            true => Ok(()),
+            false => Err(format!(
+                "Block hash mismatch: reported {reported}, computed {computed} from the header"
+            )
+            .into()),
+        }
+    }
+}
+
+impl Block<Transaction> {
+    ///The `verify_transactions_root()` function re-encodes the transactions of the block, checking each against its
+    ///hash, and checks that the root of their trie is the `transactions_root` of the block. Returns an `Err()` if the
+    ///roots differ or a transaction cannot be encoded. Together with `verify_hash()`, this checks that the transactions
+    ///are the ones the block hash commits to.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::BlockId;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let block = provider.get_block_by_number_with_tx(Some(BlockId::Latest))?.unwrap();
+    ///  block.verify_hash()?;
+    ///  block.verify_transactions_root()?;
+    ///  let receipts = provider.get_block_receipts(BlockId::Number(block.number.unwrap()))?.unwrap();
+    ///  block.verify_receipts_root(&receipts)?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn verify_transactions_root(&self) -> Result<(), Box<dyn Error>> {
+        let computed = proof::transactions_root(&self.transactions)?;
+        match computed == self.transactions_root {
+            true => Ok(()),
+            false => Err(format!(
+                "Transactions root mismatch: reported {}, computed {computed} from the transactions",
+                self.transactions_root
+            )
+            .into()),
+        }
+    }
+
+    ///The `verify_receipts_root()` function takes the receipts of every transaction of the block, in order, and checks
+    ///that the root of their trie is the `receipts_root` of the block. Returns an `Err()` if the roots differ, if the
+    ///receipts are not those of the transactions of the block or if a receipt cannot be encoded.
+    pub fn verify_receipts_root(
+        &self,
+        receipts: &[TransactionReceipt],
+    ) -> Result<(), Box<dyn Error>> {
+        let hashes = self.transactions.iter().map(|tx| tx.hash);
+        if receipts.len() != self.transactions.len()
+            || !hashes
+                .zip(receipts)
+                .all(|(hash, receipt)| receipt.transaction_hash == hash)
+        {
+            return Err("Receipts do not match the transactions of the block".into());
+        }
+        let computed = proof::receipts_root(receipts)?;
+        match computed == self.receipts_root {
+            true => Ok(()),
+            false => Err(format!(
+                "Receipts root mismatch: reported {}, computed {computed} from the receipts",
+                self.receipts_root
+            )
+            .into()),
+        }
+    }
+}
+
+fn header_u64(value: U256, field: &str) -> Result<u64, Box<dyn Error>> {
+    match value.bits() <= 64 {
+        true => Ok(value.as_u64()),
+        false => Err(format!("Block {field} {value:#x} does not fit in a u64").into()),
+    }
 }
 
-///The `Block` struct allows for returning successfully deserialized blocks with transactions from JSON-RPC requests.
+///The `BlockWithTx` type allows for returning successfully deserialized blocks with transactions from JSON-RPC requests.
 ///## Example
 ///```rust
 ///use ethrs::provider::Provider;
@@ -122,36 +735,29 @@ pub struct Block {
 ///
 ///fn main() -> Result<(), Box<dyn Error>> {
 ///  let provider = Provider::new("https://rpc.sepolia.org");
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
 ///  assert!(provider
 ///    .get_block_by_number_with_tx(
-///    None, None,
+///    None,
 ///    )?
 ///    .is_some());
 ///    Ok(())
 ///}
 ///```
-#[derive(Debug, Deserialize)]
+pub type BlockWithTx = Block<Transaction>;
+
+///The `Withdrawal` struct is a withdrawal from the beacon chain included in a block since Shanghai, crediting the amount,
+///in gwei, to the address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BlockWithTx {
-    pub number: Option<U256>,
-    pub hash: Option<String>,
-    pub parent_hash: String,
-    pub nonce: Option<U256>,
-    pub sha3_uncles: String,
-    pub logs_bloom: Option<String>,
-    pub transactions_root: String,
-    pub state_root: String,
-    pub receipts_root: String,
-    pub miner: Option<String>,
-    pub difficulty: U256,
-    pub total_difficulty: Option<U256>,
-    pub extra_data: String,
-    pub size: U256,
-    pub gas_limit: U256,
-    pub gas_used: U256,
-    pub timestamp: U256,
-    pub transactions: Vec<Transaction>,
-    pub uncles: Vec<String>,
+pub struct Withdrawal {
+    #[serde(with = "crate::utils::quantity")]
+    pub index: u64,
+    #[serde(with = "crate::utils::quantity")]
+    pub validator_index: u64,
+    pub address: Address,
+    #[serde(with = "crate::utils::quantity")]
+    pub amount: u64,
 }
 
 ///The `Transaction` struct allows for returning successfully deserialized transactions from JSON-RPC requests.
@@ -162,945 +768,3274 @@ pub struct BlockWithTx {
 ///
 ///fn main() -> Result<(), Box<dyn Error>> {
 ///  let provider = Provider::new("https://rpc.sepolia.org");
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
 ///  assert!(provider
 ///    .get_transaction_by_hash(
-///    "0x6648b858a3d2b716d4c05c5d611844eb9827e2eea5bfc9db7a92187afd4d8c17"
+///    "0x6648b858a3d2b716d4c05c5d611844eb9827e2eea5bfc9db7a92187afd4d8c17".parse()?
 ///    )?
 ///    .is_some());
 ///    Ok(())
 ///}
 ///```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
-    pub block_hash: Option<String>,
-    pub block_number: Option<U256>,
-    pub from: String,
+    pub block_hash: Option<H256>,
+    #[serde(default, with = "crate::utils::quantity_opt")]
+    pub block_number: Option<u64>,
+    pub from: Address,
     pub gas: U256,
     pub gas_price: U256,
-    pub hash: String,
+    pub hash: H256,
     pub input: String,
     pub nonce: U256,
-    pub to: Option<String>,
+    pub to: Option<Address>,
     pub transaction_index: Option<U256>,
     pub value: U256,
     pub v: String,
     pub r: String,
     pub s: String,
+    ///The EIP-2718 type of the transaction, which nodes omit for legacy transactions before EIP-2718.
+    #[serde(
+        default,
+        rename = "type",
+        with = "crate::utils::quantity_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transaction_type: Option<u64>,
+    #[serde(
+        default,
+        with = "crate::utils::quantity_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub chain_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorization_list: Option<Vec<Authorization>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionReceipt {
-    pub transaction_hash: String,
+    pub transaction_hash: H256,
     pub transaction_index: U256,
-    pub block_hash: String,
-    pub block_number: U256,
-    pub from: String,
-    pub to: Option<String>,
+    pub block_hash: H256,
+    #[serde(with = "crate::utils::quantity")]
+    pub block_number: u64,
+    pub from: Address,
+    pub to: Option<Address>,
     pub cumulative_gas_used: U256,
     pub effective_gas_price: U256,
     pub gas_used: U256,
-    pub contract_address: Option<String>,
+    pub contract_address: Option<Address>,
     pub logs: Vec<Log>,
     pub logs_bloom: String,
-    pub status: Option<U256>,
-    pub root: Option<String>,
+    ///The outcome of the transaction, read from the `status` field of the receipt or, before Byzantium, its `root`.
+    #[serde(flatten)]
+    pub status: TxStatus,
+    ///The EIP-2718 type of the transaction, which nodes omit for legacy transactions before EIP-2718.
+    #[serde(
+        default,
+        rename = "type",
+        with = "crate::utils::quantity_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transaction_type: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct TransactionInput {
-    pub from: String,
-    pub to: Option<String>,
-    pub gas: Option<U256>,
-    pub gas_price: Option<U256>,
-    pub value: Option<U256>,
-    pub data: Option<String>,
-    pub nonce: Option<U256>,
+///The `TxStatus` enum is the outcome of a mined transaction. Receipts of transactions mined before Byzantium have no
+///status, but the state root after the transaction instead.
+///## Example
+///```rust
+///use ethrs::provider::TxStatus;
+///
+///let status: TxStatus = serde_json::from_str(r#"{"status": "0x1"}"#).unwrap();
+///assert_eq!(status, TxStatus::Success);
+///assert!(status.is_success());
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "RawTxStatus", try_from = "RawTxStatus")]
+pub enum TxStatus {
+    Success,
+    Failed,
+    PreByzantiumRoot(H256),
 }
 
-#[derive(Debug, Serialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct CallInput {
-    pub from: Option<String>,
-    pub to: String,
-    pub gas: Option<U256>,
-    pub gas_price: Option<U256>,
-    pub value: Option<U256>,
-    pub data: Option<String>,
+impl TxStatus {
+    ///Returns `true` if the transaction is known to have succeeded. Transactions mined before Byzantium are not.
+    pub fn is_success(&self) -> bool {
+        *self == TxStatus::Success
+    }
+
+    ///Returns `true` if the transaction is known to have reverted. Transactions mined before Byzantium are not.
+    pub fn is_failed(&self) -> bool {
+        *self == TxStatus::Failed
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Log {
-    pub removed: bool,
-    pub log_index: U256,
-    pub transaction_index: U256,
-    pub transaction_hash: String,
-    pub block_hash: String,
-    pub block_number: U256,
-    pub address: String,
-    pub data: String,
-    pub topics: Vec<String>,
+#[derive(Serialize, Deserialize)]
+struct RawTxStatus {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    root: Option<H256>,
 }
 
-lazy_static! {
-    static ref ADDRESS_REGEX: Regex = Regex::new(r"0x[0-9A-Fa-f]{40}").unwrap();
-    static ref BLOCKHASH_REGEX: Regex = Regex::new(r"0x[0-9A-Fa-f]{64}").unwrap();
-    static ref SLOT_REGEX: Regex = Regex::new(r"0x[0-9A-Fa-f]{1,64}").unwrap();
+impl From<TxStatus> for RawTxStatus {
+    fn from(status: TxStatus) -> Self {
+        let (status, root) = match status {
+            TxStatus::Success => (Some(U256::one()), None),
+            TxStatus::Failed => (Some(U256::zero()), None),
+            TxStatus::PreByzantiumRoot(root) => (None, Some(root)),
+        };
+        RawTxStatus { status, root }
+    }
 }
 
-///The `Provider` module requires an HTTP(S) JSON-RPC URL and is responsible for handling all your JSON-RPC requests.
-///## Example
-///```rust
-///use ethrs::provider::Provider;
-///
-///let provider = Provider::new("https://rpc.sepolia.org");
-///```
-impl Provider {
-    ///The `Provider::new()` associated function takes an HTTP(S) JSON-RPC URL and returns a `Provider`
-    ///instance to make your requests.
-    ///## Example
-    ///```rust
-    ///use ethrs::provider::Provider;
-    ///
-    ///let provider: Provider = Provider::new("https://rpc.sepolia.org");
-    ///```
-    pub fn new(_url: &str) -> Provider {
-        let mut headers: HeaderMap = HeaderMap::new();
-        headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
-        Provider {
-            url: _url.to_owned(),
-            client: reqwest::blocking::Client::new(),
-            headers: headers.clone(),
+impl TryFrom<RawTxStatus> for TxStatus {
+    type Error = String;
+
+    fn try_from(raw: RawTxStatus) -> Result<Self, Self::Error> {
+        match (raw.status, raw.root) {
+            (Some(status), _) if status.is_zero() => Ok(TxStatus::Failed),
+            (Some(status), _) if status == U256::one() => Ok(TxStatus::Success),
+            (Some(status), _) => Err(format!("Invalid receipt status: {status}")),
+            (None, Some(root)) => Ok(TxStatus::PreByzantiumRoot(root)),
+            (None, None) => Err("Receipt has neither a status nor a root".to_owned()),
         }
     }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInput {
+    pub from: Address,
+    pub to: Option<Address>,
+    pub gas: Option<U256>,
+    pub gas_price: Option<U256>,
+    pub value: Option<U256>,
+    pub data: Option<String>,
+    pub nonce: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+}
 
-    ///The `gas_price()` function attempts to return the current block number as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors.
+impl TransactionInput {
+    ///The `TransactionInput::new()` associated function takes the sender and returns a `TransactionInput` without a
+    ///recipient, value or data, leaving the gas, gas price and nonce to the node.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
+    ///use ethrs::provider::TransactionInput;
+    ///use ethrs::types::{Bytes, Wei};
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
-    ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .block_number()?
-    ///      >= 2900000);
+    ///  let tx = TransactionInput::new("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?)
+    ///      .to("0x70997970c51812dc3a010c7d01b50e0d17dc79c8".parse()?)
+    ///      .value(Wei::from_ether(1))
+    ///      .data("0xd09de08a".parse::<Bytes>()?)
+    ///      .gas(50000u64);
+    ///  assert_eq!(tx.data.as_deref(), Some("0xd09de08a"));
     ///  Ok(())
     ///}
     ///```
-    pub fn block_number(&self) -> Result<u128, Box<dyn Error>> {
-        let json: RPCResponse = self
-            .client
-            .post(&self.url)
-            .body("{\"method\":\"eth_blockNumber\",\"params\":[],\"id\":1,\"jsonrpc\":\"2.0\"}")
-            .headers(self.headers.clone())
-            .send()?
-            .json()?;
+    pub fn new(from: Address) -> TransactionInput {
+        TransactionInput {
+            from,
+            to: None,
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: None,
+            nonce: None,
+            access_list: None,
+        }
+    }
 
-        match json.error {
-            Some(err) => Err(err.message.into()),
-            None => Ok(u128::from_str_radix(
-                json.result.unwrap().strip_prefix("0x").unwrap(),
-                16,
-            )?),
+    ///The `to()` function sets the recipient of the transaction.
+    pub fn to(mut self, to: Address) -> TransactionInput {
+        self.to = Some(to);
+        self
+    }
+
+    ///The `gas()` function sets the gas limit of the transaction.
+    pub fn gas(mut self, gas: impl Into<U256>) -> TransactionInput {
+        self.gas = Some(gas.into());
+        self
+    }
+
+    ///The `gas_price()` function sets the gas price of the transaction.
+    pub fn gas_price(mut self, gas_price: impl Into<Wei>) -> TransactionInput {
+        self.gas_price = Some(gas_price.into().0);
+        self
+    }
+
+    ///The `value()` function sets the amount the transaction transfers.
+    pub fn value(mut self, value: impl Into<Wei>) -> TransactionInput {
+        self.value = Some(value.into().0);
+        self
+    }
+
+    ///The `data()` function sets the calldata of the transaction, or the init code of a contract deployment.
+    pub fn data(mut self, data: impl Into<Bytes>) -> TransactionInput {
+        self.data = Some(data.into().to_string());
+        self
+    }
+
+    ///The `nonce()` function sets the nonce of the transaction.
+    pub fn nonce(mut self, nonce: u64) -> TransactionInput {
+        self.nonce = Some(U256::from(nonce));
+        self
+    }
+
+    ///The `access_list()` function sets the EIP-2930 access list of the transaction.
+    pub fn access_list(mut self, access_list: AccessList) -> TransactionInput {
+        self.access_list = Some(access_list);
+        self
+    }
+}
+
+///The `AccessListItem` struct is an entry of an EIP-2930 access list, i.e. an address and the storage slots of it that
+///a transaction accesses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_keys: Vec<H256>,
+}
+
+///An EIP-2930 access list.
+pub type AccessList = Vec<AccessListItem>;
+
+///The `AccessListResult` struct allows for returning successfully deserialized `eth_createAccessList` responses. The
+///`error` is set when the transaction reverts with the generated access list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    pub access_list: AccessList,
+    pub gas_used: U256,
+    pub error: Option<String>,
+}
+
+///The `AccountOverride` struct replaces parts of the state of an account for the duration of a call or gas estimate.
+///`state` replaces the whole storage of the account while `state_diff` only replaces the given slots, so at most one of
+///them should be set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<BTreeMap<H256, H256>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+///A state override set, mapping accounts to the overrides applied to them.
+pub type StateOverride = BTreeMap<Address, AccountOverride>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallInput {
+    pub from: Option<Address>,
+    pub to: Address,
+    pub gas: Option<U256>,
+    pub gas_price: Option<U256>,
+    pub value: Option<U256>,
+    pub data: Option<String>,
+}
+
+impl CallInput {
+    ///The `CallInput::to()` associated function takes the contract to call and returns a `CallInput` without a
+    ///sender, value or data, leaving the gas and gas price to the node.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::CallInput;
+    ///use ethrs::types::Bytes;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let call = CallInput::to("0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?)
+    ///      .data(vec![0x18, 0x16, 0x0d, 0xdd]);
+    ///  assert_eq!(call.data.as_deref(), Some("0x18160ddd"));
+    ///  assert!(call.from.is_none());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn to(to: Address) -> CallInput {
+        CallInput {
+            from: None,
+            to,
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: None,
+        }
+    }
+
+    ///The `from()` function sets the sender of the call.
+    pub fn from(mut self, from: Address) -> CallInput {
+        self.from = Some(from);
+        self
+    }
+
+    ///The `gas()` function sets the gas the call may use.
+    pub fn gas(mut self, gas: impl Into<U256>) -> CallInput {
+        self.gas = Some(gas.into());
+        self
+    }
+
+    ///The `gas_price()` function sets the gas price of the call.
+    pub fn gas_price(mut self, gas_price: impl Into<Wei>) -> CallInput {
+        self.gas_price = Some(gas_price.into().0);
+        self
+    }
+
+    ///The `value()` function sets the amount sent with the call.
+    pub fn value(mut self, value: impl Into<Wei>) -> CallInput {
+        self.value = Some(value.into().0);
+        self
+    }
+
+    ///The `data()` function sets the calldata of the call.
+    pub fn data(mut self, data: impl Into<Bytes>) -> CallInput {
+        self.data = Some(data.into().to_string());
+        self
+    }
+}
+
+///The `BlockOverrides` struct replaces fields of the block a simulation runs in, see `SimulatedBlockCalls`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub number: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub time: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub gas_limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_randao: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_base_fee: Option<U256>,
+}
+
+///The `SimulatedBlockCalls` struct is a block simulated by `eth_simulateV1`: its calls are executed in order, each one
+///seeing the state changes of the previous ones, on top of the state overrides and in a block with the block overrides
+///applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlockCalls {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    pub calls: Vec<CallInput>,
+}
+
+///The `SimulationInput` struct is the payload of `eth_simulateV1`: blocks of calls simulated one after the other on top
+///of the base block. With `validation`, calls are checked like transactions, e.g. for nonces, balances and fees, and
+///with `trace_transfers`, ether transfers are returned as logs of the `0xeeee…eeee` address.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationInput {
+    pub block_state_calls: Vec<SimulatedBlockCalls>,
+    pub trace_transfers: bool,
+    pub validation: bool,
+}
+
+///The `SimulatedBlock` struct is a block returned by `eth_simulateV1`, with the result of every call in `calls`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedBlock {
+    #[serde(flatten)]
+    pub block: Block,
+    pub calls: Vec<SimulatedCall>,
+}
+
+///The `SimulatedCall` struct is the result of a call simulated by `eth_simulateV1`. The `error` is set if the call
+///failed, with the revert data in its `data` if it reverted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    pub return_data: Bytes,
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    #[serde(with = "crate::utils::quantity")]
+    pub gas_used: u64,
+    #[serde(flatten)]
+    pub status: TxStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+///The `SimulatedCallError` struct is the error of a failed call simulated by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedCallError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    pub removed: bool,
+    pub log_index: U256,
+    pub transaction_index: U256,
+    pub transaction_hash: H256,
+    pub block_hash: H256,
+    #[serde(with = "crate::utils::quantity")]
+    pub block_number: u64,
+    pub address: Address,
+    pub data: String,
+    pub topics: Vec<H256>,
+}
+
+///The `Filter` struct describes the criteria of a log filter, as accepted by `eth_newFilter`. Empty `address` and
+///`topics` match any log, and a `None` topic matches any value at that position.
+///## Example
+///```rust
+///use ethrs::provider::Filter;
+///use ethrs::types::BlockId;
+///
+///let filter = Filter {
+///    from_block: Some(BlockId::Number(3347000)),
+///    address: vec!["0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse().unwrap()],
+///    ..Default::default()
+///};
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<H256>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub address: Vec<Address>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<Option<Vec<H256>>>,
+}
+
+///The `FilterKind` enum describes what a filter installed on the node is watching for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterKind {
+    Block,
+    PendingTransaction,
+    Log,
+}
+
+///The `FilterHandle` struct identifies a filter installed on the node and remembers its kind, so that its changes can
+///be deserialized into the right type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterHandle {
+    id: String,
+    kind: FilterKind,
+}
+
+impl FilterHandle {
+    ///Returns the filter id assigned by the node.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    ///Returns the kind of the filter.
+    pub fn kind(&self) -> FilterKind {
+        self.kind
+    }
+}
+
+///The `FilterChanges` enum contains the changes of a filter since it was last polled: block hashes for block filters,
+///transaction hashes for pending transaction filters and logs for log filters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum FilterChanges {
+    Hashes(Vec<H256>),
+    Logs(Vec<Log>),
+}
+
+///The `AccountProof` struct allows for returning successfully deserialized `eth_getProof` responses, i.e. an account
+///and a subset of its storage along with their Merkle-Patricia proofs against the state root of the block.
+///## Example
+///```rust
+///use ethrs::provider::Provider;
+///use ethrs::types::H256;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  let proof = provider.get_proof(
+///      "0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?,
+///      &[H256::ZERO],
+///      None,
+///  )?;
+///  assert_eq!(proof.storage_proof.len(), 1);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub address: Address,
+    pub balance: U256,
+    pub nonce: U256,
+    pub code_hash: H256,
+    pub storage_hash: H256,
+    pub account_proof: Vec<Bytes>,
+    pub storage_proof: Vec<StorageProof>,
+}
+
+///The `StorageProof` struct contains the value of a storage slot and its Merkle-Patricia proof against the storage root
+///of the account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageProof {
+    pub key: U256,
+    pub value: U256,
+    pub proof: Vec<Bytes>,
+}
+
+///The `ProxyKind` enum is the standard a proxy stores its implementation by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProxyKind {
+    ///An EIP-1967 proxy, storing its implementation in the EIP-1967 implementation slot.
+    Eip1967,
+    ///An EIP-1967 beacon proxy, storing a beacon whose `implementation()` returns the implementation.
+    Beacon,
+    ///An EIP-1822 (UUPS) proxy, storing its implementation in the `PROXIABLE` slot.
+    Eip1822,
+    ///A ZeppelinOS proxy, from before EIP-1967.
+    ZeppelinOs,
+}
+
+///The `Proxy` struct is the resolution of an upgradeable proxy: its kind, current implementation and, when stored in
+///their EIP-1967 slots, its admin and beacon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Proxy {
+    pub kind: ProxyKind,
+    pub implementation: Address,
+    pub admin: Option<Address>,
+    pub beacon: Option<Address>,
+}
+
+///The `Blocks` struct is an iterator over a range of blocks, returned by `Provider::blocks()`.
+#[derive(Debug, Clone)]
+pub struct Blocks<'a> {
+    provider: &'a Provider,
+    next: Option<u64>,
+    end: u64,
+}
+
+impl Iterator for Blocks<'_> {
+    type Item = Result<Block, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let number = self.next?;
+        let block = match self
+            .provider
+            .get_block_by_number(Some(BlockId::Number(number)))
+        {
+            Ok(Some(block)) => Ok(block),
+            Ok(None) => Err(format!("Block {number} not found").into()),
+            Err(err) => Err(err),
+        };
+        // the iterator ends after an error, so that e.g. `flatten()` does not retry a missing block forever
+        self.next = match block {
+            Ok(_) => number.checked_add(1).filter(|next| *next <= self.end),
+            Err(_) => None,
+        };
+        Some(block)
+    }
+}
+
+///Returns whether the error is the rejection of an `eth_getLogs` request for returning too many logs or spanning too
+///many blocks, as reported by the common clients and providers.
+fn is_log_limit_exceeded(err: &dyn Error) -> bool {
+    let message = err.to_string().to_lowercase();
+    [
+        "too many",
+        "more than",
+        "response size",
+        "is limited to",
+        "block range is too large",
+        "exceed maximum block range",
+        "range limit",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
+
+fn parse_quantity(value: &str) -> Result<u128, Box<dyn Error>> {
+    match value.strip_prefix("0x") {
+        Some(quantity) => Ok(u128::from_str_radix(quantity, 16)
+            .map_err(|err| format!("Invalid quantity {value}: {err}"))?),
+        None => Err(format!("Invalid quantity: {value}").into()),
+    }
+}
+
+///The address of the EIP-4788 beacon roots contract.
+pub const BEACON_ROOTS_ADDRESS: &str = "0x000F3df6D732807Ef1319fB7B8bB8522d0Beac02";
+
+///The address of the EIP-2935 history storage contract.
+pub const HISTORY_STORAGE_ADDRESS: &str = "0x0000F90827F1C53a10cb7A02335B175320002935";
+
+///The ring buffer length of both the EIP-4788 and EIP-2935 system contracts.
+pub const HISTORY_BUFFER_LENGTH: u64 = 8191;
+
+///The EIP-1967 storage slot of the implementation of a proxy, i.e. `keccak256("eip1967.proxy.implementation") - 1`.
+pub const EIP1967_IMPLEMENTATION_SLOT: &str =
+    "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+
+///The EIP-1967 storage slot of the admin of a proxy, i.e. `keccak256("eip1967.proxy.admin") - 1`.
+pub const EIP1967_ADMIN_SLOT: &str =
+    "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103";
+
+///The EIP-1967 storage slot of the beacon of a beacon proxy, i.e. `keccak256("eip1967.proxy.beacon") - 1`.
+pub const EIP1967_BEACON_SLOT: &str =
+    "0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50";
+
+///The EIP-1822 storage slot of the implementation of a proxy, i.e. `keccak256("PROXIABLE")`.
+pub const EIP1822_PROXIABLE_SLOT: &str =
+    "0xc5f16f0fcc639fa48a6947836d9850f504798523bf8c9a3a87d5876cf622bcf7";
+
+///The storage slot of the implementation of a ZeppelinOS proxy, i.e.
+///`keccak256("org.zeppelinos.proxy.implementation")`.
+pub const ZEPPELINOS_IMPLEMENTATION_SLOT: &str =
+    "0x7050c9e0f4ca769c69bd3a8ef740bc37934f8e2c036e5a723fd8ee048ed3f8c3";
+
+///The EIP-165 interface id of `supportsInterface(bytes4)` itself.
+pub const ERC165_INTERFACE_ID: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+
+///The EIP-165 interface id of ERC-721 collections.
+pub const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+
+///The EIP-165 interface id of the ERC-721 metadata extension, i.e. `name()`, `symbol()` and `tokenURI(uint256)`.
+pub const ERC721_METADATA_INTERFACE_ID: [u8; 4] = [0x5b, 0x5e, 0x13, 0x9f];
+
+///The EIP-165 interface id of the ERC-721 enumeration extension.
+pub const ERC721_ENUMERABLE_INTERFACE_ID: [u8; 4] = [0x78, 0x0e, 0x9d, 0x63];
+
+///The EIP-165 interface id of ERC-1155 collections.
+pub const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+
+///The EIP-165 interface id of the ERC-1155 metadata URI extension, i.e. `uri(uint256)`.
+pub const ERC1155_METADATA_URI_INTERFACE_ID: [u8; 4] = [0x0e, 0x89, 0x34, 0x1c];
+
+///The EIP-165 interface id of ERC-2981 royalties, i.e. `royaltyInfo(uint256,uint256)`.
+pub const ERC2981_INTERFACE_ID: [u8; 4] = [0x2a, 0x55, 0x20, 0x5a];
+
+///Returns how long to wait before retrying a rate limited request, from a `Retry-After` header in seconds or as an
+///HTTP date.
+fn retry_after(headers: &HeaderMap) -> Duration {
+    let value = match headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(value) => value.trim(),
+        None => return DEFAULT_RETRY_AFTER,
+    };
+    let delay = match value.parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => match httpdate::parse_http_date(value) {
+            Ok(date) => date
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO),
+            Err(_) => DEFAULT_RETRY_AFTER,
+        },
+    };
+    delay.min(MAX_RETRY_AFTER)
+}
+
+///Replaces the credentials an endpoint URL may carry, i.e. user info, query values and path segments that look like
+///API keys, with `REDACTED`.
+pub(crate) fn redact_url(url: &str) -> String {
+    let mut url = match reqwest::Url::parse(url) {
+        Ok(url) => url,
+        Err(_) => return "REDACTED".to_owned(),
+    };
+    if !url.username().is_empty() {
+        let _ = url.set_username("REDACTED");
+    }
+    if url.password().is_some() {
+        let _ = url.set_password(Some("REDACTED"));
+    }
+    let query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, _)| (key.into_owned(), "REDACTED".to_owned()))
+        .collect();
+    if !query.is_empty() {
+        url.query_pairs_mut().clear().extend_pairs(query);
+    }
+    let path: Vec<String> = url
+        .path_segments()
+        .map(|segments| {
+            segments
+                .map(|segment| {
+                    let key = segment.len() >= 16
+                        && segment
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+                    match key {
+                        true => "REDACTED".to_owned(),
+                        false => segment.to_owned(),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    url.set_path(&path.join("/"));
+    url.to_string()
+}
+
+///Redacts the endpoint URL in a transport error, which mentions it.
+fn redact_error(url: &str, err: &dyn Error) -> String {
+    let endpoint = redact_url(url);
+    let mut error = err.to_string().replace(url, &endpoint);
+    if let Ok(url) = reqwest::Url::parse(url) {
+        error = error.replace(url.as_str(), &endpoint);
+    }
+    error
+}
+
+///Appends a request and its outcome to a request dump as a JSON line.
+fn dump_exchange(
+    dump: &Mutex<File>,
+    url: &str,
+    start: SystemTime,
+    payload: &serde_json::Value,
+    response: &Result<serde_json::Value, Box<dyn Error>>,
+) {
+    let endpoint = redact_url(url);
+    let mut request = payload.clone();
+    // personal_* params may carry account passwords and private keys
+    if request["method"]
+        .as_str()
+        .map_or(false, |method| method.starts_with("personal_"))
+    {
+        request["params"] = json!("REDACTED");
+    }
+    let (response, error) = match response {
+        Ok(response) => (response.clone(), None),
+        Err(err) => (
+            serde_json::Value::Null,
+            Some(redact_error(url, err.as_ref())),
+        ),
+    };
+    let entry = json!({
+        "timestamp": start.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64,
+        "durationMs": start.elapsed().unwrap_or_default().as_millis() as u64,
+        "endpoint": endpoint,
+        "request": request,
+        "response": response,
+        "error": error,
+    });
+    let mut file = dump.lock().unwrap_or_else(|err| err.into_inner());
+    // dumping is best-effort and never fails the request
+    let _ = writeln!(file, "{entry}");
+}
+
+fn apply_field_aliases(value: &mut serde_json::Value, aliases: &[(String, String)]) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (from, to) in aliases {
+                if !object.contains_key(to) {
+                    if let Some(field) = object.remove(from) {
+                        object.insert(to.clone(), field);
+                    }
+                }
+            }
+            object
+                .values_mut()
+                .for_each(|field| apply_field_aliases(field, aliases));
+        }
+        serde_json::Value::Array(array) => array
+            .iter_mut()
+            .for_each(|item| apply_field_aliases(item, aliases)),
+        _ => {}
+    }
+}
+
+///The methods whose result is a quantity.
+const QUANTITY_METHODS: [&str; 12] = [
+    "eth_blockNumber",
+    "eth_chainId",
+    "eth_gasPrice",
+    "eth_maxPriorityFeePerGas",
+    "eth_blobBaseFee",
+    "eth_getBalance",
+    "eth_getTransactionCount",
+    "eth_estimateGas",
+    "eth_getBlockTransactionCountByHash",
+    "eth_getBlockTransactionCountByNumber",
+    "eth_getUncleCountByBlockHash",
+    "eth_getUncleCountByBlockNumber",
+];
+
+///The fields of blocks, transactions, receipts, logs, fee histories and proofs holding quantities.
+const QUANTITY_FIELDS: [&str; 30] = [
+    "number",
+    "gasLimit",
+    "gasUsed",
+    "timestamp",
+    "difficulty",
+    "totalDifficulty",
+    "size",
+    "baseFeePerGas",
+    "blobGasUsed",
+    "excessBlobGas",
+    "blockNumber",
+    "transactionIndex",
+    "logIndex",
+    "gas",
+    "gasPrice",
+    "maxFeePerGas",
+    "maxPriorityFeePerGas",
+    "maxFeePerBlobGas",
+    "value",
+    "nonce",
+    "v",
+    "type",
+    "chainId",
+    "yParity",
+    "cumulativeGasUsed",
+    "effectiveGasPrice",
+    "blobGasPrice",
+    "status",
+    "oldestBlock",
+    "balance",
+];
+
+///Rewrites a quantity with leading zeros, as a decimal string or as a JSON number into a canonical hex quantity,
+///leaving other values as is.
+fn normalize_quantity(value: &mut serde_json::Value) {
+    let quantity = match &*value {
+        serde_json::Value::String(quantity) => match quantity.strip_prefix("0x") {
+            Some(digits) if digits.starts_with('0') && digits.len() > 1 => {
+                U256::from_str_radix(digits, 16).ok()
+            }
+            Some(_) => None,
+            None if !quantity.is_empty() && quantity.bytes().all(|c| c.is_ascii_digit()) => {
+                U256::from_dec_str(quantity).ok()
+            }
+            None => None,
+        },
+        serde_json::Value::Number(number) => number.as_u64().map(U256::from),
+        serde_json::Value::Array(_) => {
+            if let serde_json::Value::Array(quantities) = value {
+                quantities.iter_mut().for_each(normalize_quantity);
+            }
+            None
+        }
+        _ => None,
+    };
+    if let Some(quantity) = quantity {
+        *value = json!(format!("0x{quantity:x}"));
+    }
+}
+
+fn normalize_quantities(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (field, value) in object.iter_mut() {
+                match QUANTITY_FIELDS.contains(&field.as_str()) {
+                    true => normalize_quantity(value),
+                    false => normalize_quantities(value),
+                }
+            }
+        }
+        serde_json::Value::Array(array) => array.iter_mut().for_each(normalize_quantities),
+        _ => {}
+    }
+}
+
+///The fields of request objects holding an address or a list of addresses, e.g. in transactions and log filters.
+const ADDRESS_FIELDS: [&str; 3] = ["from", "to", "address"];
+
+///Returns the position of the address parameter of methods taking one.
+fn address_param(method: &str) -> Option<usize> {
+    match method {
+        "eth_getBalance"
+        | "eth_getCode"
+        | "eth_getTransactionCount"
+        | "eth_getStorageAt"
+        | "eth_getProof"
+        | "eth_sign"
+        | "txpool_contentFrom" => Some(0),
+        "personal_sign" => Some(1),
+        _ => None,
+    }
+}
+
+///Returns an `Err()` if an address is not EIP-55 checksummed.
+fn check_checksum(address: &serde_json::Value) -> Result<(), Box<dyn Error>> {
+    match address {
+        serde_json::Value::String(address)
+            if address.len() == 42
+                && address.starts_with("0x")
+                && address[2..].bytes().all(|c| c.is_ascii_hexdigit())
+                && !is_checksummed(address) =>
+        {
+            Err(format!("Address {address} is not EIP-55 checksummed").into())
+        }
+        serde_json::Value::Array(addresses) => addresses.iter().try_for_each(check_checksum),
+        _ => Ok(()),
+    }
+}
+
+///Returns an `Err()` if an address in the params of a request is not EIP-55 checksummed.
+fn check_checksums(method: &str, params: &serde_json::Value) -> Result<(), Box<dyn Error>> {
+    if let Some(position) = address_param(method) {
+        check_checksum(&params[position])?;
+    }
+    match params {
+        serde_json::Value::Object(object) => object.iter().try_for_each(|(field, value)| {
+            match ADDRESS_FIELDS.contains(&field.as_str()) {
+                true => check_checksum(value),
+                false => check_checksums("", value),
+            }
+        }),
+        serde_json::Value::Array(array) => array
+            .iter()
+            .try_for_each(|value| check_checksums("", value)),
+        _ => Ok(()),
+    }
+}
+
+lazy_static! {
+    static ref SLOT_REGEX: Regex = Regex::new(r"0x[0-9A-Fa-f]{1,64}").unwrap();
+}
+
+///The `Provider` module requires an HTTP(S) JSON-RPC URL and is responsible for handling all your JSON-RPC requests.
+///## Example
+///```rust
+///use ethrs::provider::Provider;
+///
+///let provider = Provider::new("https://rpc.sepolia.org");
+///```
+impl Provider {
+    ///The `Provider::new()` associated function takes an HTTP(S) JSON-RPC URL and returns a `Provider`
+    ///instance to make your requests.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///
+    ///let provider: Provider = Provider::new("https://rpc.sepolia.org");
+    ///```
+    pub fn new(_url: &str) -> Provider {
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        Provider {
+            config: Arc::new(ProviderConfig {
+                url: _url.to_owned(),
+                headers,
+                field_aliases: vec![],
+                response_hook: None,
+                rate_limiter: None,
+                timeout: None,
+                dump: None,
+                auth: None,
+                pending_policy: PendingPolicy::Trust,
+                pending_is_latest: Arc::new(Mutex::new(None)),
+                transport: None,
+                middlewares: vec![],
+                metrics: ProviderMetrics::default(),
+                quantity_parsing: QuantityParsing::Strict,
+                cache: None,
+                in_flight: None,
+                node: None,
+                next_id: Arc::new(AtomicU64::new(1)),
+                signer: None,
+                quirks: None,
+                require_checksums: false,
+            }),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    ///The `Provider::connect()` associated function takes an HTTP(S) JSON-RPC URL and attempts to return a `Provider`
+    ///that has reached its endpoint as `Ok(Provider)`, as `preflight()` does. Returns an `Err()` describing why the
+    ///endpoint cannot be used, instead of failing on the first request.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  assert!(Provider::connect("rpc.sepolia.org").is_err());
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///#  let provider = Provider::connect(provider.url())?;
+    ///  println!("connected to chain {}", provider.node_info().unwrap().chain_id);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn connect(url: &str) -> Result<Provider, Box<dyn Error>> {
+        Provider::new(url).preflight()
+    }
+
+    ///The `preflight()` function checks that the `Provider` can reach its endpoint and attempts to return it as
+    ///`Ok(Provider)`, with the chain id and client version of the node recorded, see `node_info()`. The URL must be an
+    ///HTTP(S) URL, unless the `Provider` has a transport, and `eth_chainId` must succeed; the connection it opens is
+    ///then reused by the following requests. Returns an `Err()` naming the endpoint, with its credentials redacted, and
+    ///the cause otherwise.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///use std::time::Duration;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::builder("https://rpc.sepolia.org").timeout(Duration::from_secs(5)).build()?;
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let provider = provider.preflight()?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn preflight(mut self) -> Result<Provider, Box<dyn Error>> {
+        let endpoint = redact_url(&self.config.url);
+        if self.config.transport.is_none() {
+            let url = reqwest::Url::parse(&self.config.url)
+                .map_err(|err| format!("Invalid JSON-RPC URL {endpoint}: {err}"))?;
+            if url.scheme() != "http" && url.scheme() != "https" {
+                return Err(format!(
+                    "Invalid JSON-RPC URL {endpoint}: unsupported scheme {}",
+                    url.scheme()
+                )
+                .into());
+            }
+        }
+        let chain_id = self.chain_id().map_err(|err| {
+            let err = redact_error(&self.config.url, err.as_ref());
+            format!("Cannot connect to {endpoint}: {err}")
+        })?;
+        let client_version = self.client_version().ok();
+        let config = Arc::make_mut(&mut self.config);
+        if config.quirks.is_none() {
+            config.quirks = client_version
+                .as_deref()
+                .and_then(NodeClient::detect)
+                .map(QuirkProfile::for_client);
+        }
+        config.node = Some(NodeInfo {
+            chain_id,
+            client_version,
+        });
+        Ok(self)
+    }
+
+    ///Returns the chain id and client version of the node, if recorded by `preflight()` or `Provider::connect()`.
+    pub fn node_info(&self) -> Option<&NodeInfo> {
+        self.config.node.as_ref()
+    }
+
+    ///Returns the quirk profile of the node, if set with `with_quirks()` or detected by `preflight()` or
+    ///`Provider::connect()`.
+    pub fn quirks(&self) -> Option<&QuirkProfile> {
+        self.config.quirks.as_ref()
+    }
+
+    ///The `Provider::builder()` associated function takes an HTTP(S) JSON-RPC URL and returns a `ProviderBuilder` to
+    ///configure the underlying HTTP client, e.g. to use HTTP/2.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::{HttpVersion, Provider};
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::builder("https://rpc.sepolia.org")
+    ///      .http_version(HttpVersion::Http1Only)
+    ///      .build()?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn builder(url: &str) -> ProviderBuilder {
+        ProviderBuilder {
+            url: url.to_owned(),
+            client: reqwest::blocking::Client::builder(),
+            auth: None,
+            proxy: None,
+            proxy_auth: None,
+            signer: None,
+        }
+    }
+
+    ///Returns the JSON-RPC URL of the `Provider`.
+    pub fn url(&self) -> &str {
+        &self.config.url
+    }
+
+    ///The `with_field_aliases()` function takes a list of `(nonstandard, standard)` JSON field name pairs and returns
+    ///the `Provider` with every nonstandard field in its responses renamed before deserialization. Useful for nodes
+    ///that emit slightly different field names than the Ethereum JSON-RPC specification.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org")
+    ///    .with_field_aliases(&[("gasUsed_", "gasUsed"), ("txHash", "transactionHash")]);
+    ///```
+    pub fn with_field_aliases(mut self, aliases: &[(&str, &str)]) -> Provider {
+        Arc::make_mut(&mut self.config).field_aliases.extend(
+            aliases
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string())),
+        );
+        self
+    }
+
+    ///The `with_response_hook()` function takes a function that is called with the method name and the raw JSON result
+    ///of every request, after the field aliases are applied, and returns the `Provider`. The hook can rewrite the
+    ///result arbitrarily before it is deserialized.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use serde_json::Value;
+    ///
+    ///fn add_missing_logs_bloom(method: &str, result: &mut Value) {
+    ///    if method == "eth_getTransactionReceipt" {
+    ///        if let Some(receipt) = result.as_object_mut() {
+    ///            receipt.entry("logsBloom").or_insert_with(|| Value::from("0x"));
+    ///        }
+    ///    }
+    ///}
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org").with_response_hook(add_missing_logs_bloom);
+    ///```
+    pub fn with_response_hook(mut self, hook: fn(&str, &mut serde_json::Value)) -> Provider {
+        Arc::make_mut(&mut self.config).response_hook = Some(hook);
+        self
+    }
+
+    ///The `with_quantity_parsing()` function takes a `QuantityParsing` and returns the `Provider` parsing the quantities
+    ///in its responses accordingly, after the field aliases are applied and before the response hook is called.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::{Provider, QuantityParsing};
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org").with_quantity_parsing(QuantityParsing::Lenient);
+    ///```
+    pub fn with_quantity_parsing(mut self, quantity_parsing: QuantityParsing) -> Provider {
+        Arc::make_mut(&mut self.config).quantity_parsing = quantity_parsing;
+        self
+    }
+
+    ///The `with_checksummed_addresses()` function returns a `Provider` rejecting requests with an address that is not
+    ///EIP-55 checksummed, e.g. all-lowercase, so that typos in addresses passed to `request()` as strings are caught.
+    ///Addresses are checked in the address params of methods such as `eth_getBalance` and in the `from`, `to` and
+    ///`address` fields of request objects. Typed `Address` params lose the casing they were parsed from, so addresses
+    ///for the typed methods are checked when parsed with `parse_address()`.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use serde_json::json;
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org").with_checksummed_addresses();
+    ///assert!(provider
+    ///    .request::<String>("eth_getBalance", json!(["0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266", "latest"]))
+    ///    .is_err());
+    ///assert!(provider.parse_address("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").is_err());
+    ///```
+    pub fn with_checksummed_addresses(mut self) -> Provider {
+        Arc::make_mut(&mut self.config).require_checksums = true;
+        self
+    }
+
+    ///The `parse_address()` function takes a hexstring and attempts to return the address as `Ok(Address)`, with
+    ///`Address::from_checksummed()` if the `Provider` was configured by `with_checksummed_addresses()` and via
+    ///`FromStr` otherwise. Returns an `Err()` if the input is not a valid address, or is not EIP-55 checksummed when
+    ///checksums are required.
+    pub fn parse_address(&self, address: &str) -> Result<Address, Box<dyn Error>> {
+        match self.config.require_checksums {
+            true => Address::from_checksummed(address).map_err(|err| match err {
+                FromHexError::InvalidChecksum => {
+                    format!("Address {address} is not EIP-55 checksummed").into()
+                }
+                err => err.into(),
+            }),
+            false => Ok(address.parse()?),
+        }
+    }
+
+    ///The `with_quirks()` function takes the `QuirkProfile` of the client behind the endpoint and returns a `Provider`
+    ///correcting its known deviations, instead of detecting the client with `preflight()`.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::quirks::{NodeClient, QuirkProfile};
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org")
+    ///    .with_quirks(QuirkProfile::for_client(NodeClient::Nethermind));
+    ///```
+    pub fn with_quirks(mut self, quirks: QuirkProfile) -> Provider {
+        Arc::make_mut(&mut self.config).quirks = Some(quirks);
+        self
+    }
+
+    ///The `with_cache()` function takes a `ResponseCache` and returns the `Provider` answering repeated requests from
+    ///it until their responses expire, without sending them. The cache is shared by all clones of the `Provider`, and
+    ///can be shared with other providers by cloning it.
+    ///## Example
+    ///```rust
+    ///use ethrs::cache::ResponseCache;
+    ///use ethrs::provider::Provider;
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org").with_cache(ResponseCache::new());
+    ///```
+    pub fn with_cache(mut self, cache: ResponseCache) -> Provider {
+        Arc::make_mut(&mut self.config).cache = Some(cache);
+        self
+    }
+
+    ///The `with_request_coalescing()` function returns the `Provider` coalescing identical read requests made
+    ///concurrently, e.g. by several threads polling `block_number()`: only the first one is sent, and the others wait
+    ///for its response. Requests are coalesced across all clones of the `Provider`. State-changing requests, e.g.
+    ///`eth_sendRawTransaction`, and filter polling are always sent.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org").with_request_coalescing();
+    ///```
+    pub fn with_request_coalescing(mut self) -> Provider {
+        Arc::make_mut(&mut self.config).in_flight = Some(Arc::new(InFlight::default()));
+        self
+    }
+
+    ///The `with_transport()` function takes a `Transport` and returns the `Provider` sending its requests through it
+    ///instead of over HTTP. The headers, authentication, timeout and rate limit of the `Provider` then no longer apply,
+    ///while its response processing, e.g. field aliases or request dumps, still does.
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Provider {
+        Arc::make_mut(&mut self.config).transport = Some(Arc::new(transport));
+        self
+    }
+
+    ///The `with_middleware()` function takes a `Middleware` and returns the `Provider` with it intercepting every
+    ///request, after the middlewares added before it. Middlewares also apply when the `Provider` is the transport of
+    ///another one, e.g. an endpoint of a `FallbackProvider`.
+    ///## Example
+    ///```rust
+    ///use ethrs::middleware::{Middleware, RpcRequest};
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///#[derive(Debug)]
+    ///struct ApiKey(&'static str);
+    ///
+    ///impl Middleware for ApiKey {
+    ///    fn on_request(&self, request: &mut RpcRequest) -> Result<(), Box<dyn Error>> {
+    ///        request.headers.insert("x-api-key", self.0.parse()?);
+    ///        Ok(())
+    ///    }
+    ///}
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org").with_middleware(ApiKey("<api-key>"));
+    ///```
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Provider {
+        Arc::make_mut(&mut self.config)
+            .middlewares
+            .push(Arc::new(middleware));
+        self
+    }
+
+    ///The `metrics()` function returns a handle on the request counters and latency histograms the `Provider` keeps per
+    ///JSON-RPC method, shared by all its clones.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org");
+    ///let metrics = provider.metrics();
+    ///assert!(metrics.method("eth_blockNumber").is_none());
+    ///```
+    pub fn metrics(&self) -> ProviderMetrics {
+        self.config.metrics.clone()
+    }
+
+    ///The `with_rate_limit()` function takes a number of requests per second and a burst size, and returns the
+    ///`Provider` with requests delayed so that no more than `burst` are sent at once and no more than
+    ///`requests_per_second` on average. The limit is shared by all clones of the `Provider`. Independently of the rate
+    ///limit, requests rejected with HTTP 429 are retried up to three times after the delay in the `Retry-After` header.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org").with_rate_limit(10, 20);
+    ///```
+    pub fn with_rate_limit(mut self, requests_per_second: u32, burst: u32) -> Provider {
+        Arc::make_mut(&mut self.config).rate_limiter =
+            Some(Arc::new(RateLimiter::new(requests_per_second, burst)));
+        self
+    }
+
+    ///The `with_timeout()` function takes a duration and returns a copy of the `Provider` whose requests time out after
+    ///it, overriding the timeout the `Provider` was built with. The copy shares the connection pool and rate limit of
+    ///the original, which keeps its own timeout, so it can be used to set the timeout of a single call.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::time::Duration;
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org");
+    ///let block_number = provider.with_timeout(Duration::from_secs(2)).block_number();
+    ///```
+    pub fn with_timeout(&self, timeout: Duration) -> Provider {
+        let mut provider = self.clone();
+        Arc::make_mut(&mut provider.config).timeout = Some(timeout);
+        provider
+    }
+
+    ///The `with_request_dump()` function takes a file path and attempts to return the `Provider` as `Ok(Provider)`,
+    ///with every JSON-RPC request and its response or error appended to the file as a JSON line, along with the time
+    ///it was sent, its duration and the endpoint. Credentials in the endpoint URL, i.e. user info, query values and
+    ///API key path segments, and the params of `personal_*` requests are redacted. Returns an `Err()` if the file cannot
+    ///be opened.
+    ///## Example
+    ///```rust,no_run
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://mainnet.infura.io/v3/<api-key>").with_request_dump("requests.jsonl")?;
+    ///  provider.block_number()?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn with_request_dump(mut self, path: impl AsRef<Path>) -> Result<Provider, Box<dyn Error>> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Arc::make_mut(&mut self.config).dump = Some(Arc::new(Mutex::new(file)));
+        Ok(self)
+    }
+
+    ///The `with_pending_policy()` function takes a `PendingPolicy` and returns the `Provider` applying it to requests
+    ///for the pending block. The endpoint is probed once, on the first such request.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::{PendingPolicy, Provider};
+    ///
+    ///let provider = Provider::new("https://rpc.sepolia.org").with_pending_policy(PendingPolicy::Fallback);
+    ///```
+    pub fn with_pending_policy(mut self, policy: PendingPolicy) -> Provider {
+        let config = Arc::make_mut(&mut self.config);
+        config.pending_policy = policy;
+        config.pending_is_latest = Arc::new(Mutex::new(None));
+        self
+    }
+
+    ///The `request()` function sends an arbitrary JSON-RPC request with the given method and params and attempts to
+    ///deserialize the result as `Ok(T)`. A `null` result deserializes into `None` when `T` is an `Option`. Returns an
+    ///`Err()` on JSON-RPC errors. Useful for methods not yet covered by the `Provider` APIs.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use serde_json::json;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let chain_id: String = provider.request("eth_chainId", json!([]))?;
+    ///  assert_eq!(chain_id, "0xaa36a7");
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, Box<dyn Error>> {
+        let pending = params.as_array().map_or(false, |params| {
+            params.iter().any(|param| param == "pending")
+        });
+        if pending && !matches!(self.config.pending_policy, PendingPolicy::Trust) {
+            if let Some(result) = self.apply_pending_policy(method, &params)? {
+                return Ok(serde_json::from_value(result)?);
+            }
+        }
+        self.request_unchecked(method, params)
+    }
+
+    fn request_unchecked<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, Box<dyn Error>> {
+        let payload = json!({
+            "method": method,
+            "params": params,
+            "id": self.config.next_id.fetch_add(1, Ordering::Relaxed),
+            "jsonrpc": "2.0",
+        });
+        if let Some(quirks) = &self.config.quirks {
+            quirks.check_method(method)?;
+        }
+        if self.config.require_checksums {
+            check_checksums(method, &payload["params"])?;
+        }
+
+        let cached = self
+            .config
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(method, &payload["params"]));
+        let response = match cached {
+            Some(response) => response,
+            None => {
+                let send = || {
+                    let start = SystemTime::now();
+                    let response = self.send(&payload);
+                    if let Some(dump) = &self.config.dump {
+                        dump_exchange(dump, &self.config.url, start, &payload, &response);
+                    }
+                    response
+                };
+                let response = match &self.config.in_flight {
+                    Some(in_flight) if is_read(method, &payload["params"]) => {
+                        in_flight.coalesce(format!("{method}{}", payload["params"]), send)?
+                    }
+                    _ => send()?,
+                };
+                if let Some(cache) = &self.config.cache {
+                    cache.insert(method, &payload["params"], &response);
+                }
+                response
+            }
+        };
+        let mut response = response;
+        if let Some(quirks) = &self.config.quirks {
+            quirks.normalize_error(method, &mut response);
+        }
+        let json: JsonRPCResponse = serde_json::from_value(response)?;
+
+        match json.error {
+            Some(err) => Err(err.message.into()),
+            None => {
+                let mut result = json.result.unwrap_or(serde_json::Value::Null);
+                if let Some(quirks) = &self.config.quirks {
+                    quirks.normalize_result(&mut result);
+                }
+                if !self.config.field_aliases.is_empty() {
+                    apply_field_aliases(&mut result, &self.config.field_aliases);
+                }
+                if self.config.quantity_parsing == QuantityParsing::Lenient {
+                    match QUANTITY_METHODS.contains(&method) {
+                        true => normalize_quantity(&mut result),
+                        false => normalize_quantities(&mut result),
+                    }
+                }
+                if let Some(hook) = self.config.response_hook {
+                    hook(method, &mut result);
+                }
+                Ok(serde_json::from_value(result)?)
+            }
+        }
+    }
+
+    ///The `probe_pending()` function attempts to return whether the endpoint serves the latest block when asked for the
+    ///pending block as `Ok(bool)`, which most load-balanced endpoints silently do. Returns an `Err()` on JSON-RPC
+    ///errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  println!("pending is latest: {}", provider.probe_pending()?);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn probe_pending(&self) -> Result<bool, Box<dyn Error>> {
+        let latest: Option<Block> =
+            self.request_unchecked("eth_getBlockByNumber", json!(["latest", false]))?;
+        let pending: Option<Block> =
+            self.request_unchecked("eth_getBlockByNumber", json!(["pending", false]))?;
+        let latest = latest.ok_or("Latest block not found")?;
+        Ok(match pending {
+            Some(pending) => {
+                pending.number <= latest.number
+                    || (pending.hash.is_some() && pending.hash == latest.hash)
+            }
+            None => true,
+        })
+    }
+
+    ///Applies the pending policy to a request for the pending block, returning the result of the fallback strategy if
+    ///it replaces the request.
+    fn apply_pending_policy(
+        &self,
+        method: &str,
+        params: &serde_json::Value,
+    ) -> Result<Option<serde_json::Value>, Box<dyn Error>> {
+        let pending_is_latest = {
+            let mut probed = self
+                .config
+                .pending_is_latest
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            match *probed {
+                Some(pending_is_latest) => pending_is_latest,
+                None => {
+                    let pending_is_latest = self.probe_pending()?;
+                    *probed = Some(pending_is_latest);
+                    if let (true, PendingPolicy::Warn(warn)) =
+                        (pending_is_latest, self.config.pending_policy)
+                    {
+                        warn(&format!(
+                            "{} serves the latest block as the pending block",
+                            redact_url(&self.config.url)
+                        ));
+                    }
+                    pending_is_latest
+                }
+            }
+        };
+        if !pending_is_latest {
+            return Ok(None);
+        }
+        match (self.config.pending_policy, method) {
+            (PendingPolicy::Error, _) => Err(format!(
+                "{} serves the latest block as the pending block",
+                redact_url(&self.config.url)
+            )
+            .into()),
+            (PendingPolicy::Fallback, "eth_getTransactionCount") => {
+                let address = &params[0];
+                let latest: String =
+                    self.request_unchecked("eth_getTransactionCount", json!([address, "latest"]))?;
+                let content: serde_json::Value =
+                    self.request_unchecked("txpool_contentFrom", json!([address]))?;
+                // the pending pool only holds transactions executable in nonce order
+                let next = content["pending"]
+                    .as_object()
+                    .into_iter()
+                    .flat_map(|pending| pending.keys())
+                    .filter_map(|nonce| nonce.parse::<u128>().ok())
+                    .map(|nonce| nonce + 1)
+                    .fold(parse_quantity(&latest)?, u128::max);
+                Ok(Some(json!(format!("0x{next:x}"))))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn send(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>> {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::debug_span!(
+                "rpc",
+                method = payload["method"].as_str().unwrap_or_default(),
+                endpoint = %redact_url(&self.config.url),
+            );
+            let _entered = span.enter();
+            let start = Instant::now();
+            let response = self.intercept(payload);
+            let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+            match &response {
+                Ok(response) => match response["error"]["message"].as_str() {
+                    Some(error) => tracing::debug!(duration_ms, error, "JSON-RPC error response"),
+                    None => tracing::debug!(
+                        duration_ms,
+                        response_size = response.to_string().len(),
+                        "JSON-RPC response"
+                    ),
+                },
+                Err(error) => tracing::warn!(duration_ms, %error, "JSON-RPC request failed"),
+            }
+            response
+        }
+        #[cfg(not(feature = "tracing"))]
+        self.intercept(payload)
+    }
+
+    fn intercept(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>> {
+        let start = Instant::now();
+        let response = self.intercept_unmetered(payload);
+        let error = response
+            .as_ref()
+            .map_or(true, |response| !response["error"].is_null());
+        self.config.metrics.record(
+            payload["method"].as_str().unwrap_or_default(),
+            start.elapsed(),
+            error,
+        );
+        response
+    }
+
+    fn intercept_unmetered(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<serde_json::Value, Box<dyn Error>> {
+        let middlewares = &self.config.middlewares;
+        if middlewares.is_empty() {
+            return self.dispatch(payload, &HeaderMap::new());
+        }
+        let mut request = RpcRequest::from_payload(payload);
+        let start = Instant::now();
+        for middleware in middlewares {
+            middleware.on_request(&mut request)?;
+        }
+        match self.dispatch(&request.payload(), &request.headers) {
+            Ok(body) => {
+                let mut response = RpcResponse {
+                    body,
+                    duration: start.elapsed(),
+                };
+                for middleware in middlewares.iter().rev() {
+                    middleware.on_response(&request, &mut response);
+                }
+                Ok(response.body)
+            }
+            Err(err) => {
+                for middleware in middlewares.iter().rev() {
+                    middleware.on_error(&request, err.as_ref());
+                }
+                Err(err)
+            }
+        }
+    }
+
+    fn dispatch(
+        &self,
+        payload: &serde_json::Value,
+        headers: &HeaderMap,
+    ) -> Result<serde_json::Value, Box<dyn Error>> {
+        let response = match &self.config.transport {
+            Some(transport) => transport.send(payload)?,
+            None => self.post(payload, headers)?,
+        };
+        // servers that cannot parse the request answer with an error and a null id
+        let unparsed = response["id"].is_null() && !response["error"].is_null();
+        if !unparsed && response["id"] != payload["id"] {
+            return Err(format!(
+                "Mismatched JSON-RPC response id from {}: expected {}, got {}",
+                redact_url(&self.config.url),
+                payload["id"],
+                response["id"]
+            )
+            .into());
+        }
+        Ok(response)
+    }
+
+    fn post(
+        &self,
+        payload: &serde_json::Value,
+        headers: &HeaderMap,
+    ) -> Result<serde_json::Value, Box<dyn Error>> {
+        let mut retries = 0;
+        let response = loop {
+            if let Some(rate_limiter) = &self.config.rate_limiter {
+                rate_limiter.acquire();
+            }
+            let body = payload.to_string();
+            let mut request = self
+                .client
+                .post(&self.config.url)
+                .headers(self.config.headers.clone())
+                .headers(headers.clone());
+            if let Some(signer) = &self.config.signer {
+                request = request.headers(signer.sign(body.as_bytes())?);
+            }
+            request = request.body(body);
+            if let Some(timeout) = self.config.timeout {
+                request = request.timeout(timeout);
+            }
+            request = match &self.config.auth {
+                Some(Auth::Bearer(token)) => request.bearer_auth(token),
+                Some(Auth::Basic { username, password }) => {
+                    request.basic_auth(username, password.as_ref())
+                }
+                Some(Auth::Query { name, value }) => request.query(&[(name, value)]),
+                // header keys are part of the default headers
+                Some(Auth::Header { .. }) | None => request,
+            };
+            let response = request.send()?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                break response;
+            }
+            if retries == RATE_LIMIT_RETRIES {
+                return Err(format!(
+                    "Rate limited by {} after {retries} retries",
+                    redact_url(&self.config.url)
+                )
+                .into());
+            }
+            retries += 1;
+            thread::sleep(retry_after(response.headers()));
+        };
+        Ok(response.json()?)
+    }
+
+    ///The `block_number()` function attempts to return the current block number as `Ok(u64)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .block_number()?
+    ///      >= 2900000);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn block_number(&self) -> Result<u64, Box<dyn Error>> {
+        let result: String = self.request("eth_blockNumber", json!([]))?;
+        Ok(parse_u64(&result)?)
+    }
+
+    ///The `gas_price()` function attempts to return the current gas price as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .gas_price()?
+    ///      >= 7);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn gas_price(&self) -> Result<u128, Box<dyn Error>> {
+        self.gas_price_wei()?.try_into()
+    }
+
+    ///The `gas_price_wei()` function attempts to return the current gas price as `Ok(Wei)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  println!("gas price: {} gwei", provider.gas_price_wei()?.to_gwei());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn gas_price_wei(&self) -> Result<Wei, Box<dyn Error>> {
+        self.request("eth_gasPrice", json!([]))
+    }
+
+    ///The `chain_id()` function attempts to return the EIP-155 chain id of the node as `Ok(u64)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  assert_eq!(provider.chain_id()?, 11155111);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn chain_id(&self) -> Result<u64, Box<dyn Error>> {
+        let result: String = self.request("eth_chainId", json!([]))?;
+        Ok(parse_quantity(&result)?.try_into()?)
+    }
+
+    ///The `net_version()` function attempts to return the network id of the node as `Ok(String)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  assert_eq!(provider.net_version()?, "11155111");
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn net_version(&self) -> Result<String, Box<dyn Error>> {
+        self.request("net_version", json!([]))
+    }
+
+    ///The `net_peer_count()` function attempts to return the number of peers connected to the node as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  println!("Connected to {} peers", provider.net_peer_count()?);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn net_peer_count(&self) -> Result<u128, Box<dyn Error>> {
+        let result: String = self.request("net_peerCount", json!([]))?;
+        parse_quantity(&result)
+    }
+
+    ///The `client_version()` function attempts to return the client version string of the node, e.g. `Geth/v1.13.5-stable/linux-amd64/go1.21.4`, as `Ok(String)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let client = provider.client_version()?;
+    ///  println!("Running {}", client.split('/').next().unwrap_or_default());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn client_version(&self) -> Result<String, Box<dyn Error>> {
+        self.request("web3_clientVersion", json!([]))
+    }
+
+    ///The `web3_sha3()` function takes arbitrary data and attempts to return its Keccak-256 hash, as computed by the node, as `Ok(H256)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  assert_eq!(
+    ///      provider.web3_sha3(b"")?,
+    ///      "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470".parse()?
+    ///  );
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn web3_sha3(&self, data: &[u8]) -> Result<H256, Box<dyn Error>> {
+        self.request("web3_sha3", json!([format!("0x{}", hex::encode(data))]))
+    }
+
+    ///The `get_balance()` function takes an address and an optional block id, and attempts to return a deserialized balance as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors or if the balance does not fit in a `u128`, see `get_balance_wei()`.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_balance("0x0000000000000000000000000000000000000000".parse()?, None)? // fetches the latest balance of this address
+    ///      > 0);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_balance(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<u128, Box<dyn Error>> {
+        self.get_balance_wei(address, block)?.try_into()
+    }
+
+    ///The `get_balance_wei()` function takes an address and an optional block id, and attempts to return the balance as `Ok(Wei)`, without the `u128` range limit of `get_balance()`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let balance = provider.get_balance_wei("0x0000000000000000000000000000000000000000".parse()?, None)?;
+    ///  println!("balance: {balance}");
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_balance_wei(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<Wei, Box<dyn Error>> {
+        self.request(
+            "eth_getBalance",
+            json!([address, block.unwrap_or_default()]),
+        )
+    }
+
+    ///The `get_storage_at()` function takes an address, slot and an optional block id, and attempts to return a deserialized code hexstring as `Ok(String)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_storage_at("0x6f14c02fc1f78322cfd7d707ab90f18bad3b54f5".parse()?, "0x0", None)? // fetches the latest code at this address
+    ///      != "0x0");
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_storage_at(
+        &self,
+        address: Address,
+        slot: &str,
+        block: Option<BlockId>,
+    ) -> Result<String, Box<dyn Error>> {
+        match SLOT_REGEX.is_match(slot) {
+            true => self.request(
+                "eth_getStorageAt",
+                json!([address, slot, block.unwrap_or_default()]),
+            ),
+            false => Err("Invalid slot".into()),
+        }
+    }
+
+    ///The `get_code()` function takes an address and an optional block id, and attempts to return a deserialized string as `Ok(String)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_code("0x6f14c02fc1f78322cfd7d707ab90f18bad3b54f5".parse()?, None)? // fetches the latest code at this address
+    ///      != "0x0");
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_code(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<String, Box<dyn Error>> {
+        self.request("eth_getCode", json!([address, block.unwrap_or_default()]))
+    }
+
+    ///The `get_transaction_count()` function takes an address and an optional block id, and attempts to return a deserialized integer as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_transaction_count("0xec65818ff0f8b071e587a0bbdbecc94de739b6ec".parse()?, None)? // fetches the latest transaction count for this address
+    ///      > 0);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_transaction_count(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<u128, Box<dyn Error>> {
+        let result: String = self.request(
+            "eth_getTransactionCount",
+            json!([address, block.unwrap_or_default()]),
+        )?;
+        parse_quantity(&result)
+    }
+
+    ///The `get_proof()` function takes an address, a list of storage slots and an optional block id, and attempts to return the account and the given storage slots along with their Merkle-Patricia proofs as `Ok(AccountProof)`. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::BlockId;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let proof = provider.get_proof(
+    ///      "0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?,
+    ///      &[],
+    ///      Some(BlockId::Finalized),
+    ///  )?;
+    ///  assert!(!proof.account_proof.is_empty());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_proof(
+        &self,
+        address: Address,
+        storage_keys: &[H256],
+        block: Option<BlockId>,
+    ) -> Result<AccountProof, Box<dyn Error>> {
+        self.request(
+            "eth_getProof",
+            json!([address, storage_keys, block.unwrap_or_default()]),
+        )
+    }
+
+    ///The `get_block_transaction_count_by_hash()` function takes a blockhash and attempts to return a deserialized integer as `Ok(Some(u128))`. Returns a `None` when blockhash is not mined and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_block_transaction_count_by_hash("0x6c4925c897c45d377d8fb3ef59df7e0cf97604fc85b909bb806818368fdc6b07".parse()?)? // fetches the latest transaction count for this address
+    ///      == Some(5));
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_block_transaction_count_by_hash(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<u128>, Box<dyn Error>> {
+        let result: Option<String> =
+            self.request("eth_getBlockTransactionCountByHash", json!([block_hash]))?;
+        match result {
+            Some(count) => Ok(Some(parse_quantity(&count)?)),
+            None => Ok(None),
+        }
+    }
+
+    ///The `get_block_by_hash()` function takes a block hash and attempts to return a deserialized block *without transactions* as `Ok(Some(Block))`. If no such block exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending blocks will have some fields serialized as `None` types.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_block_by_hash("0x7caebcb62b8fdd21673bcd7d3737f3e6dc18915e08ef3c868cb42aa78eb95d06".parse()?)? // fetches the block by hash
+    ///      .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_block_by_hash(&self, block_hash: H256) -> Result<Option<Block>, Box<dyn Error>> {
+        self.request("eth_getBlockByHash", json!([block_hash, false]))
+    }
+
+    ///The `get_block_by_hash_with_tx()` function takes a block hash and attempts to return a deserialized block *with transactions* as `Ok(Some(BlockWithTx))`. If no such block exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending blocks will have some fields serialized as `None` types.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_block_by_hash_with_tx("0x7caebcb62b8fdd21673bcd7d3737f3e6dc18915e08ef3c868cb42aa78eb95d06".parse()?)? // fetches the block by hash with txs
+    ///      .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_block_by_hash_with_tx(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<BlockWithTx>, Box<dyn Error>> {
+        self.request("eth_getBlockByHash", json!([block_hash, true]))
+    }
+
+    ///The `get_block_by_number()` function takes an optional block id (defaulting to the latest block) and attempts to return a deserialized block *without transactions* as `Ok(Some(Block))`. If no such block exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending blocks will have some fields serialized as `None` types.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_block_by_number(None)? // fetches the latest block
+    ///      .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_block_by_number(
+        &self,
+        block: Option<BlockId>,
+    ) -> Result<Option<Block>, Box<dyn Error>> {
+        match block.unwrap_or_default() {
+            BlockId::Hash(block_hash) => self.get_block_by_hash(block_hash),
+            block => self.request("eth_getBlockByNumber", json!([block, false])),
+        }
+    }
+
+    ///The `get_block_by_number_with_tx()` function takes an optional block id (defaulting to the latest block) and attempts to return a deserialized block *with transactions* as `Ok(Some(BlockWithTx))`. If no such block exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending blocks will have some fields serialized as `None` types.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_block_by_number_with_tx(None)? // fetches the latest block
+    ///      .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_block_by_number_with_tx(
+        &self,
+        block: Option<BlockId>,
+    ) -> Result<Option<BlockWithTx>, Box<dyn Error>> {
+        match block.unwrap_or_default() {
+            BlockId::Hash(block_hash) => self.get_block_by_hash_with_tx(block_hash),
+            block => self.request("eth_getBlockByNumber", json!([block, true])),
+        }
+    }
+
+    ///The `get_uncle_by_block_hash_and_index()` function takes a block hash and an uncle index and attempts to return the deserialized uncle header as `Ok(Some(Block))`, with an empty `transactions` list. If no such uncle exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_uncle_by_block_hash_and_index("0x6c4925c897c45d377d8fb3ef59df7e0cf97604fc85b909bb806818368fdc6b07".parse()?, 0)?
+    ///      .is_none());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_uncle_by_block_hash_and_index(
+        &self,
+        block_hash: H256,
+        idx: u64,
+    ) -> Result<Option<Block>, Box<dyn Error>> {
+        self.request(
+            "eth_getUncleByBlockHashAndIndex",
+            json!([block_hash, format!("0x{idx:x}")]),
+        )
+    }
+
+    ///The `get_uncle_by_block_number_and_index()` function takes an optional block id (defaulting to the latest block) and an uncle index and attempts to return the deserialized uncle header as `Ok(Some(Block))`, with an empty `transactions` list. If no such uncle exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::BlockId;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_uncle_by_block_number_and_index(Some(BlockId::Number(3347000)), 0)?
+    ///      .is_none());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_uncle_by_block_number_and_index(
+        &self,
+        block: Option<BlockId>,
+        idx: u64,
+    ) -> Result<Option<Block>, Box<dyn Error>> {
+        match block.unwrap_or_default() {
+            BlockId::Hash(block_hash) => self.get_uncle_by_block_hash_and_index(block_hash, idx),
+            block => self.request(
+                "eth_getUncleByBlockNumberAndIndex",
+                json!([block, format!("0x{idx:x}")]),
+            ),
+        }
+    }
+
+    ///The `get_uncle_count_by_block_hash()` function takes a block hash and attempts to return the number of uncles in the block as `Ok(Some(u128))`. Returns a `None` when the block is not mined and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_uncle_count_by_block_hash("0x6c4925c897c45d377d8fb3ef59df7e0cf97604fc85b909bb806818368fdc6b07".parse()?)?
+    ///      == Some(0));
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_uncle_count_by_block_hash(
+        &self,
+        block_hash: H256,
+    ) -> Result<Option<u128>, Box<dyn Error>> {
+        let result: Option<String> =
+            self.request("eth_getUncleCountByBlockHash", json!([block_hash]))?;
+        match result {
+            Some(count) => Ok(Some(parse_quantity(&count)?)),
+            None => Ok(None),
+        }
+    }
+
+    ///The `get_uncle_count_by_block_number()` function takes an optional block id (defaulting to the latest block) and attempts to return the number of uncles in the block as `Ok(Some(u128))`. Returns a `None` when the block is not mined and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_uncle_count_by_block_number(None)? // post-merge blocks have no uncles
+    ///      == Some(0));
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_uncle_count_by_block_number(
+        &self,
+        block: Option<BlockId>,
+    ) -> Result<Option<u128>, Box<dyn Error>> {
+        let result: Option<String> = match block.unwrap_or_default() {
+            BlockId::Hash(block_hash) => return self.get_uncle_count_by_block_hash(block_hash),
+            block => self.request("eth_getUncleCountByBlockNumber", json!([block]))?,
+        };
+        match result {
+            Some(count) => Ok(Some(parse_quantity(&count)?)),
+            None => Ok(None),
+        }
+    }
+
+    ///The `get_transaction_by_hash()` function takes a transaction hash attempts to return a deserialized transaction as `Ok(Some(Transaction))`. If no such transaction exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending transactions will have some fields serialized as `None` types.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_transaction_by_hash("0xfb09cfce0695a6843ee3ad5ed4505ca4c8fc0b32f33c1ee12548ba78f0ee52be".parse()?)?
+    ///      .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_transaction_by_hash(
+        &self,
+        txhash: H256,
+    ) -> Result<Option<Transaction>, Box<dyn Error>> {
+        self.request("eth_getTransactionByHash", json!([txhash]))
+    }
+
+    ///The `get_transaction_by_block_hash_and_index()` function takes a block hash and transaction index and attempts to return a deserialized transaction as `Ok(Some(Transaction))`. If no such transaction exists on the index, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_transaction_by_block_hash_and_index("0xc49f9290e07575fbcf91a9349721edaff45a6600add9281e48a2948f01c1d8d4".parse()?, 1)? // fetches the block by hash and returns the tx at index 1
+    ///      .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_transaction_by_block_hash_and_index(
+        &self,
+        block_hash: H256,
+        idx: u64,
+    ) -> Result<Option<Transaction>, Box<dyn Error>> {
+        self.request(
+            "eth_getTransactionByBlockHashAndIndex",
+            json!([block_hash, format!("0x{idx:x}")]),
+        )
+    }
+
+    ///The `get_transaction_by_block_number_and_index()` function takes a block number and transaction index and attempts to return a deserialized transaction as `Ok(Some(Transaction))`. If no such transaction exists on the index, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_transaction_by_block_number_and_index(2893800, 1)? // fetches the block by number and returns the tx at index 1
+    ///      .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_transaction_by_block_number_and_index(
+        &self,
+        block_number: u64,
+        idx: u64,
+    ) -> Result<Option<Transaction>, Box<dyn Error>> {
+        self.request(
+            "eth_getTransactionByBlockNumberAndIndex",
+            json!([format!("0x{block_number:x}"), format!("0x{idx:x}")]),
+        )
+    }
+
+    ///The `get_transaction_receipt()` function takes transaction hash and attempts to return a deserialized transaction receipt as `Ok(Some(TransactionReceipt))`. If no such transaction exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::U256;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///    assert!(provider
+    ///      .get_transaction_receipt("0x71d6059608006e73a233978ee092e7a2066b2556bc4a31dfe9be1f23328ce36a".parse()?)?.is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_transaction_receipt(
+        &self,
+        txhash: H256,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>> {
+        self.request("eth_getTransactionReceipt", json!([txhash]))
+    }
+
+    ///The `get_block_receipts()` function takes a `BlockId` and attempts to return the receipts of every transaction in
+    ///the block as `Ok(Some(Vec<TransactionReceipt>))` in a single request. If no such block exists, returns `Ok(None)`
+    ///and returns an `Err()` on JSON-RPC errors, including on nodes that do not support `eth_getBlockReceipts`.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::BlockId;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let receipts = provider.get_block_receipts(BlockId::Number(3347000))?.unwrap();
+    ///  assert!(receipts.iter().all(|receipt| receipt.block_number == 3347000));
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn get_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> Result<Option<Vec<TransactionReceipt>>, Box<dyn Error>> {
+        self.request("eth_getBlockReceipts", json!([block]))
+    }
+
+    ///The `wait_for_transaction()` function takes a transaction hash, a number of confirmations, a timeout and a poll
+    ///interval, and polls the node until the transaction is mined with at least that many confirmations, returning its
+    ///receipt as `Ok(Some(TransactionReceipt))`. A transaction mined in the latest block has one confirmation. Returns
+    ///`Ok(None)` if the timeout elapses first and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///use std::time::Duration;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  assert!(provider
+    ///    .wait_for_transaction(
+    ///      "0x10e8caafb752c4b611c51dfa784168eebbf1b2819523ea6e8cdf7452552ef6c3".parse()?,
+    ///      12,
+    ///      Duration::from_secs(30),
+    ///      Duration::from_secs(1),
+    ///    )?
+    ///    .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn wait_for_transaction(
+        &self,
+        txhash: H256,
+        confirmations: u64,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(receipt) = self.get_transaction_receipt(txhash)? {
+                let latest = self.block_number()?;
+                if latest + 1 >= receipt.block_number.saturating_add(confirmations) {
+                    return Ok(Some(receipt));
+                }
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            thread::sleep(poll_interval.min(deadline - now));
+        }
+    }
+
+    ///The `beacon_root_at()` function takes a beacon block timestamp and an optional block id, and attempts to return the
+    ///parent beacon block root stored by the EIP-4788 beacon roots contract as `Ok(Some(H256))`. Returns `Ok(None)` when the
+    ///timestamp is not stored, e.g. because its ring buffer slot has since been overwritten, and returns an `Err()` on
+    ///JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use ethrs::types::BlockId;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let block = provider.get_block_by_number(None)?.unwrap();
+    ///  assert!(provider
+    ///    .beacon_root_at(block.timestamp.as_u64(), Some(BlockId::Number(block.number.unwrap())))?
+    ///    .is_some());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn beacon_root_at(
+        &self,
+        timestamp: u64,
+        block: Option<BlockId>,
+    ) -> Result<Option<H256>, Box<dyn Error>> {
+        let address: Address = BEACON_ROOTS_ADDRESS.parse()?;
+        let timestamp_slot = timestamp % HISTORY_BUFFER_LENGTH;
+        let stored_timestamp: H256 = self
+            .get_storage_at(address, &format!("0x{timestamp_slot:x}"), block)?
+            .parse()?;
+        if U256::from_big_endian(stored_timestamp.as_bytes()) != U256::from(timestamp) {
+            return Ok(None);
+        }
+
+        let root_slot = timestamp_slot + HISTORY_BUFFER_LENGTH;
+        let root: H256 = self
+            .get_storage_at(address, &format!("0x{root_slot:x}"), block)?
+            .parse()?;
+        Ok(Some(root))
+    }
+
+    ///The `block_hash_at()` function takes a block number and attempts to return its block hash from the EIP-2935 history
+    ///storage contract as `Ok(Some(H256))`. Only the last 8191 blocks before the latest block are served, so `Ok(None)` is
+    ///returned for blocks outside that window or before the contract was deployed. Returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let latest = provider.block_number()?;
+    ///  assert!(provider.block_hash_at(latest)?.is_none()); // the current block is never served
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn block_hash_at(&self, block_number: u64) -> Result<Option<H256>, Box<dyn Error>> {
+        let latest = self.block_number()?;
+        if block_number >= latest || latest - block_number > HISTORY_BUFFER_LENGTH {
+            return Ok(None);
+        }
+
+        let slot = block_number % HISTORY_BUFFER_LENGTH;
+        let hash: H256 = self
+            .get_storage_at(
+                HISTORY_STORAGE_ADDRESS.parse()?,
+                &format!("0x{slot:x}"),
+                Some(BlockId::Number(latest)),
+            )?
+            .parse()?;
+        match hash == H256::ZERO {
+            true => Ok(None),
+            false => Ok(Some(hash)),
+        }
+    }
+
+    ///The `supports_interface()` function takes the address of a contract and an EIP-165 interface id, e.g.
+    ///`ERC721_INTERFACE_ID`, and attempts to return whether the contract implements the interface as `Ok(bool)`,
+    ///following the detection procedure of EIP-165: the contract must first report that it supports EIP-165 and does
+    ///not support the invalid interface id `0xffffffff`. Accounts without code and contracts reverting or returning
+    ///malformed data, i.e. not implementing EIP-165, support no interface. Returns an `Err()` on other JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::{Provider, ERC721_INTERFACE_ID};
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  // WETH is not an ERC-721 collection
+    ///  assert!(!provider.supports_interface("0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?, ERC721_INTERFACE_ID)?);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn supports_interface(
+        &self,
+        address: Address,
+        interface_id: [u8; 4],
+    ) -> Result<bool, Box<dyn Error>> {
+        if interface_id == [0xff; 4] {
+            return Ok(false);
+        }
+        if self.query_interface(address, ERC165_INTERFACE_ID)? != Some(true)
+            || self.query_interface(address, [0xff; 4])? != Some(false)
+        {
+            return Ok(false);
+        }
+        Ok(interface_id == ERC165_INTERFACE_ID
+            || self.query_interface(address, interface_id)? == Some(true))
+    }
+
+    ///Returns the result of `supportsInterface(interface_id)`, or `None` if the call does not return a boolean.
+    fn query_interface(
+        &self,
+        address: Address,
+        interface_id: [u8; 4],
+    ) -> Result<Option<bool>, Box<dyn Error>> {
+        let mut data = ERC165_INTERFACE_ID.to_vec();
+        data.extend_from_slice(&interface_id);
+        data.resize(36, 0);
+        // the gas EIP-165 allows for the call
+        let tx = CallInput::to(address).gas(30000u64).data(data);
+        let output: Bytes = match self.call(tx, None) {
+            Ok(output) => output.parse()?,
+            Err(err) => {
+                let message = err.to_string().to_lowercase();
+                return match [
+                    "revert",
+                    "out of gas",
+                    "invalid opcode",
+                    "vm execution error",
+                ]
+                .iter()
+                .any(|error| message.contains(error))
+                {
+                    true => Ok(None),
+                    false => Err(err),
+                };
+            }
+        };
+        match output.0.get(..32) {
+            Some(word) if word[..31].iter().all(|&byte| byte == 0) && word[31] <= 1 => {
+                Ok(Some(word[31] == 1))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    ///The `resolve_proxy()` function takes an address and an optional block id, and attempts to return the resolution
+    ///of the proxy at the address as `Ok(Some(Proxy))`, reading the standard storage slots of proxies: the EIP-1967
+    ///implementation, admin and beacon slots, then the EIP-1822 and ZeppelinOS implementation slots. The implementation
+    ///of a beacon proxy is returned by `implementation()` of its beacon. Returns `Ok(None)` if no slot is set, e.g. for
+    ///contracts that are not proxies, and returns an `Err()` on JSON-RPC errors.
+    ///## Example
+    ///```rust
+    ///use ethrs::provider::Provider;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  // the Sepolia USDC proxy
+    ///  let proxy = provider.resolve_proxy("0x1c7d4b196cb0c7b01d743fbc6116a902379c7238".parse()?, None)?.unwrap();
+    ///  println!("Implementation at {}", proxy.implementation);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn resolve_proxy(
+        &self,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Result<Option<Proxy>, Box<dyn Error>> {
+        let slot = |slot: &str| -> Result<Option<Address>, Box<dyn Error>> {
+            let value: H256 = self.get_storage_at(address, slot, block)?.parse()?;
+            let mut address = [0u8; 20];
+            address.copy_from_slice(&value.0[12..]);
+            Ok(Some(Address(address)).filter(|address| *address != Address::ZERO))
+        };
+        let admin = slot(EIP1967_ADMIN_SLOT)?;
+        if let Some(implementation) = slot(EIP1967_IMPLEMENTATION_SLOT)? {
+            return Ok(Some(Proxy {
+                kind: ProxyKind::Eip1967,
+                implementation,
+                admin,
+                beacon: slot(EIP1967_BEACON_SLOT)?,
+            }));
+        }
+        if let Some(beacon) = slot(EIP1967_BEACON_SLOT)? {
+            // implementation()
+            let tx = CallInput::to(beacon).data(vec![0x5c, 0x60, 0xda, 0x1b]);
+            let output: Bytes = self.call(tx, block)?.parse()?;
+            if output.0.len() < 32 {
+                return Err(format!("Invalid implementation returned by beacon {beacon}").into());
+            }
+            let mut implementation = [0u8; 20];
+            implementation.copy_from_slice(&output.0[12..32]);
+            return Ok(Some(Proxy {
+                kind: ProxyKind::Beacon,
+                implementation: Address(implementation),
+                admin,
+                beacon: Some(beacon),
+            }));
+        }
+        for (kind, implementation_slot) in [
+            (ProxyKind::Eip1822, EIP1822_PROXIABLE_SLOT),
+            (ProxyKind::ZeppelinOs, ZEPPELINOS_IMPLEMENTATION_SLOT),
+        ] {
+            if let Some(implementation) = slot(implementation_slot)? {
+                return Ok(Some(Proxy {
+                    kind,
+                    implementation,
+                    admin,
+                    beacon: None,
+                }));
+            }
         }
+        Ok(None)
     }
 
-    ///The `gas_price()` function attempts to return the current gas price as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors.
+    ///The `get_logs()` function takes log filter criteria and attempts to return the matching logs as `Ok(Vec<Log>)`. Returns an `Err()` on JSON-RPC errors, e.g. if the node limits the size of the range.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
+    ///use ethrs::provider::{Filter, Provider};
+    ///use ethrs::types::BlockId;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .gas_price()?
-    ///      >= 7);
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let logs = provider.get_logs(&Filter {
+    ///      from_block: Some(BlockId::Number(3347000)),
+    ///      to_block: Some(BlockId::Number(3347000)),
+    ///      ..Default::default()
+    ///  })?;
+    ///  assert!(logs.iter().all(|log| log.block_number == 3347000));
     ///  Ok(())
     ///}
     ///```
-    pub fn gas_price(&self) -> Result<u128, Box<dyn Error>> {
-        let json: RPCResponse = self
-            .client
-            .post(&self.url)
-            .body("{\"method\":\"eth_gasPrice\",\"params\":[],\"id\":1,\"jsonrpc\":\"2.0\"}")
-            .headers(self.headers.clone())
-            .send()?
-            .json()?;
-
-        match json.error {
-            Some(err) => Err(err.message.into()),
-            None => Ok(u128::from_str_radix(
-                json.result.unwrap().strip_prefix("0x").unwrap(),
-                16,
-            )?),
-        }
+    pub fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, Box<dyn Error>> {
+        self.request("eth_getLogs", json!([filter]))
     }
 
-    ///The `get_code()` function takes an address, block param or block number, and attempts to return a deserialized balance as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors.
+    ///The `get_logs_paginated()` function takes log filter criteria and a number of blocks, and attempts to return the
+    ///matching logs as `Ok(Vec<Log>)`, fetching the block range of the filter in windows of at most `chunk_size` blocks.
+    ///When the node rejects a window for returning too many results or spanning too many blocks, the window is halved
+    ///and fetched again, and it grows back towards `chunk_size` after each window that succeeds. Block tags in the range
+    ///are resolved to numbers first, and filters by block hash are fetched in a single request. Returns an `Err()` on
+    ///JSON-RPC errors, or if a single block has too many results.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
+    ///use ethrs::provider::{Filter, Provider};
+    ///use ethrs::types::BlockId;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_balance("0x0000000000000000000000000000000000000000", None, None)? // fetches the latest balance of this address
-    ///      > 0);
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let logs = provider.get_logs_paginated(&Filter {
+    ///      from_block: Some(BlockId::Number(3347000)),
+    ///      to_block: Some(BlockId::Number(3347999)),
+    ///      address: vec!["0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?],
+    ///      ..Default::default()
+    ///  }, 100)?;
+    ///  assert!(logs.iter().all(|log| (3347000..=3347999).contains(&log.block_number)));
     ///  Ok(())
     ///}
     ///```
-    pub fn get_balance(
+    pub fn get_logs_paginated(
         &self,
-        address: &str,
-        block_param: Option<DefaultBlockParam>,
-        block_number: Option<u128>,
-    ) -> Result<u128, Box<dyn Error>> {
-        match ADDRESS_REGEX.is_match(address) {
-            true => {
-                let mut payload = String::new();
-                payload.push_str("{\"method\":\"eth_getBalance\",\"params\":[\"");
-                payload.push_str(address);
-                payload.push_str("\",\"");
-                match block_param {
-                    Some(DefaultBlockParam::EARLIEST) => payload.push_str("earliest"),
-                    Some(DefaultBlockParam::FINALIZED) => payload.push_str("finalized"),
-                    Some(DefaultBlockParam::SAFE) => payload.push_str("safe"),
-                    Some(DefaultBlockParam::LATEST) => payload.push_str("latest"),
-                    Some(DefaultBlockParam::PENDING) => payload.push_str("pending"),
-                    None => match block_number {
-                        Some(block) => payload.push_str(&format!("0x{block:x}")),
-                        None => payload.push_str("latest"),
-                    },
+        filter: &Filter,
+        chunk_size: u64,
+    ) -> Result<Vec<Log>, Box<dyn Error>> {
+        if filter.block_hash.is_some() {
+            return self.get_logs(filter);
+        }
+        let mut from_block = self.resolve_block_number(filter.from_block)?;
+        let to_block = self.resolve_block_number(filter.to_block)?;
+        let mut window = chunk_size.max(1);
+        let mut logs = vec![];
+        while from_block <= to_block {
+            let end = from_block.saturating_add(window - 1).min(to_block);
+            let result = self.get_logs(&Filter {
+                from_block: Some(BlockId::Number(from_block)),
+                to_block: Some(BlockId::Number(end)),
+                ..filter.clone()
+            });
+            match result {
+                Ok(window_logs) => {
+                    logs.extend(window_logs);
+                    window = window.saturating_mul(2).min(chunk_size.max(1));
+                    match end.checked_add(1) {
+                        Some(next) => from_block = next,
+                        None => break,
+                    }
                 }
-
-                payload.push_str("\"],\"id\":1,\"jsonrpc\":\"2.0\"}");
-
-                let json: RPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.message.into()),
-                    None => Ok(u128::from_str_radix(
-                        json.result.unwrap().strip_prefix("0x").unwrap(),
-                        16,
-                    )?),
+                Err(err) if end > from_block && is_log_limit_exceeded(err.as_ref()) => {
+                    window = (end - from_block + 1) / 2;
                 }
+                Err(err) => return Err(err),
             }
-            false => Err("Invalid address".into()),
         }
+        Ok(logs)
     }
 
-    ///The `get_storage_at()` function takes an address, slot, block param or block number, and attempts to return a deserialized code hexstring as `Ok(String)`. Returns an `Err()` on JSON-RPC errors.
+    ///The `blocks()` function takes an inclusive range of block numbers and returns an iterator over the blocks in the
+    ///range, fetched one at a time as the iterator advances. The iterator yields an `Err()` on JSON-RPC errors or if a
+    ///block is not found, and then ends.
     ///## Example
     ///```rust
     ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_storage_at("0x6f14c02fc1f78322cfd7d707ab90f18bad3b54f5", "0x0", None, None)? // fetches the latest code at this address
-    ///      != "0x0");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  for block in provider.blocks(3347000..=3347002) {
+    ///      println!("{:?}", block?.hash);
+    ///  }
     ///  Ok(())
     ///}
     ///```
-    pub fn get_storage_at(
-        &self,
-        address: &str,
-        slot: &str,
-        block_param: Option<DefaultBlockParam>,
-        block_number: Option<u128>,
-    ) -> Result<String, Box<dyn Error>> {
-        match ADDRESS_REGEX.is_match(address) {
-            true => match SLOT_REGEX.is_match(slot) {
-                true => {
-                    let mut payload = String::new();
-                    payload.push_str("{\"method\":\"eth_getStorageAt\",\"params\":[\"");
-                    payload.push_str(address);
-                    payload.push_str("\",\"");
-                    payload.push_str(slot);
-                    payload.push_str("\",\"");
-                    match block_param {
-                        Some(DefaultBlockParam::EARLIEST) => payload.push_str("earliest"),
-                        Some(DefaultBlockParam::FINALIZED) => payload.push_str("finalized"),
-                        Some(DefaultBlockParam::SAFE) => payload.push_str("safe"),
-                        Some(DefaultBlockParam::LATEST) => payload.push_str("latest"),
-                        Some(DefaultBlockParam::PENDING) => payload.push_str("pending"),
-                        None => match block_number {
-                            Some(block) => payload.push_str(&format!("0x{block:x}")),
-                            None => payload.push_str("latest"),
-                        },
-                    }
-                    payload.push_str("\"],\"id\":1,\"jsonrpc\":\"2.0\"}");
-
-                    let json: RPCResponse = self
-                        .client
-                        .post(&self.url)
-                        .body(payload.clone())
-                        .headers(self.headers.clone())
-                        .send()?
-                        .json()?;
-
-                    match json.error {
-                        Some(err) => Err(err.message.into()),
-                        None => Ok(json.result.unwrap()),
-                    }
-                }
-                false => Err("Invalid slot".into()),
-            },
-            false => Err("Invalid address".into()),
+    pub fn blocks(&self, range: RangeInclusive<u64>) -> Blocks<'_> {
+        let (next, end) = range.into_inner();
+        Blocks {
+            provider: self,
+            next: Some(next).filter(|next| *next <= end),
+            end,
         }
     }
 
-    ///The `get_code()` function takes an address, block param or block number, and attempts to return a deserialized string as `Ok(String)`. Returns an `Err()` on JSON-RPC errors.
+    ///The `watch_blocks()` function takes a poll interval and attempts to return a `BlockStream` yielding the blocks
+    ///mined from now on as `Ok(BlockStream)`, polling for the next block at the interval once it has caught up with the
+    ///chain head. Returns an `Err()` on JSON-RPC errors while fetching the chain head.
     ///## Example
-    ///```rust
+    ///```rust,no_run
     ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
     ///use std::error::Error;
+    ///use std::time::Duration;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
-    ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_code("0x6f14c02fc1f78322cfd7d707ab90f18bad3b54f5", None, None)? // fetches the latest code at this address
-    ///      != "0x0");
+    ///  let provider = Provider::new("http://localhost:8545");
+    ///  for block in provider.watch_blocks(Duration::from_secs(2))? {
+    ///      let block = block?;
+    ///      println!("New block {:?} with {} transactions", block.number, block.transactions.len());
+    ///  }
     ///  Ok(())
     ///}
     ///```
-    pub fn get_code(
+    pub fn watch_blocks(
         &self,
-        address: &str,
-        block_param: Option<DefaultBlockParam>,
-        block_number: Option<u128>,
-    ) -> Result<String, Box<dyn Error>> {
-        match ADDRESS_REGEX.is_match(address) {
-            true => {
-                let mut payload = String::new();
-                payload.push_str("{\"method\":\"eth_getCode\",\"params\":[\"");
-                payload.push_str(address);
-                payload.push_str("\",\"");
-                match block_param {
-                    Some(DefaultBlockParam::EARLIEST) => payload.push_str("earliest"),
-                    Some(DefaultBlockParam::FINALIZED) => payload.push_str("finalized"),
-                    Some(DefaultBlockParam::SAFE) => payload.push_str("safe"),
-                    Some(DefaultBlockParam::LATEST) => payload.push_str("latest"),
-                    Some(DefaultBlockParam::PENDING) => payload.push_str("pending"),
-                    None => match block_number {
-                        Some(block) => payload.push_str(&format!("0x{block:x}")),
-                        None => payload.push_str("latest"),
-                    },
-                }
-
-                payload.push_str("\"],\"id\":1,\"jsonrpc\":\"2.0\"}");
-
-                let json: RPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.message.into()),
-                    None => Ok(json.result.unwrap()),
-                }
-            }
-            false => Err("Invalid address".into()),
-        }
+        poll_interval: Duration,
+    ) -> Result<BlockStream<Provider>, Box<dyn Error>> {
+        let head = self.block_number()?;
+        Ok(BlockStream::new(self.clone(), head + 1).with_poll_interval(poll_interval))
     }
 
-    ///The `get_transaction_count()` function takes an address, block param or block number, and attempts to return a deserialized integer as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors.
+    ///The `watch_logs()` function takes log filter criteria and attempts to return a `LogStream` yielding the matching
+    ///logs as `Ok(LogStream)`, from the block the filter starts at or, if it has none, from the blocks mined from now
+    ///on. The stream polls for new blocks every second once it has caught up with the chain head, which can be changed
+    ///with `with_poll_interval()`. The end of the block range of the filter is ignored. Returns an `Err()` on JSON-RPC
+    ///errors while resolving the start of the range.
     ///## Example
-    ///```rust
-    ///use ethrs::provider::Provider;
+    ///```rust,no_run
+    ///use ethrs::provider::{Filter, Provider};
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
-    ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_transaction_count("0xec65818ff0f8b071e587a0bbdbecc94de739b6ec", None, None)? // fetches the latest transaction count for this address
-    ///      > 0);
+    ///  let provider = Provider::new("http://localhost:8545");
+    ///  let filter = Filter {
+    ///      address: vec!["0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?],
+    ///      ..Default::default()
+    ///  };
+    ///  for log in provider.watch_logs(filter)? {
+    ///      println!("New log in transaction {:?}", log?.transaction_hash);
+    ///  }
     ///  Ok(())
     ///}
     ///```
-    pub fn get_transaction_count(
-        &self,
-        address: &str,
-        block_param: Option<DefaultBlockParam>,
-        block_number: Option<u128>,
-    ) -> Result<u128, Box<dyn Error>> {
-        match ADDRESS_REGEX.is_match(address) {
-            true => {
-                let mut payload = String::new();
-                payload.push_str("{\"method\":\"eth_getTransactionCount\",\"params\":[\"");
-                payload.push_str(address);
-                payload.push_str("\",\"");
-                match block_param {
-                    Some(DefaultBlockParam::EARLIEST) => payload.push_str("earliest"),
-                    Some(DefaultBlockParam::FINALIZED) => payload.push_str("finalized"),
-                    Some(DefaultBlockParam::SAFE) => payload.push_str("safe"),
-                    Some(DefaultBlockParam::LATEST) => payload.push_str("latest"),
-                    Some(DefaultBlockParam::PENDING) => payload.push_str("pending"),
-                    None => match block_number {
-                        Some(block) => payload.push_str(&format!("0x{block:x}")),
-                        None => payload.push_str("latest"),
-                    },
-                }
+    pub fn watch_logs(&self, filter: Filter) -> Result<LogStream<Provider>, Box<dyn Error>> {
+        let from_block = match filter.from_block {
+            Some(block) => self.resolve_block_number(Some(block))?,
+            None => self.block_number()? + 1,
+        };
+        Ok(LogStream::new(self.clone(), filter, from_block))
+    }
 
-                payload.push_str("\"],\"id\":1,\"jsonrpc\":\"2.0\"}");
-
-                let json: RPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.message.into()),
-                    None => Ok(u128::from_str_radix(
-                        json.result.unwrap().strip_prefix("0x").unwrap(),
-                        16,
-                    )?),
-                }
-            }
-            false => Err("Invalid address".into()),
+    fn resolve_block_number(&self, block: Option<BlockId>) -> Result<u64, Box<dyn Error>> {
+        match block.unwrap_or_default() {
+            BlockId::Number(number) => Ok(number),
+            BlockId::Earliest => Ok(0),
+            BlockId::Latest => self.block_number(),
+            block => self
+                .get_block_by_number(Some(block))?
+                .and_then(|block| block.number)
+                .ok_or_else(|| format!("Block {block:?} not found").into()),
         }
     }
 
-    ///The `get_block_transaction_count_by_hash()` function takes a blockhash and attempts to return a deserialized integer as `Ok(Some(u128))`. Returns a `None` when blockhash is not mined and returns an `Err()` on JSON-RPC errors.
+    ///The `new_filter()` function takes log filter criteria and attempts to install a log filter on the node, returning its handle as `Ok(FilterHandle)`. Returns an `Err()` on JSON-RPC errors.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
+    ///use ethrs::provider::{Filter, FilterKind, Provider};
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_block_transaction_count_by_hash("0x6c4925c897c45d377d8fb3ef59df7e0cf97604fc85b909bb806818368fdc6b07")? // fetches the latest transaction count for this address
-    ///      == Some(5));
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let filter = provider.new_filter(&Filter::default())?;
+    ///  assert_eq!(filter.kind(), FilterKind::Log);
     ///  Ok(())
     ///}
     ///```
-    pub fn get_block_transaction_count_by_hash(
-        &self,
-        block_hash: &str,
-    ) -> Result<Option<u128>, Box<dyn Error>> {
-        match BLOCKHASH_REGEX.is_match(block_hash) {
-            true => {
-                let mut payload = String::new();
-                payload
-                    .push_str("{\"method\":\"eth_getBlockTransactionCountByHash\",\"params\":[\"");
-                payload.push_str(block_hash);
-                payload.push_str("\"],\"id\":1,\"jsonrpc\":\"2.0\"}");
-
-                let json: RPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.message.into()),
-                    None => match json.result {
-                        Some(result) => Ok(Some(u128::from_str_radix(
-                            result.strip_prefix("0x").unwrap(),
-                            16,
-                        )?)),
-                        None => Ok(None),
-                    },
-                }
-            }
-            false => Err("Invalid block hash".into()),
-        }
+    pub fn new_filter(&self, filter: &Filter) -> Result<FilterHandle, Box<dyn Error>> {
+        Ok(FilterHandle {
+            id: self.request("eth_newFilter", json!([filter]))?,
+            kind: FilterKind::Log,
+        })
     }
 
-    ///The `get_block_by_hash()` function takes a block hash and attempts to return a deserialized block *without transactions* as `Ok(Some(Block))`. If no such block exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending blocks will have some fields serialized as `None` types.
+    ///The `new_block_filter()` function attempts to install a filter for new blocks on the node, returning its handle as `Ok(FilterHandle)`. Returns an `Err()` on JSON-RPC errors.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
+    ///use ethrs::provider::{FilterKind, Provider};
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_block_by_hash("0x7caebcb62b8fdd21673bcd7d3737f3e6dc18915e08ef3c868cb42aa78eb95d06")? // fetches the block by hash
-    ///      .is_some());
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let filter = provider.new_block_filter()?;
+    ///  assert_eq!(filter.kind(), FilterKind::Block);
     ///  Ok(())
     ///}
     ///```
-    pub fn get_block_by_hash(&self, block_hash: &str) -> Result<Option<Block>, Box<dyn Error>> {
-        match BLOCKHASH_REGEX.is_match(block_hash) {
-            true => {
-                let mut payload = String::new();
-                match write!(payload, "{{\"method\":\"eth_getBlockByHash\",\"params\":[\"{block_hash}\",false],\"id\":1,\"jsonrpc\":\"2.0\"}}") {
-                    Ok(_) => (),
-                    Err(err) => return Err(err.into()),
-                };
+    pub fn new_block_filter(&self) -> Result<FilterHandle, Box<dyn Error>> {
+        Ok(FilterHandle {
+            id: self.request("eth_newBlockFilter", json!([]))?,
+            kind: FilterKind::Block,
+        })
+    }
 
-                let json: BlockRPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.into()),
-                    None => Ok(json.result),
-                }
-            }
-            false => Err("Invalid block hash".into()),
-        }
+    ///The `new_pending_transaction_filter()` function attempts to install a filter for new pending transactions on the node, returning its handle as `Ok(FilterHandle)`. Returns an `Err()` on JSON-RPC errors.
+    pub fn new_pending_transaction_filter(&self) -> Result<FilterHandle, Box<dyn Error>> {
+        Ok(FilterHandle {
+            id: self.request("eth_newPendingTransactionFilter", json!([]))?,
+            kind: FilterKind::PendingTransaction,
+        })
     }
 
-    ///The `get_block_by_hash_with_tx()` function takes a block hash and attempts to return a deserialized block *with transactions* as `Ok(Some(BlockWithTx))`. If no such block exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending blocks will have some fields serialized as `None` types.
+    ///The `get_filter_changes()` function takes a filter handle and attempts to return the changes since the filter was last polled as `Ok(FilterChanges)`, typed according to the kind of the filter. Returns an `Err()` on JSON-RPC errors, including when the filter has expired.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
+    ///use ethrs::provider::{FilterChanges, Provider};
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_block_by_hash_with_tx("0x7caebcb62b8fdd21673bcd7d3737f3e6dc18915e08ef3c868cb42aa78eb95d06")? // fetches the block by hash with txs
-    ///      .is_some());
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let filter = provider.new_block_filter()?;
+    ///  if let FilterChanges::Hashes(hashes) = provider.get_filter_changes(&filter)? {
+    ///      println!("{} new blocks", hashes.len());
+    ///  }
     ///  Ok(())
     ///}
     ///```
-    pub fn get_block_by_hash_with_tx(
+    pub fn get_filter_changes(
         &self,
-        block_hash: &str,
-    ) -> Result<Option<BlockWithTx>, Box<dyn Error>> {
-        match BLOCKHASH_REGEX.is_match(block_hash) {
-            true => {
-                let mut payload = String::new();
-                match write!(payload, "{{\"method\":\"eth_getBlockByHash\",\"params\":[\"{block_hash}\",true],\"id\":1,\"jsonrpc\":\"2.0\"}}") {
-                    Ok(_) => (),
-                    Err(err) => return Err(err.into()),
-                };
-                let json: BlockWithTxRPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.into()),
-                    None => Ok(json.result),
-                }
-            }
-            false => Err("Invalid block hash".into()),
+        filter: &FilterHandle,
+    ) -> Result<FilterChanges, Box<dyn Error>> {
+        match filter.kind {
+            FilterKind::Log => Ok(FilterChanges::Logs(
+                self.request("eth_getFilterChanges", json!([filter.id]))?,
+            )),
+            FilterKind::Block | FilterKind::PendingTransaction => Ok(FilterChanges::Hashes(
+                self.request("eth_getFilterChanges", json!([filter.id]))?,
+            )),
         }
     }
 
-    ///The `get_block_by_number()` function takes a default block param or block number and attempts to return a deserialized block *without transactions* as `Ok(Some(Block))`. If no such block exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending blocks will have some fields serialized as `None` types.
+    ///The `uninstall_filter()` function takes a filter handle and attempts to uninstall the filter from the node, returning whether it was installed as `Ok(bool)`. Returns an `Err()` on JSON-RPC errors.
+    pub fn uninstall_filter(&self, filter: &FilterHandle) -> Result<bool, Box<dyn Error>> {
+        self.request("eth_uninstallFilter", json!([filter.id]))
+    }
+
+    ///The `send_transaction()` function takes a transaction input struct, sends it and attempts to return a deserialized transaction hash as `Ok(H256)`. If no such transaction exists, returns `Ok(0x0...)` and returns an `Err()` on JSON-RPC errors.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
+    ///use ethrs::provider::{Provider, TransactionInput};
     ///use ethrs::types::U256;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_block_by_number(None, None)? // fetches the latest block
-    ///      .is_some());
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let tx = TransactionInput {
+    ///      from: "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?,
+    ///      to: Some("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?),
+    ///      gas: Some(U256::from(21000)),
+    ///      gas_price: Some(U256::from(1)),
+    ///      value: Some(U256::from(1)),
+    ///      data: Some("0xFF".to_owned()),
+    ///      nonce: Some(U256::from(0)),
+    ///      access_list: None,
+    ///  };
+    ///  // the RPC call itself will fail because the account is not unlocked
+    ///  assert!(provider.send_transaction(tx).is_err());
     ///  Ok(())
     ///}
     ///```
-    pub fn get_block_by_number(
-        &self,
-        block_param: Option<DefaultBlockParam>,
-        block_number: Option<u128>,
-    ) -> Result<Option<Block>, Box<dyn Error>> {
-        let mut payload = String::new();
-        payload.push_str("{\"method\":\"eth_getBlockByNumber\",\"params\":[\"");
-        match block_param {
-            Some(DefaultBlockParam::EARLIEST) => payload.push_str("earliest"),
-            Some(DefaultBlockParam::FINALIZED) => payload.push_str("finalized"),
-            Some(DefaultBlockParam::SAFE) => payload.push_str("safe"),
-            Some(DefaultBlockParam::LATEST) => payload.push_str("latest"),
-            Some(DefaultBlockParam::PENDING) => payload.push_str("pending"),
-            None => match block_number {
-                Some(block) => payload.push_str(&format!("0x{block:x}")),
-                None => payload.push_str("latest"),
-            },
-        }
-
-        payload.push_str("\",false],\"id\":1,\"jsonrpc\":\"2.0\"}");
-
-        let json: BlockRPCResponse = self
-            .client
-            .post(&self.url)
-            .body(payload.clone())
-            .headers(self.headers.clone())
-            .send()?
-            .json()?;
-
-        match json.error {
-            Some(err) => Err(err.into()),
-            None => Ok(json.result),
+    pub fn send_transaction(&self, tx: TransactionInput) -> Result<H256, Box<dyn Error>> {
+        let result: Option<H256> = self.request("eth_sendTransaction", json!([tx]))?;
+        match result {
+            Some(hash) => Ok(hash),
+            None => Err("No txhash returned".into()),
         }
     }
 
-    ///The `get_block_by_number_with_tx()` function takes a default block param or block number and attempts to return a deserialized block *with transactions* as `Ok(Some(BlockWithTx))`. If no such block exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending blocks will have some fields serialized as `None` types.
+    ///The `send_raw_transaction()` function takes a signed transaction, as encoded by `SignedTransaction::encode()`, sends
+    ///it and attempts to return its transaction hash as `Ok(H256)`. Returns an `Err()` on JSON-RPC errors, e.g. when the
+    ///node rejects the transaction.
     ///## Example
-    ///```rust
+    ///```rust,no_run
     ///use ethrs::provider::Provider;
+    ///use ethrs::transaction::{Eip1559Transaction, TypedTransaction};
     ///use ethrs::types::U256;
+    ///use ethrs::wallet::Wallet;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
-    ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_block_by_number_with_tx(None, None)? // fetches the latest block
-    ///      .is_some());
+    ///  let provider = Provider::new("http://localhost:8545");
+    ///  let wallet: Wallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse()?;
+    ///  let signed = wallet.sign_transaction(TypedTransaction::from(Eip1559Transaction {
+    ///      chain_id: 31337,
+    ///      nonce: provider.get_transaction_count(wallet.address(), None)? as u64,
+    ///      max_fee_per_gas: U256::from(2_000_000_000u64),
+    ///      gas_limit: 21000,
+    ///      to: Some(wallet.address()),
+    ///      ..Default::default()
+    ///  }))?;
+    ///  assert_eq!(provider.send_raw_transaction(&signed.encode()?)?, signed.hash);
     ///  Ok(())
     ///}
     ///```
-    pub fn get_block_by_number_with_tx(
-        &self,
-        block_param: Option<DefaultBlockParam>,
-        block_number: Option<u128>,
-    ) -> Result<Option<BlockWithTx>, Box<dyn Error>> {
-        let mut payload = String::new();
-        payload.push_str("{\"method\":\"eth_getBlockByNumber\",\"params\":[\"");
-        match block_param {
-            Some(DefaultBlockParam::EARLIEST) => payload.push_str("earliest"),
-            Some(DefaultBlockParam::FINALIZED) => payload.push_str("finalized"),
-            Some(DefaultBlockParam::SAFE) => payload.push_str("safe"),
-            Some(DefaultBlockParam::LATEST) => payload.push_str("latest"),
-            Some(DefaultBlockParam::PENDING) => payload.push_str("pending"),
-            None => match block_number {
-                Some(block) => payload.push_str(&format!("0x{block:x}")),
-                None => payload.push_str("latest"),
-            },
-        }
-
-        payload.push_str("\",true],\"id\":1,\"jsonrpc\":\"2.0\"}");
-
-        let json: BlockWithTxRPCResponse = self
-            .client
-            .post(&self.url)
-            .body(payload.clone())
-            .headers(self.headers.clone())
-            .send()?
-            .json()?;
+    pub fn send_raw_transaction(&self, raw: &[u8]) -> Result<H256, Box<dyn Error>> {
+        self.request("eth_sendRawTransaction", json!([Bytes(raw.to_vec())]))
+    }
 
-        match json.error {
-            Some(err) => Err(err.into()),
-            None => Ok(json.result),
-        }
+    ///The `sign_typed_data()` function takes an address and EIP-712 typed data as JSON, and asks the node to sign it with the key of that account via `eth_signTypedData_v4`, returning the 65-byte signature as `Ok(Bytes)`, which `Signature::from_bytes()` reads. Useful when the key lives behind the RPC, e.g. on dev nodes or wallet bridges. `TypedData::to_json()` returns typed data hashed locally as JSON. Returns an `Err()` on JSON-RPC errors, e.g. when the account is not managed by the node.
+    ///## Example
+    ///```rust,no_run
+    ///use ethrs::provider::Provider;
+    ///use serde_json::json;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("http://localhost:8545");
+    ///  let typed_data = json!({
+    ///      "types": {
+    ///          "EIP712Domain": [{"name": "name", "type": "string"}, {"name": "chainId", "type": "uint256"}],
+    ///          "Mail": [{"name": "contents", "type": "string"}],
+    ///      },
+    ///      "primaryType": "Mail",
+    ///      "domain": {"name": "Ether Mail", "chainId": 31337},
+    ///      "message": {"contents": "Hello, Bob!"},
+    ///  });
+    ///  let signature = provider.sign_typed_data("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?, &typed_data)?;
+    ///  assert_eq!(signature.len(), 65);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn sign_typed_data(
+        &self,
+        address: Address,
+        typed_data: &serde_json::Value,
+    ) -> Result<Bytes, Box<dyn Error>> {
+        self.request("eth_signTypedData_v4", json!([address, typed_data]))
     }
 
-    ///The `get_transaction_by_hash()` function takes a transaction hash attempts to return a deserialized transaction as `Ok(Some(Transaction))`. If no such transaction exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors. Pending transactions will have some fields serialized as `None` types.
+    ///The `call()` function takes a call input struct, sends it and attempts to return deserialized return data as `Ok(String)`. If no data is returned or a transaction is sent to an EOA, returns `Ok(0x0...)` and returns an `Err()` on JSON-RPC errors.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
+    ///use ethrs::provider::{Provider, CallInput};
     ///use ethrs::types::U256;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_transaction_by_hash("0xfb09cfce0695a6843ee3ad5ed4505ca4c8fc0b32f33c1ee12548ba78f0ee52be")?
-    ///      .is_some());
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let tx = CallInput {
+    ///      from: None,
+    ///      to: "0xfd6470334498a1f26db0c5915b026670499b2632".parse()?,
+    ///      gas: None,
+    ///      gas_price: None,
+    ///      value: None,
+    ///      data: Some("0xd800df5c".to_owned()),
+    ///  };
+    ///  assert_eq!(provider.call(tx, None)?, "0x00000000000000000000000000000000000000000000000000000000000003e8".to_owned());
     ///  Ok(())
     ///}
     ///```
-    pub fn get_transaction_by_hash(
-        &self,
-        txhash: &str,
-    ) -> Result<Option<Transaction>, Box<dyn Error>> {
-        match BLOCKHASH_REGEX.is_match(txhash) {
-            true => {
-                let mut payload = String::new();
-                match write!(payload, "{{\"method\":\"eth_getTransactionByHash\",\"params\":[\"{txhash}\"],\"id\":1,\"jsonrpc\":\"2.0\"}}") {
-                    Ok(_) => (),
-                    Err(err) => return Err(err.into())
-                }
-
-                let json: TxRPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.into()),
-                    None => Ok(json.result),
-                }
-            }
-            false => Err("Invalid txhash".into()),
+    pub fn call(&self, tx: CallInput, block: Option<BlockId>) -> Result<String, Box<dyn Error>> {
+        let result: Option<String> =
+            self.request("eth_call", json!([tx, block.unwrap_or_default()]))?;
+        match result {
+            Some(data) => Ok(data),
+            None => Err("No data returned".into()),
         }
     }
 
-    ///The `get_transaction_by_block_hash_and_index()` function takes a block hash and transaction index and attempts to return a deserialized transaction as `Ok(Some(Transaction))`. If no such transaction exists on the index, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors.
+    ///The `call_with_overrides()` function takes a call input struct, an optional block id and a state override set, and
+    ///attempts to return the return data of the call against the overridden state as `Ok(String)`. Returns an `Err()`
+    ///on JSON-RPC errors.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
+    ///use ethrs::provider::{AccountOverride, CallInput, Provider, StateOverride};
+    ///use ethrs::types::H256;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_transaction_by_block_hash_and_index("0xc49f9290e07575fbcf91a9349721edaff45a6600add9281e48a2948f01c1d8d4", U256::from(1))? // fetches the block by hash and returns the tx at index 1
-    ///      .is_some());
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let to = "0xfd6470334498a1f26db0c5915b026670499b2632".parse()?;
+    ///  let mut overrides = StateOverride::new();
+    ///  overrides.insert(to, AccountOverride {
+    ///      state_diff: Some([(H256::ZERO, H256::from([42u8; 32]))].into()),
+    ///      ..Default::default()
+    ///  });
+    ///  let tx = CallInput {
+    ///      from: None,
+    ///      to,
+    ///      gas: None,
+    ///      gas_price: None,
+    ///      value: None,
+    ///      data: Some("0xd800df5c".to_owned()),
+    ///  };
+    ///  assert!(provider.call_with_overrides(tx, None, &overrides)?.starts_with("0x"));
     ///  Ok(())
     ///}
     ///```
-    pub fn get_transaction_by_block_hash_and_index(
+    pub fn call_with_overrides(
         &self,
-        block_hash: &str,
-        idx: U256,
-    ) -> Result<Option<Transaction>, Box<dyn Error>> {
-        match BLOCKHASH_REGEX.is_match(block_hash) {
-            true => {
-                let mut payload = String::new();
-                match write!(payload, "{{\"method\":\"eth_getTransactionByBlockHashAndIndex\",\"params\":[\"{block_hash}\",\"0x{idx:x}\"],\"id\":1,\"jsonrpc\":\"2.0\"}}") {
-                    Ok(_) => (),
-                    Err(err) => return Err(err.into())
-                }
-
-                let json: TxRPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.into()),
-                    None => Ok(json.result),
-                }
-            }
-            false => Err("Invalid blockhash".into()),
-        }
+        tx: CallInput,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<String, Box<dyn Error>> {
+        let result: Option<String> = self.request(
+            "eth_call",
+            json!([tx, block.unwrap_or_default(), overrides]),
+        )?;
+        result.ok_or_else(|| "No data returned".into())
     }
 
-    ///The `get_transaction_by_block_number_and_index()` function takes a block number and transaction index and attempts to return a deserialized transaction as `Ok(Some(Transaction))`. If no such transaction exists on the index, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors.
+    ///The `estimate_gas()` function takes a call input struct and an optional block id (defaulting to the latest block), and attempts to return the gas the node estimates it needs as `Ok(u128)`. Returns an `Err()` on JSON-RPC errors, e.g. if the call reverts.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
-    ///use ethrs::types::U256;
+    ///use ethrs::provider::{Provider, CallInput};
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_transaction_by_block_number_and_index(U256::from(2893800), U256::from(1))? // fetches the block by number and returns the tx at index 1
-    ///      .is_some());
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let tx = CallInput {
+    ///      from: None,
+    ///      to: "0xfd6470334498a1f26db0c5915b026670499b2632".parse()?,
+    ///      gas: None,
+    ///      gas_price: None,
+    ///      value: None,
+    ///      data: Some("0xd800df5c".to_owned()),
+    ///  };
+    ///  assert!(provider.estimate_gas(tx, None)? >= 21000);
     ///  Ok(())
     ///}
     ///```
-    pub fn get_transaction_by_block_number_and_index(
+    pub fn estimate_gas(
         &self,
-        block_number: U256,
-        idx: U256,
-    ) -> Result<Option<Transaction>, Box<dyn Error>> {
-        let mut payload = String::new();
-        match write!(payload, "{{\"method\":\"eth_getTransactionByBlockNumberAndIndex\",\"params\":[\"0x{block_number:x}\",\"0x{idx:x}\"],\"id\":1,\"jsonrpc\":\"2.0\"}}") {
-            Ok(_) => (),
-            Err(err) => return Err(err.into())
-        }
-
-        let json: TxRPCResponse = self
-            .client
-            .post(&self.url)
-            .body(payload.clone())
-            .headers(self.headers.clone())
-            .send()?
-            .json()?;
-
-        match json.error {
-            Some(err) => Err(err.into()),
-            None => Ok(json.result),
-        }
+        tx: CallInput,
+        block: Option<BlockId>,
+    ) -> Result<u128, Box<dyn Error>> {
+        let result: String = match block {
+            Some(block) => self.request("eth_estimateGas", json!([tx, block]))?,
+            // some nodes reject the block parameter, so it is only sent when set
+            None => self.request("eth_estimateGas", json!([tx]))?,
+        };
+        parse_quantity(&result)
     }
 
-    ///The `get_transaction_receipt()` function takes transaction hash and attempts to return a deserialized transaction receipt as `Ok(Some(TransactionReceipt))`. If no such transaction exists, returns `Ok(None)` and returns an `Err()` on JSON-RPC errors.
+    ///The `estimate_gas_with_overrides()` function takes a call input struct, an optional block id and a state override set, and attempts to return the gas the node estimates the call needs against the overridden state as `Ok(u128)`, e.g. pretending a token allowance is already approved. Returns an `Err()` on JSON-RPC errors.
     ///## Example
     ///```rust
-    ///use ethrs::provider::Provider;
+    ///use ethrs::provider::{AccountOverride, CallInput, Provider, StateOverride};
     ///use ethrs::types::U256;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///    assert!(provider
-    ///      .get_transaction_receipt("0x71d6059608006e73a233978ee092e7a2066b2556bc4a31dfe9be1f23328ce36a")?.is_some());
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let from = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    ///  let mut overrides = StateOverride::new();
+    ///  overrides.insert(from, AccountOverride {
+    ///      balance: Some(U256::exp10(24)),
+    ///      ..Default::default()
+    ///  });
+    ///  let tx = CallInput {
+    ///      from: Some(from),
+    ///      to: "0xfd6470334498a1f26db0c5915b026670499b2632".parse()?,
+    ///      gas: None,
+    ///      gas_price: None,
+    ///      value: Some(U256::exp10(21)),
+    ///      data: None,
+    ///  };
+    ///  assert!(provider.estimate_gas_with_overrides(tx, None, &overrides)? >= 21000);
     ///  Ok(())
     ///}
     ///```
-    pub fn get_transaction_receipt(
+    pub fn estimate_gas_with_overrides(
         &self,
-        txhash: &str,
-    ) -> Result<Option<TransactionReceipt>, Box<dyn Error>> {
-        match BLOCKHASH_REGEX.is_match(txhash) {
-            true => {
-                let mut payload = String::new();
-                match write!(payload, "{{\"method\":\"eth_getTransactionReceipt\",\"params\":[\"{txhash}\"],\"id\":1,\"jsonrpc\":\"2.0\"}}") {
-                    Ok(_) => (),
-                    Err(err) => return Err(err.into())
-                }
+        tx: CallInput,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<u128, Box<dyn Error>> {
+        let result: String = self.request(
+            "eth_estimateGas",
+            json!([tx, block.unwrap_or_default(), overrides]),
+        )?;
+        parse_quantity(&result)
+    }
 
-                let json: TxReceiptRPCResponse = self
-                    .client
-                    .post(&self.url)
-                    .body(payload.clone())
-                    .headers(self.headers.clone())
-                    .send()?
-                    .json()?;
-
-                match json.error {
-                    Some(err) => Err(err.into()),
-                    None => Ok(json.result),
-                }
-            }
-            false => Err("Invalid txhash".into()),
-        }
+    ///The `simulate()` function takes a simulation input and an optional base block id (defaulting to the latest block),
+    ///and attempts to return the blocks simulated by `eth_simulateV1` on top of the base block, with the result of every
+    ///call, as `Ok(Vec<SimulatedBlock>)`. Unlike chained `eth_call`s, every call sees the state changes of the previous
+    ///ones. Returns an `Err()` on JSON-RPC errors, e.g. if the node does not support `eth_simulateV1`. Failed calls do
+    ///not make the simulation fail, see `SimulatedCall::error`.
+    ///## Example
+    ///```rust,no_run
+    ///use ethrs::provider::{CallInput, Provider, SimulatedBlockCalls, SimulationInput};
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let provider = Provider::new("http://localhost:8545");
+    ///  let from = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    ///  let counter = "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?;
+    ///  let increment = CallInput::to(counter).from(from).data(vec![0xd0, 0x9d, 0xe0, 0x8a]);
+    ///  let input = SimulationInput {
+    ///      block_state_calls: vec![SimulatedBlockCalls {
+    ///          calls: vec![increment.clone(), increment],
+    ///          ..Default::default()
+    ///      }],
+    ///      ..Default::default()
+    ///  };
+    ///  for call in &provider.simulate(&input, None)?[0].calls {
+    ///      println!("{:?}: {} gas, {} logs", call.status, call.gas_used, call.logs.len());
+    ///  }
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn simulate(
+        &self,
+        input: &SimulationInput,
+        block: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Box<dyn Error>> {
+        self.request("eth_simulateV1", json!([input, block.unwrap_or_default()]))
     }
 
-    ///The `send_transaction()` function takes a transaction input struct, sends it and attempts to return a deserialized transaction hash as `Ok(String)`. If no such transaction exists, returns `Ok(0x0...)` and returns an `Err()` on JSON-RPC errors.
+    ///The `create_access_list()` function takes a call input struct and an optional block id, and attempts to return the access list generated by the node for the call, along with the gas used with that access list, as `Ok(AccessListResult)`. Returns an `Err()` on JSON-RPC errors.
     ///## Example
     ///```rust
-    ///use ethrs::provider::{Provider, TransactionInput};
-    ///use ethrs::types::U256;
+    ///use ethrs::provider::{Provider, CallInput};
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
-    ///  let tx = TransactionInput {
-    ///      from: "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_owned(),
-    ///      to: Some("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_owned()),
-    ///      gas: Some(U256::from(21000)),
-    ///      gas_price: Some(U256::from(1)),
-    ///      value: Some(U256::from(1)),
-    ///      data: Some("0xFF".to_owned()),
-    ///      nonce: Some(U256::from(0)),
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+    ///  let tx = CallInput {
+    ///      from: None,
+    ///      to: "0xfd6470334498a1f26db0c5915b026670499b2632".parse()?,
+    ///      gas: None,
+    ///      gas_price: None,
+    ///      value: None,
+    ///      data: Some("0xd800df5c".to_owned()),
     ///  };
-    ///  // the RPC call itself will fail because the account is not unlocked
-    ///  assert!(provider.send_transaction(tx).is_err());
+    ///  let result = provider.create_access_list(tx, None)?;
+    ///  assert!(result.error.is_none());
     ///  Ok(())
     ///}
     ///```
-    pub fn send_transaction(&self, tx: TransactionInput) -> Result<String, Box<dyn Error>> {
-        let mut payload = String::new();
-
-        let tx_json = serde_json::to_string(&tx)?;
-
-        match write!(payload, "{{\"method\":\"eth_sendTransaction\",\"params\":[{tx_json}],\"id\":1,\"jsonrpc\":\"2.0\"}}") {
-            Ok(_) => (),
-            Err(err) => return Err(err.into())
-        }
-
-        let json: RPCResponse = self
-            .client
-            .post(&self.url)
-            .body(payload.clone())
-            .headers(self.headers.clone())
-            .send()?
-            .json()?;
-
-        match json.error {
-            Some(err) => Err(err.message.into()),
-            None => match json.result {
-                Some(hash) => Ok(hash),
-                None => Err("No txhash returned".into()),
-            },
-        }
+    pub fn create_access_list(
+        &self,
+        tx: CallInput,
+        block: Option<BlockId>,
+    ) -> Result<AccessListResult, Box<dyn Error>> {
+        self.request(
+            "eth_createAccessList",
+            json!([tx, block.unwrap_or_default()]),
+        )
     }
 
-    ///The `call()` function takes a call input struct, sends it and attempts to return deserialized return data as `Ok(String)`. If no data is returned or a transaction is sent to an EOA, returns `Ok(0x0...)` and returns an `Err()` on JSON-RPC errors.
+    ///The `call_decoded()` function takes a call input struct, the ABI output types of the called function and an optional block id, and attempts to decode the return data into a Rust value as `Ok(T)`. A single output decodes into `T` directly and several outputs decode into a tuple. Returns an `Err()` on JSON-RPC errors and when the return data does not match the output types.
     ///## Example
     ///```rust
+    ///use ethrs::abi::ParamType;
     ///use ethrs::provider::{Provider, CallInput};
-    ///use ethrs::types::U256;
     ///use std::error::Error;
     ///
     ///fn main() -> Result<(), Box<dyn Error>> {
     ///  let provider = Provider::new("https://rpc.sepolia.org");
+    ///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
     ///  let tx = CallInput {
     ///      from: None,
-    ///      to: "0xfd6470334498a1f26db0c5915b026670499b2632".to_owned(),
+    ///      to: "0xfd6470334498a1f26db0c5915b026670499b2632".parse()?,
     ///      gas: None,
     ///      gas_price: None,
     ///      value: None,
     ///      data: Some("0xd800df5c".to_owned()),
     ///  };
-    ///  assert_eq!(provider.call(tx, None, None)?, "0x00000000000000000000000000000000000000000000000000000000000003e8".to_owned());
+    ///  let value: u64 = provider.call_decoded(tx, &[ParamType::Uint(256)], None)?;
+    ///  assert_eq!(value, 1000);
     ///  Ok(())
     ///}
     ///```
-    pub fn call(
+    pub fn call_decoded<T: Detokenize>(
         &self,
         tx: CallInput,
-        block_param: Option<DefaultBlockParam>,
-        block_number: Option<u128>,
-    ) -> Result<String, Box<dyn Error>> {
-        let mut payload = String::new();
-
-        let tx_json = serde_json::to_string(&tx)?;
-
-        payload.push_str("{\"method\":\"eth_call\",\"params\":[");
-        payload.push_str(&tx_json);
-        payload.push_str(",\"");
-        match block_param {
-            Some(DefaultBlockParam::EARLIEST) => payload.push_str("earliest"),
-            Some(DefaultBlockParam::FINALIZED) => payload.push_str("finalized"),
-            Some(DefaultBlockParam::SAFE) => payload.push_str("safe"),
-            Some(DefaultBlockParam::LATEST) => payload.push_str("latest"),
-            Some(DefaultBlockParam::PENDING) => payload.push_str("pending"),
-            None => match block_number {
-                Some(block) => payload.push_str(&format!("0x{block:x}")),
-                None => payload.push_str("latest"),
-            },
-        }
-        payload.push_str("\"],\"id\":1,\"jsonrpc\":\"2.0\"}");
-
-        let json: RPCResponse = self
-            .client
-            .post(&self.url)
-            .body(payload.clone())
-            .headers(self.headers.clone())
-            .send()?
-            .json()?;
-
-        match json.error {
-            Some(err) => Err(err.message.into()),
-            None => match json.result {
-                Some(data) => Ok(data),
-                None => Err("No data returned".into()),
-            },
-        }
+        output_types: &[ParamType],
+        block: Option<BlockId>,
+    ) -> Result<T, Box<dyn Error>> {
+        let data: Bytes = self.call(tx, block)?.parse()?;
+        T::from_tokens(decode(output_types, &data)?)
     }
 }
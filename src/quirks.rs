@@ -0,0 +1,194 @@
+//!The quirks module provides the `QuirkProfile` of each major execution client, which a `Provider` uses to smooth over
+//!the known deviations of its node from the other clients, so that the same code runs against any of them.
+use serde_json::json;
+
+use std::error::Error;
+use std::fmt;
+
+///The `NodeClient` enum lists the execution clients with a known `QuirkProfile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeClient {
+    Geth,
+    Erigon,
+    Nethermind,
+    Besu,
+    Reth,
+}
+
+impl NodeClient {
+    ///The `NodeClient::detect()` associated function takes a client version, as returned by `web3_clientVersion`, e.g.
+    ///`Geth/v1.13.14-stable/linux-amd64/go1.21.7`, and returns the client it names, or `None` for other clients.
+    ///## Example
+    ///```rust
+    ///use ethrs::quirks::NodeClient;
+    ///
+    ///assert_eq!(NodeClient::detect("erigon/2.59.3/linux-amd64/go1.21.5"), Some(NodeClient::Erigon));
+    ///assert_eq!(NodeClient::detect("anvil/v0.2.0"), None);
+    ///```
+    pub fn detect(client_version: &str) -> Option<NodeClient> {
+        let name = client_version.split('/').next()?.trim().to_lowercase();
+        match name.as_str() {
+            "geth" => Some(NodeClient::Geth),
+            "erigon" => Some(NodeClient::Erigon),
+            "nethermind" => Some(NodeClient::Nethermind),
+            "besu" => Some(NodeClient::Besu),
+            "reth" => Some(NodeClient::Reth),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for NodeClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+///The fields holding lists, which clients returning `null` for absent fields send as `null` instead of `[]`.
+const LIST_FIELDS: [&str; 7] = [
+    "transactions",
+    "uncles",
+    "withdrawals",
+    "logs",
+    "topics",
+    "accessList",
+    "blobVersionedHashes",
+];
+
+///The messages clients reporting revert reasons in the error data send instead of Geth's `execution reverted`.
+const GENERIC_REVERT_MESSAGES: [&str; 3] =
+    ["vm execution error.", "execution reverted", "reverted"];
+
+///The `QuirkProfile` struct describes the known deviations of a client, which a `Provider` it is set on with
+///`Provider::with_quirks()` corrects so that responses look the same whatever the client:
+///- `null` fields are removed as if the client had omitted them, as Geth does, and `null` lists are made empty.
+///- Revert reasons sent in the error data under a generic message are moved into an `execution reverted: <reason>`
+///  message, as Geth sends them.
+///- Requests for methods the client does not implement fail without being sent, and "method not found" errors of any
+///  client read `Method <method> is not supported by <client>`.
+///
+///`Provider::connect()` and `Provider::preflight()` set the profile of the detected client, unless one was already set.
+///## Example
+///```rust
+///use ethrs::provider::Provider;
+///use ethrs::quirks::{NodeClient, QuirkProfile};
+///
+///let profile = QuirkProfile::for_client(NodeClient::Besu).with_unsupported_method("eth_getBlockReceipts");
+///let provider = Provider::new("https://rpc.sepolia.org").with_quirks(profile);
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuirkProfile {
+    pub client: NodeClient,
+    ///Whether the client returns `null` for absent fields instead of omitting them.
+    pub null_fields: bool,
+    ///Whether the client sends revert reasons in the error data under a generic message.
+    pub revert_reason_in_data: bool,
+    ///The methods the client does not implement.
+    pub unsupported_methods: Vec<String>,
+}
+
+impl QuirkProfile {
+    ///The `QuirkProfile::for_client()` associated function takes a client and returns its known quirks.
+    pub fn for_client(client: NodeClient) -> QuirkProfile {
+        let (null_fields, revert_reason_in_data, unsupported_methods): (_, _, &[&str]) =
+            match client {
+                NodeClient::Geth | NodeClient::Reth => (false, false, &[]),
+                NodeClient::Erigon => (true, false, &[]),
+                NodeClient::Nethermind => (true, true, &["txpool_contentFrom"]),
+                // Besu exposes its transaction pool through txpool_besu* methods instead
+                NodeClient::Besu => (
+                    true,
+                    true,
+                    &[
+                        "txpool_content",
+                        "txpool_contentFrom",
+                        "txpool_inspect",
+                        "txpool_status",
+                    ],
+                ),
+            };
+        QuirkProfile {
+            client,
+            null_fields,
+            revert_reason_in_data,
+            unsupported_methods: unsupported_methods
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+        }
+    }
+
+    ///Adds a method the client does not implement, e.g. one disabled on the node.
+    pub fn with_unsupported_method(mut self, method: &str) -> QuirkProfile {
+        self.unsupported_methods.push(method.to_owned());
+        self
+    }
+
+    ///Returns an `Err()` if the client does not implement the method.
+    pub(crate) fn check_method(&self, method: &str) -> Result<(), Box<dyn Error>> {
+        match self
+            .unsupported_methods
+            .iter()
+            .any(|unsupported| unsupported == method)
+        {
+            true => Err(self.unsupported(method).into()),
+            false => Ok(()),
+        }
+    }
+
+    fn unsupported(&self, method: &str) -> String {
+        format!("Method {method} is not supported by {}", self.client)
+    }
+
+    ///Rewrites the error of a response as Geth reports it.
+    pub(crate) fn normalize_error(&self, method: &str, response: &mut serde_json::Value) {
+        let error = &mut response["error"];
+        if !error.is_object() {
+            return;
+        }
+        if error["code"] == -32601 {
+            error["message"] = json!(self.unsupported(method));
+            return;
+        }
+        let generic = error["message"].as_str().map_or(false, |message| {
+            GENERIC_REVERT_MESSAGES.contains(&message.to_lowercase().as_str())
+        });
+        if !self.revert_reason_in_data || !generic {
+            return;
+        }
+        // revert data stays in the data, as Geth sends it, and readable reasons move to the message
+        error["message"] = match error["data"].as_str() {
+            Some(data) if !data.starts_with("0x") => {
+                let reason = data
+                    .strip_prefix("revert: ")
+                    .or_else(|| data.strip_prefix("Reverted "))
+                    .unwrap_or(data);
+                json!(format!("execution reverted: {reason}"))
+            }
+            _ => json!("execution reverted"),
+        };
+    }
+
+    ///Rewrites the fields of a result as Geth returns them.
+    pub(crate) fn normalize_result(&self, result: &mut serde_json::Value) {
+        if self.null_fields {
+            remove_null_fields(result);
+        }
+    }
+}
+
+fn remove_null_fields(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(object) => {
+            object.retain(|field, value| {
+                if value.is_null() && LIST_FIELDS.contains(&field.as_str()) {
+                    *value = json!([]);
+                }
+                !value.is_null()
+            });
+            object.values_mut().for_each(remove_null_fields);
+        }
+        serde_json::Value::Array(array) => array.iter_mut().for_each(remove_null_fields),
+        _ => {}
+    }
+}
@@ -0,0 +1,135 @@
+//!The quorum module provides the `QuorumProvider`, which sends every request to several endpoints and only returns a
+//!response when enough of them agree on it, protecting against a single malicious or buggy endpoint.
+use crate::provider::{Provider, Transport};
+
+use std::error::Error;
+use std::sync::Arc;
+use std::thread;
+
+///The `QuorumProvider` struct sends every request to all of its endpoints concurrently and returns the response that
+///at least `quorum` of them agree on. Responses agree if their results, or their JSON-RPC errors, are equal, so
+///requests whose result depends on the chain head, e.g. `eth_blockNumber`, may fail to reach a quorum while endpoints
+///are at different heights: prefer pinning such requests to a block.
+///
+///A `QuorumProvider` is a `Transport`: `provider()` returns a `Provider` exposing every JSON-RPC method through it.
+///## Example
+///```rust
+///use ethrs::quorum::QuorumProvider;
+///
+///let quorum = QuorumProvider::new(
+///    &[
+///        "https://rpc.sepolia.org",
+///        "https://ethereum-sepolia-rpc.publicnode.com",
+///        "https://sepolia.drpc.org",
+///    ],
+///    2,
+///);
+///let provider = quorum.provider();
+///if let Ok(chain_id) = provider.chain_id() {
+///    assert_eq!(chain_id, 11155111);
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct QuorumProvider {
+    endpoints: Arc<Vec<Provider>>,
+    quorum: usize,
+}
+
+impl QuorumProvider {
+    ///The `QuorumProvider::new()` associated function takes a list of HTTP(S) JSON-RPC URLs and the number of
+    ///endpoints that must agree on a response, and returns a `QuorumProvider` over them. Panics if the quorum is zero
+    ///or larger than the number of endpoints.
+    pub fn new(urls: &[&str], quorum: usize) -> QuorumProvider {
+        QuorumProvider::from_providers(urls.iter().map(|url| Provider::new(url)).collect(), quorum)
+    }
+
+    ///The `QuorumProvider::from_providers()` associated function takes a list of providers, e.g. built with timeouts
+    ///or authentication, and the number of them that must agree on a response, and returns a `QuorumProvider` over
+    ///them. Panics if the quorum is zero or larger than the number of providers.
+    pub fn from_providers(providers: Vec<Provider>, quorum: usize) -> QuorumProvider {
+        assert!(
+            quorum > 0 && quorum <= providers.len(),
+            "QuorumProvider needs a quorum between 1 and the number of endpoints"
+        );
+        QuorumProvider {
+            endpoints: Arc::new(providers),
+            quorum,
+        }
+    }
+
+    ///The `QuorumProvider::majority()` associated function takes a list of providers and returns a `QuorumProvider`
+    ///requiring more than half of them to agree. Panics if the list is empty.
+    pub fn majority(providers: Vec<Provider>) -> QuorumProvider {
+        let quorum = providers.len() / 2 + 1;
+        QuorumProvider::from_providers(providers, quorum)
+    }
+
+    ///Returns a `Provider` sending its requests through the `QuorumProvider`.
+    pub fn provider(&self) -> Provider {
+        Provider::new(self.endpoints[0].url()).with_transport(self.clone())
+    }
+
+    ///Returns the endpoints, in order.
+    pub fn endpoints(&self) -> &[Provider] {
+        &self.endpoints
+    }
+
+    ///Returns the number of endpoints that must agree on a response.
+    pub fn quorum(&self) -> usize {
+        self.quorum
+    }
+}
+
+impl Transport for QuorumProvider {
+    fn send(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>> {
+        let results: Vec<Result<serde_json::Value, String>> = thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .endpoints
+                .iter()
+                .map(|endpoint| {
+                    scope.spawn(move || endpoint.send(payload).map_err(|err| err.to_string()))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err("Endpoint thread panicked".to_owned()))
+                })
+                .collect()
+        });
+
+        // responses are grouped by result and error only, as ids and other members may differ between endpoints
+        let mut groups: Vec<(&serde_json::Value, usize)> = vec![];
+        let mut errors = vec![];
+        for (endpoint, result) in self.endpoints.iter().zip(&results) {
+            match result {
+                Ok(response) => {
+                    let agrees = |other: &&serde_json::Value| {
+                        other.get("result") == response.get("result")
+                            && other.get("error") == response.get("error")
+                    };
+                    match groups.iter_mut().find(|(other, _)| agrees(other)) {
+                        Some((_, votes)) => *votes += 1,
+                        None => groups.push((response, 1)),
+                    }
+                }
+                Err(err) => errors.push(format!("{}: {err}", endpoint.url())),
+            }
+        }
+        if let Some((response, _)) = groups.iter().find(|(_, votes)| *votes >= self.quorum) {
+            return Ok((*response).clone());
+        }
+        let votes = groups.iter().map(|(_, votes)| *votes).max().unwrap_or(0);
+        let mut message = format!(
+            "No quorum: {votes} of {} endpoints agreed, {} required",
+            self.endpoints.len(),
+            self.quorum
+        );
+        if !errors.is_empty() {
+            message.push_str(&format!(" ({})", errors.join(", ")));
+        }
+        Err(message.into())
+    }
+}
@@ -0,0 +1,573 @@
+//!The rlp module implements the Recursive Length Prefix encoding the execution layer hashes and signs its data
+//!structures with. Raw items are handled with `RlpItem`, and typed values with the `Encodable` and `Decodable` traits,
+//!which are implemented for integers, addresses, hashes, byte strings, lists, block headers, logs and access lists.
+use crate::provider::{AccessListItem, Log};
+use crate::types::{Address, Bytes, H256, U256};
+use crate::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use std::error::Error;
+
+///The maximum number of nested lists `RlpItem::decode()` accepts, which bounds its recursion on untrusted input.
+pub const MAX_DEPTH: usize = 256;
+
+///The `RlpItem` enum is a decoded RLP item, i.e. a byte string or a list of items.
+///## Example
+///```rust
+///use ethrs::rlp::RlpItem;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let item = RlpItem::List(vec![RlpItem::String(b"cat".to_vec()), RlpItem::String(b"dog".to_vec())]);
+///  let encoded = item.encode();
+///  assert_eq!(hex::encode(&encoded), "c88363617483646f67");
+///  assert_eq!(RlpItem::decode(&encoded)?, item);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RlpItem {
+    String(Vec<u8>),
+    List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+    ///Returns the RLP encoding of the item.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            RlpItem::String(data) => encode_string(data),
+            RlpItem::List(items) => {
+                encode_list(&items.iter().map(RlpItem::encode).collect::<Vec<_>>())
+            }
+        }
+    }
+
+    ///The `RlpItem::decode()` associated function takes RLP-encoded data and attempts to return the item it encodes as
+    ///`Ok(RlpItem)`. Returns an `Err()` if the data is not a single, canonically encoded item, or if its lists are
+    ///nested deeper than `MAX_DEPTH`.
+    pub fn decode(data: &[u8]) -> Result<RlpItem, Box<dyn Error>> {
+        RlpItem::decode_nested(data, 0)
+    }
+
+    fn decode_nested(data: &[u8], depth: usize) -> Result<RlpItem, Box<dyn Error>> {
+        match decode_item(data)? {
+            (false, payload) => Ok(RlpItem::String(payload.to_vec())),
+            (true, _) if depth >= MAX_DEPTH => Err("RLP nesting too deep".into()),
+            (true, _) => Ok(RlpItem::List(
+                decode_list(data)?
+                    .into_iter()
+                    .map(|item| RlpItem::decode_nested(item, depth + 1))
+                    .collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+
+    ///Returns the bytes of a string item, or an `Err()` for a list.
+    pub fn as_bytes(&self) -> Result<&[u8], Box<dyn Error>> {
+        match self {
+            RlpItem::String(data) => Ok(data),
+            RlpItem::List(_) => Err("Expected an RLP string".into()),
+        }
+    }
+
+    ///Returns the items of a list item, or an `Err()` for a string.
+    pub fn as_list(&self) -> Result<&[RlpItem], Box<dyn Error>> {
+        match self {
+            RlpItem::List(items) => Ok(items),
+            RlpItem::String(_) => Err("Expected an RLP list".into()),
+        }
+    }
+}
+
+///The `Encodable` trait converts values into RLP items.
+pub trait Encodable {
+    ///Converts the value into an RLP item.
+    fn to_rlp(&self) -> RlpItem;
+}
+
+///The `Decodable` trait converts RLP items into values.
+pub trait Decodable: Sized {
+    ///Attempts to convert an RLP item into the value, returning an `Err()` if it does not encode one.
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>>;
+}
+
+///The `encode()` function takes an `Encodable` value and returns its RLP encoding.
+///## Example
+///```rust
+///use ethrs::rlp::encode;
+///
+///assert_eq!(encode(&1024u64), [0x82, 0x04, 0x00]);
+///assert_eq!(encode(&vec![1u64, 2, 3]), [0xc3, 0x01, 0x02, 0x03]);
+///```
+pub fn encode<T: Encodable + ?Sized>(value: &T) -> Vec<u8> {
+    value.to_rlp().encode()
+}
+
+///The `decode()` function takes RLP-encoded data and attempts to return the value it encodes as `Ok(T)`. Returns an
+///`Err()` if the data is not canonically encoded or does not encode a `T`.
+///## Example
+///```rust
+///use ethrs::rlp::decode;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  assert_eq!(decode::<u64>(&[0x82, 0x04, 0x00])?, 1024);
+///  assert!(decode::<u64>(&[0x82, 0x00, 0x04]).is_err());
+///  Ok(())
+///}
+///```
+pub fn decode<T: Decodable>(data: &[u8]) -> Result<T, Box<dyn Error>> {
+    T::from_rlp(&RlpItem::decode(data)?)
+}
+
+impl Encodable for RlpItem {
+    fn to_rlp(&self) -> RlpItem {
+        self.clone()
+    }
+}
+
+impl Decodable for RlpItem {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        Ok(item.clone())
+    }
+}
+
+// Integers are encoded big-endian without leading zeros, zero being the empty string.
+fn decode_uint(item: &RlpItem, size: usize) -> Result<&[u8], Box<dyn Error>> {
+    let bytes = item.as_bytes()?;
+    if bytes.first() == Some(&0) {
+        return Err("RLP integer has leading zeros".into());
+    }
+    if bytes.len() > size {
+        return Err("RLP integer overflows".into());
+    }
+    Ok(bytes)
+}
+
+macro_rules! impl_rlp_uint {
+    ($($uint:ty),*) => {
+        $(
+            impl Encodable for $uint {
+                fn to_rlp(&self) -> RlpItem {
+                    let bytes = self.to_be_bytes();
+                    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+                    RlpItem::String(bytes[zeros..].to_vec())
+                }
+            }
+
+            impl Decodable for $uint {
+                fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+                    // the length is checked, so the value fits
+                    Ok(decode_uint(item, std::mem::size_of::<$uint>())?
+                        .iter()
+                        .fold(0u128, |value, byte| (value << 8) | *byte as u128) as $uint)
+                }
+            }
+        )*
+    };
+}
+
+impl_rlp_uint!(u8, u16, u32, u64, u128, usize);
+
+impl Encodable for U256 {
+    fn to_rlp(&self) -> RlpItem {
+        let mut bytes = [0u8; 32];
+        self.to_big_endian(&mut bytes);
+        RlpItem::String(bytes[32 - (self.bits() + 7) / 8..].to_vec())
+    }
+}
+
+impl Decodable for U256 {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        Ok(U256::from_big_endian(decode_uint(item, 32)?))
+    }
+}
+
+impl Encodable for bool {
+    fn to_rlp(&self) -> RlpItem {
+        (*self as u8).to_rlp()
+    }
+}
+
+impl Decodable for bool {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        match u8::from_rlp(item)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(format!("Invalid RLP boolean: {value}").into()),
+        }
+    }
+}
+
+impl Encodable for Bytes {
+    fn to_rlp(&self) -> RlpItem {
+        RlpItem::String(self.0.clone())
+    }
+}
+
+impl Decodable for Bytes {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        Ok(Bytes(item.as_bytes()?.to_vec()))
+    }
+}
+
+fn decode_fixed<const N: usize>(item: &RlpItem) -> Result<[u8; N], Box<dyn Error>> {
+    item.as_bytes()?.try_into().map_err(|_| {
+        format!(
+            "Expected an RLP string of {N} bytes, found {}",
+            item.as_bytes().map_or(0, |bytes| bytes.len())
+        )
+        .into()
+    })
+}
+
+impl Encodable for Address {
+    fn to_rlp(&self) -> RlpItem {
+        RlpItem::String(self.0.to_vec())
+    }
+}
+
+impl Decodable for Address {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        Ok(Address(decode_fixed(item)?))
+    }
+}
+
+///A missing address, e.g. the recipient of a contract creation, is encoded as the empty string.
+impl Encodable for Option<Address> {
+    fn to_rlp(&self) -> RlpItem {
+        match self {
+            Some(address) => address.to_rlp(),
+            None => RlpItem::String(vec![]),
+        }
+    }
+}
+
+impl Decodable for Option<Address> {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        match item.as_bytes()?.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(Address::from_rlp(item)?)),
+        }
+    }
+}
+
+impl Encodable for H256 {
+    fn to_rlp(&self) -> RlpItem {
+        RlpItem::String(self.0.to_vec())
+    }
+}
+
+impl Decodable for H256 {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        Ok(H256(decode_fixed(item)?))
+    }
+}
+
+impl<T: Encodable> Encodable for Vec<T> {
+    fn to_rlp(&self) -> RlpItem {
+        RlpItem::List(self.iter().map(Encodable::to_rlp).collect())
+    }
+}
+
+impl<T: Decodable> Decodable for Vec<T> {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        item.as_list()?.iter().map(T::from_rlp).collect()
+    }
+}
+
+///Returns the fields of a list item, checking their number.
+pub(crate) fn fields(item: &RlpItem, expected: usize) -> Result<&[RlpItem], Box<dyn Error>> {
+    let fields = item.as_list()?;
+    match fields.len() == expected {
+        true => Ok(fields),
+        false => Err(format!(
+            "Expected an RLP list of {expected} items, found {}",
+            fields.len()
+        )
+        .into()),
+    }
+}
+
+impl Encodable for AccessListItem {
+    fn to_rlp(&self) -> RlpItem {
+        RlpItem::List(vec![self.address.to_rlp(), self.storage_keys.to_rlp()])
+    }
+}
+
+impl Decodable for AccessListItem {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        let fields = fields(item, 2)?;
+        Ok(AccessListItem {
+            address: Address::from_rlp(&fields[0])?,
+            storage_keys: Vec::from_rlp(&fields[1])?,
+        })
+    }
+}
+
+///Logs are encoded as in receipts, i.e. as their address, topics and data. Data that is not valid hex, which nodes never
+///return, is encoded as empty.
+impl Encodable for Log {
+    fn to_rlp(&self) -> RlpItem {
+        let data = self.data.parse::<Bytes>().unwrap_or_default();
+        RlpItem::List(vec![
+            self.address.to_rlp(),
+            self.topics.to_rlp(),
+            data.to_rlp(),
+        ])
+    }
+}
+
+///The `Header` struct is an execution block header with every field its hash covers, deserializable from the block
+///objects of JSON-RPC responses. The fields added by forks are `None` in blocks before them.
+///## Example
+///```rust
+///use ethrs::provider::Provider;
+///use ethrs::rlp::Header;
+///use serde_json::json;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("https://rpc.sepolia.org");
+///#  let Some(provider) = ethrs::testing::sepolia().provider_or_skip() else { return Ok(()); };
+///  let block: serde_json::Value = provider.request("eth_getBlockByNumber", json!(["latest", false]))?;
+///  let header: Header = serde_json::from_value(block.clone())?;
+///  assert_eq!(header.hash(), block["hash"].as_str().unwrap().parse()?);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Header {
+    pub parent_hash: H256,
+    #[serde(rename = "sha3Uncles")]
+    pub ommers_hash: H256,
+    #[serde(rename = "miner")]
+    pub beneficiary: Address,
+    pub state_root: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Bytes,
+    pub difficulty: U256,
+    #[serde(with = "crate::utils::quantity")]
+    pub number: u64,
+    #[serde(with = "crate::utils::quantity")]
+    pub gas_limit: u64,
+    #[serde(with = "crate::utils::quantity")]
+    pub gas_used: u64,
+    #[serde(with = "crate::utils::quantity")]
+    pub timestamp: u64,
+    pub extra_data: Bytes,
+    pub mix_hash: H256,
+    pub nonce: Bytes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<H256>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub blob_gas_used: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::quantity_opt"
+    )]
+    pub excess_blob_gas: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_beacon_block_root: Option<H256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_hash: Option<H256>,
+}
+
+impl Header {
+    ///Returns the hash of the header, i.e. the hash of the block.
+    pub fn hash(&self) -> H256 {
+        H256(keccak256(&encode(self)))
+    }
+}
+
+impl Encodable for Header {
+    fn to_rlp(&self) -> RlpItem {
+        let mut fields = vec![
+            self.parent_hash.to_rlp(),
+            self.ommers_hash.to_rlp(),
+            self.beneficiary.to_rlp(),
+            self.state_root.to_rlp(),
+            self.transactions_root.to_rlp(),
+            self.receipts_root.to_rlp(),
+            self.logs_bloom.to_rlp(),
+            self.difficulty.to_rlp(),
+            self.number.to_rlp(),
+            self.gas_limit.to_rlp(),
+            self.gas_used.to_rlp(),
+            self.timestamp.to_rlp(),
+            self.extra_data.to_rlp(),
+            self.mix_hash.to_rlp(),
+            self.nonce.to_rlp(),
+        ];
+        // fields added by forks are only encoded from their fork on, in order
+        let forks = [
+            self.base_fee_per_gas.as_ref().map(Encodable::to_rlp),
+            self.withdrawals_root.as_ref().map(Encodable::to_rlp),
+            self.blob_gas_used.as_ref().map(Encodable::to_rlp),
+            self.excess_blob_gas.as_ref().map(Encodable::to_rlp),
+            self.parent_beacon_block_root
+                .as_ref()
+                .map(Encodable::to_rlp),
+            self.requests_hash.as_ref().map(Encodable::to_rlp),
+        ];
+        fields.extend(forks.into_iter().map_while(|field| field));
+        RlpItem::List(fields)
+    }
+}
+
+impl Decodable for Header {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        let fields = item.as_list()?;
+        if !(15..=21).contains(&fields.len()) {
+            return Err(format!(
+                "Expected a block header of 15 to 21 fields, found {}",
+                fields.len()
+            )
+            .into());
+        }
+        let fork = |index: usize| fields.get(index);
+        Ok(Header {
+            parent_hash: H256::from_rlp(&fields[0])?,
+            ommers_hash: H256::from_rlp(&fields[1])?,
+            beneficiary: Address::from_rlp(&fields[2])?,
+            state_root: H256::from_rlp(&fields[3])?,
+            transactions_root: H256::from_rlp(&fields[4])?,
+            receipts_root: H256::from_rlp(&fields[5])?,
+            logs_bloom: Bytes::from_rlp(&fields[6])?,
+            difficulty: U256::from_rlp(&fields[7])?,
+            number: u64::from_rlp(&fields[8])?,
+            gas_limit: u64::from_rlp(&fields[9])?,
+            gas_used: u64::from_rlp(&fields[10])?,
+            timestamp: u64::from_rlp(&fields[11])?,
+            extra_data: Bytes::from_rlp(&fields[12])?,
+            mix_hash: H256::from_rlp(&fields[13])?,
+            nonce: Bytes::from_rlp(&fields[14])?,
+            base_fee_per_gas: fork(15).map(U256::from_rlp).transpose()?,
+            withdrawals_root: fork(16).map(H256::from_rlp).transpose()?,
+            blob_gas_used: fork(17).map(u64::from_rlp).transpose()?,
+            excess_blob_gas: fork(18).map(u64::from_rlp).transpose()?,
+            parent_beacon_block_root: fork(19).map(H256::from_rlp).transpose()?,
+            requests_hash: fork(20).map(H256::from_rlp).transpose()?,
+        })
+    }
+}
+
+// Splits an RLP item into whether it is a list and its payload, checking that it is canonical and spans the whole
+// input.
+fn decode_item(data: &[u8]) -> Result<(bool, &[u8]), Box<dyn Error>> {
+    let (is_list, offset, length) = header(data)?;
+    match offset.checked_add(length) {
+        Some(end) if end == data.len() => Ok((is_list, &data[offset..])),
+        _ => Err("Invalid RLP length".into()),
+    }
+}
+
+// Returns whether the first RLP item of `data` is a list, the length of its prefix and the length of its payload.
+fn header(data: &[u8]) -> Result<(bool, usize, usize), Box<dyn Error>> {
+    let (is_list, offset, length) = match data.first() {
+        None => return Err("Empty RLP item".into()),
+        Some(&prefix) if prefix < 0x80 => return Ok((false, 0, 1)),
+        Some(&prefix) if prefix < 0xb8 => (false, 1, (prefix - 0x80) as usize),
+        Some(&prefix) if prefix < 0xc0 => {
+            let size = (prefix - 0xb7) as usize;
+            (false, 1 + size, read_length(&data[1..], size)?)
+        }
+        Some(&prefix) if prefix < 0xf8 => (true, 1, (prefix - 0xc0) as usize),
+        Some(&prefix) => {
+            let size = (prefix - 0xf7) as usize;
+            (true, 1 + size, read_length(&data[1..], size)?)
+        }
+    };
+    if !is_list && length == 1 && data.get(1).map_or(false, |byte| *byte < 0x80) {
+        return Err("Non-canonical RLP: single byte below 0x80 with a prefix".into());
+    }
+    Ok((is_list, offset, length))
+}
+
+fn read_length(data: &[u8], size: usize) -> Result<usize, Box<dyn Error>> {
+    if size > 8 || data.len() < size {
+        return Err("Invalid RLP length".into());
+    }
+    if data[0] == 0 {
+        return Err("Non-canonical RLP: length with leading zeros".into());
+    }
+    let length = data[..size]
+        .iter()
+        .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+    match length >= 56 {
+        true => Ok(length),
+        false => Err("Non-canonical RLP: long form for a short payload".into()),
+    }
+}
+
+// Returns the length of the first RLP item in `data`, including its prefix.
+fn item_length(data: &[u8]) -> Result<usize, Box<dyn Error>> {
+    let (_, offset, length) = header(data)?;
+    match offset.checked_add(length) {
+        Some(end) if end <= data.len() => Ok(end),
+        _ => Err("Invalid RLP length".into()),
+    }
+}
+
+///The `decode_string()` function takes an RLP-encoded string and attempts to return its bytes as `Ok(&[u8])`. Returns
+///an `Err()` if the data is not a single, canonically encoded string.
+pub fn decode_string(data: &[u8]) -> Result<&[u8], Box<dyn Error>> {
+    match decode_item(data)? {
+        (false, payload) => Ok(payload),
+        (true, _) => Err("Expected an RLP string".into()),
+    }
+}
+
+///The `decode_list()` function takes an RLP-encoded list and attempts to return the encoded items it contains as
+///`Ok(Vec<&[u8]>)`, without decoding them. Returns an `Err()` if the data is not a single, canonically encoded list.
+pub fn decode_list(data: &[u8]) -> Result<Vec<&[u8]>, Box<dyn Error>> {
+    let mut payload = match decode_item(data)? {
+        (true, payload) => payload,
+        (false, _) => return Err("Expected an RLP list".into()),
+    };
+    let mut items = vec![];
+    while !payload.is_empty() {
+        let length = item_length(payload)?;
+        items.push(&payload[..length]);
+        payload = &payload[length..];
+    }
+    Ok(items)
+}
+
+fn encode_length(length: usize, offset: u8) -> Vec<u8> {
+    if length < 56 {
+        return vec![offset + length as u8];
+    }
+    let bytes = length.to_be_bytes();
+    let trimmed = &bytes[bytes.iter().position(|byte| *byte != 0).unwrap_or(0)..];
+    let mut prefix = vec![offset + 55 + trimmed.len() as u8];
+    prefix.extend_from_slice(trimmed);
+    prefix
+}
+
+///The `encode_string()` function takes bytes and returns their RLP encoding as a string.
+pub fn encode_string(data: &[u8]) -> Vec<u8> {
+    if data.len() == 1 && data[0] < 0x80 {
+        return data.to_vec();
+    }
+    let mut encoded = encode_length(data.len(), 0x80);
+    encoded.extend_from_slice(data);
+    encoded
+}
+
+///The `encode_list()` function takes RLP-encoded items and returns the RLP encoding of the list of them.
+pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut encoded = encode_length(payload.len(), 0xc0);
+    encoded.extend(payload);
+    encoded
+}
@@ -0,0 +1,187 @@
+//!The signature module provides the `Signature` type of the ECDSA signatures transactions and messages are signed with,
+//!which recovers and verifies their signer, as the `ecrecover` precompile does, and handles the `v` of EIP-155.
+use crate::provider::Transaction;
+use crate::types::{parse_u256, public_key_to_address, Address, FromHexError, H256, U256};
+use k256::ecdsa::{RecoveryId, VerifyingKey};
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+///Half the order of the secp256k1 curve, above which EIP-2 rejects the `s` of transaction signatures.
+const HALF_ORDER: [u64; 4] = [
+    0xdfe92f46681b20a0,
+    0x5d576e7357a4501d,
+    0xffffffffffffffff,
+    0x7fffffffffffffff,
+];
+
+///The `Signature` struct is a secp256k1 ECDSA signature. `v` is kept as found in the signed payload, i.e. `0` or `1` in
+///typed transactions, `27` or `28` in messages and pre-EIP-155 transactions, and `35 + 2 * chainId` or one more in
+///EIP-155 transactions. Signatures are parsed from and displayed as 65-byte hexstrings, i.e. `r || s || v` with a `v` of
+///`27` or `28`, as returned by `personal_sign`.
+///## Example
+///```rust
+///use ethrs::signature::Signature;
+///use ethrs::types::U256;
+///
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let signature = Signature { r: U256::one(), s: U256::one(), v: 38 };
+///  assert_eq!(signature.y_parity()?, 1);
+///  assert_eq!(signature.chain_id(), Some(1));
+///  assert_eq!(signature.with_chain_id(None)?.v, 28);
+///  assert!(Signature { v: 29, ..signature }.y_parity().is_err());
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Signature {
+    pub r: U256,
+    pub s: U256,
+    pub v: u64,
+}
+
+impl Signature {
+    ///Returns the parity of the y coordinate of the signature point, which `v` encodes, or an `Err()` if `v` is not
+    ///`0`, `1`, `27`, `28` or at least `35`.
+    pub fn y_parity(&self) -> Result<u8, Box<dyn Error>> {
+        match self.v {
+            0 | 1 => Ok(self.v as u8),
+            // 27 + parity, or 35 + 2 * chainId + parity
+            27 | 28 | 35.. => Ok(1 - (self.v % 2) as u8),
+            v => Err(format!("Invalid signature v: {v}").into()),
+        }
+    }
+
+    ///Returns the chain id encoded in `v` by EIP-155, or `None` if `v` does not encode one.
+    pub fn chain_id(&self) -> Option<u64> {
+        (self.v >= 35).then(|| (self.v - 35) / 2)
+    }
+
+    ///Returns the signature with `v` encoding the chain id as specified by EIP-155, or `27` or `28` without a chain id.
+    ///Returns an `Err()` if `v` is invalid, or if the chain id is too large for `v` to fit in 64 bits.
+    pub fn with_chain_id(mut self, chain_id: Option<u64>) -> Result<Signature, Box<dyn Error>> {
+        let y_parity = u64::from(self.y_parity()?);
+        self.v = match chain_id {
+            Some(chain_id) => chain_id
+                .checked_mul(2)
+                .and_then(|v| v.checked_add(35 + y_parity))
+                .ok_or_else(|| format!("Chain id {chain_id} is too large for EIP-155"))?,
+            None => 27 + y_parity,
+        };
+        Ok(self)
+    }
+
+    ///Returns whether `s` is in the lower half of the curve order, as EIP-2 requires of transaction signatures.
+    pub fn is_low_s(&self) -> bool {
+        self.s <= U256(HALF_ORDER)
+    }
+
+    ///The `recover()` function takes the hash of a message and attempts to return the address of the key that signed
+    ///it as `Ok(Address)`. Signatures with a high `s` are accepted, as by the `ecrecover` precompile. Returns an `Err()`
+    ///if the signature is invalid, e.g. if `r` or `s` is out of range or `v` is invalid, or if no key is recovered from
+    ///it.
+    ///## Example
+    ///```rust
+    ///use ethrs::utils::hash_message;
+    ///use ethrs::wallet::Wallet;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let wallet: Wallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse()?;
+    ///  let signature = wallet.sign_message(b"hello");
+    ///  assert_eq!(signature.recover(hash_message(b"hello"))?, wallet.address());
+    ///  assert!(signature.verify(hash_message(b"hello"), wallet.address()));
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn recover(&self, message_hash: H256) -> Result<Address, Box<dyn Error>> {
+        let y_parity = self.y_parity()?;
+        let mut bytes = [0u8; 64];
+        self.r.to_big_endian(&mut bytes[..32]);
+        self.s.to_big_endian(&mut bytes[32..]);
+        let signature =
+            k256::ecdsa::Signature::from_slice(&bytes).map_err(|_| "Invalid signature")?;
+        // the signature with the opposite s is that of the opposite point
+        let (signature, flipped) = match signature.normalize_s() {
+            Some(normalized) => (normalized, true),
+            None => (signature, false),
+        };
+        let recovery_id = RecoveryId::new((y_parity == 1) ^ flipped, false);
+        let key = VerifyingKey::recover_from_prehash(&message_hash.0, &signature, recovery_id)
+            .map_err(|_| "Could not recover the signer of the signature")?;
+        public_key_to_address(key.to_encoded_point(false).as_bytes())
+    }
+
+    ///Returns whether the message hash was signed by the address.
+    pub fn verify(&self, message_hash: H256, address: Address) -> bool {
+        self.recover(message_hash)
+            .map_or(false, |signer| signer == address)
+    }
+
+    ///Returns the signature as 65 bytes, i.e. `r || s || v` with a `v` of `27` or `28`. An invalid `v`, which is below
+    ///`35`, is kept as is.
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        self.r.to_big_endian(&mut bytes[..32]);
+        self.s.to_big_endian(&mut bytes[32..64]);
+        bytes[64] = self
+            .y_parity()
+            .map_or(self.v as u8, |y_parity| 27 + y_parity);
+        bytes
+    }
+
+    ///The `Signature::from_bytes()` associated function takes a 65-byte signature, i.e. `r || s || v`, and attempts to
+    ///return it as `Ok(Signature)`. Returns an `Err()` if it is not 65 bytes long, or if `v` is not `0`, `1`, `27` or
+    ///`28`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Signature, Box<dyn Error>> {
+        if bytes.len() != 65 {
+            return Err(
+                format!("Expected a 65-byte signature, found {} bytes", bytes.len()).into(),
+            );
+        }
+        match bytes[64] {
+            0 | 1 | 27 | 28 => Ok(Signature {
+                r: U256::from_big_endian(&bytes[..32]),
+                s: U256::from_big_endian(&bytes[32..64]),
+                v: u64::from(bytes[64]),
+            }),
+            v => Err(format!("Invalid signature v: {v}").into()),
+        }
+    }
+}
+
+impl FromStr for Signature {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))
+            .map_err(|_| FromHexError::InvalidCharacter)?;
+        Signature::from_bytes(&bytes)
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.to_bytes()))
+    }
+}
+
+///Reads the signature of a transaction returned by the node.
+impl TryFrom<&Transaction> for Signature {
+    type Error = Box<dyn Error>;
+
+    fn try_from(transaction: &Transaction) -> Result<Self, Self::Error> {
+        let v = parse_u256(&transaction.v)?;
+        if v > U256::from(u64::MAX) {
+            return Err(format!("Invalid signature v: {}", transaction.v).into());
+        }
+        Ok(Signature {
+            r: parse_u256(&transaction.r)?,
+            s: parse_u256(&transaction.s)?,
+            v: v.as_u64(),
+        })
+    }
+}
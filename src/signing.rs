@@ -0,0 +1,113 @@
+//!The signing module provides the `RequestSigner` trait, which computes per-request signature headers as required by
+//!some authenticated RPC gateways, and the `HmacSigner`, which implements the usual timestamp and body digest scheme.
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use sha2::{Digest, Sha256};
+
+use std::error::Error;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+///The `RequestSigner` trait computes the headers authenticating a request from its exact HTTP body. A `Provider` built
+///with `ProviderBuilder::signer()` calls it for every HTTP request it sends, retries included, so that signatures
+///covering a timestamp stay fresh.
+pub trait RequestSigner: fmt::Debug + Send + Sync {
+    fn sign(&self, body: &[u8]) -> Result<HeaderMap, Box<dyn Error>>;
+}
+
+///The `HmacSigner` struct signs requests with HMAC-SHA256. Every request is sent with the current Unix time in seconds
+///in the timestamp header, `x-timestamp` by default, and with the hex-encoded HMAC of `<timestamp>.<digest>` in the
+///signature header, `x-signature` by default, where `<digest>` is the hex-encoded SHA-256 hash of the body. An optional
+///key id header tells the gateway which secret was used. Its `Debug` output does not show the secret.
+///## Example
+///```rust
+///use ethrs::provider::Provider;
+///use ethrs::signing::HmacSigner;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let signer = HmacSigner::new(b"<secret>")
+///      .with_headers("x-gateway-timestamp", "x-gateway-signature")?
+///      .with_key_id("x-gateway-key", "<key-id>")?;
+///  let provider = Provider::builder("https://rpc.gateway.internal").signer(signer).build()?;
+///  Ok(())
+///}
+///```
+#[derive(Clone)]
+pub struct HmacSigner {
+    secret: Vec<u8>,
+    timestamp_header: HeaderName,
+    signature_header: HeaderName,
+    key_id: Option<(HeaderName, HeaderValue)>,
+}
+
+impl HmacSigner {
+    ///The `HmacSigner::new()` associated function takes the shared secret and returns a signer using the default
+    ///headers, without a key id.
+    pub fn new(secret: &[u8]) -> HmacSigner {
+        HmacSigner {
+            secret: secret.to_vec(),
+            timestamp_header: HeaderName::from_static("x-timestamp"),
+            signature_header: HeaderName::from_static("x-signature"),
+            key_id: None,
+        }
+    }
+
+    ///Sets the names of the timestamp and signature headers. Returns an `Err()` if a name is not a valid header name.
+    pub fn with_headers(
+        mut self,
+        timestamp_header: &str,
+        signature_header: &str,
+    ) -> Result<HmacSigner, Box<dyn Error>> {
+        self.timestamp_header = HeaderName::from_bytes(timestamp_header.as_bytes())?;
+        self.signature_header = HeaderName::from_bytes(signature_header.as_bytes())?;
+        Ok(self)
+    }
+
+    ///Sets the header sending the id of the secret, and the id. Returns an `Err()` if the header name or the id is not
+    ///valid in a header.
+    pub fn with_key_id(mut self, header: &str, key_id: &str) -> Result<HmacSigner, Box<dyn Error>> {
+        self.key_id = Some((
+            HeaderName::from_bytes(header.as_bytes())?,
+            HeaderValue::from_str(key_id)?,
+        ));
+        Ok(self)
+    }
+
+    ///The `signature()` function takes a Unix timestamp in seconds and a request body, and returns the hex-encoded
+    ///signature sent for them.
+    pub fn signature(&self, timestamp: u64, body: &[u8]) -> String {
+        let digest = hex::encode(Sha256::digest(body));
+        // HMAC accepts keys of any length
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC key");
+        mac.update(format!("{timestamp}.{digest}").as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+impl RequestSigner for HmacSigner {
+    fn sign(&self, body: &[u8]) -> Result<HeaderMap, Box<dyn Error>> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let mut headers = HeaderMap::new();
+        headers.insert(self.timestamp_header.clone(), timestamp.into());
+        headers.insert(
+            self.signature_header.clone(),
+            HeaderValue::from_str(&self.signature(timestamp, body))?,
+        );
+        if let Some((header, key_id)) = &self.key_id {
+            headers.insert(header.clone(), key_id.clone());
+        }
+        Ok(headers)
+    }
+}
+
+// the secret is kept out of logs and debug output
+impl fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("timestamp_header", &self.timestamp_header)
+            .field("signature_header", &self.signature_header)
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
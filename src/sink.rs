@@ -0,0 +1,387 @@
+//!The sink module provides a small delivery abstraction for chain events, forwarding new blocks, matched logs and
+//!transaction confirmations to channels, callbacks or HTTP webhooks. With the `nats` and `kafka` features, events can
+//!also be published to message queues, encoded as JSON or, with the `protobuf` feature, as protobuf.
+use serde::Serialize;
+
+use crate::provider::{Block, Log, TransactionReceipt};
+
+use std::error::Error;
+#[cfg(feature = "nats")]
+use std::io::{BufRead, BufReader, Write};
+#[cfg(feature = "nats")]
+use std::net::TcpStream;
+use std::sync::mpsc::Sender;
+#[cfg(feature = "nats")]
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+///The `Event` enum represents a single chain event delivered to a `Sink`. It serializes as
+///`{"type": "block" | "log" | "confirmation", "data": ...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+// events are moved once into a sink, so boxing blocks would only cost an allocation
+#[allow(clippy::large_enum_variant)]
+pub enum Event {
+    Block(Block),
+    Log(Log),
+    Confirmation(TransactionReceipt),
+}
+
+impl Event {
+    ///Returns the type of the event, i.e. `block`, `log` or `confirmation`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::Block(_) => "block",
+            Event::Log(_) => "log",
+            Event::Confirmation(_) => "confirmation",
+        }
+    }
+}
+
+///The `Encoding` enum selects how message queue sinks serialize events: as the JSON of `Event`, or as the `Event`
+///message of `proto/ethrs.proto` with the `protobuf` feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Json,
+    #[cfg(feature = "protobuf")]
+    Protobuf,
+}
+
+impl Encoding {
+    ///The `encode()` function takes an event and attempts to return its serialized form as `Ok(Vec<u8>)`.
+    pub fn encode(&self, event: &Event) -> Result<Vec<u8>, Box<dyn Error>> {
+        match self {
+            Encoding::Json => Ok(serde_json::to_vec(event)?),
+            #[cfg(feature = "protobuf")]
+            Encoding::Protobuf => Ok(prost::Message::encode_to_vec(&crate::proto::Event::from(
+                event,
+            ))),
+        }
+    }
+}
+
+///The `Sink` trait is implemented by every event destination. Implementations should return an `Err()` only once
+///delivery has definitively failed.
+pub trait Sink {
+    fn send(&self, event: &Event) -> Result<(), Box<dyn Error>>;
+}
+
+///The `ChannelSink` struct forwards events to an `mpsc` channel.
+///## Example
+///```rust
+///use ethrs::sink::{ChannelSink, Event, Sink};
+///use std::sync::mpsc::channel;
+///
+///let (sender, receiver) = channel::<Event>();
+///let sink = ChannelSink::new(sender);
+///// events sent to the sink can now be consumed from the receiver, e.g. on another thread
+///assert!(receiver.try_recv().is_err());
+///```
+#[derive(Debug, Clone)]
+pub struct ChannelSink {
+    sender: Sender<Event>,
+}
+
+impl ChannelSink {
+    pub fn new(sender: Sender<Event>) -> ChannelSink {
+        ChannelSink { sender }
+    }
+}
+
+impl Sink for ChannelSink {
+    fn send(&self, event: &Event) -> Result<(), Box<dyn Error>> {
+        Ok(self.sender.send(event.clone())?)
+    }
+}
+
+///The `CallbackSink` struct forwards events to a user-supplied closure.
+pub struct CallbackSink<F: Fn(&Event) -> Result<(), Box<dyn Error>>> {
+    callback: F,
+}
+
+impl<F: Fn(&Event) -> Result<(), Box<dyn Error>>> CallbackSink<F> {
+    pub fn new(callback: F) -> CallbackSink<F> {
+        CallbackSink { callback }
+    }
+}
+
+impl<F: Fn(&Event) -> Result<(), Box<dyn Error>>> Sink for CallbackSink<F> {
+    fn send(&self, event: &Event) -> Result<(), Box<dyn Error>> {
+        (self.callback)(event)
+    }
+}
+
+///The `WebhookSink` struct POSTs every event as JSON to an HTTP endpoint, retrying failed deliveries (transport errors
+///and non-2xx responses) with exponential backoff.
+///## Example
+///```rust
+///use ethrs::sink::WebhookSink;
+///use std::time::Duration;
+///
+///let sink = WebhookSink::new("https://example.com/hooks/ethrs")
+///    .with_retries(5)
+///    .with_backoff(Duration::from_millis(250));
+///```
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+    url: String,
+    client: reqwest::blocking::Client,
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl WebhookSink {
+    ///The `WebhookSink::new()` associated function takes a webhook URL and returns a sink retrying three times, starting
+    ///with a 500ms backoff.
+    pub fn new(url: &str) -> WebhookSink {
+        WebhookSink {
+            url: url.to_owned(),
+            client: reqwest::blocking::Client::new(),
+            max_retries: 3,
+            backoff: Duration::from_millis(500),
+        }
+    }
+
+    ///Sets the number of retries after the first failed delivery.
+    pub fn with_retries(mut self, max_retries: u32) -> WebhookSink {
+        self.max_retries = max_retries;
+        self
+    }
+
+    ///Sets the initial backoff, which doubles after every failed attempt.
+    pub fn with_backoff(mut self, backoff: Duration) -> WebhookSink {
+        self.backoff = backoff;
+        self
+    }
+}
+
+impl Sink for WebhookSink {
+    fn send(&self, event: &Event) -> Result<(), Box<dyn Error>> {
+        let body = serde_json::to_string(event)?;
+        let mut backoff = self.backoff;
+        let mut attempt = 0;
+        loop {
+            let error: Box<dyn Error> = match self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
+                .send()
+            {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => format!("Webhook returned {}", response.status()).into(),
+                Err(err) => err.into(),
+            };
+
+            if attempt >= self.max_retries {
+                return Err(error);
+            }
+            attempt += 1;
+            thread::sleep(backoff);
+            backoff *= 2;
+        }
+    }
+}
+
+///The `NatsSink` struct publishes every event to a NATS server, on the `<prefix>.<type>` subject, e.g. `eth.block`.
+///Publishing waits for the server to acknowledge the message and reconnects once if the connection was lost.
+///## Example
+///```rust
+///use ethrs::sink::{Encoding, NatsSink};
+///
+///let sink = NatsSink::new("nats://localhost:4222", "eth").with_encoding(Encoding::Json);
+///```
+#[cfg(feature = "nats")]
+#[derive(Debug)]
+pub struct NatsSink {
+    address: String,
+    subject_prefix: String,
+    encoding: Encoding,
+    timeout: Duration,
+    connection: Mutex<Option<BufReader<TcpStream>>>,
+}
+
+#[cfg(feature = "nats")]
+impl NatsSink {
+    ///The `NatsSink::new()` associated function takes the address of a NATS server, with or without the `nats://`
+    ///scheme, and a subject prefix, and returns a sink publishing JSON with a 5s timeout. It connects on the first
+    ///event.
+    pub fn new(url: &str, subject_prefix: &str) -> NatsSink {
+        NatsSink {
+            address: url.trim_start_matches("nats://").to_owned(),
+            subject_prefix: subject_prefix.to_owned(),
+            encoding: Encoding::default(),
+            timeout: Duration::from_secs(5),
+            connection: Mutex::new(None),
+        }
+    }
+
+    ///Sets the encoding of published events.
+    pub fn with_encoding(mut self, encoding: Encoding) -> NatsSink {
+        self.encoding = encoding;
+        self
+    }
+
+    ///Sets the timeout of connecting to the server and of each publish.
+    pub fn with_timeout(mut self, timeout: Duration) -> NatsSink {
+        self.timeout = timeout;
+        self
+    }
+
+    fn connect(&self) -> Result<BufReader<TcpStream>, Box<dyn Error>> {
+        let stream = TcpStream::connect(&self.address)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        let mut connection = BufReader::new(stream);
+        let mut info = String::new();
+        connection.read_line(&mut info)?;
+        if !info.starts_with("INFO") {
+            return Err(format!("Unexpected NATS greeting: {}", info.trim_end()).into());
+        }
+        connection
+            .get_mut()
+            .write_all(b"CONNECT {\"verbose\":false,\"pedantic\":false,\"name\":\"ethrs\"}\r\n")?;
+        Ok(connection)
+    }
+
+    fn publish(
+        connection: &mut BufReader<TcpStream>,
+        subject: &str,
+        payload: &[u8],
+    ) -> Result<(), Box<dyn Error>> {
+        let stream = connection.get_mut();
+        stream.write_all(format!("PUB {subject} {}\r\n", payload.len()).as_bytes())?;
+        stream.write_all(payload)?;
+        // the server answers the PING once it has processed the PUB before it
+        stream.write_all(b"\r\nPING\r\n")?;
+        stream.flush()?;
+        loop {
+            let mut line = String::new();
+            if connection.read_line(&mut line)? == 0 {
+                return Err("NATS connection closed".into());
+            }
+            match line.trim_end() {
+                "PONG" => return Ok(()),
+                "PING" => connection.get_mut().write_all(b"PONG\r\n")?,
+                error if error.starts_with("-ERR") => {
+                    return Err(format!("NATS error: {error}").into())
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(feature = "nats")]
+impl Sink for NatsSink {
+    fn send(&self, event: &Event) -> Result<(), Box<dyn Error>> {
+        let payload = self.encoding.encode(event)?;
+        let subject = format!("{}.{}", self.subject_prefix, event.kind());
+        let mut connection = self
+            .connection
+            .lock()
+            .map_err(|_| "NATS connection poisoned")?;
+        if let Some(existing) = connection.as_mut() {
+            if NatsSink::publish(existing, &subject, &payload).is_ok() {
+                return Ok(());
+            }
+        }
+        *connection = None;
+        let mut fresh = self.connect()?;
+        NatsSink::publish(&mut fresh, &subject, &payload)?;
+        *connection = Some(fresh);
+        Ok(())
+    }
+}
+
+///The `KafkaSink` struct publishes every event to a Kafka topic through a Kafka REST proxy (v2 API), keyed by the type
+///of the event. JSON events are embedded as JSON and protobuf events as base64.
+///## Example
+///```rust
+///use ethrs::sink::KafkaSink;
+///
+///let sink = KafkaSink::new("http://localhost:8082", "ethereum-events");
+///```
+#[cfg(feature = "kafka")]
+#[derive(Debug, Clone)]
+pub struct KafkaSink {
+    url: String,
+    encoding: Encoding,
+    client: reqwest::blocking::Client,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaSink {
+    ///The `KafkaSink::new()` associated function takes the URL of a Kafka REST proxy and a topic, and returns a sink
+    ///publishing JSON.
+    pub fn new(rest_proxy_url: &str, topic: &str) -> KafkaSink {
+        KafkaSink {
+            url: format!("{}/topics/{topic}", rest_proxy_url.trim_end_matches('/')),
+            encoding: Encoding::default(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    ///Sets the encoding of published events.
+    pub fn with_encoding(mut self, encoding: Encoding) -> KafkaSink {
+        self.encoding = encoding;
+        self
+    }
+}
+
+#[cfg(feature = "kafka")]
+impl Sink for KafkaSink {
+    fn send(&self, event: &Event) -> Result<(), Box<dyn Error>> {
+        let (content_type, record) = match self.encoding {
+            Encoding::Json => (
+                "application/vnd.kafka.json.v2+json",
+                serde_json::json!({"key": event.kind(), "value": event}),
+            ),
+            #[cfg(feature = "protobuf")]
+            encoding => (
+                "application/vnd.kafka.binary.v2+json",
+                serde_json::json!({
+                    "key": base64(event.kind().as_bytes()),
+                    "value": base64(&encoding.encode(event)?),
+                }),
+            ),
+        };
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(serde_json::json!({ "records": [record] }).to_string())
+            .send()?;
+        if !response.status().is_success() {
+            return Err(format!("Kafka REST proxy returned {}", response.status()).into());
+        }
+        let body: serde_json::Value = response.json()?;
+        match body["offsets"][0]["error"].as_str() {
+            Some(error) => Err(format!("Kafka REST proxy error: {error}").into()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(feature = "kafka", feature = "protobuf"))]
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            match i <= chunk.len() {
+                true => encoded.push(ALPHABET[(triple >> shift & 0x3f) as usize] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,272 @@
+//!The snapshot module tracks the gas used by named contract calls across runs, in the `.gas-snapshot` format of Foundry,
+//!so that test harnesses can fail on gas regressions.
+use crate::provider::{CallInput, Provider};
+use crate::trace::{debug_trace_transaction, GethTrace, TracerOptions};
+use crate::types::H256;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::str::FromStr;
+
+///The `Tolerance` enum sets how much the gas used by a call may increase before it is reported as a regression, either
+///in gas or as a fraction of the previous value, e.g. `Relative(0.01)` for 1%.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    Absolute(u64),
+    Relative(f64),
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance::Absolute(0)
+    }
+}
+
+impl Tolerance {
+    ///Returns `true` if the gas used going from `before` to `after` is within the tolerance.
+    pub fn allows(&self, before: u64, after: u64) -> bool {
+        let increase = after.saturating_sub(before);
+        match *self {
+            Tolerance::Absolute(gas) => increase <= gas,
+            Tolerance::Relative(fraction) => increase as f64 <= before as f64 * fraction,
+        }
+    }
+}
+
+///The `GasChange` struct is the gas used by a call in the previous and the current snapshot, with `None` if the call is
+///missing from either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasChange {
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+}
+
+impl GasChange {
+    ///Returns the difference in gas used, if the call is in both snapshots.
+    pub fn delta(&self) -> Option<i128> {
+        Some(self.after? as i128 - self.before? as i128)
+    }
+}
+
+///The `GasDiff` struct holds the calls whose gas used differs between two snapshots, along with the tolerance it was
+///computed with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasDiff {
+    pub changes: BTreeMap<String, GasChange>,
+    pub tolerance: Tolerance,
+}
+
+impl GasDiff {
+    ///Returns the calls whose gas used increased beyond the tolerance.
+    pub fn regressions(&self) -> impl Iterator<Item = (&str, &GasChange)> {
+        self.changes
+            .iter()
+            .filter(|(_, change)| match (change.before, change.after) {
+                (Some(before), Some(after)) => !self.tolerance.allows(before, after),
+                _ => false,
+            })
+            .map(|(name, change)| (name.as_str(), change))
+    }
+
+    ///The `check()` function returns `Ok(())` if no call regressed beyond the tolerance, and an `Err()` listing the
+    ///regressions otherwise.
+    pub fn check(&self) -> Result<(), Box<dyn Error>> {
+        let regressions: Vec<String> = self
+            .regressions()
+            .map(|(name, change)| {
+                format!(
+                    "{name}: {} -> {}",
+                    change.before.unwrap_or_default(),
+                    change.after.unwrap_or_default()
+                )
+            })
+            .collect();
+        match regressions.is_empty() {
+            true => Ok(()),
+            false => Err(format!("Gas regressions: {}", regressions.join(", ")).into()),
+        }
+    }
+}
+
+impl fmt::Display for GasDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, change) in &self.changes {
+            match (change.before, change.after) {
+                (Some(before), Some(after)) => {
+                    let delta = after as i128 - before as i128;
+                    writeln!(
+                        f,
+                        "{name} (gas: {before} -> {after} | {delta:+} {:+.3}%)",
+                        delta as f64 * 100.0 / before.max(1) as f64
+                    )?
+                }
+                (None, Some(after)) => writeln!(f, "{name} (gas: added {after})")?,
+                (Some(before), None) => writeln!(f, "{name} (gas: removed {before})")?,
+                (None, None) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+///The `GasSnapshot` struct maps call names to the gas they used. It is read from and written to files in the
+///`.gas-snapshot` format, i.e. one `<name> (gas: <gas>)` line per call, sorted by name.
+///## Example
+///```rust,no_run
+///use ethrs::provider::{CallInput, Provider};
+///use ethrs::snapshot::{GasSnapshot, Tolerance};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let previous = GasSnapshot::load(".gas-snapshot")?;
+///  let mut snapshot = GasSnapshot::new();
+///  snapshot.estimate(&provider, "Counter:increment()", CallInput {
+///      from: None,
+///      to: "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?,
+///      gas: None,
+///      gas_price: None,
+///      value: None,
+///      data: Some("0xd09de08a".to_owned()),
+///  })?;
+///  let diff = snapshot.diff(&previous, Tolerance::Relative(0.01));
+///  print!("{diff}");
+///  diff.check()?;
+///  snapshot.save(".gas-snapshot")?;
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasSnapshot {
+    entries: BTreeMap<String, u64>,
+}
+
+impl GasSnapshot {
+    ///The `new()` function returns an empty `GasSnapshot`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///The `load()` function takes the path of a snapshot file and attempts to return the snapshot as
+    ///`Ok(GasSnapshot)`, or an empty snapshot if the file does not exist. Returns an `Err()` if the file cannot be read
+    ///or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<GasSnapshot, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(snapshot) => snapshot.parse(),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(GasSnapshot::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    ///The `save()` function takes a path and attempts to write the snapshot to it as `Ok(())`. Returns an `Err()` if the
+    ///file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    ///The `record()` function records the gas used by a call, returning the gas it previously recorded, if any.
+    pub fn record(&mut self, name: impl Into<String>, gas: u64) -> Option<u64> {
+        self.entries.insert(name.into(), gas)
+    }
+
+    ///The `estimate()` function takes a provider, a call name and a call input struct, and attempts to record and return
+    ///the gas estimated for the call as `Ok(u64)`. Returns an `Err()` on JSON-RPC errors.
+    pub fn estimate(
+        &mut self,
+        provider: &Provider,
+        name: &str,
+        tx: CallInput,
+    ) -> Result<u64, Box<dyn Error>> {
+        let gas = provider.estimate_gas(tx, None)?.try_into()?;
+        self.record(name, gas);
+        Ok(gas)
+    }
+
+    ///The `trace()` function takes a provider exposing the `debug_*` namespace, a call name and the hash of a mined
+    ///transaction, and attempts to record and return the gas the transaction used as `Ok(u64)`. Returns an `Err()` on
+    ///JSON-RPC errors.
+    pub fn trace(
+        &mut self,
+        provider: &Provider,
+        name: &str,
+        txhash: H256,
+    ) -> Result<u64, Box<dyn Error>> {
+        let gas = match debug_trace_transaction(
+            provider,
+            txhash,
+            &TracerOptions::call_tracer(true, false),
+        )? {
+            GethTrace::Call(frame) => frame.gas_used.try_into()?,
+            _ => return Err("Unexpected trace".into()),
+        };
+        self.record(name, gas);
+        Ok(gas)
+    }
+
+    ///Returns the gas used by a call, if recorded.
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.entries.get(name).copied()
+    }
+
+    ///The `diff()` function compares the snapshot against a previous one and returns the calls whose gas used changed,
+    ///was added or was removed.
+    pub fn diff(&self, previous: &GasSnapshot, tolerance: Tolerance) -> GasDiff {
+        let mut changes = BTreeMap::new();
+        for name in self.entries.keys().chain(previous.entries.keys()) {
+            let change = GasChange {
+                before: previous.get(name),
+                after: self.get(name),
+            };
+            if change.before != change.after {
+                changes.insert(name.clone(), change);
+            }
+        }
+        GasDiff { changes, tolerance }
+    }
+
+    ///Returns an iterator over the recorded calls, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.entries.iter().map(|(name, gas)| (name.as_str(), *gas))
+    }
+
+    ///Returns the number of recorded calls.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    ///Returns `true` if no call is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl FromStr for GasSnapshot {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut snapshot = GasSnapshot::new();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (name, gas) = line
+                .trim_end()
+                .strip_suffix(')')
+                .and_then(|line| line.rsplit_once(" (gas: "))
+                .ok_or(format!("Invalid snapshot line: {line}"))?;
+            snapshot.record(name, gas.parse()?);
+        }
+        Ok(snapshot)
+    }
+}
+
+impl fmt::Display for GasSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, gas) in &self.entries {
+            writeln!(f, "{name} (gas: {gas})")?;
+        }
+        Ok(())
+    }
+}
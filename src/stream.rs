@@ -0,0 +1,664 @@
+//!The stream module provides iterators following the chain, `BlockStream` and `LogStream`, along with combinators to
+//!compose them into pipelines: grouping logs by block, holding items back until they are confirmed, joining logs with
+//!their transactions and throttling. `ReorgAwareStream` follows the chain head while detecting reorganizations, while
+//!streams with a `Finality` other than `Latest` only yield data that is unlikely or unable to be reorganized away.
+use serde::{Deserialize, Serialize};
+
+use crate::client::JsonRpcClient;
+use crate::provider::{Block, Filter, Log, Transaction, TransactionReceipt};
+use crate::types::{BlockId, H256};
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::thread;
+use std::time::{Duration, Instant};
+
+///The `BlockNumbered` trait is implemented by chain data mined in a block, which `confirmed()` uses to tell how many
+///confirmations an item has.
+pub trait BlockNumbered {
+    ///Returns the number of the block the item was mined in, or `None` if it is pending.
+    fn block_number(&self) -> Option<u64>;
+}
+
+impl<TX> BlockNumbered for Block<TX> {
+    fn block_number(&self) -> Option<u64> {
+        self.number
+    }
+}
+
+impl BlockNumbered for Log {
+    fn block_number(&self) -> Option<u64> {
+        Some(self.block_number)
+    }
+}
+
+impl BlockNumbered for TransactionReceipt {
+    fn block_number(&self) -> Option<u64> {
+        Some(self.block_number)
+    }
+}
+
+impl BlockNumbered for BlockLogs {
+    fn block_number(&self) -> Option<u64> {
+        Some(self.block_number)
+    }
+}
+
+///The `BlockLogs` struct holds the consecutive logs of a stream mined in the same block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockLogs {
+    pub block_number: u64,
+    pub block_hash: H256,
+    pub logs: Vec<Log>,
+}
+
+///The `Finality` enum sets how far behind the chain head `BlockStream` and `LogStream` stay: `Latest` follows the head
+///itself, `Confirmations(n)` yields a block once it has `n` confirmations, the block itself being the first one, and
+///`Safe` and `Finalized` follow the blocks the consensus layer marked as such.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Finality {
+    #[default]
+    Latest,
+    Confirmations(u64),
+    Safe,
+    Finalized,
+}
+
+impl Finality {
+    ///Returns the latest block number final enough to be yielded, or `None` if there is none yet.
+    fn head<P: JsonRpcClient>(&self, client: &P) -> Result<Option<u64>, Box<dyn Error>> {
+        let tag = match self {
+            Finality::Latest => return client.block_number().map(Some),
+            Finality::Confirmations(confirmations) => {
+                let head = client.block_number()?;
+                return Ok(head.checked_sub(confirmations.saturating_sub(1)));
+            }
+            Finality::Safe => BlockId::Safe,
+            Finality::Finalized => BlockId::Finalized,
+        };
+        Ok(client
+            .get_block_by_number(Some(tag))?
+            .and_then(|block| block.number))
+    }
+}
+
+///The `BlockStream` struct iterates over the blocks of the chain from a starting block onwards, waiting for every next
+///block to be mined, or to be final enough with `with_finality()`. It never ends on its own and yields an `Err()` on
+///JSON-RPC errors, after which iterating again retries the same block.
+///## Example
+///```rust,no_run
+///use ethrs::provider::Provider;
+///use ethrs::stream::BlockStream;
+///
+///let provider = Provider::new("http://localhost:8545");
+///for block in BlockStream::new(provider, 17000000).take(10) {
+///    println!("{:?}", block.unwrap().hash);
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct BlockStream<P: JsonRpcClient> {
+    client: P,
+    next: u64,
+    poll_interval: Duration,
+    finality: Finality,
+    head: Option<u64>,
+}
+
+impl<P: JsonRpcClient> BlockStream<P> {
+    ///The `BlockStream::new()` associated function takes a client and the first block to yield, and returns a stream
+    ///polling for new blocks every second.
+    pub fn new(client: P, from_block: u64) -> BlockStream<P> {
+        BlockStream {
+            client,
+            next: from_block,
+            poll_interval: Duration::from_secs(1),
+            finality: Finality::Latest,
+            head: None,
+        }
+    }
+
+    ///Sets how often the stream polls for the next block once it has caught up with the chain head.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> BlockStream<P> {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    ///Sets how final a block must be for the stream to yield it.
+    pub fn with_finality(mut self, finality: Finality) -> BlockStream<P> {
+        self.finality = finality;
+        self
+    }
+
+    ///Returns whether the next block is final enough to be fetched, polling the final head only when the last known
+    ///one is behind it.
+    fn is_final(&mut self) -> Result<bool, Box<dyn Error>> {
+        if self.finality == Finality::Latest || self.head.map_or(false, |head| head >= self.next) {
+            return Ok(true);
+        }
+        self.head = self.finality.head(&self.client)?;
+        Ok(self.head.map_or(false, |head| head >= self.next))
+    }
+
+    ///Turns the stream into a `ReorgAwareStream` remembering the given number of latest blocks to detect
+    ///reorganizations of the chain up to that depth.
+    pub fn track_reorgs(self, depth: usize) -> ReorgAwareStream<P> {
+        ReorgAwareStream {
+            stream: self,
+            depth: depth.max(1),
+            history: VecDeque::new(),
+        }
+    }
+}
+
+impl<P: JsonRpcClient> Iterator for BlockStream<P> {
+    type Item = Result<Block, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.is_final() {
+                Ok(true) => {}
+                Ok(false) => {
+                    thread::sleep(self.poll_interval);
+                    continue;
+                }
+                Err(err) => return Some(Err(err)),
+            }
+            match self
+                .client
+                .get_block_by_number(Some(BlockId::Number(self.next)))
+            {
+                Ok(Some(block)) => {
+                    self.next += 1;
+                    return Some(Ok(block));
+                }
+                Ok(None) => thread::sleep(self.poll_interval),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+///The `BlockEvent` enum is yielded by `ReorgAwareStream`: either the next block of the chain, or a reorganization
+///replacing the blocks after `common_ancestor`, with the blocks that were yielded before in `dropped` and the blocks of
+///the new chain in `new`, both in block order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockEvent {
+    NewBlock(Block),
+    Reorg {
+        common_ancestor: Block,
+        dropped: Vec<Block>,
+        new: Vec<Block>,
+    },
+}
+
+///The `ReorgAwareStream` struct iterates over the blocks of the chain like `BlockStream`, remembering the latest ones
+///to check that every next block builds on them. When the parent hash of a block does not match, the stream walks the
+///new chain back to the last block both chains share and yields a `BlockEvent::Reorg`, then carries on from the new
+///chain. A reorganization is noticed once the block after the last yielded one is mined on the new chain. Reorgs
+///deeper than the tracked blocks yield an `Err()`, after which the stream starts tracking the new chain afresh.
+///## Example
+///```rust,no_run
+///use ethrs::provider::Provider;
+///use ethrs::stream::BlockEvent;
+///use std::time::Duration;
+///
+///let provider = Provider::new("http://localhost:8545");
+///for event in provider.watch_blocks(Duration::from_secs(12)).unwrap().track_reorgs(64) {
+///    match event.unwrap() {
+///        BlockEvent::NewBlock(block) => println!("New block {:?}", block.number),
+///        BlockEvent::Reorg { common_ancestor, dropped, .. } => {
+///            println!("{} blocks dropped after {:?}", dropped.len(), common_ancestor.number)
+///        }
+///    }
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct ReorgAwareStream<P: JsonRpcClient> {
+    stream: BlockStream<P>,
+    depth: usize,
+    history: VecDeque<Block>,
+}
+
+impl<P: JsonRpcClient> ReorgAwareStream<P> {
+    fn push(&mut self, block: Block) {
+        self.history.push_back(block);
+        while self.history.len() > self.depth {
+            self.history.pop_front();
+        }
+    }
+
+    ///Walks the new chain back from `block` until a parent is found among the tracked blocks, returning the new chain
+    ///after it. The state of the stream is left untouched, so that the walk can be retried on errors.
+    fn reorg(&self, block: &Block) -> Result<Option<Vec<Block>>, Box<dyn Error>> {
+        let mut new = vec![block.clone()];
+        for ancestor in self.history.iter().rev() {
+            if ancestor.hash == Some(new[0].parent_hash) {
+                return Ok(Some(new));
+            }
+            let Some(number) = ancestor.number else {
+                break;
+            };
+            let parent = self
+                .stream
+                .client
+                .get_block_by_number(Some(BlockId::Number(number)))?
+                .ok_or(format!("Block {number} not found"))?;
+            new.insert(0, parent);
+        }
+        Ok(None)
+    }
+}
+
+impl<P: JsonRpcClient> Iterator for ReorgAwareStream<P> {
+    type Item = Result<BlockEvent, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let block = match self.stream.next()? {
+            Ok(block) => block,
+            Err(err) => return Some(Err(err)),
+        };
+        let builds_on_head = match self.history.back() {
+            Some(head) => head.hash == Some(block.parent_hash),
+            None => true,
+        };
+        if builds_on_head {
+            self.push(block.clone());
+            return Some(Ok(BlockEvent::NewBlock(block)));
+        }
+        // the block is fetched again once the new chain has been walked back
+        self.stream.next -= 1;
+        match self.reorg(&block) {
+            Ok(Some(new)) => {
+                self.stream.next += 1;
+                // every tracked block after the common ancestor was replaced by one of the new chain but the last
+                let ancestor = self.history.len() - new.len();
+                let dropped = self.history.split_off(ancestor + 1).into();
+                let common_ancestor = self.history[ancestor].clone();
+                for block in &new {
+                    self.push(block.clone());
+                }
+                Some(Ok(BlockEvent::Reorg {
+                    common_ancestor,
+                    dropped,
+                    new,
+                }))
+            }
+            Ok(None) => {
+                self.stream.next += 1;
+                self.history.clear();
+                self.push(block);
+                Some(Err(format!(
+                    "Reorg deeper than the {} tracked blocks",
+                    self.depth
+                )
+                .into()))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+///The `LogStream` struct iterates over the logs matching a filter from a starting block onwards, in block order,
+///fetching up to 1000 blocks per `eth_getLogs` request and waiting for new blocks once it has caught up with the chain
+///head, or the latest block final enough with `with_finality()`. The block range of the filter is ignored. It never
+///ends on its own and yields an `Err()` on JSON-RPC errors, after which iterating again retries the same block range.
+///## Example
+///```rust,no_run
+///use ethrs::provider::{Filter, Provider};
+///use ethrs::stream::{LogStream, LogStreamExt};
+///
+///let provider = Provider::new("http://localhost:8545");
+///let filter = Filter {
+///    address: vec!["0x5fbdb2315678afecb367f032d93f642f64180aa3".parse().unwrap()],
+///    ..Default::default()
+///};
+///for block in LogStream::new(provider, filter, 17000000).group_by_block() {
+///    let block = block.unwrap();
+///    println!("{}: {} logs", block.block_number, block.logs.len());
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct LogStream<P: JsonRpcClient> {
+    client: P,
+    filter: Filter,
+    next: u64,
+    batch_size: u64,
+    poll_interval: Duration,
+    finality: Finality,
+    bloom_filter: bool,
+    buffer: VecDeque<Log>,
+}
+
+impl<P: JsonRpcClient> LogStream<P> {
+    ///The `LogStream::new()` associated function takes a client, the filter of the logs to yield and the first block to
+    ///search, and returns a stream polling for new blocks every second.
+    pub fn new(client: P, filter: Filter, from_block: u64) -> LogStream<P> {
+        LogStream {
+            client,
+            filter,
+            next: from_block,
+            batch_size: 1000,
+            poll_interval: Duration::from_secs(1),
+            finality: Finality::Latest,
+            bloom_filter: false,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    ///Sets the maximum number of blocks searched per `eth_getLogs` request.
+    pub fn with_batch_size(mut self, batch_size: u64) -> LogStream<P> {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    ///Sets how often the stream polls for new blocks once it has caught up with the chain head.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> LogStream<P> {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    ///Sets how final a block must be for the stream to yield its logs.
+    pub fn with_finality(mut self, finality: Finality) -> LogStream<P> {
+        self.finality = finality;
+        self
+    }
+
+    ///Sets whether the stream fetches blocks one at a time and checks their log bloom against the filter, only
+    ///requesting the logs of blocks that may contain matches. This saves requests for filters matching few blocks
+    ///once the stream follows the chain head, but costs one request per block when catching up.
+    pub fn with_bloom_filter(mut self, bloom_filter: bool) -> LogStream<P> {
+        self.bloom_filter = bloom_filter;
+        self
+    }
+
+    fn fetch(&mut self) -> Result<bool, Box<dyn Error>> {
+        let head = match self.finality.head(&self.client)? {
+            Some(head) if head >= self.next => head,
+            _ => return Ok(false),
+        };
+        if self.bloom_filter {
+            return self.fetch_block();
+        }
+        let to_block = head.min(self.next + self.batch_size - 1);
+        let logs = self.client.get_logs(&Filter {
+            from_block: Some(BlockId::Number(self.next)),
+            to_block: Some(BlockId::Number(to_block)),
+            block_hash: None,
+            ..self.filter.clone()
+        })?;
+        self.buffer.extend(logs);
+        self.next = to_block + 1;
+        Ok(true)
+    }
+
+    fn fetch_block(&mut self) -> Result<bool, Box<dyn Error>> {
+        let Some(block) = self
+            .client
+            .get_block_by_number(Some(BlockId::Number(self.next)))?
+        else {
+            return Ok(false);
+        };
+        // blocks without a well-formed bloom cannot be ruled out
+        if block
+            .bloom()
+            .map_or(true, |bloom| bloom.matches(&self.filter))
+        {
+            let logs = self.client.get_logs(&Filter {
+                from_block: None,
+                to_block: None,
+                block_hash: block.hash,
+                ..self.filter.clone()
+            })?;
+            self.buffer.extend(logs);
+        }
+        self.next += 1;
+        Ok(true)
+    }
+}
+
+impl<P: JsonRpcClient> Iterator for LogStream<P> {
+    type Item = Result<Log, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(log) = self.buffer.pop_front() {
+                return Some(Ok(log));
+            }
+            match self.fetch() {
+                Ok(true) => {}
+                Ok(false) => thread::sleep(self.poll_interval),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+///The `StreamExt` trait provides combinators over any iterator of chain data, such as `BlockStream` and `LogStream`.
+///Errors are passed through as they come.
+pub trait StreamExt<T>: Iterator<Item = Result<T, Box<dyn Error>>> + Sized {
+    ///Holds every item back until its block has the given number of confirmations, the block itself being the first
+    ///one, polling the chain head through the client at the given interval. Items without a block number are yielded
+    ///right away.
+    fn confirmed<P: JsonRpcClient>(
+        self,
+        client: P,
+        confirmations: u64,
+        poll_interval: Duration,
+    ) -> Confirmed<Self, T, P>
+    where
+        T: BlockNumbered,
+    {
+        Confirmed {
+            inner: self,
+            client,
+            confirmations,
+            poll_interval,
+            head: None,
+            pending: None,
+        }
+    }
+
+    ///Yields items at most once per interval, sleeping before an item that comes too soon after the previous one.
+    fn throttle(self, interval: Duration) -> Throttle<Self> {
+        Throttle {
+            inner: self,
+            interval,
+            last: None,
+        }
+    }
+}
+
+impl<T, I: Iterator<Item = Result<T, Box<dyn Error>>>> StreamExt<T> for I {}
+
+///The `LogStreamExt` trait provides combinators over iterators of logs, such as `LogStream`.
+pub trait LogStreamExt: Iterator<Item = Result<Log, Box<dyn Error>>> + Sized {
+    ///Groups consecutive logs mined in the same block. A group is yielded once a log of another block arrives or the
+    ///stream ends, so the group of the latest block of a live stream is yielded with a delay.
+    fn group_by_block(self) -> GroupByBlock<Self> {
+        GroupByBlock {
+            inner: self,
+            group: None,
+        }
+    }
+
+    ///Yields every log along with the transaction that emitted it, fetched through the client. Consecutive logs of the
+    ///same transaction share a single request.
+    fn with_transactions<P: JsonRpcClient>(self, client: P) -> WithTransactions<Self, P> {
+        WithTransactions {
+            inner: self,
+            client,
+            last: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = Result<Log, Box<dyn Error>>>> LogStreamExt for I {}
+
+///The `Confirmed` struct is the iterator returned by `StreamExt::confirmed()`.
+#[derive(Debug)]
+pub struct Confirmed<I, T, P> {
+    inner: I,
+    client: P,
+    confirmations: u64,
+    poll_interval: Duration,
+    head: Option<u64>,
+    pending: Option<T>,
+}
+
+impl<I, T, P> Confirmed<I, T, P>
+where
+    T: BlockNumbered,
+{
+    fn is_confirmed(&self, item: &T) -> bool {
+        match (item.block_number(), self.head) {
+            (None, _) => true,
+            (Some(block_number), Some(head)) => {
+                head + 1 >= block_number.saturating_add(self.confirmations)
+            }
+            (Some(_), None) => false,
+        }
+    }
+}
+
+impl<I, T, P> Iterator for Confirmed<I, T, P>
+where
+    I: Iterator<Item = Result<T, Box<dyn Error>>>,
+    T: BlockNumbered,
+    P: JsonRpcClient,
+{
+    type Item = Result<T, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = match self.pending.take() {
+            Some(item) => item,
+            None => match self.inner.next()? {
+                Ok(item) => item,
+                Err(err) => return Some(Err(err)),
+            },
+        };
+        // the chain head is only polled when the last known one does not confirm the item
+        if !self.is_confirmed(&item) {
+            loop {
+                match self.client.block_number() {
+                    Ok(head) => self.head = Some(head),
+                    Err(err) => {
+                        self.pending = Some(item);
+                        return Some(Err(err));
+                    }
+                }
+                if self.is_confirmed(&item) {
+                    break;
+                }
+                thread::sleep(self.poll_interval);
+            }
+        }
+        Some(Ok(item))
+    }
+}
+
+///The `Throttle` struct is the iterator returned by `StreamExt::throttle()`.
+#[derive(Debug)]
+pub struct Throttle<I> {
+    inner: I,
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl<I: Iterator> Iterator for Throttle<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.inner.next()?;
+        if let Some(last) = self.last {
+            let elapsed = last.elapsed();
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+        self.last = Some(Instant::now());
+        Some(item)
+    }
+}
+
+///The `GroupByBlock` struct is the iterator returned by `LogStreamExt::group_by_block()`.
+#[derive(Debug)]
+pub struct GroupByBlock<I> {
+    inner: I,
+    group: Option<BlockLogs>,
+}
+
+impl<I: Iterator<Item = Result<Log, Box<dyn Error>>>> Iterator for GroupByBlock<I> {
+    type Item = Result<BlockLogs, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let log = match self.inner.next() {
+                Some(Ok(log)) => log,
+                Some(Err(err)) => return Some(Err(err)),
+                None => return self.group.take().map(Ok),
+            };
+            match &mut self.group {
+                Some(group)
+                    if group.block_number == log.block_number
+                        && group.block_hash == log.block_hash =>
+                {
+                    group.logs.push(log)
+                }
+                _ => {
+                    let group = self.group.replace(BlockLogs {
+                        block_number: log.block_number,
+                        block_hash: log.block_hash,
+                        logs: vec![log],
+                    });
+                    if let Some(group) = group {
+                        return Some(Ok(group));
+                    }
+                }
+            }
+        }
+    }
+}
+
+///The `WithTransactions` struct is the iterator returned by `LogStreamExt::with_transactions()`.
+#[derive(Debug)]
+pub struct WithTransactions<I, P> {
+    inner: I,
+    client: P,
+    last: Option<Transaction>,
+}
+
+impl<I, P> Iterator for WithTransactions<I, P>
+where
+    I: Iterator<Item = Result<Log, Box<dyn Error>>>,
+    P: JsonRpcClient,
+{
+    type Item = Result<(Log, Transaction), Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let log = match self.inner.next()? {
+            Ok(log) => log,
+            Err(err) => return Some(Err(err)),
+        };
+        let cached = self
+            .last
+            .as_ref()
+            .filter(|tx| tx.hash == log.transaction_hash);
+        let tx = match cached {
+            Some(tx) => tx.clone(),
+            None => match self.client.get_transaction_by_hash(log.transaction_hash) {
+                Ok(Some(tx)) => tx,
+                Ok(None) => {
+                    return Some(Err(format!(
+                        "Transaction {} not found",
+                        log.transaction_hash
+                    )
+                    .into()))
+                }
+                Err(err) => return Some(Err(err)),
+            },
+        };
+        self.last = Some(tx.clone());
+        Some(Ok((log, tx)))
+    }
+}
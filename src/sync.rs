@@ -0,0 +1,172 @@
+//!The sync module downloads ranges of the chain concurrently: the `BlockFetcher` fetches blocks with their
+//!transactions, and optionally their receipts, on a pool of threads and hands them out in block order, so that
+//!backfills are not bound by the latency of one request at a time.
+use crate::provider::{BlockWithTx, Provider, TransactionReceipt};
+use crate::types::BlockId;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::ops::RangeInclusive;
+use std::sync::mpsc::{channel, sync_channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// How many times a block is fetched when its receipts are not found by its hash, e.g. because of a reorg.
+const REORG_ATTEMPTS: usize = 3;
+
+///The `FetchedBlock` struct is a block fetched by a `BlockFetcher`, with its receipts in transaction order if they are
+///fetched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedBlock {
+    pub block: BlockWithTx,
+    pub receipts: Vec<TransactionReceipt>,
+}
+
+///The `BlockFetcher` struct fetches the blocks of a range concurrently. Up to `concurrency` blocks are requested at a
+///time, and blocks fetched ahead of the next one in order are buffered, up to twice the concurrency, until they can be
+///handed out. The output is bounded as well, so a slow consumer slows the fetching down instead of filling memory.
+///## Example
+///```rust,no_run
+///use ethrs::provider::Provider;
+///use ethrs::sync::BlockFetcher;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let fetcher = BlockFetcher::new(Provider::new("http://localhost:8545"))
+///      .with_concurrency(16)
+///      .with_receipts(true);
+///  for fetched in fetcher.fetch(17000000..=17999999) {
+///      let fetched = fetched?;
+///      let gas_used: u64 = fetched.receipts.iter().map(|receipt| receipt.gas_used.as_u64()).sum();
+///      println!("{:?}: {} transactions, {gas_used} gas", fetched.block.number, fetched.block.transactions.len());
+///  }
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone)]
+pub struct BlockFetcher {
+    provider: Provider,
+    concurrency: usize,
+    receipts: bool,
+}
+
+impl BlockFetcher {
+    ///The `BlockFetcher::new()` associated function takes a `Provider` and returns a `BlockFetcher` fetching 4 blocks
+    ///at a time, without their receipts.
+    pub fn new(provider: Provider) -> BlockFetcher {
+        BlockFetcher {
+            provider,
+            concurrency: 4,
+            receipts: false,
+        }
+    }
+
+    ///The `with_concurrency()` function sets how many blocks are requested at a time.
+    pub fn with_concurrency(mut self, concurrency: usize) -> BlockFetcher {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    ///The `with_receipts()` function sets whether the receipts of every block are fetched as well, with
+    ///`eth_getBlockReceipts`. Receipts are fetched by block hash, so that they belong to the fetched block even if the
+    ///chain reorganizes in between.
+    pub fn with_receipts(mut self, receipts: bool) -> BlockFetcher {
+        self.receipts = receipts;
+        self
+    }
+
+    ///The `fetch()` function takes an inclusive range of block numbers and starts fetching it in the background,
+    ///returning an iterator over the fetched blocks in block order. The iterator yields an `Err()` on JSON-RPC errors
+    ///or if a block is not found, and ends after it. Dropping the iterator stops the fetching.
+    pub fn fetch(&self, range: RangeInclusive<u64>) -> FetchedBlocks {
+        let (output, receiver) = sync_channel(self.concurrency);
+        let (jobs, job_receiver) = channel::<u64>();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let (results, result_receiver) = channel();
+        for _ in 0..self.concurrency {
+            let fetcher = self.clone();
+            let job_receiver = job_receiver.clone();
+            let results: Sender<(u64, Result<FetchedBlock, String>)> = results.clone();
+            thread::spawn(move || loop {
+                let job = match job_receiver.lock() {
+                    Ok(job_receiver) => job_receiver.recv(),
+                    Err(_) => return,
+                };
+                let Ok(number) = job else {
+                    return;
+                };
+                let result = fetcher.fetch_block(number).map_err(|err| err.to_string());
+                if results.send((number, result)).is_err() {
+                    return;
+                }
+            });
+        }
+
+        let window = self.concurrency as u64 * 2;
+        thread::spawn(move || {
+            let (start, end) = range.into_inner();
+            let mut next_job = Some(start).filter(|start| *start <= end);
+            let mut next_output = next_job;
+            let mut fetched = BTreeMap::new();
+            while let Some(next) = next_output {
+                // only blocks within the window after the next one to hand out are requested
+                while let Some(job) = next_job.filter(|job| job - next < window) {
+                    if jobs.send(job).is_err() {
+                        return;
+                    }
+                    next_job = job.checked_add(1).filter(|job| *job <= end);
+                }
+                let Ok((number, result)) = result_receiver.recv() else {
+                    return;
+                };
+                fetched.insert(number, result);
+                while let Some(result) = next_output.and_then(|next| fetched.remove(&next)) {
+                    let failed = result.is_err();
+                    if output.send(result).is_err() || failed {
+                        return;
+                    }
+                    next_output = next_output
+                        .and_then(|next| next.checked_add(1))
+                        .filter(|next| *next <= end);
+                }
+            }
+        });
+        FetchedBlocks { receiver }
+    }
+
+    fn fetch_block(&self, number: u64) -> Result<FetchedBlock, Box<dyn Error>> {
+        // a block reorganized out before its receipts are fetched is fetched again
+        for _ in 0..REORG_ATTEMPTS {
+            let block = self
+                .provider
+                .get_block_by_number_with_tx(Some(BlockId::Number(number)))?
+                .ok_or(format!("Block {number} not found"))?;
+            if !self.receipts || block.transactions.is_empty() {
+                return Ok(FetchedBlock {
+                    block,
+                    receipts: vec![],
+                });
+            }
+            let hash = block.hash.ok_or(format!("Block {number} has no hash"))?;
+            if let Some(receipts) = self.provider.get_block_receipts(BlockId::Hash(hash))? {
+                return Ok(FetchedBlock { block, receipts });
+            }
+        }
+        Err(format!("Receipts of block {number} not found").into())
+    }
+}
+
+///The `FetchedBlocks` struct is the iterator over the blocks fetched by `BlockFetcher::fetch()`, in block order.
+#[derive(Debug)]
+pub struct FetchedBlocks {
+    receiver: Receiver<Result<FetchedBlock, String>>,
+}
+
+impl Iterator for FetchedBlocks {
+    type Item = Result<FetchedBlock, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.receiver.recv().ok()?;
+        Some(result.map_err(Into::into))
+    }
+}
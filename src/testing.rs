@@ -0,0 +1,205 @@
+//!The testing module helps test suites that run against public JSON-RPC endpoints: `TestEndpoints` reads the endpoint
+//!list from an environment variable, skips unreachable endpoints and retries requests that fail on flaky ones, so that
+//!a single endpoint hiccup does not fail a CI run. Tests are skipped when no endpoint is reachable, unless
+//!`ETHRS_REQUIRE_ENDPOINTS=1` is set, as in CI, where they fail instead.
+use crate::fallback::FallbackProvider;
+use crate::provider::{Provider, Transport};
+
+use std::env;
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+///The environment variable read by `sepolia()`, holding a comma-separated list of Sepolia JSON-RPC URLs.
+pub const ENDPOINTS_VAR: &str = "ETHRS_SEPOLIA_RPC_URLS";
+
+///The environment variable read by `TestEndpoints::provider_or_skip()`, which fails instead of skipping when no
+///endpoint is reachable if it is set to `1`.
+pub const REQUIRE_ENDPOINTS_VAR: &str = "ETHRS_REQUIRE_ENDPOINTS";
+
+///The probe errors returned by `TestEndpoints::provider()`, as `(url, error)` pairs.
+pub type ProbeErrors = Vec<(String, Box<dyn Error>)>;
+
+///The public Sepolia endpoints used by `sepolia()` when `ENDPOINTS_VAR` is not set.
+pub const SEPOLIA_ENDPOINTS: [&str; 3] = [
+    "https://rpc.sepolia.org",
+    "https://ethereum-sepolia-rpc.publicnode.com",
+    "https://sepolia.drpc.org",
+];
+
+///The `TestEndpoints` struct describes the endpoints a test suite runs against. `provider()` probes them and returns a
+///`Provider` over the reachable ones, which fails over between them and retries failed requests, and
+///`provider_or_skip()` returns `None` instead of the probe errors so that tests can be skipped when no endpoint is
+///reachable, e.g. in an offline sandbox.
+///## Example
+///```rust
+///use ethrs::testing;
+///use std::error::Error;
+///
+///fn test_chain_id() -> Result<(), Box<dyn Error>> {
+///  let Some(provider) = testing::sepolia().provider_or_skip() else {
+///      return Ok(());
+///  };
+///  assert_eq!(provider.chain_id()?, 11155111);
+///  Ok(())
+///}
+///# test_chain_id().unwrap();
+///```
+#[derive(Debug, Clone)]
+pub struct TestEndpoints {
+    urls: Vec<String>,
+    chain_id: Option<u64>,
+    attempts: u32,
+    backoff: Duration,
+    timeout: Duration,
+}
+
+///The `sepolia()` function returns the Sepolia endpoints listed in the `ETHRS_SEPOLIA_RPC_URLS` environment variable,
+///or the public `SEPOLIA_ENDPOINTS`, checked to serve chain 11155111.
+pub fn sepolia() -> TestEndpoints {
+    TestEndpoints::from_env(ENDPOINTS_VAR, &SEPOLIA_ENDPOINTS).with_chain_id(11155111)
+}
+
+impl TestEndpoints {
+    ///The `TestEndpoints::new()` associated function takes a list of JSON-RPC URLs and returns `TestEndpoints` making 3
+    ///attempts per request, 1 second apart, with a 10 second timeout per attempt.
+    pub fn new(urls: &[&str]) -> TestEndpoints {
+        TestEndpoints {
+            urls: urls.iter().map(|url| url.to_string()).collect(),
+            chain_id: None,
+            attempts: 3,
+            backoff: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    ///The `TestEndpoints::from_env()` associated function takes the name of an environment variable and default URLs,
+    ///and returns `TestEndpoints` over the comma-separated URLs of the variable if it is set and not empty, or over the
+    ///defaults otherwise.
+    pub fn from_env(var: &str, defaults: &[&str]) -> TestEndpoints {
+        let value = env::var(var).unwrap_or_default();
+        let urls: Vec<&str> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .collect();
+        match urls.is_empty() {
+            true => TestEndpoints::new(defaults),
+            false => TestEndpoints::new(&urls),
+        }
+    }
+
+    ///Sets the chain id endpoints must serve to be considered reachable, which catches misconfigured endpoint lists.
+    pub fn with_chain_id(mut self, chain_id: u64) -> TestEndpoints {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    ///Sets how many times requests are attempted before failing, at least once. Endpoints are probed once.
+    pub fn with_attempts(mut self, attempts: u32) -> TestEndpoints {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    ///Sets the delay between attempts.
+    pub fn with_backoff(mut self, backoff: Duration) -> TestEndpoints {
+        self.backoff = backoff;
+        self
+    }
+
+    ///Sets the time each attempt may take.
+    pub fn with_timeout(mut self, timeout: Duration) -> TestEndpoints {
+        self.timeout = timeout;
+        self
+    }
+
+    ///Returns the endpoint URLs.
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    ///The `retry()` function calls `f` until it succeeds, up to the configured number of attempts, and returns its
+    ///last result.
+    pub fn retry<T>(
+        &self,
+        mut f: impl FnMut() -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        let mut attempt = 1;
+        loop {
+            match f() {
+                Err(_) if attempt < self.attempts => {
+                    attempt += 1;
+                    thread::sleep(self.backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn probe(&self, url: &str) -> Result<Provider, Box<dyn Error>> {
+        let provider = Provider::builder(url).timeout(self.timeout).build()?;
+        let chain_id = provider.chain_id()?;
+        match self.chain_id {
+            Some(expected) if expected != chain_id => {
+                Err(format!("{url} serves chain {chain_id} instead of {expected}").into())
+            }
+            _ => Ok(provider),
+        }
+    }
+
+    ///The `provider()` function probes every endpoint once and returns a `Provider` over the reachable ones as
+    ///`Ok(Provider)`, which fails over to the next endpoint and retries when a request fails on all of them. Returns
+    ///the probe error of every endpoint as an `Err()` when no endpoint is reachable.
+    pub fn provider(&self) -> Result<Provider, ProbeErrors> {
+        let mut reachable = vec![];
+        let mut errors = vec![];
+        for url in &self.urls {
+            match self.probe(url) {
+                Ok(provider) => reachable.push(provider),
+                Err(err) => errors.push((url.clone(), err)),
+            }
+        }
+        if reachable.is_empty() {
+            return Err(errors);
+        }
+        let transport = Retrying {
+            endpoints: self.clone(),
+            inner: FallbackProvider::from_providers(reachable),
+        };
+        Ok(Provider::new(transport.inner.active().url()).with_transport(transport))
+    }
+
+    ///The `provider_or_skip()` function returns the `Provider` of `provider()` as `Some(Provider)`, or `None` when no
+    ///endpoint is reachable so that the calling test can be skipped.
+    ///## Panics
+    ///Panics with the probe errors when no endpoint is reachable and `ETHRS_REQUIRE_ENDPOINTS` is set to `1`, so that
+    ///a CI run cannot pass without testing anything.
+    pub fn provider_or_skip(&self) -> Option<Provider> {
+        match self.provider() {
+            Ok(provider) => Some(provider),
+            Err(_) if env::var(REQUIRE_ENDPOINTS_VAR).map_or(true, |value| value != "1") => None,
+            Err(errors) => {
+                let errors: Vec<String> = errors
+                    .iter()
+                    .map(|(url, err)| format!("{url}: {err}"))
+                    .collect();
+                panic!(
+                    "no reachable endpoint and {REQUIRE_ENDPOINTS_VAR} is set: {}",
+                    errors.join(", ")
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Retrying {
+    endpoints: TestEndpoints,
+    inner: FallbackProvider,
+}
+
+impl Transport for Retrying {
+    fn send(&self, payload: &serde_json::Value) -> Result<serde_json::Value, Box<dyn Error>> {
+        self.endpoints.retry(|| self.inner.send(payload))
+    }
+}
@@ -0,0 +1,499 @@
+//!The trace module provides typed access to the geth `debug_*` tracing namespace, i.e. re-executing transactions with
+//!the default struct logger or one of the built-in `callTracer` and `prestateTracer` tracers. Transactions, calls and
+//!whole blocks can be traced with the same options and result types.
+//!
+//!It also provides the Parity-style `trace_*` namespace exposed by Erigon, Nethermind and Reth, which returns flat
+//!traces of every call, create, self-destruct and block reward.
+use primitive_types::U256;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::json;
+
+use crate::provider::{CallInput, Provider};
+use crate::types::{Address, BlockId, Bytes, H256};
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+///The `Tracer` enum selects the tracer a transaction is re-executed with. Custom tracers are passed as their name or
+///JavaScript source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tracer {
+    CallTracer,
+    PrestateTracer,
+    Custom(String),
+}
+
+impl Serialize for Tracer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            Tracer::CallTracer => "callTracer",
+            Tracer::PrestateTracer => "prestateTracer",
+            Tracer::Custom(tracer) => tracer,
+        })
+    }
+}
+
+///The `TracerOptions` struct configures a `debug_trace*` call. Without a tracer, the default struct logger is used and
+///the `disable_*`/`enable_*` flags control what it records.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracer: Option<Tracer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracer_config: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub disable_storage: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub disable_stack: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub enable_memory: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub enable_return_data: bool,
+}
+
+impl TracerOptions {
+    ///Returns the options for the `callTracer`, optionally only tracing the top-level call and collecting logs.
+    pub fn call_tracer(only_top_call: bool, with_log: bool) -> TracerOptions {
+        TracerOptions {
+            tracer: Some(Tracer::CallTracer),
+            tracer_config: Some(json!({"onlyTopCall": only_top_call, "withLog": with_log})),
+            ..Default::default()
+        }
+    }
+
+    ///Returns the options for the `prestateTracer`, optionally returning the state before and after the transaction.
+    pub fn prestate_tracer(diff_mode: bool) -> TracerOptions {
+        TracerOptions {
+            tracer: Some(Tracer::PrestateTracer),
+            tracer_config: Some(json!({ "diffMode": diff_mode })),
+            ..Default::default()
+        }
+    }
+}
+
+///The `StructLog` struct is a single opcode step recorded by the default struct logger.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u64,
+    pub op: String,
+    pub gas: u64,
+    pub gas_cost: u64,
+    pub depth: u64,
+    pub error: Option<String>,
+    pub stack: Option<Vec<U256>>,
+    pub memory: Option<Vec<String>>,
+    pub storage: Option<BTreeMap<H256, H256>>,
+    pub return_data: Option<Bytes>,
+    pub refund: Option<u64>,
+}
+
+///The `DefaultFrame` struct is the result of the default struct logger.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultFrame {
+    pub failed: bool,
+    pub gas: u64,
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
+}
+
+///The `CallLog` struct is a log emitted within a call, as collected by the `callTracer` with `withLog` enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallLog {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+}
+
+///The `CallFrame` struct is a call recorded by the `callTracer`, along with the calls it made. The `kind` is the
+///opcode of the call, e.g. `CALL`, `DELEGATECALL` or `CREATE2`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub value: Option<U256>,
+    pub gas: U256,
+    pub gas_used: U256,
+    pub input: Bytes,
+    pub output: Option<Bytes>,
+    pub error: Option<String>,
+    pub revert_reason: Option<String>,
+    #[serde(default)]
+    pub calls: Vec<CallFrame>,
+    #[serde(default)]
+    pub logs: Vec<CallLog>,
+}
+
+///The `AccountState` struct is the state of an account as recorded by the `prestateTracer`. Fields that are unchanged
+///are omitted in diff mode.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    pub balance: Option<U256>,
+    pub nonce: Option<u64>,
+    pub code: Option<Bytes>,
+    #[serde(default)]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+///The `PrestateFrame` enum is the result of the `prestateTracer`: the state of every account the transaction touched,
+///or the state of the modified accounts before and after it in diff mode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrestateFrame {
+    Diff {
+        pre: BTreeMap<Address, AccountState>,
+        post: BTreeMap<Address, AccountState>,
+    },
+    Default(BTreeMap<Address, AccountState>),
+}
+
+///The `GethTrace` enum is the result of a `debug_trace*` call, depending on the tracer it was made with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum GethTrace {
+    Default(DefaultFrame),
+    Call(Box<CallFrame>),
+    Prestate(PrestateFrame),
+    Custom(serde_json::Value),
+}
+
+///The `TraceResult` struct is the trace of one transaction of a block, or the error tracing it failed with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceResult {
+    pub tx_hash: Option<H256>,
+    pub result: Option<GethTrace>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTraceResult {
+    tx_hash: Option<H256>,
+    result: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+impl GethTrace {
+    fn from_value(
+        options: &TracerOptions,
+        value: serde_json::Value,
+    ) -> Result<GethTrace, Box<dyn Error>> {
+        Ok(match &options.tracer {
+            None => GethTrace::Default(serde_json::from_value(value)?),
+            Some(Tracer::CallTracer) => GethTrace::Call(serde_json::from_value(value)?),
+            Some(Tracer::PrestateTracer) => GethTrace::Prestate(serde_json::from_value(value)?),
+            Some(Tracer::Custom(_)) => GethTrace::Custom(value),
+        })
+    }
+}
+
+///The `debug_trace_transaction()` function takes a provider exposing the `debug_*` namespace, a transaction hash and the
+///tracer options, and attempts to return the trace of re-executing the transaction as `Ok(GethTrace)`, with the variant
+///matching the tracer. Returns an `Err()` on JSON-RPC errors.
+///## Example
+///```rust,no_run
+///use ethrs::provider::Provider;
+///use ethrs::trace::{debug_trace_transaction, GethTrace, TracerOptions};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let txhash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8".parse()?;
+///  if let GethTrace::Call(frame) = debug_trace_transaction(&provider, txhash, &TracerOptions::call_tracer(false, false))? {
+///      println!("{} reverted: {:?}", frame.kind, frame.revert_reason);
+///  }
+///  Ok(())
+///}
+///```
+pub fn debug_trace_transaction(
+    provider: &Provider,
+    txhash: H256,
+    options: &TracerOptions,
+) -> Result<GethTrace, Box<dyn Error>> {
+    let value = provider.request("debug_traceTransaction", json!([txhash, options]))?;
+    GethTrace::from_value(options, value)
+}
+
+///The `debug_trace_call()` function takes a provider exposing the `debug_*` namespace, a call input struct, an optional
+///block id and the tracer options, and attempts to return the trace of executing the call on top of the block as
+///`Ok(GethTrace)`, with the variant matching the tracer. Returns an `Err()` on JSON-RPC errors.
+///## Example
+///```rust,no_run
+///use ethrs::provider::{CallInput, Provider};
+///use ethrs::trace::{debug_trace_call, GethTrace, TracerOptions};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let tx = CallInput {
+///      from: Some("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?),
+///      to: "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?,
+///      gas: None,
+///      gas_price: None,
+///      value: None,
+///      data: Some("0xd09de08a".to_owned()),
+///  };
+///  if let GethTrace::Prestate(state) = debug_trace_call(&provider, tx, None, &TracerOptions::prestate_tracer(true))? {
+///      println!("{state:?}");
+///  }
+///  Ok(())
+///}
+///```
+pub fn debug_trace_call(
+    provider: &Provider,
+    tx: CallInput,
+    block: Option<BlockId>,
+    options: &TracerOptions,
+) -> Result<GethTrace, Box<dyn Error>> {
+    let value = provider.request(
+        "debug_traceCall",
+        json!([tx, block.unwrap_or_default(), options]),
+    )?;
+    GethTrace::from_value(options, value)
+}
+
+///The `debug_trace_block_by_number()` function takes a provider exposing the `debug_*` namespace, a block id and the
+///tracer options, and attempts to return the traces of every transaction in the block, in order, as
+///`Ok(Vec<TraceResult>)`. Returns an `Err()` on JSON-RPC errors.
+pub fn debug_trace_block_by_number(
+    provider: &Provider,
+    block: BlockId,
+    options: &TracerOptions,
+) -> Result<Vec<TraceResult>, Box<dyn Error>> {
+    let results = provider.request("debug_traceBlockByNumber", json!([block, options]))?;
+    trace_results(options, results)
+}
+
+///The `debug_trace_block_by_hash()` function takes a provider exposing the `debug_*` namespace, a block hash and the
+///tracer options, and attempts to return the traces of every transaction in the block, in order, as
+///`Ok(Vec<TraceResult>)`. Returns an `Err()` on JSON-RPC errors.
+pub fn debug_trace_block_by_hash(
+    provider: &Provider,
+    block_hash: H256,
+    options: &TracerOptions,
+) -> Result<Vec<TraceResult>, Box<dyn Error>> {
+    let results = provider.request("debug_traceBlockByHash", json!([block_hash, options]))?;
+    trace_results(options, results)
+}
+
+fn trace_results(
+    options: &TracerOptions,
+    results: Vec<RawTraceResult>,
+) -> Result<Vec<TraceResult>, Box<dyn Error>> {
+    results
+        .into_iter()
+        .map(|raw| {
+            Ok(TraceResult {
+                tx_hash: raw.tx_hash,
+                result: raw
+                    .result
+                    .map(|value| GethTrace::from_value(options, value))
+                    .transpose()?,
+                error: raw.error,
+            })
+        })
+        .collect()
+}
+
+///The `CallAction` struct is the action of a `call` trace. The `call_type` is e.g. `call`, `delegatecall` or
+///`staticcall`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallAction {
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    pub gas: U256,
+    pub input: Bytes,
+    pub call_type: String,
+}
+
+///The `CreateAction` struct is the action of a `create` trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAction {
+    pub from: Address,
+    pub value: U256,
+    pub gas: U256,
+    pub init: Bytes,
+}
+
+///The `SuicideAction` struct is the action of a `suicide` trace, i.e. a `SELFDESTRUCT`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuicideAction {
+    pub address: Address,
+    pub refund_address: Address,
+    pub balance: U256,
+}
+
+///The `RewardAction` struct is the action of a `reward` trace. The `reward_type` is `block` or `uncle`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardAction {
+    pub author: Address,
+    pub value: U256,
+    pub reward_type: String,
+}
+
+///The `Action` enum is the action of a flat trace, tagged by its `type`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "action", rename_all = "lowercase")]
+pub enum Action {
+    Call(CallAction),
+    Create(CreateAction),
+    #[serde(alias = "selfdestruct")]
+    Suicide(SuicideAction),
+    Reward(RewardAction),
+}
+
+///The `CallOutput` struct is the result of a successful `call` trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallOutput {
+    pub gas_used: U256,
+    pub output: Bytes,
+}
+
+///The `CreateOutput` struct is the result of a successful `create` trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOutput {
+    pub gas_used: U256,
+    pub code: Bytes,
+    pub address: Address,
+}
+
+///The `TraceOutput` enum is the result of a successful `call` or `create` trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TraceOutput {
+    Create(CreateOutput),
+    Call(CallOutput),
+}
+
+///The `Trace` struct is a flat trace, located in the call tree of its transaction by `trace_address`. Failed traces
+///have an `error` instead of a `result`, and traces returned by `trace_call()` are not located in a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trace {
+    #[serde(flatten)]
+    pub action: Action,
+    pub result: Option<TraceOutput>,
+    pub error: Option<String>,
+    pub subtraces: usize,
+    pub trace_address: Vec<usize>,
+    pub transaction_hash: Option<H256>,
+    pub transaction_position: Option<u64>,
+    pub block_hash: Option<H256>,
+    pub block_number: Option<u64>,
+}
+
+///The `TraceType` enum selects what `trace_call()` returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceType {
+    Trace,
+    VmTrace,
+    StateDiff,
+}
+
+///The `TraceResults` struct is the result of `trace_call()`, with the fields that were not requested left empty.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceResults {
+    pub output: Bytes,
+    #[serde(default)]
+    pub trace: Vec<Trace>,
+    pub vm_trace: Option<serde_json::Value>,
+    pub state_diff: Option<serde_json::Value>,
+}
+
+///The `TraceFilter` struct selects the traces returned by `trace_filter()`. Empty address lists match every address.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockId>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub from_address: Vec<Address>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub to_address: Vec<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+///The `trace_transaction()` function takes a provider exposing the `trace_*` namespace and a transaction hash, and
+///attempts to return the flat traces of the transaction as `Ok(Some(Vec<Trace>))`. Returns `Ok(None)` if the
+///transaction is not found and returns an `Err()` on JSON-RPC errors.
+///## Example
+///```rust,no_run
+///use ethrs::provider::Provider;
+///use ethrs::trace::{trace_transaction, Action};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let provider = Provider::new("http://localhost:8545");
+///  let txhash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8".parse()?;
+///  for trace in trace_transaction(&provider, txhash)?.unwrap_or_default() {
+///      if let Action::Call(call) = trace.action {
+///          println!("{:?} {} -> {}", trace.trace_address, call.from, call.to);
+///      }
+///  }
+///  Ok(())
+///}
+///```
+pub fn trace_transaction(
+    provider: &Provider,
+    txhash: H256,
+) -> Result<Option<Vec<Trace>>, Box<dyn Error>> {
+    provider.request("trace_transaction", json!([txhash]))
+}
+
+///The `trace_block()` function takes a provider exposing the `trace_*` namespace and a block id, and attempts to return
+///the flat traces of every transaction in the block, followed by the block rewards, as `Ok(Some(Vec<Trace>))`. Returns
+///`Ok(None)` if the block is not found and returns an `Err()` on JSON-RPC errors.
+pub fn trace_block(
+    provider: &Provider,
+    block: BlockId,
+) -> Result<Option<Vec<Trace>>, Box<dyn Error>> {
+    provider.request("trace_block", json!([block]))
+}
+
+///The `trace_filter()` function takes a provider exposing the `trace_*` namespace and a trace filter, and attempts to
+///return the matching flat traces as `Ok(Vec<Trace>)`. Returns an `Err()` on JSON-RPC errors.
+pub fn trace_filter(
+    provider: &Provider,
+    filter: &TraceFilter,
+) -> Result<Vec<Trace>, Box<dyn Error>> {
+    provider.request("trace_filter", json!([filter]))
+}
+
+///The `trace_call()` function takes a provider exposing the `trace_*` namespace, a call input struct, the trace types to
+///return and an optional block id, and attempts to return the output and traces of executing the call on top of the
+///block as `Ok(TraceResults)`. Returns an `Err()` on JSON-RPC errors.
+pub fn trace_call(
+    provider: &Provider,
+    tx: CallInput,
+    trace_types: &[TraceType],
+    block: Option<BlockId>,
+) -> Result<TraceResults, Box<dyn Error>> {
+    provider.request(
+        "trace_call",
+        json!([tx, trace_types, block.unwrap_or_default()]),
+    )
+}
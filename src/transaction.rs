@@ -0,0 +1,840 @@
+//!The transaction module provides the `TypedTransaction` envelope of EIP-2718, covering legacy, access list (EIP-2930),
+//!dynamic fee (EIP-1559), blob (EIP-4844) and set code (EIP-7702) transactions, with their RLP encoding and the hashes
+//!they are signed and identified by.
+use crate::provider::{AccessList, AccessListItem, Transaction};
+use crate::rlp::{self, Decodable, Encodable, RlpItem};
+use crate::signature::Signature;
+use crate::types::{Address, Bytes, H256, U256};
+use crate::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use std::error::Error;
+
+///The `LegacyTransaction` struct is a transaction from before EIP-2718. Transactions with a `chain_id` are signed with
+///replay protection, as specified by EIP-155.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LegacyTransaction {
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    pub gas_price: U256,
+    pub gas_limit: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+}
+
+///The `Eip2930Transaction` struct is a type 1 transaction, which declares the accounts and storage slots it accesses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip2930Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub gas_price: U256,
+    pub gas_limit: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+}
+
+///The `Eip1559Transaction` struct is a type 2 transaction, which pays the base fee of its block and a priority fee.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip1559Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+}
+
+///The `Eip4844Transaction` struct is a type 3 transaction, which carries blobs identified by their versioned hashes.
+///Blob transactions cannot create contracts, so `to` is required.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip4844Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    pub max_fee_per_blob_gas: U256,
+    pub blob_versioned_hashes: Vec<H256>,
+}
+
+///The `Eip7702Transaction` struct is a type 4 transaction, which sets the code of the authorizing accounts. Set code
+///transactions cannot create contracts, so `to` is required.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip7702Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    pub authorization_list: Vec<Authorization>,
+}
+
+///The `Authorization` struct is a signed EIP-7702 authorization, delegating the code of its signer to `address`. A
+///`chain_id` of zero authorizes the delegation on every chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Authorization {
+    pub chain_id: U256,
+    pub address: Address,
+    #[serde(with = "crate::utils::quantity")]
+    pub nonce: u64,
+    #[serde(with = "y_parity")]
+    pub y_parity: u8,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Authorization {
+    ///Returns the hash the authority signs, i.e. the Keccak-256 hash of `0x05 || rlp([chain_id, address, nonce])`.
+    pub fn signing_hash(&self) -> H256 {
+        let mut payload = vec![0x05];
+        payload.extend(rlp::encode(&RlpItem::List(vec![
+            self.chain_id.to_rlp(),
+            self.address.to_rlp(),
+            self.nonce.to_rlp(),
+        ])));
+        H256(keccak256(&payload))
+    }
+}
+
+impl Encodable for Authorization {
+    fn to_rlp(&self) -> RlpItem {
+        RlpItem::List(vec![
+            self.chain_id.to_rlp(),
+            self.address.to_rlp(),
+            self.nonce.to_rlp(),
+            self.y_parity.to_rlp(),
+            self.r.to_rlp(),
+            self.s.to_rlp(),
+        ])
+    }
+}
+
+impl Decodable for Authorization {
+    fn from_rlp(item: &RlpItem) -> Result<Self, Box<dyn Error>> {
+        let fields = rlp::fields(item, 6)?;
+        Ok(Authorization {
+            chain_id: U256::from_rlp(&fields[0])?,
+            address: Address::from_rlp(&fields[1])?,
+            nonce: u64::from_rlp(&fields[2])?,
+            y_parity: u8::from_rlp(&fields[3])?,
+            r: U256::from_rlp(&fields[4])?,
+            s: U256::from_rlp(&fields[5])?,
+        })
+    }
+}
+
+///Serializes the `u8` y parity of authorizations as a hex quantity.
+mod y_parity {
+    use serde::{de, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(value: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::utils::quantity::serialize(&u64::from(*value), serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+        let value = crate::utils::quantity::deserialize(deserializer)?;
+        u8::try_from(value).map_err(|_| de::Error::custom(format!("Invalid y parity: {value}")))
+    }
+}
+
+///The `TypedTransaction` enum is an unsigned transaction of any of the types of EIP-2718. Typed transactions are
+///encoded as their type byte followed by the RLP list of their fields, and legacy transactions as the RLP list alone.
+///## Example
+///```rust
+///use ethrs::transaction::{LegacyTransaction, TypedTransaction};
+///use ethrs::types::U256;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  // the example transaction of EIP-155
+///  let transaction = TypedTransaction::Legacy(LegacyTransaction {
+///    chain_id: Some(1),
+///    nonce: 9,
+///    gas_price: U256::from(20_000_000_000u64),
+///    gas_limit: 21000,
+///    to: Some("0x3535353535353535353535353535353535353535".parse()?),
+///    value: U256::from(1_000_000_000_000_000_000u64),
+///    ..Default::default()
+///  });
+///  assert_eq!(
+///    transaction.signing_hash().to_string(),
+///    "0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+///  );
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypedTransaction {
+    Legacy(LegacyTransaction),
+    Eip2930(Eip2930Transaction),
+    Eip1559(Eip1559Transaction),
+    Eip4844(Eip4844Transaction),
+    Eip7702(Eip7702Transaction),
+}
+
+impl TypedTransaction {
+    ///Returns the EIP-2718 type of the transaction, `0` for legacy transactions.
+    pub fn tx_type(&self) -> u8 {
+        match self {
+            TypedTransaction::Legacy(_) => 0,
+            TypedTransaction::Eip2930(_) => 1,
+            TypedTransaction::Eip1559(_) => 2,
+            TypedTransaction::Eip4844(_) => 3,
+            TypedTransaction::Eip7702(_) => 4,
+        }
+    }
+
+    ///Returns the chain id of the transaction, or `None` for legacy transactions without replay protection.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.chain_id,
+            TypedTransaction::Eip2930(tx) => Some(tx.chain_id),
+            TypedTransaction::Eip1559(tx) => Some(tx.chain_id),
+            TypedTransaction::Eip4844(tx) => Some(tx.chain_id),
+            TypedTransaction::Eip7702(tx) => Some(tx.chain_id),
+        }
+    }
+
+    ///Returns the nonce of the transaction.
+    pub fn nonce(&self) -> u64 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.nonce,
+            TypedTransaction::Eip2930(tx) => tx.nonce,
+            TypedTransaction::Eip1559(tx) => tx.nonce,
+            TypedTransaction::Eip4844(tx) => tx.nonce,
+            TypedTransaction::Eip7702(tx) => tx.nonce,
+        }
+    }
+
+    ///Returns the gas limit of the transaction.
+    pub fn gas_limit(&self) -> u64 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.gas_limit,
+            TypedTransaction::Eip2930(tx) => tx.gas_limit,
+            TypedTransaction::Eip1559(tx) => tx.gas_limit,
+            TypedTransaction::Eip4844(tx) => tx.gas_limit,
+            TypedTransaction::Eip7702(tx) => tx.gas_limit,
+        }
+    }
+
+    ///Returns the recipient of the transaction, or `None` for contract creations.
+    pub fn to(&self) -> Option<Address> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.to,
+            TypedTransaction::Eip2930(tx) => tx.to,
+            TypedTransaction::Eip1559(tx) => tx.to,
+            TypedTransaction::Eip4844(tx) => Some(tx.to),
+            TypedTransaction::Eip7702(tx) => Some(tx.to),
+        }
+    }
+
+    ///Returns the value of the transaction in wei.
+    pub fn value(&self) -> U256 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.value,
+            TypedTransaction::Eip2930(tx) => tx.value,
+            TypedTransaction::Eip1559(tx) => tx.value,
+            TypedTransaction::Eip4844(tx) => tx.value,
+            TypedTransaction::Eip7702(tx) => tx.value,
+        }
+    }
+
+    ///Returns the input data of the transaction.
+    pub fn input(&self) -> &Bytes {
+        match self {
+            TypedTransaction::Legacy(tx) => &tx.input,
+            TypedTransaction::Eip2930(tx) => &tx.input,
+            TypedTransaction::Eip1559(tx) => &tx.input,
+            TypedTransaction::Eip4844(tx) => &tx.input,
+            TypedTransaction::Eip7702(tx) => &tx.input,
+        }
+    }
+
+    ///Returns the access list of the transaction, which is empty for legacy transactions.
+    pub fn access_list(&self) -> &[AccessListItem] {
+        match self {
+            TypedTransaction::Legacy(_) => &[],
+            TypedTransaction::Eip2930(tx) => &tx.access_list,
+            TypedTransaction::Eip1559(tx) => &tx.access_list,
+            TypedTransaction::Eip4844(tx) => &tx.access_list,
+            TypedTransaction::Eip7702(tx) => &tx.access_list,
+        }
+    }
+
+    ///Returns the highest price per gas the transaction pays, i.e. its gas price or its max fee per gas.
+    pub fn max_fee_per_gas(&self) -> U256 {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.gas_price,
+            TypedTransaction::Eip2930(tx) => tx.gas_price,
+            TypedTransaction::Eip1559(tx) => tx.max_fee_per_gas,
+            TypedTransaction::Eip4844(tx) => tx.max_fee_per_gas,
+            TypedTransaction::Eip7702(tx) => tx.max_fee_per_gas,
+        }
+    }
+
+    ///Raises the fees of the transaction by the given percentage, rounded up and by at least one wei, as nodes only
+    ///accept a replacement of a pending transaction paying more than it, by at least 10% for most of them. The gas price
+    ///of legacy and access list transactions is raised, as are the max fee and max priority fee per gas of the others
+    ///and the max fee per blob gas of blob transactions.
+    ///## Example
+    ///```rust
+    ///use ethrs::transaction::{LegacyTransaction, TypedTransaction};
+    ///use ethrs::types::U256;
+    ///
+    ///let mut transaction = TypedTransaction::from(LegacyTransaction { gas_price: U256::from(1001), ..Default::default() });
+    ///transaction.bump_fees(10);
+    ///assert_eq!(transaction.max_fee_per_gas(), U256::from(1102));
+    ///```
+    pub fn bump_fees(&mut self, percent: u64) {
+        let bump = |fee: &mut U256| {
+            let raised = (fee.saturating_mul(U256::from(100 + percent)) + 99) / 100;
+            *fee = raised.max(fee.saturating_add(U256::one()));
+        };
+        match self {
+            TypedTransaction::Legacy(tx) => bump(&mut tx.gas_price),
+            TypedTransaction::Eip2930(tx) => bump(&mut tx.gas_price),
+            TypedTransaction::Eip1559(tx) => {
+                bump(&mut tx.max_fee_per_gas);
+                bump(&mut tx.max_priority_fee_per_gas);
+            }
+            TypedTransaction::Eip4844(tx) => {
+                bump(&mut tx.max_fee_per_gas);
+                bump(&mut tx.max_priority_fee_per_gas);
+                bump(&mut tx.max_fee_per_blob_gas);
+            }
+            TypedTransaction::Eip7702(tx) => {
+                bump(&mut tx.max_fee_per_gas);
+                bump(&mut tx.max_priority_fee_per_gas);
+            }
+        }
+    }
+
+    ///Returns the fields of the transaction, without its signature.
+    fn fields(&self) -> Vec<RlpItem> {
+        match self {
+            TypedTransaction::Legacy(tx) => vec![
+                tx.nonce.to_rlp(),
+                tx.gas_price.to_rlp(),
+                tx.gas_limit.to_rlp(),
+                tx.to.to_rlp(),
+                tx.value.to_rlp(),
+                tx.input.to_rlp(),
+            ],
+            TypedTransaction::Eip2930(tx) => vec![
+                tx.chain_id.to_rlp(),
+                tx.nonce.to_rlp(),
+                tx.gas_price.to_rlp(),
+                tx.gas_limit.to_rlp(),
+                tx.to.to_rlp(),
+                tx.value.to_rlp(),
+                tx.input.to_rlp(),
+                tx.access_list.to_rlp(),
+            ],
+            TypedTransaction::Eip1559(tx) => vec![
+                tx.chain_id.to_rlp(),
+                tx.nonce.to_rlp(),
+                tx.max_priority_fee_per_gas.to_rlp(),
+                tx.max_fee_per_gas.to_rlp(),
+                tx.gas_limit.to_rlp(),
+                tx.to.to_rlp(),
+                tx.value.to_rlp(),
+                tx.input.to_rlp(),
+                tx.access_list.to_rlp(),
+            ],
+            TypedTransaction::Eip4844(tx) => vec![
+                tx.chain_id.to_rlp(),
+                tx.nonce.to_rlp(),
+                tx.max_priority_fee_per_gas.to_rlp(),
+                tx.max_fee_per_gas.to_rlp(),
+                tx.gas_limit.to_rlp(),
+                tx.to.to_rlp(),
+                tx.value.to_rlp(),
+                tx.input.to_rlp(),
+                tx.access_list.to_rlp(),
+                tx.max_fee_per_blob_gas.to_rlp(),
+                tx.blob_versioned_hashes.to_rlp(),
+            ],
+            TypedTransaction::Eip7702(tx) => vec![
+                tx.chain_id.to_rlp(),
+                tx.nonce.to_rlp(),
+                tx.max_priority_fee_per_gas.to_rlp(),
+                tx.max_fee_per_gas.to_rlp(),
+                tx.gas_limit.to_rlp(),
+                tx.to.to_rlp(),
+                tx.value.to_rlp(),
+                tx.input.to_rlp(),
+                tx.access_list.to_rlp(),
+                tx.authorization_list.to_rlp(),
+            ],
+        }
+    }
+
+    ///Prefixes the RLP encoding of the fields with the type byte, except for legacy transactions.
+    fn envelope(&self, fields: Vec<RlpItem>) -> Vec<u8> {
+        let encoded = rlp::encode(&RlpItem::List(fields));
+        match self.tx_type() {
+            0 => encoded,
+            tx_type => [vec![tx_type], encoded].concat(),
+        }
+    }
+
+    ///Returns the encoding of the transaction that is signed. Legacy transactions with a chain id are encoded with the
+    ///chain id and two empty fields appended, as specified by EIP-155.
+    pub fn encode_unsigned(&self) -> Vec<u8> {
+        let mut fields = self.fields();
+        if let TypedTransaction::Legacy(LegacyTransaction {
+            chain_id: Some(chain_id),
+            ..
+        }) = self
+        {
+            fields.extend([chain_id.to_rlp(), 0u8.to_rlp(), 0u8.to_rlp()]);
+        }
+        self.envelope(fields)
+    }
+
+    ///Returns the hash the sender signs, i.e. the Keccak-256 hash of `encode_unsigned()`.
+    pub fn signing_hash(&self) -> H256 {
+        H256(keccak256(&self.encode_unsigned()))
+    }
+
+    ///Returns the `v` the signature is encoded with, i.e. the y parity for typed transactions, and `27` or `28`, or
+    ///`35 + 2 * chainId` or one more with EIP-155, for legacy transactions. Returns an `Err()` if the `v` of the
+    ///signature is invalid, or if the chain id is too large to be encoded in `v`.
+    fn v(&self, signature: &Signature) -> Result<u64, Box<dyn Error>> {
+        match self {
+            TypedTransaction::Legacy(legacy) => Ok(signature.with_chain_id(legacy.chain_id)?.v),
+            _ => Ok(u64::from(signature.y_parity()?)),
+        }
+    }
+
+    ///The `encode_signed()` function takes the signature of the transaction and returns the signed transaction as it is
+    ///sent with `eth_sendRawTransaction`. Blob transactions are encoded without their blobs, as they are included in
+    ///blocks. Returns an `Err()` if the `v` of the signature is invalid or cannot be encoded, i.e. for a legacy
+    ///transaction with a chain id too large for `v` to fit in 64 bits.
+    pub fn encode_signed(&self, signature: &Signature) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut fields = self.fields();
+        fields.extend([
+            self.v(signature)?.to_rlp(),
+            signature.r.to_rlp(),
+            signature.s.to_rlp(),
+        ]);
+        Ok(self.envelope(fields))
+    }
+
+    ///Returns the hash of the transaction signed with the signature, i.e. the Keccak-256 hash of `encode_signed()`, or
+    ///an `Err()` as `encode_signed()` does.
+    pub fn hash(&self, signature: &Signature) -> Result<H256, Box<dyn Error>> {
+        Ok(H256(keccak256(&self.encode_signed(signature)?)))
+    }
+
+    ///The `into_rpc()` function takes the signature of the transaction and its sender, and returns the transaction as
+    ///the node returns pending transactions, i.e. without block fields. The gas price of transactions with dynamic fees
+    ///is their max fee per gas. Returns an `Err()` as `encode_signed()` does.
+    pub fn into_rpc(
+        self,
+        signature: &Signature,
+        from: Address,
+    ) -> Result<Transaction, Box<dyn Error>> {
+        let hash = self.hash(signature)?;
+        let v = self.v(signature)?;
+        let mut transaction = Transaction {
+            block_hash: None,
+            block_number: None,
+            from,
+            gas: U256::from(self.gas_limit()),
+            gas_price: self.max_fee_per_gas(),
+            hash,
+            input: self.input().to_string(),
+            nonce: U256::from(self.nonce()),
+            to: self.to(),
+            transaction_index: None,
+            value: self.value(),
+            v: format!("0x{v:x}"),
+            r: format!("0x{:x}", signature.r),
+            s: format!("0x{:x}", signature.s),
+            transaction_type: Some(u64::from(self.tx_type())),
+            chain_id: self.chain_id(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+            authorization_list: None,
+        };
+        match self {
+            TypedTransaction::Legacy(_) => {}
+            TypedTransaction::Eip2930(tx) => transaction.access_list = Some(tx.access_list),
+            TypedTransaction::Eip1559(tx) => {
+                transaction.max_fee_per_gas = Some(tx.max_fee_per_gas);
+                transaction.max_priority_fee_per_gas = Some(tx.max_priority_fee_per_gas);
+                transaction.access_list = Some(tx.access_list);
+            }
+            TypedTransaction::Eip4844(tx) => {
+                transaction.max_fee_per_gas = Some(tx.max_fee_per_gas);
+                transaction.max_priority_fee_per_gas = Some(tx.max_priority_fee_per_gas);
+                transaction.access_list = Some(tx.access_list);
+                transaction.max_fee_per_blob_gas = Some(tx.max_fee_per_blob_gas);
+                transaction.blob_versioned_hashes = Some(tx.blob_versioned_hashes);
+            }
+            TypedTransaction::Eip7702(tx) => {
+                transaction.max_fee_per_gas = Some(tx.max_fee_per_gas);
+                transaction.max_priority_fee_per_gas = Some(tx.max_priority_fee_per_gas);
+                transaction.access_list = Some(tx.access_list);
+                transaction.authorization_list = Some(tx.authorization_list);
+            }
+        }
+        Ok(transaction)
+    }
+
+    ///The `decode()` function takes a raw signed transaction as a hexstring, as sent with `eth_sendRawTransaction`, and
+    ///attempts to return it with its signature, its hash and the sender recovered from its signature as
+    ///`Ok(SignedTransaction)`. Blob transactions are also accepted with their blobs, which are dropped. Returns an `Err()`
+    ///if the transaction is not valid RLP, has an unknown type or unexpected fields, or if its signature is invalid, which
+    ///includes signatures with a high `s`, as EIP-2 specifies.
+    ///## Example
+    ///```rust
+    ///use ethrs::transaction::TypedTransaction;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  // the example transaction of EIP-155
+    ///  let signed = TypedTransaction::decode(
+    ///    "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195\
+    ///     fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+    ///  )?;
+    ///  assert_eq!(signed.transaction.nonce(), 9);
+    ///  assert_eq!(signed.transaction.chain_id(), Some(1));
+    ///  assert_eq!(signed.from, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".parse()?);
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn decode(raw: &str) -> Result<SignedTransaction, Box<dyn Error>> {
+        let raw: Bytes = raw.parse()?;
+        TypedTransaction::decode_bytes(&raw)
+    }
+
+    ///The `decode_bytes()` function takes a raw signed transaction and attempts to return it as `decode()` does.
+    pub fn decode_bytes(raw: &[u8]) -> Result<SignedTransaction, Box<dyn Error>> {
+        let (transaction, signature) = match raw.first() {
+            None => return Err("Empty raw transaction".into()),
+            Some(0xc0..=0xff) => TypedTransaction::decode_legacy(&RlpItem::decode(raw)?)?,
+            Some(&tx_type @ 0x00..=0x7f) => {
+                let item = RlpItem::decode(&raw[1..])?;
+                match item.as_list()?.first() {
+                    // blob transactions are sent as [transaction, blobs, commitments, proofs]
+                    Some(inner @ RlpItem::List(_)) if tx_type == 3 => {
+                        rlp::fields(&item, 4)?;
+                        TypedTransaction::decode_typed(tx_type, inner)?
+                    }
+                    _ => TypedTransaction::decode_typed(tx_type, &item)?,
+                }
+            }
+            Some(byte) => return Err(format!("Invalid raw transaction prefix {byte:#x}").into()),
+        };
+        if !signature.is_low_s() {
+            return Err("Invalid transaction signature: s is above half the curve order".into());
+        }
+        let from = signature.recover(transaction.signing_hash())?;
+        Ok(SignedTransaction {
+            hash: transaction.hash(&signature)?,
+            transaction,
+            signature,
+            from,
+        })
+    }
+
+    fn decode_legacy(item: &RlpItem) -> Result<(TypedTransaction, Signature), Box<dyn Error>> {
+        let fields = rlp::fields(item, 9)?;
+        let signature = decode_signature(&fields[6..])?;
+        let chain_id = match signature.v {
+            27 | 28 => None,
+            v if v >= 35 => Some((v - 35) / 2),
+            v => return Err(format!("Invalid legacy transaction v: {v}").into()),
+        };
+        let transaction = TypedTransaction::Legacy(LegacyTransaction {
+            chain_id,
+            nonce: u64::from_rlp(&fields[0])?,
+            gas_price: U256::from_rlp(&fields[1])?,
+            gas_limit: u64::from_rlp(&fields[2])?,
+            to: Option::from_rlp(&fields[3])?,
+            value: U256::from_rlp(&fields[4])?,
+            input: Bytes::from_rlp(&fields[5])?,
+        });
+        Ok((transaction, signature))
+    }
+
+    fn decode_typed(
+        tx_type: u8,
+        item: &RlpItem,
+    ) -> Result<(TypedTransaction, Signature), Box<dyn Error>> {
+        let size = match tx_type {
+            1 => 8,
+            2 => 9,
+            3 => 11,
+            4 => 10,
+            tx_type => return Err(format!("Unsupported transaction type {tx_type:#x}").into()),
+        };
+        let fields = rlp::fields(item, size + 3)?;
+        let signature = decode_signature(&fields[size..])?;
+        if signature.v > 1 {
+            return Err(format!("Invalid y parity: {}", signature.v).into());
+        }
+        let transaction = match tx_type {
+            1 => TypedTransaction::Eip2930(Eip2930Transaction {
+                chain_id: u64::from_rlp(&fields[0])?,
+                nonce: u64::from_rlp(&fields[1])?,
+                gas_price: U256::from_rlp(&fields[2])?,
+                gas_limit: u64::from_rlp(&fields[3])?,
+                to: Option::from_rlp(&fields[4])?,
+                value: U256::from_rlp(&fields[5])?,
+                input: Bytes::from_rlp(&fields[6])?,
+                access_list: Vec::from_rlp(&fields[7])?,
+            }),
+            2 => TypedTransaction::Eip1559(Eip1559Transaction {
+                chain_id: u64::from_rlp(&fields[0])?,
+                nonce: u64::from_rlp(&fields[1])?,
+                max_priority_fee_per_gas: U256::from_rlp(&fields[2])?,
+                max_fee_per_gas: U256::from_rlp(&fields[3])?,
+                gas_limit: u64::from_rlp(&fields[4])?,
+                to: Option::from_rlp(&fields[5])?,
+                value: U256::from_rlp(&fields[6])?,
+                input: Bytes::from_rlp(&fields[7])?,
+                access_list: Vec::from_rlp(&fields[8])?,
+            }),
+            3 => TypedTransaction::Eip4844(Eip4844Transaction {
+                chain_id: u64::from_rlp(&fields[0])?,
+                nonce: u64::from_rlp(&fields[1])?,
+                max_priority_fee_per_gas: U256::from_rlp(&fields[2])?,
+                max_fee_per_gas: U256::from_rlp(&fields[3])?,
+                gas_limit: u64::from_rlp(&fields[4])?,
+                to: Address::from_rlp(&fields[5])?,
+                value: U256::from_rlp(&fields[6])?,
+                input: Bytes::from_rlp(&fields[7])?,
+                access_list: Vec::from_rlp(&fields[8])?,
+                max_fee_per_blob_gas: U256::from_rlp(&fields[9])?,
+                blob_versioned_hashes: Vec::from_rlp(&fields[10])?,
+            }),
+            _ => TypedTransaction::Eip7702(Eip7702Transaction {
+                chain_id: u64::from_rlp(&fields[0])?,
+                nonce: u64::from_rlp(&fields[1])?,
+                max_priority_fee_per_gas: U256::from_rlp(&fields[2])?,
+                max_fee_per_gas: U256::from_rlp(&fields[3])?,
+                gas_limit: u64::from_rlp(&fields[4])?,
+                to: Address::from_rlp(&fields[5])?,
+                value: U256::from_rlp(&fields[6])?,
+                input: Bytes::from_rlp(&fields[7])?,
+                access_list: Vec::from_rlp(&fields[8])?,
+                authorization_list: Vec::from_rlp(&fields[9])?,
+            }),
+        };
+        Ok((transaction, signature))
+    }
+}
+
+///Decodes the `v`, `r` and `s` fields ending a signed transaction.
+fn decode_signature(fields: &[RlpItem]) -> Result<Signature, Box<dyn Error>> {
+    Ok(Signature {
+        v: u64::from_rlp(&fields[0])?,
+        r: U256::from_rlp(&fields[1])?,
+        s: U256::from_rlp(&fields[2])?,
+    })
+}
+
+///The `SignedTransaction` struct is a decoded signed transaction, as returned by `TypedTransaction::decode()`. The
+///`signature` keeps its `v` as encoded in the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTransaction {
+    pub transaction: TypedTransaction,
+    pub signature: Signature,
+    ///The sender, recovered from the signature.
+    pub from: Address,
+    pub hash: H256,
+}
+
+impl SignedTransaction {
+    ///Returns the signed transaction as it is sent with `eth_sendRawTransaction`, or an `Err()` as
+    ///`TypedTransaction::encode_signed()` does.
+    pub fn encode(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.transaction.encode_signed(&self.signature)
+    }
+
+    ///Returns the transaction as the node returns pending transactions, as `TypedTransaction::into_rpc()` does.
+    pub fn into_rpc(self) -> Result<Transaction, Box<dyn Error>> {
+        self.transaction.into_rpc(&self.signature, self.from)
+    }
+}
+
+impl From<LegacyTransaction> for TypedTransaction {
+    fn from(transaction: LegacyTransaction) -> Self {
+        TypedTransaction::Legacy(transaction)
+    }
+}
+
+impl From<Eip2930Transaction> for TypedTransaction {
+    fn from(transaction: Eip2930Transaction) -> Self {
+        TypedTransaction::Eip2930(transaction)
+    }
+}
+
+impl From<Eip1559Transaction> for TypedTransaction {
+    fn from(transaction: Eip1559Transaction) -> Self {
+        TypedTransaction::Eip1559(transaction)
+    }
+}
+
+impl From<Eip4844Transaction> for TypedTransaction {
+    fn from(transaction: Eip4844Transaction) -> Self {
+        TypedTransaction::Eip4844(transaction)
+    }
+}
+
+impl From<Eip7702Transaction> for TypedTransaction {
+    fn from(transaction: Eip7702Transaction) -> Self {
+        TypedTransaction::Eip7702(transaction)
+    }
+}
+
+///Returns a field of a typed transaction returned by the node, which must be set.
+fn required<T: Clone>(field: &Option<T>, name: &str, tx_type: u64) -> Result<T, Box<dyn Error>> {
+    field
+        .clone()
+        .ok_or_else(|| format!("Missing {name} in type {tx_type} transaction").into())
+}
+
+///Returns a quantity of a transaction returned by the node, which must fit in a `u64`.
+fn to_u64(value: U256, name: &str) -> Result<u64, Box<dyn Error>> {
+    match value > U256::from(u64::MAX) {
+        true => Err(format!("Invalid {name}: {value} overflows a u64").into()),
+        false => Ok(value.as_u64()),
+    }
+}
+
+///Reads a transaction returned by the node, e.g. by `Provider::get_transaction_by_hash()`. The chain id of legacy
+///transactions the node returns without one is read from their EIP-155 `v`. Returns an `Err()` if a field of the type
+///of the transaction is missing, or if the type is unknown.
+impl TryFrom<&Transaction> for TypedTransaction {
+    type Error = Box<dyn Error>;
+
+    fn try_from(transaction: &Transaction) -> Result<Self, Self::Error> {
+        let tx_type = transaction.transaction_type.unwrap_or(0);
+        let nonce = to_u64(transaction.nonce, "nonce")?;
+        let gas_limit = to_u64(transaction.gas, "gas")?;
+        let input: Bytes = transaction.input.parse()?;
+        let chain_id = || required(&transaction.chain_id, "chainId", tx_type);
+        let access_list = transaction.access_list.clone().unwrap_or_default();
+        let fees = || -> Result<(U256, U256), Box<dyn Error>> {
+            Ok((
+                required(
+                    &transaction.max_priority_fee_per_gas,
+                    "maxPriorityFeePerGas",
+                    tx_type,
+                )?,
+                required(&transaction.max_fee_per_gas, "maxFeePerGas", tx_type)?,
+            ))
+        };
+        let to = || required(&transaction.to, "to", tx_type);
+        Ok(match tx_type {
+            0 => {
+                let v = Signature::try_from(transaction)?.v;
+                TypedTransaction::Legacy(LegacyTransaction {
+                    // some nodes return the chain id of pre-EIP-155 transactions, which do not sign it
+                    chain_id: match v {
+                        27 | 28 => None,
+                        _ => transaction
+                            .chain_id
+                            .or_else(|| (v >= 35).then(|| (v - 35) / 2)),
+                    },
+                    nonce,
+                    gas_price: transaction.gas_price,
+                    gas_limit,
+                    to: transaction.to,
+                    value: transaction.value,
+                    input,
+                })
+            }
+            1 => TypedTransaction::Eip2930(Eip2930Transaction {
+                chain_id: chain_id()?,
+                nonce,
+                gas_price: transaction.gas_price,
+                gas_limit,
+                to: transaction.to,
+                value: transaction.value,
+                input,
+                access_list,
+            }),
+            2 => {
+                let (max_priority_fee_per_gas, max_fee_per_gas) = fees()?;
+                TypedTransaction::Eip1559(Eip1559Transaction {
+                    chain_id: chain_id()?,
+                    nonce,
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                    gas_limit,
+                    to: transaction.to,
+                    value: transaction.value,
+                    input,
+                    access_list,
+                })
+            }
+            3 => {
+                let (max_priority_fee_per_gas, max_fee_per_gas) = fees()?;
+                TypedTransaction::Eip4844(Eip4844Transaction {
+                    chain_id: chain_id()?,
+                    nonce,
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                    gas_limit,
+                    to: to()?,
+                    value: transaction.value,
+                    input,
+                    access_list,
+                    max_fee_per_blob_gas: required(
+                        &transaction.max_fee_per_blob_gas,
+                        "maxFeePerBlobGas",
+                        tx_type,
+                    )?,
+                    blob_versioned_hashes: required(
+                        &transaction.blob_versioned_hashes,
+                        "blobVersionedHashes",
+                        tx_type,
+                    )?,
+                })
+            }
+            4 => {
+                let (max_priority_fee_per_gas, max_fee_per_gas) = fees()?;
+                TypedTransaction::Eip7702(Eip7702Transaction {
+                    chain_id: chain_id()?,
+                    nonce,
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                    gas_limit,
+                    to: to()?,
+                    value: transaction.value,
+                    input,
+                    access_list,
+                    authorization_list: required(
+                        &transaction.authorization_list,
+                        "authorizationList",
+                        tx_type,
+                    )?,
+                })
+            }
+            tx_type => return Err(format!("Unsupported transaction type {tx_type:#x}").into()),
+        })
+    }
+}
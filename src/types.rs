@@ -1 +1,646 @@
+//!The types module contains the strongly typed primitives used across the crate, such as `Address`, `H256`, `Bytes` and `U256`,
+//!along with helpers to parse, format and serialize `U256` values as decimal strings or hex quantities.
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use serde::ser::SerializeMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::utils::keccak256;
+
 pub use primitive_types::U256;
+
+///The `FromHexError` enum describes why a hexstring could not be parsed into one of the fixed-size types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FromHexError {
+    InvalidLength { expected: usize, found: usize },
+    InvalidCharacter,
+    InvalidChecksum,
+}
+
+impl fmt::Display for FromHexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromHexError::InvalidLength { expected, found } => write!(
+                f,
+                "Invalid length: expected {expected} hex characters, found {found}"
+            ),
+            FromHexError::InvalidCharacter => write!(f, "Invalid hex character"),
+            FromHexError::InvalidChecksum => write!(f, "Invalid EIP-55 checksum"),
+        }
+    }
+}
+
+impl Error for FromHexError {}
+
+fn decode_fixed<const N: usize>(s: &str) -> Result<[u8; N], FromHexError> {
+    let stripped = s.strip_prefix("0x").unwrap_or(s);
+    if stripped.len() != N * 2 {
+        return Err(FromHexError::InvalidLength {
+            expected: N * 2,
+            found: stripped.len(),
+        });
+    }
+    let mut bytes = [0u8; N];
+    hex::decode_to_slice(stripped, &mut bytes).map_err(|_| FromHexError::InvalidCharacter)?;
+    Ok(bytes)
+}
+
+///The `Address` struct is a 20-byte EVM account address. It is parsed from a hexstring via `FromStr`, validating the
+///EIP-55 checksum when the input is mixed-case, or via `Address::from_checksummed()`, which requires the checksum, and
+///is displayed in its checksummed form.
+///## Example
+///```rust
+///use ethrs::types::Address;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let address: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+///  assert_eq!(address.to_string(), "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+///  // mixed-case inputs must carry a valid checksum
+///  assert!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92267".parse::<Address>().is_err());
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address(pub [u8; 20]);
+
+impl Address {
+    ///The zero address, `0x0000000000000000000000000000000000000000`.
+    pub const ZERO: Address = Address([0u8; 20]);
+
+    ///Returns the raw bytes of the address.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    ///The `Address::from_checksummed()` associated function takes a hexstring and attempts to return the address as
+    ///`Ok(Address)`, like `FromStr` but rejecting inputs that are not EIP-55 checksummed, e.g. all-lowercase, so that
+    ///typos cannot slip through a single-case input. Returns an `Err()` if the input is not 20 hex-encoded bytes or
+    ///does not carry a valid checksum.
+    ///## Example
+    ///```rust
+    ///use ethrs::types::Address;
+    ///
+    ///assert!(Address::from_checksummed("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").is_ok());
+    ///assert!(Address::from_checksummed("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").is_err());
+    ///```
+    pub fn from_checksummed(s: &str) -> Result<Address, FromHexError> {
+        let address = Address(decode_fixed::<20>(s)?);
+        match address.to_checksum()[2..] == *s.strip_prefix("0x").unwrap_or(s) {
+            true => Ok(address),
+            false => Err(FromHexError::InvalidChecksum),
+        }
+    }
+
+    ///Returns the EIP-55 checksummed hexstring of the address.
+    pub fn to_checksum(&self) -> String {
+        let lower = hex::encode(self.0);
+        let hash = keccak256(lower.as_bytes());
+        let mut checksummed = String::with_capacity(42);
+        checksummed.push_str("0x");
+        for (i, c) in lower.chars().enumerate() {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+            if nibble >= 8 {
+                checksummed.push(c.to_ascii_uppercase());
+            } else {
+                checksummed.push(c);
+            }
+        }
+        checksummed
+    }
+}
+
+impl FromStr for Address {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let address = Address(decode_fixed::<20>(s)?);
+        let stripped = s.strip_prefix("0x").unwrap_or(s);
+        let is_mixed_case = stripped.chars().any(|c| c.is_ascii_lowercase())
+            && stripped.chars().any(|c| c.is_ascii_uppercase());
+        if is_mixed_case && address.to_checksum()[2..] != *stripped {
+            return Err(FromHexError::InvalidChecksum);
+        }
+        Ok(address)
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(bytes: [u8; 20]) -> Self {
+        Address(bytes)
+    }
+}
+
+impl AsRef<[u8]> for Address {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_checksum())
+    }
+}
+
+impl fmt::LowerHex for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_checksum())
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+///The `public_key_to_address()` function takes a secp256k1 public key, SEC1-encoded either compressed (33 bytes) or
+///uncompressed (65 bytes), or as the raw 64 bytes of its coordinates, and attempts to return the address of the key,
+///i.e. the last 20 bytes of the keccak256 hash of its coordinates, as `Ok(Address)`. Returns an `Err()` if the bytes
+///are not a point on the curve.
+///## Example
+///```rust
+///use ethrs::types::{public_key_to_address, Address};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let compressed = hex::decode("038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75")?;
+///  assert_eq!(
+///      public_key_to_address(&compressed)?,
+///      "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse::<Address>()?
+///  );
+///  Ok(())
+///}
+///```
+pub fn public_key_to_address(public_key: &[u8]) -> Result<Address, Box<dyn Error>> {
+    let key = match public_key.len() {
+        64 => k256::PublicKey::from_sec1_bytes(&[&[0x04], public_key].concat()),
+        _ => k256::PublicKey::from_sec1_bytes(public_key),
+    }
+    .map_err(|_| "Invalid secp256k1 public key")?;
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Ok(Address(address))
+}
+
+///The `H256` struct is a 32-byte hash, used for block hashes, transaction hashes, storage slots and log topics. It is
+///parsed from a hexstring via `FromStr` and displayed as a lowercase `0x`-prefixed hexstring.
+///## Example
+///```rust
+///use ethrs::types::H256;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let hash: H256 = "0x6c4925c897c45d377d8fb3ef59df7e0cf97604fc85b909bb806818368fdc6b07".parse()?;
+///  assert_eq!(hash.to_string(), "0x6c4925c897c45d377d8fb3ef59df7e0cf97604fc85b909bb806818368fdc6b07");
+///  // addresses are not hashes
+///  assert!("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse::<H256>().is_err());
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct H256(pub [u8; 32]);
+
+impl H256 {
+    ///The zero hash, `0x0000000000000000000000000000000000000000000000000000000000000000`.
+    pub const ZERO: H256 = H256([0u8; 32]);
+
+    ///Returns the raw bytes of the hash.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl FromStr for H256 {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(H256(decode_fixed::<32>(s)?))
+    }
+}
+
+impl From<[u8; 32]> for H256 {
+    fn from(bytes: [u8; 32]) -> Self {
+        H256(bytes)
+    }
+}
+
+impl AsRef<[u8]> for H256 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for H256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::LowerHex for H256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl Serialize for H256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for H256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+///The `Bytes` struct is an arbitrary-length byte string, serialized as a `0x`-prefixed hexstring. It is used for raw
+///binary data such as Merkle proof nodes.
+///## Example
+///```rust
+///use ethrs::types::Bytes;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let bytes: Bytes = "0xdeadbeef".parse()?;
+///  assert_eq!(bytes.as_ref(), &[0xde, 0xad, 0xbe, 0xef]);
+///  assert_eq!(bytes.to_string(), "0xdeadbeef");
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl FromStr for Bytes {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let stripped = s.strip_prefix("0x").unwrap_or(s);
+        match hex::decode(stripped) {
+            Ok(bytes) => Ok(Bytes(bytes)),
+            Err(hex::FromHexError::OddLength) => Err(FromHexError::InvalidLength {
+                expected: stripped.len() + 1,
+                found: stripped.len(),
+            }),
+            Err(_) => Err(FromHexError::InvalidCharacter),
+        }
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Bytes(bytes)
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(bytes: Bytes) -> Self {
+        bytes.0
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(&self.0))
+    }
+}
+
+impl fmt::LowerHex for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&hex::encode(&self.0))
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+///The `BlockId` enum identifies a block either by number, by hash or by one of the default block tags. It is accepted
+///by every `Provider` API that operates on a specific block, where `None` stands for `BlockId::Latest`.
+///## Example
+///```rust
+///use ethrs::types::BlockId;
+///
+///assert_eq!(BlockId::from(16), BlockId::Number(16));
+///assert_eq!(serde_json::to_string(&BlockId::from(16)).unwrap(), "\"0x10\"");
+///assert_eq!(serde_json::to_string(&BlockId::Finalized).unwrap(), "\"finalized\"");
+///```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BlockId {
+    Number(u64),
+    Hash(H256),
+    #[default]
+    Latest,
+    Earliest,
+    Pending,
+    Safe,
+    Finalized,
+}
+
+impl From<u64> for BlockId {
+    fn from(number: u64) -> Self {
+        BlockId::Number(number)
+    }
+}
+
+///Block numbers used to be returned as `u128` or `U256`: the `TryFrom` conversions keep such numbers usable as block
+///ids, and fail if they do not fit in a `u64`.
+impl TryFrom<u128> for BlockId {
+    type Error = Box<dyn Error>;
+
+    fn try_from(number: u128) -> Result<Self, Self::Error> {
+        match u64::try_from(number) {
+            Ok(number) => Ok(BlockId::Number(number)),
+            Err(_) => Err(format!("Block number {number} does not fit in a u64").into()),
+        }
+    }
+}
+
+impl TryFrom<U256> for BlockId {
+    type Error = Box<dyn Error>;
+
+    fn try_from(number: U256) -> Result<Self, Self::Error> {
+        match number.bits() <= 64 {
+            true => Ok(BlockId::Number(number.as_u64())),
+            false => Err(format!("Block number {number} does not fit in a u64").into()),
+        }
+    }
+}
+
+impl From<H256> for BlockId {
+    fn from(hash: H256) -> Self {
+        BlockId::Hash(hash)
+    }
+}
+
+impl Serialize for BlockId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BlockId::Number(number) => serializer.serialize_str(&format!("0x{number:x}")),
+            // EIP-1898 block hash parameter
+            BlockId::Hash(hash) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("blockHash", hash)?;
+                map.end()
+            }
+            BlockId::Latest => serializer.serialize_str("latest"),
+            BlockId::Earliest => serializer.serialize_str("earliest"),
+            BlockId::Pending => serializer.serialize_str("pending"),
+            BlockId::Safe => serializer.serialize_str("safe"),
+            BlockId::Finalized => serializer.serialize_str("finalized"),
+        }
+    }
+}
+
+///The `Wei` struct is an amount of ether, or of any native currency, in wei. It is serialized as a hex quantity like
+///`U256` and displayed in ether.
+///## Example
+///```rust
+///use ethrs::types::{Wei, U256};
+///
+///let balance = Wei::from_gwei(1_500_000_000);
+///assert_eq!(balance.to_ether(), "1.5");
+///assert_eq!(balance.to_gwei(), "1500000000");
+///assert_eq!(balance.to_string(), "1.5 ETH");
+///assert_eq!(u128::try_from(balance).unwrap(), 1_500_000_000_000_000_000);
+///assert!(u128::try_from(Wei(U256::MAX)).is_err());
+///```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Wei(pub U256);
+
+impl Wei {
+    ///Zero wei.
+    pub const ZERO: Wei = Wei(U256::zero());
+
+    ///Returns the amount of wei in the given amount of gwei.
+    pub fn from_gwei(gwei: u64) -> Wei {
+        Wei(U256::from(gwei) * U256::exp10(9))
+    }
+
+    ///Returns the amount of wei in the given amount of ether.
+    pub fn from_ether(ether: u64) -> Wei {
+        Wei(U256::from(ether) * U256::exp10(18))
+    }
+
+    ///Returns the amount as a `U256`.
+    pub fn as_u256(&self) -> U256 {
+        self.0
+    }
+
+    ///Returns the amount as a decimal string in units of `10^decimals` wei, without trailing zeros.
+    pub fn format_units(&self, decimals: usize) -> String {
+        crate::units::format_units(self.0, decimals)
+    }
+
+    ///Returns the amount as a decimal string in gwei.
+    pub fn to_gwei(&self) -> String {
+        self.format_units(9)
+    }
+
+    ///Returns the amount as a decimal string in ether.
+    pub fn to_ether(&self) -> String {
+        self.format_units(18)
+    }
+
+    ///Returns the sum of two amounts, or `None` on overflow.
+    pub fn checked_add(self, other: Wei) -> Option<Wei> {
+        self.0.checked_add(other.0).map(Wei)
+    }
+
+    ///Returns the difference of two amounts, or `None` if `other` is larger.
+    pub fn checked_sub(self, other: Wei) -> Option<Wei> {
+        self.0.checked_sub(other.0).map(Wei)
+    }
+}
+
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ETH", self.to_ether())
+    }
+}
+
+impl From<U256> for Wei {
+    fn from(value: U256) -> Self {
+        Wei(value)
+    }
+}
+
+impl From<u128> for Wei {
+    fn from(value: u128) -> Self {
+        Wei(U256::from(value))
+    }
+}
+
+impl From<Wei> for U256 {
+    fn from(value: Wei) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Wei> for u128 {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: Wei) -> Result<Self, Self::Error> {
+        match value.0.bits() <= 128 {
+            true => Ok(value.0.as_u128()),
+            false => Err(format!("{} wei does not fit in a u128", value.0).into()),
+        }
+    }
+}
+
+///The `parse_u256()` function takes a decimal string, e.g. `1000000`, or a `0x`-prefixed hex quantity and attempts to
+///return it as `Ok(U256)`. Returns an `Err()` if the string is not a number or overflows a `U256`.
+///## Example
+///```rust
+///use ethrs::types::{parse_u256, U256};
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  assert_eq!(parse_u256("1000000")?, U256::from(1_000_000));
+///  assert_eq!(parse_u256("0xf4240")?, U256::from(1_000_000));
+///  Ok(())
+///}
+///```
+pub fn parse_u256(value: &str) -> Result<U256, Box<dyn Error>> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(digits) if !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_hexdigit()) => {
+            U256::from_str_radix(digits, 16).ok()
+        }
+        Some(_) => None,
+        None if !value.is_empty() && value.bytes().all(|c| c.is_ascii_digit()) => {
+            U256::from_dec_str(value).ok()
+        }
+        None => None,
+    };
+    parsed.ok_or_else(|| format!("Invalid U256: {value:?}").into())
+}
+
+///The `format_u256()` function takes a `U256` and a thousands separator, and returns the decimal string of the value with
+///its digits grouped by three, e.g. `1,000,000`.
+///## Example
+///```rust
+///use ethrs::types::{format_u256, U256};
+///
+///assert_eq!(format_u256(U256::from(1_000_000), ','), "1,000,000");
+///assert_eq!(format_u256(U256::from(999), '_'), "999");
+///```
+pub fn format_u256(value: U256, separator: char) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn deserialize_u256<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(value) => parse_u256(&value).map_err(de::Error::custom),
+        serde_json::Value::Number(value) => value
+            .as_u64()
+            .map(U256::from)
+            .ok_or_else(|| de::Error::custom(format!("Invalid U256: {value}"))),
+        value => Err(de::Error::custom(format!("Invalid U256: {value}"))),
+    }
+}
+
+///Serializes `U256` fields as hex quantities, e.g. `0xf4240`, with `#[serde(with = "ethrs::types::u256_quantity")]`,
+///and deserializes them from hex quantities, decimal strings or JSON integers.
+///## Example
+///```rust
+///use ethrs::types::U256;
+///use serde::{Deserialize, Serialize};
+///use std::error::Error;
+///
+///#[derive(Serialize, Deserialize)]
+///struct Transfer {
+///    #[serde(with = "ethrs::types::u256_quantity")]
+///    value: U256,
+///}
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let transfer: Transfer = serde_json::from_str(r#"{"value": "1000000"}"#)?;
+///  assert_eq!(serde_json::to_string(&transfer)?, r#"{"value":"0xf4240"}"#);
+///  Ok(())
+///}
+///```
+pub mod u256_quantity {
+    use super::U256;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{value:x}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        super::deserialize_u256(deserializer)
+    }
+}
+
+///Serializes `U256` fields as decimal strings, e.g. `1000000`, with `#[serde(with = "ethrs::types::u256_decimal")]`,
+///and deserializes them from hex quantities, decimal strings or JSON integers.
+pub mod u256_decimal {
+    use super::U256;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        super::deserialize_u256(deserializer)
+    }
+}
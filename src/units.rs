@@ -0,0 +1,108 @@
+//!The units module converts between amounts in wei, or in the smallest unit of any token, and decimal strings in larger
+//!units such as gwei and ether, without going through floating point, so that conversions are lossless.
+use crate::types::U256;
+
+use std::error::Error;
+
+///The number of decimals of gwei.
+pub const GWEI_DECIMALS: usize = 9;
+///The number of decimals of ether.
+pub const ETHER_DECIMALS: usize = 18;
+
+///The `parse_units()` function takes a non-negative decimal string, e.g. `1.5`, and a number of decimals, and attempts
+///to return the amount in units of `10^-decimals` as `Ok(U256)`. Returns an `Err()` if the string is not a decimal
+///number, if it has more significant decimals than `decimals`, as they cannot be represented, or on overflow.
+///## Example
+///```rust
+///use ethrs::types::U256;
+///use ethrs::units::parse_units;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  assert_eq!(parse_units("1.5", 6)?, U256::from(1_500_000));
+///  assert_eq!(parse_units("0.0000001", 6).unwrap_err().to_string(), "0.0000001 has more than 6 decimals");
+///  Ok(())
+///}
+///```
+pub fn parse_units(value: &str, decimals: usize) -> Result<U256, Box<dyn Error>> {
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if integer.is_empty() && fraction.is_empty()
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("Invalid decimal number: {value:?}").into());
+    }
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals {
+        return Err(format!("{value} has more than {decimals} decimals").into());
+    }
+    let digits = format!("{integer}{fraction:0<decimals$}");
+    let digits = digits.trim_start_matches('0');
+    match digits.is_empty() {
+        true => Ok(U256::zero()),
+        false => U256::from_dec_str(digits).map_err(|_| format!("{value} overflows a U256").into()),
+    }
+}
+
+///The `parse_gwei()` function takes a decimal string in gwei and attempts to return the amount in wei as `Ok(U256)`.
+///Returns an `Err()` as `parse_units()` does.
+pub fn parse_gwei(value: &str) -> Result<U256, Box<dyn Error>> {
+    parse_units(value, GWEI_DECIMALS)
+}
+
+///The `parse_ether()` function takes a decimal string in ether and attempts to return the amount in wei as `Ok(U256)`.
+///Returns an `Err()` as `parse_units()` does.
+///## Example
+///```rust
+///use ethrs::types::U256;
+///use ethrs::units::parse_ether;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  assert_eq!(parse_ether("1.5")?, U256::from(1_500_000_000_000_000_000u128));
+///  Ok(())
+///}
+///```
+pub fn parse_ether(value: &str) -> Result<U256, Box<dyn Error>> {
+    parse_units(value, ETHER_DECIMALS)
+}
+
+///The `format_units()` function takes an amount in units of `10^-decimals` and a number of decimals, and returns it as a
+///decimal string without trailing zeros, which `parse_units()` parses back into the same amount.
+///## Example
+///```rust
+///use ethrs::types::U256;
+///use ethrs::units::format_units;
+///
+///assert_eq!(format_units(U256::from(1_500_000), 6), "1.5");
+///assert_eq!(format_units(U256::from(42), 6), "0.000042");
+///assert_eq!(format_units(U256::from(42), 0), "42");
+///```
+pub fn format_units(amount: U256, decimals: usize) -> String {
+    let digits = amount.to_string();
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    match fraction.trim_end_matches('0') {
+        "" => integer.to_owned(),
+        fraction => format!("{integer}.{fraction}"),
+    }
+}
+
+///The `format_gwei()` function takes an amount in wei and returns it as a decimal string in gwei.
+pub fn format_gwei(amount: U256) -> String {
+    format_units(amount, GWEI_DECIMALS)
+}
+
+///The `format_ether()` function takes an amount in wei and returns it as a decimal string in ether.
+///## Example
+///```rust
+///use ethrs::types::U256;
+///use ethrs::units::format_ether;
+///
+///assert_eq!(format_ether(U256::from(1_500_000_000_000_000_000u128)), "1.5");
+///```
+pub fn format_ether(amount: U256) -> String {
+    format_units(amount, ETHER_DECIMALS)
+}
@@ -0,0 +1,162 @@
+//!The utils module contains the hashing helpers needed to build calldata and log filters by hand: `keccak256()`,
+//!`function_selector()` and `event_topic()`, the EIP-191 `hash_message()`, and the EIP-55 address checksum helpers
+//!`to_checksum_address()` and `is_checksummed()`.
+use crate::types::{Address, FromHexError, H256};
+use tiny_keccak::{Hasher, Keccak};
+
+///The `keccak256()` function takes arbitrary bytes and returns their Keccak-256 hash, as used by the EVM.
+///## Example
+///```rust
+///use ethrs::utils::keccak256;
+///
+///assert_eq!(
+///    hex::encode(keccak256(b"")),
+///    "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+///);
+///```
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut output);
+    output
+}
+
+///The `function_selector()` function takes a function signature, e.g. `transfer(address,uint256)`, and returns its
+///4-byte selector, i.e. the first bytes of its hash. Whitespace in the signature is ignored, but parameter types must be
+///canonical, e.g. `uint256` rather than `uint`.
+///## Example
+///```rust
+///use ethrs::utils::function_selector;
+///
+///assert_eq!(function_selector("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+///assert_eq!(function_selector("transfer(address, uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+///```
+pub fn function_selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(canonical(signature).as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+///The `event_topic()` function takes an event signature, e.g. `Transfer(address,address,uint256)`, and returns its
+///topic, i.e. the hash logs of the event have as their first topic. Whitespace in the signature is ignored, but
+///parameter types must be canonical and without `indexed` keywords.
+///## Example
+///```rust
+///use ethrs::utils::event_topic;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  assert_eq!(
+///      event_topic("Transfer(address,address,uint256)"),
+///      "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse()?
+///  );
+///  Ok(())
+///}
+///```
+pub fn event_topic(signature: &str) -> H256 {
+    H256(keccak256(canonical(signature).as_bytes()))
+}
+
+///The `hash_message()` function takes a message and returns the hash signed by `personal_sign` and `eth_sign`, i.e. the
+///Keccak-256 hash of the message prefixed with `\x19Ethereum Signed Message:\n` and its length, as specified by EIP-191.
+///## Example
+///```rust
+///use ethrs::utils::hash_message;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  assert_eq!(
+///      hash_message(b"Hello World"),
+///      "0xa1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2".parse()?
+///  );
+///  Ok(())
+///}
+///```
+pub fn hash_message(message: &[u8]) -> H256 {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message);
+    H256(keccak256(&data))
+}
+
+fn canonical(signature: &str) -> String {
+    signature.split_whitespace().collect()
+}
+
+///The `to_checksum_address()` function takes a hex address in any casing and attempts to return its EIP-55 checksummed
+///form as `Ok(String)`. The casing of the input is not validated, use `is_checksummed()` or parse it as an `Address` for
+///that. Returns an `Err()` if the input is not 20 hex-encoded bytes.
+///## Example
+///```rust
+///use ethrs::utils::to_checksum_address;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  assert_eq!(
+///      to_checksum_address("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266")?,
+///      "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+///  );
+///  Ok(())
+///}
+///```
+pub fn to_checksum_address(address: &str) -> Result<String, FromHexError> {
+    Ok(address.to_lowercase().parse::<Address>()?.to_checksum())
+}
+
+///The `is_checksummed()` function takes a hex address and returns `true` if it is `0x`-prefixed and cased as its EIP-55
+///checksum, which all-lowercase and all-uppercase addresses are not, unless their checksum happens to match.
+///## Example
+///```rust
+///use ethrs::utils::is_checksummed;
+///
+///assert!(is_checksummed("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+///assert!(!is_checksummed("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"));
+///assert!(!is_checksummed("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92267"));
+///```
+pub fn is_checksummed(address: &str) -> bool {
+    address.starts_with("0x")
+        && to_checksum_address(address).map_or(false, |checksummed| checksummed == address)
+}
+
+///Parses a hex quantity into a `u64`, failing instead of truncating when it does not fit.
+pub(crate) fn parse_u64(value: &str) -> Result<u64, String> {
+    let digits = value
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("Invalid quantity: {value}"))?;
+    u64::from_str_radix(digits, 16).map_err(|err| format!("Invalid quantity {value}: {err}"))
+}
+
+///Serializes `u64` fields, e.g. block numbers, as hex quantities.
+pub(crate) mod quantity {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{value:x}"))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        super::parse_u64(&String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+///Serializes `Option<u64>` fields as hex quantities or `null`.
+pub(crate) mod quantity_opt {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<u64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => super::quantity::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u64>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| super::parse_u64(&value).map_err(de::Error::custom))
+            .transpose()
+    }
+}
@@ -0,0 +1,387 @@
+//!The wallet module handles secp256k1 keys: parsing public keys in their compressed and uncompressed forms, holding
+//!private keys as a `Wallet`, which can be derived from a mnemonic or read from a keystore and signs hashes, messages
+//!and transactions, and searching for vanity addresses.
+use crate::eip712::TypedData;
+use crate::hd::{self, DerivationPath};
+use crate::keystore::{Kdf, Keystore};
+use crate::signature::Signature;
+use crate::transaction::{SignedTransaction, TypedTransaction};
+use crate::types::{public_key_to_address, Address, H256, U256};
+use crate::utils::hash_message;
+
+use k256::ecdsa::SigningKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use rand_core::OsRng;
+
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::channel;
+use std::thread;
+
+///The `PublicKey` struct is a secp256k1 public key. It is parsed from its SEC1 encoding, compressed (33 bytes) or
+///uncompressed (65 bytes), or from the raw 64 bytes of its coordinates, and can be converted between these forms.
+///## Example
+///```rust
+///use ethrs::wallet::PublicKey;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let key: PublicKey = "0x038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75".parse()?;
+///  assert_eq!(key.address().to_string(), "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+///  assert_eq!(PublicKey::from_slice(&key.to_uncompressed())?, key);
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(k256::PublicKey);
+
+impl PublicKey {
+    ///The `PublicKey::from_slice()` associated function takes a public key, SEC1-encoded or as the raw 64 bytes of its
+    ///coordinates, and attempts to return it as `Ok(PublicKey)`. Returns an `Err()` if the bytes are not a point on the
+    ///curve.
+    pub fn from_slice(bytes: &[u8]) -> Result<PublicKey, Box<dyn Error>> {
+        let key = match bytes.len() {
+            64 => k256::PublicKey::from_sec1_bytes(&[&[0x04], bytes].concat()),
+            _ => k256::PublicKey::from_sec1_bytes(bytes),
+        };
+        Ok(PublicKey(key.map_err(|_| "Invalid secp256k1 public key")?))
+    }
+
+    ///Returns the 33-byte compressed SEC1 encoding of the key.
+    pub fn to_compressed(&self) -> [u8; 33] {
+        let mut bytes = [0u8; 33];
+        bytes.copy_from_slice(self.0.to_encoded_point(true).as_bytes());
+        bytes
+    }
+
+    ///Returns the 65-byte uncompressed SEC1 encoding of the key.
+    pub fn to_uncompressed(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes.copy_from_slice(self.0.to_encoded_point(false).as_bytes());
+        bytes
+    }
+
+    ///Returns the address of the key.
+    pub fn address(&self) -> Address {
+        // an uncompressed encoding is always a valid key
+        public_key_to_address(&self.to_uncompressed()).unwrap_or_default()
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PublicKey::from_slice(&hex::decode(s.strip_prefix("0x").unwrap_or(s))?)
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.to_compressed()))
+    }
+}
+
+///The `Wallet` struct holds a secp256k1 private key. Its `Debug` output only shows the address, so that the key does
+///not end up in logs.
+///## Example
+///```rust
+///use ethrs::wallet::Wallet;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let wallet: Wallet = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse()?;
+///  assert_eq!(wallet.address().to_string(), "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+///  let random = Wallet::random();
+///  assert_eq!(random.public_key().address(), random.address());
+///  Ok(())
+///}
+///```
+#[derive(Clone)]
+pub struct Wallet {
+    key: SigningKey,
+    address: Address,
+}
+
+impl Wallet {
+    ///The `Wallet::random()` associated function returns a wallet with a private key from the OS random number
+    ///generator.
+    pub fn random() -> Wallet {
+        Wallet::from_signing_key(SigningKey::random(&mut OsRng))
+    }
+
+    ///The `Wallet::from_private_key()` associated function takes a 32-byte private key and attempts to return its
+    ///wallet as `Ok(Wallet)`. Returns an `Err()` if the key is zero or not below the order of the curve.
+    pub fn from_private_key(private_key: &[u8; 32]) -> Result<Wallet, Box<dyn Error>> {
+        let key =
+            SigningKey::from_slice(private_key).map_err(|_| "Invalid secp256k1 private key")?;
+        Ok(Wallet::from_signing_key(key))
+    }
+
+    ///The `Wallet::from_keystore()` associated function takes the path of a JSON keystore file and its password, and
+    ///attempts to return the wallet of the key it holds as `Ok(Wallet)`. Returns an `Err()` if the file cannot be read
+    ///or parsed, or as `Keystore::decrypt()` does.
+    ///## Example
+    ///```rust
+    ///use ethrs::keystore::Kdf;
+    ///use ethrs::wallet::Wallet;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let wallet = Wallet::random();
+    ///  let path = std::env::temp_dir().join(format!("{:x}.json", wallet.address()));
+    ///  wallet.to_keystore("<password>", Kdf::LIGHT)?.save(&path)?;
+    ///  assert_eq!(Wallet::from_keystore(&path, "<password>")?.address(), wallet.address());
+    ///  # std::fs::remove_file(path)?;
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn from_keystore(path: impl AsRef<Path>, password: &str) -> Result<Wallet, Box<dyn Error>> {
+        Wallet::from_private_key(&Keystore::load(path)?.decrypt(password)?)
+    }
+
+    ///The `Wallet::from_mnemonic()` associated function takes a BIP-39 mnemonic and an account index, and attempts to
+    ///return the wallet of the account as `Ok(Wallet)`, derived along `m/44'/60'/0'/0/<index>` without a passphrase.
+    ///Returns an `Err()` if the mnemonic is invalid.
+    ///## Example
+    ///```rust
+    ///use ethrs::wallet::Wallet;
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let wallet = Wallet::from_mnemonic("test test test test test test test test test test test junk", 1)?;
+    ///  assert_eq!(wallet.address().to_string(), "0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn from_mnemonic(mnemonic: &str, index: u32) -> Result<Wallet, Box<dyn Error>> {
+        Wallet::from_mnemonic_path(mnemonic, "", &DerivationPath::ethereum(index))
+    }
+
+    ///The `Wallet::from_mnemonic_path()` associated function takes a BIP-39 mnemonic, its passphrase and a derivation
+    ///path, and attempts to return the wallet of the key derived along the path as `Ok(Wallet)`. Returns an `Err()` if
+    ///the mnemonic is invalid.
+    pub fn from_mnemonic_path(
+        mnemonic: &str,
+        passphrase: &str,
+        path: &DerivationPath,
+    ) -> Result<Wallet, Box<dyn Error>> {
+        let seed = hd::mnemonic_to_seed(mnemonic, passphrase)?;
+        Wallet::from_private_key(&hd::derive_key(&seed, path)?)
+    }
+
+    ///The `to_keystore()` function takes a password and a key derivation function, and attempts to return the private
+    ///key of the wallet encrypted with the password as `Ok(Keystore)`. Returns an `Err()` as `Keystore::encrypt()` does.
+    pub fn to_keystore(&self, password: &str, kdf: Kdf) -> Result<Keystore, Box<dyn Error>> {
+        Keystore::encrypt(&self.private_key(), password, kdf)
+    }
+
+    fn from_signing_key(key: SigningKey) -> Wallet {
+        let address = PublicKey(key.verifying_key().into()).address();
+        Wallet { key, address }
+    }
+
+    ///Returns the address of the wallet.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    ///Returns the public key of the wallet.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(self.key.verifying_key().into())
+    }
+
+    ///Returns the private key of the wallet.
+    pub fn private_key(&self) -> [u8; 32] {
+        self.key.to_bytes().into()
+    }
+
+    ///Returns the signature of the hash by the wallet, with a low `s` and a `v` of `27` or `28`.
+    pub fn sign_hash(&self, hash: H256) -> Signature {
+        // the prehash has the size of the field, which is the only failure
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(&hash.0)
+            .expect("32-byte prehash");
+        Signature {
+            r: U256::from_big_endian(&signature.r().to_bytes()),
+            s: U256::from_big_endian(&signature.s().to_bytes()),
+            v: 27 + u64::from(recovery_id.is_y_odd()),
+        }
+    }
+
+    ///Returns the signature of the message by the wallet, as `personal_sign` signs it, i.e. of its EIP-191 hash.
+    pub fn sign_message(&self, message: &[u8]) -> Signature {
+        self.sign_hash(hash_message(message))
+    }
+
+    ///The `sign_typed_data()` function takes EIP-712 typed data and attempts to return its signature by the wallet as
+    ///`Ok(Signature)`, as `eth_signTypedData_v4` returns it. Returns an `Err()` if the typed data cannot be hashed.
+    pub fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, Box<dyn Error>> {
+        Ok(self.sign_hash(typed_data.signing_hash()?))
+    }
+
+    ///The `sign_transaction()` function takes a transaction and attempts to return it signed by the wallet as
+    ///`Ok(SignedTransaction)`, with the `v` of its signature as encoded in the transaction. Returns an `Err()` if the
+    ///signed transaction cannot be encoded, e.g. for a legacy transaction with a chain id too large for EIP-155.
+    ///## Example
+    ///```rust
+    ///use ethrs::transaction::{Eip1559Transaction, TypedTransaction};
+    ///use ethrs::wallet::Wallet;
+    ///
+    ///use std::error::Error;
+    ///
+    ///fn main() -> Result<(), Box<dyn Error>> {
+    ///  let wallet = Wallet::random();
+    ///  let signed = wallet.sign_transaction(Eip1559Transaction { chain_id: 1, ..Default::default() }.into())?;
+    ///  assert_eq!(TypedTransaction::decode_bytes(&signed.encode()?)?.from, wallet.address());
+    ///  Ok(())
+    ///}
+    ///```
+    pub fn sign_transaction(
+        &self,
+        transaction: TypedTransaction,
+    ) -> Result<SignedTransaction, Box<dyn Error>> {
+        let signature = self.sign_hash(transaction.signing_hash());
+        let signature = match transaction {
+            TypedTransaction::Legacy(ref legacy) => signature.with_chain_id(legacy.chain_id)?,
+            _ => Signature {
+                v: u64::from(signature.y_parity()?),
+                ..signature
+            },
+        };
+        Ok(SignedTransaction {
+            hash: transaction.hash(&signature)?,
+            transaction,
+            signature,
+            from: self.address,
+        })
+    }
+}
+
+impl FromStr for Wallet {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut private_key = [0u8; 32];
+        hex::decode_to_slice(s.strip_prefix("0x").unwrap_or(s), &mut private_key)
+            .map_err(|_| "Invalid private key hexstring")?;
+        Wallet::from_private_key(&private_key)
+    }
+}
+
+impl fmt::Debug for Wallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wallet")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+///The `VanitySearch` struct searches for a wallet whose address starts and ends with given hex patterns, generating
+///random keys on several threads. Patterns are matched case-insensitively, unless they contain uppercase letters, in
+///which case they are matched against the EIP-55 checksummed address. Every hex character of the patterns makes the
+///search 16 times longer on average, or about 32 times for checksummed letters.
+///## Example
+///```rust
+///use ethrs::wallet::VanitySearch;
+///use std::error::Error;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let wallet = VanitySearch::new().with_prefix("00").with_suffix("f").run()?.unwrap();
+///  assert!(format!("{:x}", wallet.address()).starts_with("00"));
+///  Ok(())
+///}
+///```
+#[derive(Debug, Clone, Default)]
+pub struct VanitySearch {
+    prefix: String,
+    suffix: String,
+    threads: Option<usize>,
+    max_attempts: Option<u64>,
+}
+
+impl VanitySearch {
+    ///The `VanitySearch::new()` associated function returns a search matching any address, on as many threads as the
+    ///machine can run in parallel, without an attempt limit.
+    pub fn new() -> VanitySearch {
+        VanitySearch::default()
+    }
+
+    ///Sets the hex characters the address must start with, after `0x`.
+    pub fn with_prefix(mut self, prefix: &str) -> VanitySearch {
+        self.prefix = prefix.strip_prefix("0x").unwrap_or(prefix).to_owned();
+        self
+    }
+
+    ///Sets the hex characters the address must end with.
+    pub fn with_suffix(mut self, suffix: &str) -> VanitySearch {
+        self.suffix = suffix.to_owned();
+        self
+    }
+
+    ///Sets the number of threads generating keys, at least 1.
+    pub fn with_threads(mut self, threads: usize) -> VanitySearch {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
+    ///Sets the number of keys generated before the search gives up.
+    pub fn with_max_attempts(mut self, max_attempts: u64) -> VanitySearch {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    ///The `matches()` function returns `true` if the address matches the prefix and suffix of the search.
+    pub fn matches(&self, address: &Address) -> bool {
+        let checksummed = [&self.prefix, &self.suffix]
+            .iter()
+            .any(|pattern| pattern.chars().any(|c| c.is_ascii_uppercase()));
+        let hex = match checksummed {
+            true => address.to_checksum()[2..].to_owned(),
+            false => format!("{address:x}"),
+        };
+        hex.starts_with(&self.prefix) && hex.ends_with(&self.suffix)
+    }
+
+    ///The `run()` function searches for a matching wallet and attempts to return it as `Ok(Some(Wallet))`. Returns
+    ///`Ok(None)` once the maximum number of attempts is reached, and returns an `Err()` if the patterns are longer than
+    ///an address or contain non-hex characters.
+    pub fn run(&self) -> Result<Option<Wallet>, Box<dyn Error>> {
+        let pattern = format!("{}{}", self.prefix, self.suffix);
+        if pattern.len() > 40 || !pattern.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(
+                format!("Invalid vanity pattern: {}...{}", self.prefix, self.suffix).into(),
+            );
+        }
+        let threads = self
+            .threads
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, |threads| threads.get()));
+        let found = AtomicBool::new(false);
+        let attempts = AtomicU64::new(0);
+        let (sender, receiver) = channel();
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                let sender = sender.clone();
+                let (found, attempts) = (&found, &attempts);
+                scope.spawn(move || {
+                    while !found.load(Ordering::Relaxed) {
+                        let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+                        if self.max_attempts.map_or(false, |max| attempt >= max) {
+                            return;
+                        }
+                        let wallet = Wallet::random();
+                        if self.matches(&wallet.address()) && !found.swap(true, Ordering::Relaxed) {
+                            let _ = sender.send(wallet);
+                        }
+                    }
+                });
+            }
+        });
+        drop(sender);
+        Ok(receiver.try_recv().ok())
+    }
+}
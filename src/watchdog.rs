@@ -0,0 +1,181 @@
+//!The watchdog module detects endpoints that silently serve a stale chain head, by comparing the age of the latest
+//!block against the block time expected on the chain, and fails over to fallback endpoints when they do.
+use crate::provider::{redact_url, Provider};
+
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+///The number of expected block times the head may lag behind the wall clock before `Watchdog::for_chain()` reports it.
+pub const LAG_BLOCKS: u32 = 5;
+
+///The `expected_block_time()` function takes a chain id and returns the block time of the chain, if known.
+pub fn expected_block_time(chain_id: u64) -> Option<Duration> {
+    match chain_id {
+        // Ethereum mainnet, Sepolia and Holesky
+        1 | 11155111 | 17000 => Some(Duration::from_secs(12)),
+        // OP Mainnet, Base, Zora, Polygon PoS and their testnets
+        10 | 11155420 | 8453 | 84532 | 7777777 | 137 | 80002 => Some(Duration::from_secs(2)),
+        // BNB Smart Chain
+        56 | 97 => Some(Duration::from_secs(3)),
+        // Gnosis
+        100 => Some(Duration::from_secs(5)),
+        // Arbitrum One and Sepolia
+        42161 | 421614 => Some(Duration::from_millis(250)),
+        _ => None,
+    }
+}
+
+///The `HeadStatus` struct describes the latest block of an endpoint and how long ago it was produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeadStatus {
+    pub number: u64,
+    pub timestamp: u64,
+    pub age: Duration,
+}
+
+///The `Health` enum is the outcome of a watchdog check of an endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Health {
+    Healthy(HeadStatus),
+    Lagging(HeadStatus),
+    Unreachable(String),
+}
+
+impl Health {
+    ///Returns `true` if the endpoint is reachable and its head is recent enough.
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Health::Healthy(_))
+    }
+}
+
+type AlarmCallback = Box<dyn FnMut(&str, &Health) + Send>;
+type FailoverCallback = Box<dyn FnMut(&str, &str) + Send>;
+
+///The `Watchdog` struct monitors the active endpoint among a primary and fallback providers. Every check fetches the
+///latest block and, if the endpoint is unreachable or its head is older than the maximum lag, calls the alarm
+///callbacks with the endpoint URL and switches to the next provider, calling the failover callbacks with the old and
+///new URLs. The URLs passed to the callbacks have their credentials, such as API keys, redacted.
+///## Example
+///```rust,no_run
+///use ethrs::provider::Provider;
+///use ethrs::watchdog::Watchdog;
+///use std::error::Error;
+///use std::sync::atomic::AtomicBool;
+///use std::time::Duration;
+///
+///fn main() -> Result<(), Box<dyn Error>> {
+///  let mut watchdog = Watchdog::for_chain(Provider::new("https://rpc.sepolia.org"))?
+///      .with_fallback(Provider::new("https://ethereum-sepolia-rpc.publicnode.com"))
+///      .on_alarm(|url, health| eprintln!("{url} is unhealthy: {health:?}"))
+///      .on_failover(|from, to| eprintln!("failing over from {from} to {to}"));
+///  watchdog.run(&AtomicBool::new(false), Duration::from_secs(12));
+///  Ok(())
+///}
+///```
+pub struct Watchdog {
+    providers: Vec<Provider>,
+    active: usize,
+    max_lag: Duration,
+    on_alarm: Vec<AlarmCallback>,
+    on_failover: Vec<FailoverCallback>,
+}
+
+impl Watchdog {
+    ///The `Watchdog::new()` associated function takes the primary `Provider` and the maximum age of its latest block,
+    ///and returns a watchdog without fallbacks or callbacks.
+    pub fn new(provider: Provider, max_lag: Duration) -> Watchdog {
+        Watchdog {
+            providers: vec![provider],
+            active: 0,
+            max_lag,
+            on_alarm: vec![],
+            on_failover: vec![],
+        }
+    }
+
+    ///The `Watchdog::for_chain()` associated function takes the primary `Provider` and attempts to return a watchdog
+    ///allowing `LAG_BLOCKS` block times of the chain of the provider as `Ok(Watchdog)`. Returns an `Err()` on JSON-RPC
+    ///errors or if the block time of the chain is unknown.
+    pub fn for_chain(provider: Provider) -> Result<Watchdog, Box<dyn Error>> {
+        let chain_id = provider.chain_id()?;
+        let block_time = expected_block_time(chain_id)
+            .ok_or(format!("Unknown block time for chain {chain_id}"))?;
+        Ok(Watchdog::new(provider, block_time * LAG_BLOCKS))
+    }
+
+    ///Adds a fallback provider, which is failed over to in the order they were added.
+    pub fn with_fallback(mut self, provider: Provider) -> Watchdog {
+        self.providers.push(provider);
+        self
+    }
+
+    ///Adds a callback called with the endpoint URL and its health whenever a check finds it unhealthy.
+    pub fn on_alarm(mut self, callback: impl FnMut(&str, &Health) + Send + 'static) -> Watchdog {
+        self.on_alarm.push(Box::new(callback));
+        self
+    }
+
+    ///Adds a callback called with the URLs of the previous and the new active endpoint on every failover.
+    pub fn on_failover(mut self, callback: impl FnMut(&str, &str) + Send + 'static) -> Watchdog {
+        self.on_failover.push(Box::new(callback));
+        self
+    }
+
+    ///Returns the active provider.
+    pub fn provider(&self) -> &Provider {
+        &self.providers[self.active]
+    }
+
+    ///Returns the maximum age of the latest block of a healthy endpoint.
+    pub fn max_lag(&self) -> Duration {
+        self.max_lag
+    }
+
+    fn head(&self) -> Result<HeadStatus, Box<dyn Error>> {
+        let block = self
+            .provider()
+            .get_block_by_number(None)?
+            .ok_or("Latest block not found")?;
+        let timestamp = block.timestamp.try_into()?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        Ok(HeadStatus {
+            number: block.number.ok_or("Latest block is pending")?,
+            timestamp,
+            age: Duration::from_secs(now.saturating_sub(timestamp)),
+        })
+    }
+
+    ///The `check()` function checks the active endpoint and returns its health. If it is unhealthy, the alarm
+    ///callbacks are called and, if there are fallbacks, the next provider becomes active.
+    pub fn check(&mut self) -> Health {
+        let health = match self.head() {
+            Ok(head) if head.age > self.max_lag => Health::Lagging(head),
+            Ok(head) => Health::Healthy(head),
+            Err(err) => Health::Unreachable(err.to_string()),
+        };
+        if !health.is_healthy() {
+            let url = redact_url(self.provider().url());
+            for callback in &mut self.on_alarm {
+                callback(&url, &health);
+            }
+            if self.providers.len() > 1 {
+                self.active = (self.active + 1) % self.providers.len();
+                let next = redact_url(self.provider().url());
+                for callback in &mut self.on_failover {
+                    callback(&url, &next);
+                }
+            }
+        }
+        health
+    }
+
+    ///The `run()` function checks the active endpoint at every interval until `stop` is set.
+    pub fn run(&mut self, stop: &AtomicBool, interval: Duration) {
+        while !stop.load(Ordering::Relaxed) {
+            self.check();
+            thread::sleep(interval);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use ethrs::abi::{decode, encode, Abi, Detokenize, ParamType, Token};
+use ethrs::types::{Address, Bytes, U256};
+
+use std::error::Error;
+
+#[test]
+fn test_encode_dynamic() {
+    // sam(bytes,bool,uint256[]) with ("dave", true, [1, 2, 3]) from the Solidity ABI specification
+    let encoded = encode(&[
+        Token::Bytes(b"dave".to_vec()),
+        Token::Bool(true),
+        Token::Array(vec![
+            Token::Uint(U256::from(1)),
+            Token::Uint(U256::from(2)),
+            Token::Uint(U256::from(3)),
+        ]),
+    ]);
+    assert_eq!(
+        hex::encode(encoded),
+        concat!(
+            "0000000000000000000000000000000000000000000000000000000000000060",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "0000000000000000000000000000000000000000000000000000000000000004",
+            "6461766500000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+        )
+    );
+}
+
+#[test]
+fn test_encode_static() {
+    let encoded = encode(&[
+        Token::Address(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+                .parse()
+                .unwrap(),
+        ),
+        Token::FixedBytes(vec![0xab, 0xcd]),
+        Token::FixedArray(vec![Token::Bool(false), Token::Bool(true)]),
+    ]);
+    assert_eq!(
+        hex::encode(encoded),
+        concat!(
+            "0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "abcd000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        )
+    );
+}
+
+#[test]
+fn test_param_type_parse() -> Result<(), Box<dyn Error>> {
+    for name in [
+        "address",
+        "uint8",
+        "int256",
+        "bytes32",
+        "string[]",
+        "(address,(uint256,bytes)[2])[]",
+    ] {
+        assert_eq!(name.parse::<ParamType>()?.to_string(), name);
+    }
+    assert_eq!("uint".parse::<ParamType>()?, ParamType::Uint(256));
+    assert!("uint256[".parse::<ParamType>().is_err());
+    assert!("(uint256".parse::<ParamType>().is_err());
+    assert!("float".parse::<ParamType>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_decode_roundtrip() -> Result<(), Box<dyn Error>> {
+    let address: Address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".parse()?;
+    let tokens = vec![
+        Token::Bytes(b"dave".to_vec()),
+        Token::Tuple(vec![
+            Token::Address(address),
+            Token::Array(vec![
+                Token::String("a".to_owned()),
+                Token::String("b".to_owned()),
+            ]),
+        ]),
+        Token::FixedArray(vec![Token::Bool(false), Token::Bool(true)]),
+        Token::FixedBytes(vec![0xab, 0xcd]),
+    ];
+    let params: Vec<ParamType> = ["bytes", "(address,string[])", "bool[2]", "bytes2"]
+        .iter()
+        .map(|param| param.parse())
+        .collect::<Result<_, _>>()?;
+    let encoded = encode(&tokens);
+    assert_eq!(decode(&params, &encoded)?, tokens);
+
+    let (bytes, (owner, names), flags, _): (Bytes, (Address, Vec<String>), Vec<bool>, Token) =
+        Detokenize::from_tokens(decode(&params, &encoded)?)?;
+    assert_eq!(bytes.as_ref(), b"dave");
+    assert_eq!(owner, address);
+    assert_eq!(names, vec!["a", "b"]);
+    assert_eq!(flags, vec![false, true]);
+
+    assert!(decode(&params, &encoded[..100]).is_err());
+    assert!(
+        <(u8, bool)>::from_tokens(vec![Token::Uint(U256::from(256)), Token::Bool(true)]).is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decode_invalid() {
+    let mut word = [0u8; 32];
+    word[31] = 2;
+    assert!(decode(&[ParamType::Bool], &word).is_err());
+    word[0] = 1;
+    assert!(decode(&[ParamType::Address], &word).is_err());
+    // an offset pointing past the end of the data
+    assert!(decode(&[ParamType::Bytes], &word).is_err());
+    assert!(decode(&[ParamType::Tuple(vec![ParamType::Uint(256); 2])], &word).is_err());
+}
+
+#[test]
+fn test_json_abi() -> Result<(), Box<dyn Error>> {
+    let abi: Abi = serde_json::from_str(
+        r#"[
+            {"type": "fallback", "stateMutability": "nonpayable"},
+            {"type": "error", "name": "Unauthorized", "inputs": [{"name": "caller", "type": "address"}]},
+            {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ]},
+            {"type": "function", "name": "submit", "stateMutability": "nonpayable", "outputs": [], "inputs": [
+                {"name": "orders", "type": "tuple[]", "components": [
+                    {"name": "maker", "type": "address"},
+                    {"name": "amounts", "type": "uint256[2]"}
+                ]}
+            ]}
+        ]"#,
+    )?;
+    assert_eq!(
+        abi.event("Transfer").unwrap().topic()?,
+        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse()?
+    );
+    assert_eq!(
+        abi.errors().next().unwrap().signature()?,
+        "Unauthorized(address)"
+    );
+    let submit = abi.function("submit").unwrap();
+    assert_eq!(submit.signature()?, "submit((address,uint256[2])[])");
+    let order = Token::Tuple(vec![
+        Token::Address(Address::ZERO),
+        Token::FixedArray(vec![Token::Uint(U256::one()), Token::Uint(U256::one())]),
+    ]);
+    assert!(submit.encode_input(&[Token::Array(vec![order])]).is_ok());
+    assert!(submit
+        .encode_input(&[Token::Array(vec![Token::Bool(true)])])
+        .is_err());
+    assert!(abi.constructor().is_none());
+    Ok(())
+}
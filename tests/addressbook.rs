@@ -0,0 +1,52 @@
+use ethrs::addressbook::{AddressBook, WELL_KNOWN};
+use ethrs::types::Address;
+
+use std::error::Error;
+
+#[test]
+fn test_well_known() {
+    let book = AddressBook::with_well_known();
+    assert_eq!(book.len(), WELL_KNOWN.len());
+    assert_eq!(book.label(&Address::ZERO), Some("Zero"));
+    assert_eq!(book.address("Zero"), Some(Address::ZERO));
+}
+
+#[test]
+fn test_insert_and_remove() -> Result<(), Box<dyn Error>> {
+    let first: Address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".parse()?;
+    let second: Address = "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359".parse()?;
+    let mut book = AddressBook::new();
+    assert_eq!(book.insert(first, "Treasury"), None);
+    assert_eq!(book.insert(first, "Multisig"), Some("Treasury".to_owned()));
+    assert_eq!(book.address("Treasury"), None);
+
+    // labels are unique, so relabelling moves them
+    book.insert(second, "Multisig");
+    assert_eq!(book.label(&first), None);
+    assert_eq!(book.address("Multisig"), Some(second));
+    assert_eq!(book.len(), 1);
+
+    assert_eq!(book.remove(&second), Some("Multisig".to_owned()));
+    assert!(book.is_empty());
+    assert_eq!(book.annotate(&first), first.to_string());
+    Ok(())
+}
+
+#[test]
+fn test_serde() -> Result<(), Box<dyn Error>> {
+    let book: AddressBook =
+        serde_json::from_str(r#"{"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed":"Treasury"}"#)?;
+    assert_eq!(
+        book.address("Treasury"),
+        Some("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".parse()?)
+    );
+    assert_eq!(
+        serde_json::to_string(&book)?,
+        r#"{"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed":"Treasury"}"#
+    );
+
+    let mut merged = AddressBook::with_well_known();
+    merged.extend(book);
+    assert_eq!(merged.len(), WELL_KNOWN.len() + 1);
+    Ok(())
+}
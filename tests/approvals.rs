@@ -0,0 +1,108 @@
+mod common;
+
+use common::serve_with;
+use ethrs::approvals::ApprovalScanner;
+use ethrs::provider::Provider;
+use ethrs::types::{Address, U256};
+use serde_json::json;
+
+use std::error::Error;
+
+const TOPIC: &str = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+const OWNER: &str = "0x000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+const TOKEN: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const NFT: &str = "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512";
+const UNLIMITED: &str = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+const SPENT: &str = "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc";
+
+fn approval(token: &str, topics: &[String], block_number: u64) -> serde_json::Value {
+    json!({
+        "removed": false,
+        "logIndex": "0x0",
+        "transactionIndex": "0x0",
+        "transactionHash": "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8",
+        "blockHash": "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6",
+        "blockNumber": format!("0x{block_number:x}"),
+        "address": token,
+        "data": "0x",
+        "topics": topics,
+    })
+}
+
+fn topic(address: &str) -> String {
+    format!("0x{:0>64}", address.trim_start_matches("0x"))
+}
+
+#[test]
+fn test_approval_scanner() -> Result<(), Box<dyn Error>> {
+    let url = serve_with(|method, params| match method {
+        "eth_blockNumber" => json!("0x14"),
+        "eth_getLogs" => {
+            assert_eq!(params[0]["topics"], json!([[TOPIC], [OWNER]]));
+            match params[0]["fromBlock"].as_str().unwrap() {
+                "0xa" => json!([
+                    approval(
+                        TOKEN,
+                        &[TOPIC.to_owned(), OWNER.to_owned(), topic(UNLIMITED)],
+                        11
+                    ),
+                    approval(
+                        TOKEN,
+                        &[TOPIC.to_owned(), OWNER.to_owned(), topic(SPENT)],
+                        12
+                    ),
+                    // an ERC-721 approval indexes the token id
+                    approval(
+                        NFT,
+                        &[
+                            TOPIC.to_owned(),
+                            OWNER.to_owned(),
+                            topic(UNLIMITED),
+                            topic("01")
+                        ],
+                        12
+                    ),
+                ]),
+                _ => json!([approval(
+                    TOKEN,
+                    &[TOPIC.to_owned(), OWNER.to_owned(), topic(UNLIMITED)],
+                    17
+                )]),
+            }
+        }
+        "eth_call" => {
+            let data = params[0]["data"].as_str().unwrap();
+            assert!(data.starts_with("0xdd62ed3e"));
+            assert_eq!(params[1], "0x14");
+            match data.ends_with(UNLIMITED.trim_start_matches("0x")) {
+                true => json!(format!("0x{}", "f".repeat(64))),
+                false => json!(format!("0x{:064x}", 0)),
+            }
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let owner: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    let approvals = ApprovalScanner::new(Provider::new(&url))
+        .with_from_block(10)
+        .with_batch_size(5)
+        .scan(owner)?;
+    assert_eq!(approvals.len(), 1);
+    let approval = &approvals[0];
+    assert_eq!(approval.token, TOKEN.parse()?);
+    assert_eq!(approval.spender, UNLIMITED.parse()?);
+    assert_eq!(approval.allowance, U256::MAX);
+    assert_eq!(approval.last_approved_block, 17);
+    assert!(approval.is_unlimited());
+
+    let revoke = approval.revoke_transaction(owner);
+    assert_eq!(revoke.to, Some(approval.token));
+    assert_eq!(
+        revoke.data.unwrap(),
+        format!(
+            "0x095ea7b3{}{:064x}",
+            topic(UNLIMITED).trim_start_matches("0x"),
+            0
+        )
+    );
+    Ok(())
+}
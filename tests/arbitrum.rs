@@ -0,0 +1,25 @@
+use ethrs::arbitrum::{RetryableGasParams, RetryableTicket};
+use ethrs::types::{Address, U256};
+
+#[test]
+fn test_retryable_ticket() {
+    let ticket = RetryableTicket {
+        from: Address::ZERO,
+        to: Address::ZERO,
+        l2_call_value: U256::from(5),
+        excess_fee_refund_address: Address::ZERO,
+        call_value_refund_address: Address::ZERO,
+        data: vec![0xff; 33],
+    };
+    let gas_params = RetryableGasParams {
+        max_submission_cost: U256::from(1000),
+        gas_limit: U256::from(21000),
+        max_fee_per_gas: U256::from(10),
+    };
+    assert_eq!(ticket.deposit(&gas_params), U256::from(211005));
+    let calldata = ticket.calldata(&gas_params);
+    // selector + 8 head words + length word + 2 data words
+    assert_eq!(calldata.len(), 4 + 32 * 11);
+    assert_eq!(calldata[4 + 32 * 7 + 31], 0x00);
+    assert_eq!(calldata[4 + 32 * 8 + 31], 33);
+}
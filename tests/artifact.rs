@@ -0,0 +1,82 @@
+use ethrs::abi::AbiItem;
+use ethrs::artifact::{Artifact, LinkOffset};
+
+use std::error::Error;
+
+#[test]
+fn test_load_foundry() -> Result<(), Box<dyn Error>> {
+    let artifact = Artifact::load("tests/fixtures/Counter.json")?;
+    assert_eq!(artifact.contract_name.as_deref(), Some("Counter"));
+    assert_eq!(artifact.abi.0.len(), 5);
+    assert!(matches!(artifact.abi.0[1], AbiItem::Receive { .. }));
+    assert_eq!(
+        artifact.abi.function("number").unwrap().selector()?,
+        [0x83, 0x81, 0xf5, 0x8a]
+    );
+    assert_eq!(
+        artifact.abi.function("increment").unwrap().selector()?,
+        [0xd0, 0x9d, 0xe0, 0x8a]
+    );
+    assert!(artifact.is_linked());
+    assert_eq!(artifact.deployed_bytecode_bytes()?.len(), 5);
+    Ok(())
+}
+
+#[test]
+fn test_hardhat() -> Result<(), Box<dyn Error>> {
+    let artifact = Artifact::from_json(
+        r#"{
+            "_format": "hh-sol-artifact-1",
+            "contractName": "Vault",
+            "sourceName": "contracts/Vault.sol",
+            "abi": [],
+            "bytecode": "0x73__$1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e$__63",
+            "deployedBytecode": "0x6080",
+            "linkReferences": {
+                "contracts/Math.sol": {"Math": [{"start": 1, "length": 20}]}
+            },
+            "deployedLinkReferences": {}
+        }"#,
+    )?;
+    assert_eq!(artifact.contract_name.as_deref(), Some("Vault"));
+    assert!(!artifact.is_linked());
+    assert_eq!(
+        artifact.link_references["contracts/Math.sol"]["Math"],
+        vec![LinkOffset {
+            start: 1,
+            length: 20
+        }]
+    );
+    assert!(artifact.bytecode_bytes().is_err());
+    assert!(artifact.deployed_bytecode_bytes().is_ok());
+    assert!(Artifact::from_json(r#"{"abi": []}"#).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_link() -> Result<(), Box<dyn Error>> {
+    let mut artifact = Artifact::from_json(
+        r#"{
+            "abi": [],
+            "bytecode": {
+                "object": "0x73__$1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e$__6373__$1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e$__",
+                "linkReferences": {"src/Math.sol": {"Math": [{"start": 1, "length": 20}, {"start": 23, "length": 20}]}}
+            },
+            "deployedBytecode": {
+                "object": "0x73__$1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e$__",
+                "linkReferences": {"src/Math.sol": {"Math": [{"start": 1, "length": 20}]}}
+            }
+        }"#,
+    )?;
+    assert_eq!(artifact.libraries(), vec!["src/Math.sol:Math"]);
+    let address = "0x5FbDB2315678afecb367f032d93F642f64180aa3".parse()?;
+    assert!(artifact.link("Other", address).is_err());
+    artifact.link("src/Math.sol:Math", address)?;
+    assert!(artifact.is_linked());
+    assert!(artifact.libraries().is_empty());
+    let library = "5fbdb2315678afecb367f032d93f642f64180aa3";
+    assert_eq!(artifact.bytecode, format!("0x73{library}6373{library}"));
+    assert_eq!(artifact.deployed_bytecode, format!("0x73{library}"));
+    assert_eq!(artifact.bytecode_bytes()?.len(), 43);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::serve_with;
+use ethrs::balancer::{Endpoint, LoadBalancedProvider};
+use ethrs::provider::Provider;
+
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+fn counting_endpoint(delay: Duration) -> (String, Arc<AtomicUsize>) {
+    let count = Arc::new(AtomicUsize::new(0));
+    let served = count.clone();
+    let url = serve_with(move |_, _| {
+        thread::sleep(delay);
+        serde_json::json!(format!("0x{:x}", served.fetch_add(1, Ordering::SeqCst) + 1))
+    });
+    (url, count)
+}
+
+#[test]
+fn test_load_balanced_provider_weights() -> Result<(), Box<dyn Error>> {
+    let (heavy, heavy_count) = counting_endpoint(Duration::ZERO);
+    let (light, light_count) = counting_endpoint(Duration::ZERO);
+    let (unused, unused_count) = counting_endpoint(Duration::ZERO);
+    let balancer = LoadBalancedProvider::from_endpoints(vec![
+        Endpoint::new(Provider::new(&heavy)).with_weight(3),
+        Endpoint::new(Provider::new(&light)),
+        Endpoint::new(Provider::new(&unused)).with_weight(0),
+    ]);
+    let provider = balancer.provider();
+    for _ in 0..8 {
+        provider.block_number()?;
+    }
+    assert_eq!(heavy_count.load(Ordering::SeqCst), 6);
+    assert_eq!(light_count.load(Ordering::SeqCst), 2);
+    assert_eq!(unused_count.load(Ordering::SeqCst), 0);
+    assert_eq!(balancer.in_flight(), [0, 0, 0]);
+    Ok(())
+}
+
+#[test]
+fn test_load_balanced_provider_concurrency() -> Result<(), Box<dyn Error>> {
+    let (slow, slow_count) = counting_endpoint(Duration::from_millis(500));
+    let (fast, fast_count) = counting_endpoint(Duration::ZERO);
+    let balancer = LoadBalancedProvider::from_endpoints(vec![
+        Endpoint::new(Provider::new(&slow))
+            .with_weight(10)
+            .with_max_concurrency(1),
+        Endpoint::new(Provider::new(&fast)),
+    ]);
+    let provider = balancer.provider();
+    let pending = {
+        let provider = provider.clone();
+        thread::spawn(move || provider.block_number().unwrap())
+    };
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(balancer.in_flight(), [1, 0]);
+    // the slow endpoint is at its limit despite its weight
+    provider.block_number()?;
+    provider.block_number()?;
+    assert_eq!(fast_count.load(Ordering::SeqCst), 2);
+    pending.join().unwrap();
+    assert_eq!(slow_count.load(Ordering::SeqCst), 1);
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use ethrs::bloom::Bloom;
+use ethrs::provider::Filter;
+use ethrs::types::{Address, FromHexError, H256};
+use ethrs::utils::event_topic;
+
+use std::error::Error;
+
+const TOKEN: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+
+#[test]
+fn test_accrue() -> Result<(), Box<dyn Error>> {
+    let token: Address = TOKEN.parse()?;
+    let mut bloom = Bloom::default();
+    assert!(!bloom.contains_address(&token));
+    bloom.accrue_address(&token);
+    let bits: u32 = bloom.0.iter().map(|byte| byte.count_ones()).sum();
+    assert!((1..=3).contains(&bits));
+    assert!(bloom.contains_address(&token));
+    assert!(bloom.contains_input(&token.0));
+    assert!(!bloom.contains_topic(&event_topic("Transfer(address,address,uint256)")));
+    Ok(())
+}
+
+#[test]
+fn test_matches() -> Result<(), Box<dyn Error>> {
+    let token: Address = TOKEN.parse()?;
+    let other: Address = "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512".parse()?;
+    let transfer = event_topic("Transfer(address,address,uint256)");
+    let approval = event_topic("Approval(address,address,uint256)");
+    let mut bloom = Bloom::default();
+    bloom.accrue_address(&token);
+    bloom.accrue_topic(&transfer);
+
+    let filter = |address: Vec<Address>, topics: Vec<Option<Vec<H256>>>| Filter {
+        address,
+        topics,
+        ..Default::default()
+    };
+    assert!(bloom.matches(&Filter::default()));
+    assert!(bloom.matches(&filter(vec![other, token], vec![])));
+    assert!(!bloom.matches(&filter(vec![other], vec![])));
+    assert!(bloom.matches(&filter(vec![], vec![Some(vec![approval, transfer])])));
+    assert!(!bloom.matches(&filter(vec![token], vec![Some(vec![approval])])));
+    // wildcard positions match anything
+    assert!(bloom.matches(&filter(vec![token], vec![None, Some(vec![])])));
+    assert!(!bloom.matches(&filter(vec![], vec![None, Some(vec![approval])])));
+    Ok(())
+}
+
+#[test]
+fn test_parse() -> Result<(), Box<dyn Error>> {
+    let mut bloom = Bloom::default();
+    bloom.accrue_topic(&event_topic("Transfer(address,address,uint256)"));
+    let hex = bloom.to_string();
+    assert_eq!(hex.len(), 514);
+    assert_eq!(hex.parse::<Bloom>()?, bloom);
+    assert_eq!(serde_json::to_value(bloom)?, serde_json::json!(hex));
+    assert_eq!(serde_json::from_value::<Bloom>(hex.into())?, bloom);
+    assert_eq!(
+        "0x".parse::<Bloom>(),
+        Err(FromHexError::InvalidLength {
+            expected: 512,
+            found: 0
+        })
+    );
+    assert_eq!(
+        format!("0x{}", "zz".repeat(256)).parse::<Bloom>(),
+        Err(FromHexError::InvalidCharacter)
+    );
+    Ok(())
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::{block, serve_with};
+use ethrs::cache::{classify, CacheClass, ResponseCache};
+use ethrs::provider::Provider;
+use ethrs::types::BlockId;
+use serde_json::json;
+
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_classify() {
+    let hash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8";
+    let address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    assert_eq!(
+        classify("eth_chainId", &json!([])),
+        Some(CacheClass::Immutable)
+    );
+    assert_eq!(
+        classify("eth_getTransactionReceipt", &json!([hash])),
+        Some(CacheClass::Immutable)
+    );
+    assert_eq!(
+        classify("eth_getBalance", &json!([address, {"blockHash": hash}])),
+        Some(CacheClass::Immutable)
+    );
+    assert_eq!(
+        classify("eth_getStorageAt", &json!([address, "0x0", "0x10"])),
+        Some(CacheClass::Historical)
+    );
+    assert_eq!(
+        classify("eth_call", &json!([{"to": address}, "latest"])),
+        Some(CacheClass::Volatile)
+    );
+    assert_eq!(
+        classify("eth_call", &json!([{"to": address}, "pending"])),
+        None
+    );
+    assert_eq!(
+        classify(
+            "eth_getLogs",
+            &json!([{"fromBlock": "0x1", "toBlock": "0x10"}])
+        ),
+        Some(CacheClass::Historical)
+    );
+    assert_eq!(
+        classify("eth_getLogs", &json!([{"fromBlock": "0x1"}])),
+        Some(CacheClass::Volatile)
+    );
+    assert_eq!(
+        classify("eth_gasPrice", &json!([])),
+        Some(CacheClass::Volatile)
+    );
+    assert_eq!(classify("eth_sendRawTransaction", &json!(["0x"])), None);
+    assert_eq!(classify("eth_getFilterChanges", &json!(["0x1"])), None);
+}
+
+#[test]
+fn test_response_cache() -> Result<(), Box<dyn Error>> {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let url = serve_with(move |method, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        match method {
+            "eth_getBlockByNumber" => block(16, 1700000000),
+            "eth_gasPrice" => json!("0x3b9aca00"),
+            "eth_getTransactionByHash" => json!(null),
+            _ => json!("0x1"),
+        }
+    });
+    let cache =
+        ResponseCache::new().with_ttl(CacheClass::Volatile, Some(Duration::from_millis(200)));
+    let provider = Provider::new(&url).with_cache(cache.clone());
+
+    for _ in 0..3 {
+        assert_eq!(provider.chain_id()?, 1);
+        let block = provider.get_block_by_number(Some(BlockId::Number(16)))?;
+        assert_eq!(block.unwrap().number, Some(16));
+        assert_eq!(provider.clone().gas_price()?, 1_000_000_000);
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+    assert_eq!(cache.len(), 3);
+
+    // volatile responses expire
+    thread::sleep(Duration::from_millis(250));
+    provider.gas_price()?;
+    assert_eq!(requests.load(Ordering::SeqCst), 4);
+
+    // unknown transactions may show up later
+    let hash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8".parse()?;
+    assert!(provider.get_transaction_by_hash(hash)?.is_none());
+    assert!(provider.get_transaction_by_hash(hash)?.is_none());
+    assert_eq!(requests.load(Ordering::SeqCst), 6);
+
+    // pending requests are never cached
+    provider.get_block_by_number(Some(BlockId::Pending))?;
+    provider.get_block_by_number(Some(BlockId::Pending))?;
+    assert_eq!(requests.load(Ordering::SeqCst), 8);
+
+    cache.clear();
+    provider.chain_id()?;
+    assert_eq!(requests.load(Ordering::SeqCst), 9);
+    Ok(())
+}
+
+#[test]
+fn test_response_cache_eviction() -> Result<(), Box<dyn Error>> {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let url = serve_with(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        json!("0x1")
+    });
+    let cache = ResponseCache::new().with_max_entries(2);
+    let provider = Provider::new(&url).with_cache(cache.clone());
+    let address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    provider.chain_id()?;
+    provider.get_balance(address, Some(BlockId::Number(1)))?;
+    provider.get_balance(address, Some(BlockId::Number(2)))?;
+    assert_eq!(cache.len(), 2);
+    // the immutable response outlives the historical ones
+    provider.chain_id()?;
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use ethrs::abi::{Abi, Token};
+use ethrs::calldata::FunctionRegistry;
+use ethrs::types::{Address, U256};
+
+use std::error::Error;
+
+const ABI: &str = r#"[{
+    "type": "function",
+    "name": "approve",
+    "inputs": [{"name": "spender", "type": "address"}, {"name": "value", "type": "uint256"}],
+    "outputs": [{"name": "", "type": "bool"}],
+    "stateMutability": "nonpayable"
+}, {
+    "type": "function",
+    "name": "multicall",
+    "inputs": [{"name": "deadline", "type": "uint256"}, {"name": "data", "type": "bytes[]"}],
+    "outputs": [{"name": "", "type": "bytes[]"}],
+    "stateMutability": "payable"
+}, {
+    "type": "function",
+    "name": "aggregate3",
+    "inputs": [{
+        "name": "calls",
+        "type": "tuple[]",
+        "components": [
+            {"name": "target", "type": "address"},
+            {"name": "allowFailure", "type": "bool"},
+            {"name": "callData", "type": "bytes"}
+        ]
+    }],
+    "outputs": [],
+    "stateMutability": "payable"
+}]"#;
+
+#[test]
+fn test_function_registry() -> Result<(), Box<dyn Error>> {
+    let abi: Abi = serde_json::from_str(ABI)?;
+    let mut registry = FunctionRegistry::new();
+    registry.register_abi(&abi)?;
+    registry.register_abi(&abi)?;
+    assert_eq!(registry.len(), 3);
+
+    let spender: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    let approve = abi
+        .function("approve")
+        .unwrap()
+        .encode_input(&[Token::Address(spender), Token::Uint(U256::MAX)])?;
+    let multicall = abi.function("multicall").unwrap().encode_input(&[
+        Token::Uint(1700000000.into()),
+        Token::Array(vec![
+            Token::Bytes(approve.clone()),
+            Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+        ]),
+    ])?;
+    let aggregate3 = abi
+        .function("aggregate3")
+        .unwrap()
+        .encode_input(&[Token::Array(vec![Token::Tuple(vec![
+            Token::Address(spender),
+            Token::Bool(false),
+            Token::Bytes(multicall),
+        ])])])?;
+
+    let call = registry.decode_input(&aggregate3).unwrap();
+    assert_eq!(call.signature, "aggregate3((address,bool,bytes)[])");
+    assert_eq!(call.calls.len(), 1);
+    let multicall = &call.calls[0];
+    assert_eq!(multicall.name, "multicall");
+    assert_eq!(
+        multicall.param("deadline"),
+        Some(&Token::Uint(1700000000.into()))
+    );
+    // calls to unknown functions are not listed
+    assert_eq!(multicall.calls.len(), 1);
+    assert_eq!(multicall.calls[0].name, "approve");
+    assert_eq!(
+        multicall.calls[0].param("spender"),
+        Some(&Token::Address(spender))
+    );
+    assert!(multicall.calls[0].calls.is_empty());
+
+    assert!(registry.decode_input(&[0xde, 0xad, 0xbe, 0xef]).is_none());
+    assert!(registry.decode_input(&approve[..20]).is_none());
+    assert!(registry.decode_input(&[]).is_none());
+    Ok(())
+}
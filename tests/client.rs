@@ -0,0 +1,42 @@
+mod common;
+
+use common::{rpc, serve};
+use ethrs::abi::ParamType;
+use ethrs::client::JsonRpcClient;
+use ethrs::provider::{CallInput, Provider};
+use ethrs::types::Address;
+
+use std::error::Error;
+
+fn is_contract(client: &impl JsonRpcClient, address: Address) -> Result<bool, Box<dyn Error>> {
+    Ok(client.get_code(address, None)? != "0x")
+}
+
+fn total_supply(client: &impl JsonRpcClient, token: Address) -> Result<u64, Box<dyn Error>> {
+    let tx = CallInput {
+        from: None,
+        to: token,
+        gas: None,
+        gas_price: None,
+        value: None,
+        data: Some("0x18160ddd".to_owned()),
+    };
+    client.call_decoded(tx, &[ParamType::Uint(256)], None)
+}
+
+#[test]
+fn test_json_rpc_client() -> Result<(), Box<dyn Error>> {
+    let (url, server) = serve(vec![
+        rpc(serde_json::json!("0x")),
+        rpc(serde_json::json!("0x6080")),
+        rpc(serde_json::json!(format!("0x{:064x}", 1000))),
+    ]);
+    let provider = Provider::new(&url);
+    let address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    assert!(!is_contract(&provider, address)?);
+    assert!(is_contract(&provider, address)?);
+    assert_eq!(total_supply(&provider, address)?, 1000);
+    let requests = server.join().unwrap();
+    assert!(requests[2].contains("\"eth_call\""));
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+///Serves the given `(status, body)` responses in order, one per connection, on a local port. JSON-RPC responses with
+///id 1, as built by `rpc()`, get the id of their request. Returns the URL to connect to and a handle resolving to the
+///request bodies received.
+#[allow(dead_code)]
+pub fn serve(responses: Vec<(&'static str, String)>) -> (String, JoinHandle<Vec<String>>) {
+    let (url, handle) = serve_requests(responses);
+    let handle = thread::spawn(move || {
+        let requests = handle.join().unwrap();
+        requests.into_iter().map(|(_, body)| body).collect()
+    });
+    (url, handle)
+}
+
+///Like `serve()`, but the handle resolves to the request heads, i.e. the request line and headers, along with the
+///bodies.
+#[allow(dead_code)]
+pub fn serve_requests(
+    responses: Vec<(&'static str, String)>,
+) -> (String, JoinHandle<Vec<(String, String)>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut requests = vec![];
+        for (status, response) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length: ") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let body = String::from_utf8(body).unwrap();
+            let response = match serde_json::from_str::<serde_json::Value>(&response) {
+                Ok(mut json) if json["id"] == 1 => {
+                    json["id"] = request_id(&body);
+                    json.to_string()
+                }
+                _ => response,
+            };
+            requests.push((head, body));
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+        }
+        requests
+    });
+    (url, handle)
+}
+
+///Returns the id of a JSON-RPC request body.
+fn request_id(body: &str) -> serde_json::Value {
+    serde_json::from_str::<serde_json::Value>(body)
+        .map_or(serde_json::Value::Null, |request| request["id"].clone())
+}
+
+///Returns a successful JSON-RPC response with the given result.
+#[allow(dead_code)]
+pub fn rpc(result: serde_json::Value) -> (&'static str, String) {
+    (
+        "200 OK",
+        serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": result}).to_string(),
+    )
+}
+
+///Returns a successful transaction receipt mined in block 1, with the given contract address if it is a deployment.
+#[allow(dead_code)]
+pub fn receipt(txhash: &str, contract_address: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "transactionHash": txhash,
+        "transactionIndex": "0x0",
+        "blockHash": "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6",
+        "blockNumber": "0x1",
+        "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+        "to": null,
+        "cumulativeGasUsed": "0x5208",
+        "effectiveGasPrice": "0x1",
+        "gasUsed": "0x5208",
+        "contractAddress": contract_address,
+        "logs": [],
+        "logsBloom": "0x",
+        "status": "0x1",
+        "root": null,
+    })
+}
+
+///Serves JSON-RPC requests on a local port until the test exits, answering each with the result the handler computes
+///from the request method and params. Returns the URL to connect to.
+#[allow(dead_code)]
+pub fn serve_with(
+    handler: impl Fn(&str, &serde_json::Value) -> serde_json::Value + Send + 'static,
+) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut reader = BufReader::new(stream.unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length: ") {
+                    content_length = length.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": handler(request["method"].as_str().unwrap_or_default(), &request["params"]),
+            })
+            .to_string();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+///Returns a block header with the given number and timestamp.
+#[allow(dead_code)]
+pub fn block(number: u64, timestamp: u64) -> serde_json::Value {
+    let hash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8";
+    serde_json::json!({
+        "number": format!("0x{number:x}"),
+        "hash": hash,
+        "parentHash": hash,
+        "nonce": "0x0",
+        "sha3Uncles": hash,
+        "logsBloom": "0x",
+        "transactionsRoot": hash,
+        "stateRoot": hash,
+        "receiptsRoot": hash,
+        "miner": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+        "difficulty": "0x0",
+        "totalDifficulty": null,
+        "extraData": "0x",
+        "size": "0x200",
+        "gasLimit": "0x1c9c380",
+        "gasUsed": "0x0",
+        "timestamp": format!("0x{timestamp:x}"),
+        "uncles": [],
+    })
+}
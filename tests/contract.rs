@@ -0,0 +1,215 @@
+use ethrs::abi::{Abi, Token};
+use ethrs::artifact::Artifact;
+use ethrs::contract::{Contract, ContractFactory};
+use ethrs::provider::Provider;
+use ethrs::types::{Address, U256};
+
+use std::error::Error;
+use std::time::Duration;
+
+mod common;
+
+#[test]
+fn test_deploy() -> Result<(), Box<dyn Error>> {
+    let txhash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8";
+    let (url, server) = common::serve(vec![(
+        "200 OK",
+        serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": txhash}).to_string(),
+    )]);
+    let artifact = Artifact::load("tests/fixtures/Counter.json")?;
+    let factory = ContractFactory::from_artifact(&artifact, Provider::new(&url))?;
+
+    let data = factory.deploy_data(&[Token::Uint(U256::from(42))])?;
+    assert_eq!(
+        &data[..factory.bytecode().len()],
+        factory.bytecode().as_ref()
+    );
+    assert_eq!(data[data.len() - 1], 42);
+    assert!(factory.deploy_data(&[]).is_err());
+    assert!(factory.deploy_data(&[Token::Bool(true)]).is_err());
+
+    let deployed = factory.deploy(
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?,
+        &[Token::Uint(U256::from(42))],
+    )?;
+    assert_eq!(deployed, txhash.parse()?);
+    let request: serde_json::Value = serde_json::from_str(&server.join().unwrap()[0])?;
+    assert_eq!(request["method"], "eth_sendTransaction");
+    assert_eq!(request["params"][0]["to"], serde_json::Value::Null);
+    assert_eq!(request["params"][0]["data"], data.to_string());
+    Ok(())
+}
+
+#[test]
+fn test_deploy_libraries() -> Result<(), Box<dyn Error>> {
+    let txhash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8";
+    let library = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+    let deployer = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    let (url, server) = common::serve(vec![
+        common::rpc(txhash.into()),
+        common::rpc(common::receipt(txhash, Some(library))),
+        common::rpc("0x1".into()),
+    ]);
+    let vault = Artifact::from_json(
+        r#"{
+            "contractName": "Vault",
+            "abi": [],
+            "bytecode": "0x73__$1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e$__63",
+            "deployedBytecode": "0x",
+            "linkReferences": {"contracts/Math.sol": {"Math": [{"start": 1, "length": 20}]}}
+        }"#,
+    )?;
+    let math = Artifact::from_json(
+        r#"{"contractName": "Math", "abi": [], "bytecode": "0x60806040", "deployedBytecode": "0x6080"}"#,
+    )?;
+    assert!(ContractFactory::from_artifact(&vault, Provider::new(&url)).is_err());
+
+    let (factory, libraries) = ContractFactory::deploy_libraries(
+        &vault,
+        &[math],
+        deployer.parse()?,
+        Provider::new(&url),
+        Duration::from_secs(5),
+    )?;
+    assert_eq!(libraries["contracts/Math.sol:Math"], library.parse()?);
+    assert_eq!(
+        factory.bytecode().to_string(),
+        format!("0x73{}63", &library[2..])
+    );
+    let requests = server.join().unwrap();
+    let request: serde_json::Value = serde_json::from_str(&requests[0])?;
+    assert_eq!(request["method"], "eth_sendTransaction");
+    assert_eq!(request["params"][0]["data"], "0x60806040");
+
+    let linked = ContractFactory::from_linked_artifact(
+        &vault,
+        &[("Math", library.parse()?)],
+        Provider::new(&url),
+    )?;
+    assert_eq!(linked.bytecode(), factory.bytecode());
+    assert!(ContractFactory::deploy_libraries(
+        &vault,
+        &[],
+        deployer.parse()?,
+        Provider::new(&url),
+        Duration::from_secs(5),
+    )
+    .is_err());
+    Ok(())
+}
+
+fn erc20_abi() -> Abi {
+    serde_json::from_value(serde_json::json!([
+        {
+            "type": "function",
+            "name": "balanceOf",
+            "inputs": [{"name": "owner", "type": "address"}],
+            "outputs": [{"name": "", "type": "uint256"}],
+            "stateMutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "transfer",
+            "inputs": [{"name": "to", "type": "address"}, {"name": "value", "type": "uint256"}],
+            "outputs": [{"name": "", "type": "bool"}],
+            "stateMutability": "nonpayable"
+        },
+        {
+            "type": "function",
+            "name": "transfer",
+            "inputs": [{"name": "to", "type": "address"}],
+            "outputs": [],
+            "stateMutability": "nonpayable"
+        },
+        {
+            "type": "function",
+            "name": "metadata",
+            "inputs": [],
+            "outputs": [{"name": "", "type": "string"}, {"name": "", "type": "uint8"}],
+            "stateMutability": "view"
+        }
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn test_contract_call_fn() -> Result<(), Box<dyn Error>> {
+    let balance = format!("0x{:064x}", 1000);
+    let metadata = format!(
+        "0x{:064x}{:064x}{:064x}{:0<64}",
+        0x40,
+        18,
+        4,
+        hex::encode("WETH")
+    );
+    let (url, server) = common::serve(vec![
+        common::rpc(serde_json::json!(balance)),
+        common::rpc(serde_json::json!(metadata)),
+    ]);
+    let address: Address = "0x7b79995e5f793a07bc00c21412e50ecae098e7f9".parse()?;
+    let owner: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    let contract = Contract::new(address, erc20_abi(), Provider::new(&url));
+
+    let balance: U256 = contract.call_fn("balanceOf", &[Token::Address(owner)])?;
+    assert_eq!(balance, U256::from(1000));
+    let (symbol, decimals): (String, u8) = contract.call_fn("metadata", &[])?;
+    assert_eq!((symbol.as_str(), decimals), ("WETH", 18));
+
+    let requests = server.join().unwrap();
+    let request: serde_json::Value = serde_json::from_str(&requests[0])?;
+    assert_eq!(request["method"], "eth_call");
+    assert_eq!(request["params"][0]["to"], serde_json::json!(address));
+    assert_eq!(
+        request["params"][0]["data"],
+        contract
+            .encode_fn("balanceOf", &[Token::Address(owner)])?
+            .to_string()
+    );
+    assert_eq!(request["params"][1], "latest");
+
+    assert_eq!(
+        contract
+            .call_fn::<U256>("totalSupply", &[])
+            .unwrap_err()
+            .to_string(),
+        "Function totalSupply not found in the ABI"
+    );
+    assert!(contract
+        .call_fn::<U256>("balanceOf", &[Token::Bool(true)])
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_contract_encode_tx() -> Result<(), Box<dyn Error>> {
+    let address: Address = "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?;
+    let from: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    let to: Address = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".parse()?;
+    let contract = Contract::new(address, erc20_abi(), Provider::new("http://localhost:8545"));
+
+    let tx = contract.encode_tx(
+        from,
+        "transfer",
+        &[Token::Address(to), Token::Uint(U256::from(1))],
+    )?;
+    assert_eq!((tx.from, tx.to), (from, Some(address)));
+    let data = tx.data.unwrap();
+    assert!(data.starts_with("0xa9059cbb"));
+    assert_eq!(data.len(), 2 + 8 + 128);
+
+    // overloads are picked by signature
+    let tx = contract.encode_tx(from, "transfer(address)", &[Token::Address(to)])?;
+    assert_eq!(tx.data.unwrap().len(), 2 + 8 + 64);
+    assert!(contract
+        .encode_tx(from, "transfer(address,bool)", &[])
+        .is_err());
+    assert_eq!(contract.function("transfer")?.inputs.len(), 2);
+
+    let factory = ContractFactory::from_artifact(
+        &Artifact::load("tests/fixtures/Counter.json")?,
+        Provider::new("http://localhost:8545"),
+    )?;
+    assert_eq!(factory.at(address).address(), address);
+    assert_eq!(factory.at(address).abi(), factory.abi());
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use ethrs::abi::Token;
+use ethrs::artifact::Artifact;
+use ethrs::contract::ContractFactory;
+use ethrs::deployer::{create2_address, Deployer, Manifest, CREATE2_DEPLOYER};
+use ethrs::provider::Provider;
+use ethrs::types::{Address, H256};
+
+use std::env;
+use std::error::Error;
+use std::fs;
+
+mod common;
+
+#[test]
+fn test_create2_address() -> Result<(), Box<dyn Error>> {
+    // examples from EIP-1014
+    assert_eq!(
+        create2_address(
+            "0x0000000000000000000000000000000000000000".parse()?,
+            H256::ZERO,
+            &[0x00]
+        ),
+        "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38".parse()?
+    );
+    assert_eq!(
+        create2_address(
+            "0x00000000000000000000000000000000deadbeef".parse()?,
+            "0x00000000000000000000000000000000000000000000000000000000cafebabe".parse()?,
+            &hex::decode("deadbeef")?,
+        ),
+        "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7".parse()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_deploy_manifest() -> Result<(), Box<dyn Error>> {
+    let txhash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8";
+    let counter = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+    let path = env::temp_dir().join(format!("ethrs-manifest-{}.json", std::process::id()));
+    let artifact = Artifact::load("tests/fixtures/Counter.json")?;
+
+    let (url, server) = common::serve(vec![
+        common::rpc("0x7a69".into()),
+        common::rpc(txhash.into()),
+        common::rpc(common::receipt(txhash, Some(counter))),
+        common::rpc("0x1".into()),
+        common::rpc("0x6080".into()),
+        // the second deployment only checks the recorded code
+        common::rpc("0x6080".into()),
+    ]);
+    let factory = ContractFactory::from_artifact(&artifact, Provider::new(&url))?;
+    let mut deployer = Deployer::new(
+        Provider::new(&url),
+        "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?,
+    )?
+    .with_manifest(&path)?;
+    let args = [Token::Uint(42.into())];
+    let deployment = deployer.deploy("Counter", &factory, &args)?;
+    assert_eq!(deployment.address, counter.parse()?);
+    assert_eq!(deployment.transaction_hash, Some(txhash.parse()?));
+    assert_eq!(deployer.deploy("Counter", &factory, &args)?, deployment);
+    let methods: Vec<String> = server
+        .join()
+        .unwrap()
+        .iter()
+        .map(|request| {
+            serde_json::from_str::<serde_json::Value>(request).unwrap()["method"].to_string()
+        })
+        .collect();
+    assert_eq!(
+        methods
+            .iter()
+            .filter(|method| method.contains("eth_sendTransaction"))
+            .count(),
+        1
+    );
+
+    let manifest = Manifest::load(&path)?;
+    assert_eq!(manifest.get(31337, "Counter"), Some(&deployment));
+    assert_eq!(&manifest, deployer.manifest());
+
+    // a different contract at the recorded address is an error rather than a silent redeploy
+    let (url, _) = common::serve(vec![
+        common::rpc("0x7a69".into()),
+        common::rpc("0x6001".into()),
+    ]);
+    let mut deployer = Deployer::new(
+        Provider::new(&url),
+        "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?,
+    )?
+    .with_manifest(&path)?;
+    assert!(deployer.deploy("Counter", &factory, &args).is_err());
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_deploy_create2() -> Result<(), Box<dyn Error>> {
+    let txhash = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8";
+    let artifact = Artifact::load("tests/fixtures/Counter.json")?;
+    let salt = H256::ZERO;
+    let (url, server) = common::serve(vec![
+        common::rpc("0x1".into()),
+        common::rpc("0x".into()),
+        common::rpc("0x7fff".into()),
+        common::rpc(txhash.into()),
+        common::rpc(common::receipt(txhash, None)),
+        common::rpc("0x1".into()),
+        common::rpc("0x6080".into()),
+    ]);
+    let factory = ContractFactory::from_artifact(&artifact, Provider::new(&url))?;
+    let mut deployer = Deployer::new(
+        Provider::new(&url),
+        "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?,
+    )?;
+    let deployment =
+        deployer.deploy_create2("Counter", &factory, &[Token::Uint(42.into())], salt)?;
+    let init_code = factory.deploy_data(&[Token::Uint(42.into())])?;
+    assert_eq!(
+        deployment.address,
+        create2_address(CREATE2_DEPLOYER.parse()?, salt, &init_code)
+    );
+    assert_eq!(deployment.salt, Some(salt));
+
+    let requests = server.join().unwrap();
+    let request: serde_json::Value = serde_json::from_str(&requests[3])?;
+    assert_eq!(request["method"], "eth_sendTransaction");
+    assert_eq!(
+        request["params"][0]["to"]
+            .as_str()
+            .unwrap()
+            .parse::<Address>()?,
+        CREATE2_DEPLOYER.parse()?
+    );
+    assert_eq!(
+        request["params"][0]["data"],
+        format!("0x{}{}", "00".repeat(32), &init_code.to_string()[2..])
+    );
+    Ok(())
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{rpc, serve_requests};
+use ethrs::dev::DevNode;
+use ethrs::provider::Provider;
+use ethrs::types::{Address, Bytes, U256};
+use serde_json::{json, Value};
+
+use std::error::Error;
+
+#[test]
+fn test_dev_node() -> Result<(), Box<dyn Error>> {
+    let (url, requests) = serve_requests(vec![
+        rpc(json!("0x1")),
+        rpc(json!("0x0")),
+        rpc(json!(null)),
+        rpc(json!(3600)),
+        rpc(json!(null)),
+        rpc(json!(null)),
+        rpc(json!(null)),
+        rpc(json!(null)),
+        rpc(json!(true)),
+        rpc(json!(false)),
+    ]);
+    let node = DevNode::new(Provider::new(&url));
+    let address: Address = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".parse()?;
+    let code: Bytes = "0x6080604052".parse()?;
+
+    let snapshot = node.snapshot()?;
+    assert_eq!(snapshot, U256::one());
+    node.mine()?;
+    node.set_next_block_timestamp(1700000000)?;
+    node.increase_time(3600)?;
+    node.set_balance(address, U256::exp10(18))?;
+    node.impersonate_account(address)?;
+    node.stop_impersonating_account(address)?;
+    node.set_code(address, &code)?;
+    assert!(node.revert(snapshot)?);
+    assert!(!node.revert(snapshot)?);
+
+    let requests: Vec<Value> = requests
+        .join()
+        .unwrap()
+        .iter()
+        .map(|(_, body)| serde_json::from_str(body).unwrap())
+        .collect();
+    let calls: Vec<(&str, &Value)> = requests
+        .iter()
+        .map(|request| (request["method"].as_str().unwrap(), &request["params"]))
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            ("evm_snapshot", &json!([])),
+            ("evm_mine", &json!([])),
+            ("evm_setNextBlockTimestamp", &json!([1700000000])),
+            ("evm_increaseTime", &json!([3600])),
+            ("anvil_setBalance", &json!([address, "0xde0b6b3a7640000"])),
+            ("anvil_impersonateAccount", &json!([address])),
+            ("anvil_stopImpersonatingAccount", &json!([address])),
+            ("hardhat_setCode", &json!([address, "0x6080604052"])),
+            ("evm_revert", &json!(["0x1"])),
+            ("evm_revert", &json!(["0x1"])),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_dev_node_errors() -> Result<(), Box<dyn Error>> {
+    let (url, _) = serve_requests(vec![(
+        "200 OK",
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32601, "message": "the method evm_mine does not exist/is not available" }
+        })
+        .to_string(),
+    )]);
+    assert!(DevNode::new(Provider::new(&url)).mine().is_err());
+    Ok(())
+}
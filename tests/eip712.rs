@@ -0,0 +1,217 @@
+use ethrs::eip712::{Eip712Domain, TypedData};
+use ethrs::types::{H256, U256};
+use ethrs::utils::keccak256;
+use ethrs::wallet::Wallet;
+use serde_json::json;
+
+use std::error::Error;
+
+// the example of EIP-712
+fn mail() -> TypedData {
+    serde_json::from_value(json!({
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"},
+            ],
+            "Person": [{"name": "name", "type": "string"}, {"name": "wallet", "type": "address"}],
+            "Mail": [
+                {"name": "from", "type": "Person"},
+                {"name": "to", "type": "Person"},
+                {"name": "contents", "type": "string"},
+            ],
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+        },
+        "message": {
+            "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+            "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+            "contents": "Hello, Bob!",
+        },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_typed_data_hashes() -> Result<(), Box<dyn Error>> {
+    let typed_data = mail();
+    assert_eq!(
+        typed_data.encode_type("Mail")?,
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    );
+    assert_eq!(
+        typed_data.domain.separator(),
+        "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f".parse()?
+    );
+    assert_eq!(
+        typed_data.hash_struct("Mail", &typed_data.message)?,
+        "0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e".parse()?
+    );
+    assert_eq!(
+        typed_data.signing_hash()?,
+        "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2".parse()?
+    );
+    assert_eq!(
+        serde_json::from_value::<TypedData>(typed_data.to_json())?,
+        typed_data
+    );
+    Ok(())
+}
+
+#[test]
+fn test_sign_typed_data() -> Result<(), Box<dyn Error>> {
+    let wallet = Wallet::from_private_key(&keccak256(b"cow"))?;
+    assert_eq!(
+        wallet.address(),
+        "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826".parse()?
+    );
+    let signature = wallet.sign_typed_data(&mail())?;
+    assert_eq!(signature.v, 28);
+    assert_eq!(
+        signature.r,
+        U256::from_str_radix(
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+            16
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        signature.s,
+        U256::from_str_radix(
+            "07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+            16
+        )
+        .unwrap()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_typed_data_arrays() -> Result<(), Box<dyn Error>> {
+    let mut typed_data = mail();
+    typed_data.types.insert(
+        "Group".to_owned(),
+        serde_json::from_value(json!([
+            {"name": "name", "type": "string"},
+            {"name": "members", "type": "Person[]"},
+            {"name": "ids", "type": "uint8[2]"},
+        ]))?,
+    );
+    let alice = json!({"name": "Alice", "wallet": "0x0000000000000000000000000000000000000001"});
+    let bob = json!({"name": "Bob", "wallet": "0x0000000000000000000000000000000000000002"});
+    let group = json!({"name": "g", "members": [alice, bob], "ids": [1, "0x2"]});
+    assert_eq!(
+        typed_data.encode_type("Group")?,
+        "Group(string name,Person[] members,uint8[2] ids)Person(string name,address wallet)"
+    );
+    let members = [
+        typed_data.hash_struct("Person", &alice)?.0,
+        typed_data.hash_struct("Person", &bob)?.0,
+    ]
+    .concat();
+    let mut ids = [0u8; 64];
+    ids[31] = 1;
+    ids[63] = 2;
+    let expected = [
+        &typed_data.type_hash("Group")?.0[..],
+        &keccak256(b"g"),
+        &keccak256(&members),
+        &keccak256(&ids),
+    ]
+    .concat();
+    assert_eq!(
+        typed_data.hash_struct("Group", &group)?,
+        H256(keccak256(&expected))
+    );
+    let mut wrong_length = group.clone();
+    wrong_length["ids"] = json!([1]);
+    assert!(typed_data.hash_struct("Group", &wrong_length).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_typed_data_values() -> Result<(), Box<dyn Error>> {
+    let mut typed_data = mail();
+    typed_data.types.insert(
+        "Values".to_owned(),
+        serde_json::from_value(json!([
+            {"name": "negative", "type": "int8"},
+            {"name": "flag", "type": "bool"},
+            {"name": "tag", "type": "bytes4"},
+            {"name": "data", "type": "bytes"},
+            {"name": "amount", "type": "uint256"},
+        ]))?,
+    );
+    let values = json!({
+        "negative": -1,
+        "flag": true,
+        "tag": "0x01020304",
+        "data": "0xdeadbeef",
+        "amount": "1000000000000000000",
+    });
+    let mut tag = [0u8; 32];
+    tag[..4].copy_from_slice(&[1, 2, 3, 4]);
+    let mut flag = [0u8; 32];
+    flag[31] = 1;
+    let mut amount = [0u8; 32];
+    U256::from(1_000_000_000_000_000_000u64).to_big_endian(&mut amount);
+    let expected = [
+        &typed_data.type_hash("Values")?.0[..],
+        &[0xff; 32],
+        &flag,
+        &tag,
+        &keccak256(&[0xde, 0xad, 0xbe, 0xef]),
+        &amount,
+    ]
+    .concat();
+    assert_eq!(
+        typed_data.hash_struct("Values", &values)?,
+        H256(keccak256(&expected))
+    );
+    for (field, value) in [
+        ("negative", json!(-129)),
+        ("negative", json!(128)),
+        ("flag", json!("true")),
+        ("tag", json!("0x0102030405")),
+    ] {
+        let mut invalid = values.clone();
+        invalid[field] = value;
+        assert!(typed_data.hash_struct("Values", &invalid).is_err());
+    }
+    let mut min = values.clone();
+    min["negative"] = json!(-128);
+    assert!(typed_data.hash_struct("Values", &min).is_ok());
+    assert_eq!(
+        typed_data
+            .hash_struct("Values", &json!({}))
+            .unwrap_err()
+            .to_string(),
+        "Missing EIP-712 field Values.negative"
+    );
+    assert_eq!(
+        typed_data.encode_type("Order").unwrap_err().to_string(),
+        "Unknown EIP-712 type Order"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_domain_separator() {
+    let domain = Eip712Domain {
+        name: Some("Permit".to_owned()),
+        ..Default::default()
+    };
+    let expected = [
+        keccak256(b"EIP712Domain(string name)"),
+        keccak256(b"Permit"),
+    ]
+    .concat();
+    assert_eq!(domain.separator(), H256(keccak256(&expected)));
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{serve_requests, serve_with};
+use ethrs::abi::{encode, Token};
+use ethrs::ens::{namehash, Ens, ENS_REGISTRY};
+use ethrs::provider::Provider;
+use ethrs::types::{Address, Bytes, H256, U256};
+use ethrs::utils::function_selector;
+use serde_json::json;
+
+use std::error::Error;
+
+const RESOLVER: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const COLLECTION: &str = "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512";
+const MULTI_TOKEN: &str = "0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0";
+const OWNER: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+const PUNK: &str = "eyJuYW1lIjoiUHVuayIsImltYWdlIjoiaXBmczovL2lwZnMvUW1JbWFnZS9wdW5rLnBuZyJ9";
+
+fn returns(tokens: &[Token]) -> serde_json::Value {
+    json!(Bytes(encode(tokens)))
+}
+
+///Returns the text records of the names served by `node()`.
+fn records(name: &str) -> Vec<(&'static str, String)> {
+    match name {
+        "alice.eth" => vec![
+            ("url", "https://alice.example".to_owned()),
+            ("avatar", "ipfs://QmAvatar/alice.png".to_owned()),
+        ],
+        "punk.eth" => vec![("avatar", format!("eip155:1/erc721:{COLLECTION}/42"))],
+        "sold.eth" => vec![("avatar", format!("eip155:1/erc721:{COLLECTION}/7"))],
+        "badge.eth" => vec![("avatar", format!("eip155:1/erc1155:{MULTI_TOKEN}/255"))],
+        "bridged.eth" => vec![("avatar", format!("eip155:10/erc721:{COLLECTION}/42"))],
+        "broken.eth" => vec![("avatar", "eip155:1/erc721:0x1234/42".to_owned())],
+        _ => vec![],
+    }
+}
+
+///Serves an ENS registry and resolver along with the NFT collections of the avatars, the ERC-1155 metadata being
+///served at `metadata`.
+fn node(metadata: String) -> String {
+    let names = [
+        "alice.eth",
+        "punk.eth",
+        "sold.eth",
+        "badge.eth",
+        "bridged.eth",
+        "broken.eth",
+    ];
+    serve_with(move |method, params| {
+        if method == "eth_chainId" {
+            return json!("0x1");
+        }
+        assert_eq!(method, "eth_call");
+        let to = params[0]["to"].as_str().unwrap().to_lowercase();
+        let data: Bytes = params[0]["data"].as_str().unwrap().parse().unwrap();
+        let (selector, args) = data.0.split_at(4);
+        let call = |signature: &str| selector == function_selector(signature);
+        let name = names
+            .iter()
+            .find(|name| args.len() >= 32 && namehash(name).0 == args[..32])
+            .copied();
+        let owner = Token::Address(OWNER.parse().unwrap());
+        if to == ENS_REGISTRY.to_lowercase() {
+            assert!(call("resolver(bytes32)"));
+            let resolver = match name {
+                Some(_) => RESOLVER.parse().unwrap(),
+                None => Address::ZERO,
+            };
+            returns(&[Token::Address(resolver)])
+        } else if to == RESOLVER && call("addr(bytes32)") {
+            returns(&[owner])
+        } else if to == RESOLVER && call("text(bytes32,string)") {
+            let key = &args[96..96 + args[95] as usize];
+            let text = records(name.unwrap())
+                .into_iter()
+                .find(|(record, _)| record.as_bytes() == key)
+                .map(|(_, text)| text)
+                .unwrap_or_default();
+            returns(&[Token::String(text)])
+        } else if to == COLLECTION && call("ownerOf(uint256)") {
+            match U256::from_big_endian(args) == U256::from(42) {
+                true => returns(&[owner]),
+                false => returns(&[Token::Address(RESOLVER.parse().unwrap())]),
+            }
+        } else if to == COLLECTION && call("tokenURI(uint256)") {
+            returns(&[Token::String(format!(
+                "data:application/json;base64,{PUNK}"
+            ))])
+        } else if to == MULTI_TOKEN && call("balanceOf(address,uint256)") {
+            returns(&[Token::Uint(U256::from(1))])
+        } else if to == MULTI_TOKEN && call("uri(uint256)") {
+            returns(&[Token::String(format!("{metadata}/{{id}}.json"))])
+        } else {
+            panic!("unexpected call to {to}");
+        }
+    })
+}
+
+#[test]
+fn test_namehash() {
+    assert_eq!(namehash(""), H256::ZERO);
+    assert_eq!(
+        hex::encode(namehash("foo.eth").0),
+        "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+    );
+    assert_eq!(namehash("Foo.ETH"), namehash("foo.eth"));
+}
+
+#[test]
+fn test_get_text() -> Result<(), Box<dyn Error>> {
+    let ens = Ens::new(Provider::new(&node(String::new())));
+    assert_eq!(ens.resolver("alice.eth")?, Some(RESOLVER.parse()?));
+    assert_eq!(ens.resolve_name("alice.eth")?, Some(OWNER.parse()?));
+    assert_eq!(
+        ens.get_text("alice.eth", "url")?.as_deref(),
+        Some("https://alice.example")
+    );
+    assert_eq!(ens.get_text("alice.eth", "com.twitter")?, None);
+    // a name without a resolver
+    assert_eq!(ens.resolver("nobody.eth")?, None);
+    assert_eq!(ens.resolve_name("nobody.eth")?, None);
+    assert_eq!(ens.get_text("nobody.eth", "url")?, None);
+    Ok(())
+}
+
+#[test]
+fn test_get_avatar() -> Result<(), Box<dyn Error>> {
+    let (metadata, requests) = serve_requests(vec![(
+        "200 OK",
+        json!({"name": "Badge", "image": "https://badges.example/255.png"}).to_string(),
+    )]);
+    let ens = Ens::new(Provider::new(&node(metadata)));
+    assert_eq!(
+        ens.get_avatar("alice.eth")?.as_deref(),
+        Some("https://ipfs.io/ipfs/QmAvatar/alice.png")
+    );
+    assert_eq!(
+        ens.clone()
+            .with_ipfs_gateway("https://gateway.example/ipfs/")
+            .get_avatar("punk.eth")?
+            .as_deref(),
+        Some("https://gateway.example/ipfs/QmImage/punk.png")
+    );
+    assert_eq!(
+        ens.get_avatar("badge.eth")?.as_deref(),
+        Some("https://badges.example/255.png")
+    );
+    let (head, _) = &requests.join().unwrap()[0];
+    assert!(head.starts_with(&format!("GET /{:064x}.json ", 255)));
+    // the token is held by another address
+    assert_eq!(ens.get_avatar("sold.eth")?, None);
+    assert_eq!(ens.get_avatar("nobody.eth")?, None);
+    assert!(ens.get_avatar("bridged.eth").is_err());
+    assert!(ens.get_avatar("broken.eth").is_err());
+    Ok(())
+}
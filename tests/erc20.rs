@@ -0,0 +1,94 @@
+mod common;
+
+use common::serve_with;
+use ethrs::abi::{encode, Token as AbiToken};
+use ethrs::erc20::Token;
+use ethrs::provider::Provider;
+use ethrs::types::{Address, BlockId, Bytes, U256};
+use ethrs::utils::function_selector;
+use serde_json::json;
+
+use std::error::Error;
+
+const TOKEN: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const OWNER: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+const SPENDER: &str = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+
+fn returns(tokens: &[AbiToken]) -> serde_json::Value {
+    json!(Bytes(encode(tokens)))
+}
+
+///Serves a token answering its reads, with a `bytes32` symbol if `legacy` is set.
+fn node(legacy: bool) -> String {
+    serve_with(move |method, params| {
+        assert_eq!(method, "eth_call");
+        let data: Bytes = params[0]["data"].as_str().unwrap().parse().unwrap();
+        let (selector, args) = data.0.split_at(4);
+        let call = |signature: &str| selector == function_selector(signature);
+        if call("balanceOf(address)") {
+            assert_eq!(args[12..32], OWNER.parse::<Address>().unwrap().0);
+            returns(&[AbiToken::Uint(U256::from(1000))])
+        } else if call("allowance(address,address)") {
+            assert_eq!(args.len(), 64);
+            assert_eq!(params[1], "0x10");
+            returns(&[AbiToken::Uint(U256::MAX)])
+        } else if call("totalSupply()") {
+            returns(&[AbiToken::Uint(U256::exp10(24))])
+        } else if call("name()") {
+            returns(&[AbiToken::String("Wrapped Ether".to_owned())])
+        } else if call("symbol()") && legacy {
+            let mut symbol = b"MKR".to_vec();
+            symbol.resize(32, 0);
+            json!(Bytes(symbol))
+        } else if call("symbol()") {
+            returns(&[AbiToken::String("WETH".to_owned())])
+        } else if call("decimals()") && legacy {
+            returns(&[AbiToken::Uint(U256::from(256))])
+        } else if call("decimals()") {
+            returns(&[AbiToken::Uint(U256::from(18))])
+        } else {
+            json!("0x")
+        }
+    })
+}
+
+#[test]
+fn test_token_reads() -> Result<(), Box<dyn Error>> {
+    let token = Token::new(TOKEN.parse()?, Provider::new(&node(false)));
+    assert_eq!(token.address(), TOKEN.parse()?);
+    assert_eq!(token.balance_of(OWNER.parse()?)?, U256::from(1000));
+    assert_eq!(token.total_supply()?, U256::exp10(24));
+    assert_eq!(token.name()?, "Wrapped Ether");
+    assert_eq!(token.symbol()?, "WETH");
+    assert_eq!(token.decimals()?, 18);
+    let token = token.with_block(BlockId::Number(16));
+    assert_eq!(
+        token.allowance(OWNER.parse()?, SPENDER.parse()?)?,
+        U256::MAX
+    );
+    Ok(())
+}
+
+#[test]
+fn test_legacy_token() -> Result<(), Box<dyn Error>> {
+    let token = Token::new(TOKEN.parse()?, Provider::new(&node(true)));
+    assert_eq!(token.symbol()?, "MKR");
+    assert!(token.decimals().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_calldata() -> Result<(), Box<dyn Error>> {
+    let token = Token::new(TOKEN.parse()?, Provider::new("http://localhost:8545"));
+    let spender: Address = SPENDER.parse()?;
+    let expected = [
+        &[0xa9, 0x05, 0x9c, 0xbb][..],
+        &encode(&[AbiToken::Address(spender), AbiToken::Uint(U256::from(5))]),
+    ]
+    .concat();
+    assert_eq!(token.transfer_calldata(spender, U256::from(5)).0, expected);
+    let approve = token.approve_calldata(spender, U256::MAX);
+    assert_eq!(approve.0[..4], [0x09, 0x5e, 0xa7, 0xb3]);
+    assert_eq!(approve.0[36..], [0xff; 32]);
+    Ok(())
+}
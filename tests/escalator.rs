@@ -0,0 +1,148 @@
+mod common;
+
+use common::{receipt, serve_with};
+use ethrs::escalator::GasEscalator;
+use ethrs::provider::Provider;
+use ethrs::transaction::{Eip1559Transaction, LegacyTransaction, TypedTransaction};
+use ethrs::types::U256;
+use ethrs::wallet::Wallet;
+use serde_json::json;
+
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn transfer(wallet: &Wallet) -> TypedTransaction {
+    TypedTransaction::from(Eip1559Transaction {
+        chain_id: 31337,
+        nonce: 7,
+        max_priority_fee_per_gas: U256::from(100),
+        max_fee_per_gas: U256::from(1000),
+        gas_limit: 21000,
+        to: Some(wallet.address()),
+        ..Default::default()
+    })
+}
+
+///Serves a node mining the transaction sent after the given number of transactions, recording the transactions sent.
+fn node(
+    pending: serde_json::Value,
+    mined_after: usize,
+) -> (String, Arc<Mutex<Vec<TypedTransaction>>>) {
+    let sent = Arc::new(Mutex::new(vec![]));
+    let recorded = sent.clone();
+    let hashes = Arc::new(Mutex::new(vec![]));
+    let url = serve_with(move |method, params| match method {
+        "eth_getTransactionByHash" => pending.clone(),
+        "eth_sendRawTransaction" => {
+            let signed = TypedTransaction::decode(params[0].as_str().unwrap()).unwrap();
+            recorded.lock().unwrap().push(signed.transaction);
+            let txhash = json!(signed.hash);
+            hashes.lock().unwrap().push(txhash.clone());
+            txhash
+        }
+        "eth_getTransactionReceipt" => {
+            let hashes = hashes.lock().unwrap();
+            match hashes.get(mined_after) {
+                Some(mined) if *mined == params[0] => receipt(mined.as_str().unwrap(), None),
+                _ => json!(null),
+            }
+        }
+        _ => json!(null),
+    });
+    (url, sent)
+}
+
+#[test]
+fn test_bump_fees() {
+    let mut transaction = TypedTransaction::from(Eip1559Transaction {
+        max_priority_fee_per_gas: U256::zero(),
+        max_fee_per_gas: U256::from(1000),
+        ..Default::default()
+    });
+    transaction.bump_fees(15);
+    let TypedTransaction::Eip1559(bumped) = &transaction else {
+        panic!("unexpected transaction type");
+    };
+    assert_eq!(bumped.max_fee_per_gas, U256::from(1150));
+    // fees are raised by at least one wei
+    assert_eq!(bumped.max_priority_fee_per_gas, U256::one());
+    let mut legacy = TypedTransaction::from(LegacyTransaction {
+        gas_price: U256::from(3),
+        ..Default::default()
+    });
+    legacy.bump_fees(10);
+    assert_eq!(legacy.max_fee_per_gas(), U256::from(4));
+}
+
+#[test]
+fn test_replace_transaction() -> Result<(), Box<dyn Error>> {
+    let wallet: Wallet = KEY.parse()?;
+    let pending = wallet.sign_transaction(transfer(&wallet))?;
+    let (url, sent) = node(
+        serde_json::to_value(pending.clone().into_rpc()?)?,
+        usize::MAX,
+    );
+    let escalator = GasEscalator::new(Provider::new(&url), wallet.clone());
+    let replacement = escalator.replace_transaction(pending.hash, 25)?;
+    assert_eq!(replacement.from, wallet.address());
+    assert_ne!(replacement.hash, pending.hash);
+    let mut expected = transfer(&wallet);
+    expected.bump_fees(25);
+    assert_eq!(replacement.transaction, expected);
+    assert_eq!(*sent.lock().unwrap(), vec![expected]);
+
+    let other = GasEscalator::new(Provider::new(&url), Wallet::random());
+    assert!(other.replace_transaction(pending.hash, 25).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_send_transaction() -> Result<(), Box<dyn Error>> {
+    let wallet: Wallet = KEY.parse()?;
+    let (url, sent) = node(json!(null), 2);
+    let escalator = GasEscalator::new(Provider::new(&url), wallet.clone())
+        .with_bump_percent(20)
+        .with_interval(Duration::from_millis(50))
+        .with_timeout(Duration::from_secs(5), Duration::from_millis(10));
+    let receipt = escalator
+        .send_transaction(transfer(&wallet))?
+        .expect("mined");
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent.len(), 3);
+    assert_eq!(
+        receipt.transaction_hash,
+        wallet.sign_transaction(sent[2].clone())?.hash
+    );
+    let fees: Vec<U256> = sent.iter().map(|tx| tx.max_fee_per_gas()).collect();
+    assert_eq!(
+        fees,
+        vec![U256::from(1000), U256::from(1200), U256::from(1440)]
+    );
+    assert!(sent.iter().all(|tx| tx.nonce() == 7));
+    Ok(())
+}
+
+#[test]
+fn test_max_fee_per_gas() -> Result<(), Box<dyn Error>> {
+    let wallet: Wallet = KEY.parse()?;
+    let (url, sent) = node(json!(null), usize::MAX);
+    let escalator = GasEscalator::new(Provider::new(&url), wallet.clone())
+        .with_interval(Duration::from_millis(20))
+        .with_max_fee_per_gas(U256::from(1250))
+        .with_timeout(Duration::from_millis(300), Duration::from_millis(5));
+    assert!(escalator.send_transaction(transfer(&wallet))?.is_none());
+    let fees: Vec<U256> = sent
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|tx| tx.max_fee_per_gas())
+        .collect();
+    assert_eq!(
+        fees,
+        vec![U256::from(1000), U256::from(1100), U256::from(1210)]
+    );
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use ethrs::abi::{Abi, Token};
+use ethrs::events::{EventLog, EventRegistry};
+use ethrs::provider::Log;
+use ethrs::types::{H256, U256};
+
+use std::error::Error;
+
+const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const OWNER: &str = "0x000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+
+fn log(topics: &[&str], data: &str) -> Log {
+    serde_json::from_value(serde_json::json!({
+        "removed": false,
+        "logIndex": "0x0",
+        "transactionIndex": "0x0",
+        "transactionHash": "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8",
+        "blockHash": "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6",
+        "blockNumber": "0x1",
+        "address": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+        "data": data,
+        "topics": topics,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_event_registry() -> Result<(), Box<dyn Error>> {
+    let abi: Abi = serde_json::from_str(
+        r#"[{
+            "type": "event",
+            "name": "Transfer",
+            "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ],
+            "anonymous": false
+        }, {
+            "type": "event",
+            "name": "Transfer",
+            "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "tokenId", "type": "uint256", "indexed": true}
+            ],
+            "anonymous": false
+        }, {
+            "type": "event",
+            "name": "Registered",
+            "inputs": [
+                {"name": "name", "type": "string", "indexed": true},
+                {"name": "owner", "type": "address", "indexed": false},
+                {"name": "label", "type": "string", "indexed": false}
+            ],
+            "anonymous": false
+        }, {
+            "type": "event",
+            "name": "Ping",
+            "inputs": [],
+            "anonymous": true
+        }]"#,
+    )?;
+    let mut registry = EventRegistry::new();
+    registry.register_abi(&abi)?;
+    registry.register_abi(&abi)?;
+    assert_eq!(registry.len(), 3);
+    assert_eq!(registry.events(&TRANSFER.parse()?).len(), 2);
+    assert!(registry
+        .register(abi.event("Ping").unwrap().clone())
+        .is_err());
+
+    let erc20 = log(
+        &[TRANSFER, OWNER, &format!("0x{}", "00".repeat(32))],
+        "0x000000000000000000000000000000000000000000000000000000000000002a",
+    );
+    let erc721 = log(
+        &[
+            TRANSFER,
+            OWNER,
+            OWNER,
+            "0x0000000000000000000000000000000000000000000000000000000000000007",
+        ],
+        "0x",
+    );
+    let name_hash = "0x5c3a3c2b4e8e5aa1fbb5a1c6b2e42ed6ec3b0bbc37b07ed3a8e7e7c6a7de5cf1";
+    let registered_topic = abi.event("Registered").unwrap().topic()?.to_string();
+    let registered = log(
+        &[&registered_topic, name_hash],
+        &format!(
+            "0x{}{}{}{}",
+            &OWNER[2..],
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000005",
+            "616c696365000000000000000000000000000000000000000000000000000000"
+        ),
+    );
+    let unknown = log(
+        &["0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"],
+        "0x",
+    );
+    let malformed = log(&[TRANSFER, OWNER], "0x");
+
+    let decoded = registry.decode_logs(vec![erc20, erc721, registered, unknown, malformed]);
+    let names: Vec<Option<&str>> = decoded.iter().map(EventLog::name).collect();
+    assert_eq!(
+        names,
+        vec![
+            Some("Transfer"),
+            Some("Transfer"),
+            Some("Registered"),
+            None,
+            None
+        ]
+    );
+    match &decoded[0] {
+        EventLog::Known(event) => {
+            assert_eq!(event.signature, "Transfer(address,address,uint256)");
+            assert_eq!(
+                event.param("from"),
+                Some(&Token::Address(
+                    "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?
+                ))
+            );
+            assert_eq!(event.param("value"), Some(&Token::Uint(U256::from(42))));
+        }
+        other => panic!("unexpected log {other:?}"),
+    }
+    match &decoded[1] {
+        EventLog::Known(event) => {
+            assert_eq!(event.param("tokenId"), Some(&Token::Uint(U256::from(7))))
+        }
+        other => panic!("unexpected log {other:?}"),
+    }
+    match &decoded[2] {
+        EventLog::Known(event) => {
+            assert_eq!(
+                event.param("name"),
+                Some(&Token::FixedBytes(
+                    name_hash.parse::<H256>()?.as_bytes().to_vec()
+                ))
+            );
+            assert_eq!(
+                event.param("label"),
+                Some(&Token::String("alice".to_owned()))
+            );
+        }
+        other => panic!("unexpected log {other:?}"),
+    }
+    assert_eq!(decoded[3].log().topics.len(), 1);
+    Ok(())
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use common::{rpc, serve, serve_with};
+use ethrs::fallback::{find_transaction, FallbackProvider};
+use ethrs::provider::Provider;
+use ethrs::types::H256;
+use serde_json::json;
+
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_fallback_provider() -> Result<(), Box<dyn Error>> {
+    // nothing listens on port 1
+    let down = "http://127.0.0.1:1";
+    let (url, server) = serve(vec![
+        rpc(serde_json::json!("0x10")),
+        ("200 OK", serde_json::json!({"jsonrpc": "2.0", "id": 1, "error": {"code": 3, "message": "execution reverted"}}).to_string()),
+        ("502 Bad Gateway", "<html>bad gateway</html>".to_owned()),
+    ]);
+    let fallback = FallbackProvider::from_providers(vec![
+        Provider::new(down),
+        Provider::new(&url),
+        Provider::new(down),
+    ]);
+    let provider = fallback.provider();
+    assert_eq!(provider.block_number()?, 16);
+    assert_eq!(fallback.active().url(), url);
+    // JSON-RPC errors are returned without failing over
+    assert_eq!(
+        provider.chain_id().unwrap_err().to_string(),
+        "execution reverted"
+    );
+    assert_eq!(fallback.active().url(), url);
+    // every endpoint fails
+    assert!(provider.block_number().is_err());
+    server.join().unwrap();
+
+    let health = fallback.health();
+    assert_eq!(health[0].failures, 2);
+    assert_eq!(health[1].successes, 2);
+    assert_eq!(health[1].failures, 1);
+    assert!(!health[1].is_healthy());
+    assert!(health[2].last_error.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_fallback_provider_fail_back() -> Result<(), Box<dyn Error>> {
+    let (primary, primary_server) = serve(vec![
+        ("503 Service Unavailable", String::new()),
+        rpc(serde_json::json!("0x3")),
+    ]);
+    let (secondary, secondary_server) = serve(vec![
+        rpc(serde_json::json!("0x1")),
+        rpc(serde_json::json!("0x2")),
+    ]);
+    let fallback =
+        FallbackProvider::new(&[&primary, &secondary]).with_fail_back(Duration::from_millis(200));
+    let provider = fallback.provider();
+    assert_eq!(provider.block_number()?, 1);
+    assert_eq!(provider.block_number()?, 2);
+    assert_eq!(fallback.active().url(), secondary);
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(provider.block_number()?, 3);
+    assert_eq!(fallback.active().url(), primary);
+    primary_server.join().unwrap();
+    secondary_server.join().unwrap();
+    Ok(())
+}
+
+const TXHASH: &str = "0x6648b858a3d2b716d4c05c5d611844eb9827e2eea5bfc9db7a92187afd4d8c17";
+
+fn transaction(block_hash: Option<&str>) -> serde_json::Value {
+    json!({
+        "blockHash": block_hash,
+        "blockNumber": block_hash.map(|_| "0x10"),
+        "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+        "gas": "0x5208",
+        "gasPrice": "0x3b9aca00",
+        "hash": TXHASH,
+        "input": "0x",
+        "nonce": "0x7",
+        "to": "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+        "transactionIndex": block_hash.map(|_| "0x0"),
+        "value": "0x1",
+        "v": "0x1",
+        "r": "0x1",
+        "s": "0x1",
+    })
+}
+
+#[test]
+fn test_find_transaction() -> Result<(), Box<dyn Error>> {
+    let txhash: H256 = TXHASH.parse()?;
+    let unknown = serve_with(|method, _| match method {
+        "eth_getTransactionByHash" => json!(null),
+        _ => json!({"pending": {}, "queued": {}}),
+    });
+    let mined = serve_with(|_, _| {
+        transaction(Some(
+            "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8",
+        ))
+    });
+    let pooled = serve_with(|method, _| match method {
+        "eth_getTransactionByHash" => json!(null),
+        _ => json!({
+            "pending": {},
+            "queued": {"0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266": {"7": transaction(None)}},
+        }),
+    });
+    let down = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        format!("http://{}", listener.local_addr()?)
+    };
+
+    let providers = [
+        Provider::new(&down),
+        Provider::new(&unknown),
+        Provider::new(&mined),
+    ];
+    let found = find_transaction(&providers, txhash, false)?.unwrap();
+    assert_eq!((found.provider, found.from_txpool), (2, false));
+    assert_eq!(found.transaction.block_number, Some(16));
+
+    let providers = [Provider::new(&unknown), Provider::new(&pooled)];
+    assert!(find_transaction(&providers, txhash, false)?.is_none());
+    let found = find_transaction(&providers, txhash, true)?.unwrap();
+    assert_eq!((found.provider, found.from_txpool), (1, true));
+    assert_eq!(found.transaction.hash, txhash);
+    assert_eq!(found.transaction.block_hash, None);
+
+    assert!(find_transaction(&[Provider::new(&down)], txhash, true)
+        .unwrap_err()
+        .to_string()
+        .starts_with("All providers failed"));
+
+    let fallback = FallbackProvider::new(&[&unknown, &mined]);
+    assert_eq!(fallback.find_transaction(txhash)?.unwrap().provider, 1);
+    Ok(())
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::{rpc, serve_requests};
+use ethrs::flashbots::{Bundle, CallBundle, Flashbots, FlashbotsSigner};
+use ethrs::signature::Signature;
+use ethrs::types::{Address, BlockId, Bytes, H256, U256};
+use ethrs::utils::{hash_message, keccak256};
+use ethrs::wallet::Wallet;
+use serde_json::json;
+
+use std::error::Error;
+
+const PRIVATE_KEY: [u8; 32] = [0x42; 32];
+const SIGNED_TX: &str = "0x02f86b0180843b9aca00850ba43b740082520894";
+
+///Returns the `X-Flashbots-Signature` header of a request head.
+fn signature_header(head: &str) -> &str {
+    head.lines()
+        .find_map(|line| line.strip_prefix("x-flashbots-signature: "))
+        .unwrap()
+}
+
+#[test]
+fn test_signature() -> Result<(), Box<dyn Error>> {
+    let wallet = Wallet::from_private_key(&PRIVATE_KEY)?;
+    let body = br#"{"jsonrpc":"2.0","id":1,"method":"eth_sendBundle","params":[]}"#;
+    let header = FlashbotsSigner::new(wallet.clone()).signature(body);
+    let (address, signature) = header.split_once(':').unwrap();
+    assert_eq!(address.parse::<Address>()?, wallet.address());
+    let digest = format!("0x{}", hex::encode(keccak256(body)));
+    let signature: Signature = signature.parse()?;
+    assert!(signature.verify(hash_message(digest.as_bytes()), wallet.address()));
+    Ok(())
+}
+
+#[test]
+fn test_bundles() -> Result<(), Box<dyn Error>> {
+    let bundle_hash = format!("0x{}", "ab".repeat(32));
+    let tx_hash = format!("0x{}", "cd".repeat(32));
+    let (url, requests) = serve_requests(vec![
+        rpc(json!({ "bundleHash": bundle_hash })),
+        rpc(json!({
+            "bundleGasPrice": "476190476193",
+            "bundleHash": bundle_hash,
+            "coinbaseDiff": "20000000000126000",
+            "ethSentToCoinbase": "20000000000000000",
+            "gasFees": "126000",
+            "results": [{
+                "coinbaseDiff": "20000000000126000",
+                "ethSentToCoinbase": "20000000000000000",
+                "fromAddress": "0x02A727155aeF8609c9f7F2179b2a1f560B39F5A0",
+                "gasFees": "126000",
+                "gasPrice": "476190476193",
+                "gasUsed": 21000,
+                "toAddress": "0x73625f59CAdc5009Cb458B751b3E7b6b48C06f2C",
+                "txHash": tx_hash,
+                "value": "0x"
+            }],
+            "stateBlockNumber": 5221585,
+            "totalGasUsed": 21000
+        })),
+        rpc(json!(tx_hash)),
+        rpc(json!(true)),
+    ]);
+    let wallet = Wallet::from_private_key(&PRIVATE_KEY)?;
+    let flashbots = Flashbots::new(&url, wallet.clone())?;
+    let signed_tx: Bytes = SIGNED_TX.parse()?;
+    let tx_hash: H256 = tx_hash.parse()?;
+
+    let bundle = Bundle {
+        txs: vec![signed_tx.clone()],
+        block_number: 5221586,
+        reverting_tx_hashes: vec![tx_hash],
+        ..Default::default()
+    };
+    assert_eq!(flashbots.send_bundle(&bundle)?, bundle_hash.parse()?);
+    let simulation = flashbots.call_bundle(&CallBundle {
+        txs: vec![signed_tx.clone()],
+        block_number: 5221586,
+        state_block_number: BlockId::Latest,
+        timestamp: None,
+    })?;
+    assert_eq!(simulation.coinbase_diff, U256::from(20000000000126000u64));
+    assert_eq!(simulation.state_block_number, 5221585);
+    assert_eq!(simulation.results[0].tx_hash, tx_hash);
+    assert_eq!(simulation.results[0].gas_used, 21000);
+    assert_eq!(simulation.results[0].value, Some(Bytes(vec![])));
+    assert_eq!(simulation.results[0].error, None);
+    assert_eq!(
+        flashbots.send_private_transaction(&signed_tx, Some(5221600))?,
+        tx_hash
+    );
+    assert!(flashbots.cancel_private_transaction(tx_hash)?);
+
+    let requests = requests.join().unwrap();
+    let params: Vec<serde_json::Value> = requests
+        .iter()
+        .map(|(_, body)| serde_json::from_str::<serde_json::Value>(body).unwrap()["params"].clone())
+        .collect();
+    assert_eq!(
+        params[0],
+        json!([{
+            "txs": [SIGNED_TX],
+            "blockNumber": "0x4facd2",
+            "revertingTxHashes": [tx_hash]
+        }])
+    );
+    assert_eq!(
+        params[1],
+        json!([{ "txs": [SIGNED_TX], "blockNumber": "0x4facd2", "stateBlockNumber": "latest" }])
+    );
+    assert_eq!(
+        params[2],
+        json!([{ "tx": SIGNED_TX, "maxBlockNumber": "0x4face0" }])
+    );
+    assert_eq!(params[3], json!([{ "txHash": tx_hash }]));
+    // every request is signed
+    for (head, body) in &requests {
+        assert_eq!(
+            signature_header(head),
+            FlashbotsSigner::new(wallet.clone()).signature(body.as_bytes())
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use ethrs::hd::{derive_key, generate_mnemonic, mnemonic_to_seed, DerivationPath, HARDENED};
+use ethrs::wallet::Wallet;
+
+use std::error::Error;
+
+const MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+#[test]
+fn test_derive_key() -> Result<(), Box<dyn Error>> {
+    // test vector 1 of BIP-32
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f")?;
+    for (path, key) in [
+        (
+            "m",
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+        ),
+        (
+            "m/0'",
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+        ),
+        (
+            "m/0'/1",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+        ),
+        (
+            "m/0'/1/2'",
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+        ),
+    ] {
+        assert_eq!(hex::encode(derive_key(&seed, &path.parse()?)?), key);
+    }
+    assert!(derive_key(&[0u8; 8], &DerivationPath::ethereum(0)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_from_mnemonic() -> Result<(), Box<dyn Error>> {
+    assert_eq!(
+        Wallet::from_mnemonic(MNEMONIC, 0)?.address(),
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?
+    );
+    assert_eq!(
+        Wallet::from_mnemonic(MNEMONIC, 1)?.address(),
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".parse()?
+    );
+    assert_ne!(
+        Wallet::from_mnemonic_path(MNEMONIC, "passphrase", &DerivationPath::ethereum(0))?.address(),
+        Wallet::from_mnemonic(MNEMONIC, 0)?.address()
+    );
+    // the checksum of the last word does not match
+    assert!(Wallet::from_mnemonic(&MNEMONIC.replace("junk", "test"), 0).is_err());
+    assert!(Wallet::from_mnemonic("test test test", 0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_generate_mnemonic() -> Result<(), Box<dyn Error>> {
+    for words in [12, 15, 18, 21, 24] {
+        let mnemonic = generate_mnemonic(words)?;
+        assert_eq!(mnemonic.split(' ').count(), words);
+        assert!(mnemonic_to_seed(&mnemonic, "").is_ok());
+    }
+    assert_ne!(generate_mnemonic(12)?, generate_mnemonic(12)?);
+    assert!(generate_mnemonic(13).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_derivation_path() -> Result<(), Box<dyn Error>> {
+    let path: DerivationPath = "m/44'/60'/0'/0/7".parse()?;
+    assert_eq!(path, DerivationPath::ethereum(7));
+    assert_eq!(path.to_string(), "m/44'/60'/0'/0/7");
+    assert_eq!("m".parse::<DerivationPath>()?, DerivationPath(vec![]));
+    assert_eq!(
+        "m/1H/2h".parse::<DerivationPath>()?,
+        DerivationPath(vec![1 | HARDENED, 2 | HARDENED])
+    );
+    for invalid in [
+        "",
+        "44'/60'",
+        "m/",
+        "m//0",
+        "m/+1",
+        "m/1''",
+        "m/-1",
+        "m/2147483648",
+        "n/0",
+    ] {
+        assert!(invalid.parse::<DerivationPath>().is_err(), "{invalid}");
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::serve_with;
+use ethrs::indexer::{Coordinator, Phase};
+use ethrs::provider::{Filter, Provider};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn block(value: &serde_json::Value) -> u64 {
+    u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+}
+
+#[test]
+fn test_coordinator_handoff() {
+    let heads = AtomicUsize::new(0);
+    let url = serve_with(move |method, params| match method {
+        // the chain advances by four blocks after the boundary is fixed
+        "eth_blockNumber" => match heads.fetch_add(1, Ordering::SeqCst) {
+            0 => json!("0x12"),
+            _ => json!("0x16"),
+        },
+        "eth_getLogs" => {
+            assert_eq!(
+                params[0]["address"][0],
+                "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+            );
+            (block(&params[0]["fromBlock"])..=block(&params[0]["toBlock"]))
+                .map(|number| {
+                    json!({
+                        "removed": false,
+                        "logIndex": "0x0",
+                        "transactionIndex": "0x0",
+                        "transactionHash": "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8",
+                        "blockHash": "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6",
+                        "blockNumber": format!("0x{number:x}"),
+                        "address": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+                        "data": "0x",
+                        "topics": []
+                    })
+                })
+                .collect()
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let filter = Filter {
+        address: vec!["0x5fbdb2315678afecb367f032d93f642f64180aa3"
+            .parse()
+            .unwrap()],
+        ..Default::default()
+    };
+    let stop = AtomicBool::new(false);
+    let mut batches = vec![];
+    Coordinator::new(Provider::new(&url), filter, 5)
+        .with_batch_size(4)
+        .with_confirmations(2)
+        .with_poll_interval(Duration::from_millis(10))
+        .run(&stop, |batch| {
+            if batch.to_block == 20 {
+                stop.store(true, Ordering::SeqCst);
+            }
+            batches.push(batch);
+            Ok(())
+        })
+        .unwrap();
+
+    let ranges: Vec<_> = batches
+        .iter()
+        .map(|batch| (batch.from_block, batch.to_block, batch.phase))
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![
+            (5, 8, Phase::Backfill),
+            (9, 12, Phase::Backfill),
+            (13, 16, Phase::Backfill),
+            (17, 20, Phase::Live),
+        ]
+    );
+    let blocks: Vec<u64> = batches
+        .iter()
+        .flat_map(|batch| batch.logs.iter().map(|log| log.block_number))
+        .collect();
+    assert_eq!(blocks, (5..=20).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_coordinator_handler_error() {
+    let url = serve_with(|method, _| match method {
+        "eth_blockNumber" => json!("0x10"),
+        _ => json!([]),
+    });
+    let stop = AtomicBool::new(false);
+    let mut calls = 0;
+    let result = Coordinator::new(Provider::new(&url), Filter::default(), 0)
+        .with_poll_interval(Duration::from_millis(10))
+        .run(&stop, |_| {
+            calls += 1;
+            Err("handler failed".into())
+        });
+    assert_eq!(result.unwrap_err().to_string(), "handler failed");
+    assert_eq!(calls, 1);
+    // the caller's flag is left alone
+    assert!(!stop.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_coordinator_live_buffer() {
+    let heads = AtomicUsize::new(0);
+    let live_requests = Arc::new(AtomicUsize::new(0));
+    let requests = live_requests.clone();
+    // the chain advances by a block on every poll
+    let url = serve_with(move |method, params| match method {
+        "eth_blockNumber" => json!(format!("0x{:x}", heads.fetch_add(1, Ordering::SeqCst))),
+        _ => {
+            if block(&params[0]["fromBlock"]) > 0 {
+                requests.fetch_add(1, Ordering::SeqCst);
+            }
+            json!([])
+        }
+    });
+    let stop = AtomicBool::new(false);
+    let result = Coordinator::new(Provider::new(&url), Filter::default(), 0)
+        .with_batch_size(1)
+        .with_poll_interval(Duration::from_millis(1))
+        .run(&stop, |batch| match batch.phase {
+            Phase::Backfill => {
+                std::thread::sleep(Duration::from_millis(300));
+                Ok(())
+            }
+            Phase::Live => Err("handler failed".into()),
+        });
+    assert_eq!(result.unwrap_err().to_string(), "handler failed");
+    // live tailing waits on a full buffer instead of running ahead of the slow backfill
+    assert!(live_requests.load(Ordering::SeqCst) < 32);
+}
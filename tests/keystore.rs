@@ -0,0 +1,135 @@
+use ethrs::keystore::{Kdf, Keystore};
+use ethrs::wallet::Wallet;
+use serde_json::json;
+
+use std::error::Error;
+
+const PASSWORD: &str = "testpassword";
+const PRIVATE_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+// the PBKDF2 test vector of Web3 Secret Storage
+fn pbkdf2() -> serde_json::Value {
+    json!({
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": "6087dab2f9fdbbfaddc31a909735c1e6"},
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd",
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2",
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3,
+    })
+}
+
+#[test]
+fn test_decrypt_pbkdf2_keystore() -> Result<(), Box<dyn Error>> {
+    let keystore: Keystore = pbkdf2().to_string().parse()?;
+    assert_eq!(keystore.kdf()?, Kdf::Pbkdf2 { c: 262144 });
+    assert_eq!(hex::encode(keystore.decrypt(PASSWORD)?), PRIVATE_KEY);
+    assert_eq!(
+        keystore.decrypt("wrongpassword").unwrap_err().to_string(),
+        "Invalid keystore password"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decrypt_capitalized_keystore() -> Result<(), Box<dyn Error>> {
+    // old Geth versions wrote the crypto field capitalized
+    let mut keystore = pbkdf2();
+    let crypto = keystore["crypto"].take();
+    keystore["Crypto"] = crypto;
+    keystore.as_object_mut().unwrap().remove("crypto");
+    let keystore: Keystore = keystore.to_string().parse()?;
+    assert_eq!(hex::encode(keystore.decrypt(PASSWORD)?), PRIVATE_KEY);
+    Ok(())
+}
+
+#[test]
+fn test_keystore_round_trip() -> Result<(), Box<dyn Error>> {
+    let wallet = Wallet::random();
+    for kdf in [
+        Kdf::Scrypt {
+            log_n: 10,
+            r: 8,
+            p: 1,
+        },
+        Kdf::Pbkdf2 { c: 1000 },
+    ] {
+        let keystore = wallet.to_keystore(PASSWORD, kdf)?;
+        assert_eq!(keystore.version, 3);
+        assert_eq!(keystore.kdf()?, kdf);
+        assert_eq!(
+            keystore.address,
+            Some(
+                format!("{:x}", wallet.address())
+                    .trim_start_matches("0x")
+                    .to_owned()
+            )
+        );
+        // version 4 UUIDs
+        assert_eq!(keystore.id.len(), 36);
+        assert_eq!(&keystore.id[14..15], "4");
+        let parsed: Keystore = keystore.to_string().parse()?;
+        assert_eq!(parsed, keystore);
+        assert_eq!(parsed.decrypt(PASSWORD)?, wallet.private_key());
+        // the private key is only written encrypted
+        assert!(!keystore
+            .to_string()
+            .contains(&hex::encode(wallet.private_key())));
+        assert!(!format!("{keystore:?}").contains("ciphertext"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_wallet_from_keystore_file() -> Result<(), Box<dyn Error>> {
+    let wallet = Wallet::random();
+    let path = std::env::temp_dir().join(format!("ethrs-keystore-{:x}.json", wallet.address()));
+    wallet
+        .to_keystore(PASSWORD, Kdf::Pbkdf2 { c: 1000 })?
+        .save(&path)?;
+    let loaded = Wallet::from_keystore(&path, PASSWORD);
+    std::fs::remove_file(&path)?;
+    assert_eq!(loaded?.address(), wallet.address());
+    assert!(Wallet::from_keystore(&path, PASSWORD).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_invalid_keystores() -> Result<(), Box<dyn Error>> {
+    let mut keystore = pbkdf2();
+    keystore["address"] = json!("0000000000000000000000000000000000000001");
+    let error = keystore
+        .to_string()
+        .parse::<Keystore>()?
+        .decrypt(PASSWORD)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Keystore key does not match its address 0000000000000000000000000000000000000001"
+    );
+    let mut keystore = pbkdf2();
+    keystore["crypto"]["cipher"] = json!("aes-128-cbc");
+    let error = keystore.to_string().parse::<Keystore>()?.decrypt(PASSWORD);
+    assert_eq!(
+        error.unwrap_err().to_string(),
+        "Unsupported keystore cipher: aes-128-cbc"
+    );
+    let mut keystore = pbkdf2();
+    keystore["crypto"]["kdf"] = json!("argon2");
+    let error = keystore.to_string().parse::<Keystore>()?.kdf();
+    assert_eq!(
+        error.unwrap_err().to_string(),
+        "Unsupported keystore KDF: argon2"
+    );
+    assert!("{}".parse::<Keystore>().is_err());
+    Ok(())
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::{rpc, serve};
+use ethrs::metrics::{MethodMetrics, LATENCY_BUCKETS};
+use ethrs::provider::Provider;
+use serde_json::json;
+
+use std::net::TcpListener;
+use std::time::Duration;
+
+#[test]
+fn test_provider_metrics() {
+    let (url, _) = serve(vec![
+        rpc(json!("0x10")),
+        rpc(json!("0x10")),
+        rpc(json!("0x10")),
+        (
+            "200 OK",
+            json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -32601, "message": "Method not found"}})
+                .to_string(),
+        ),
+    ]);
+    let provider = Provider::new(&url);
+    let metrics = provider.metrics();
+    let clone = provider.clone();
+    for _ in 0..3 {
+        clone.block_number().unwrap();
+    }
+    assert!(provider.chain_id().is_err());
+
+    let block_number = metrics.method("eth_blockNumber").unwrap();
+    assert_eq!(block_number.requests, 3);
+    assert_eq!(block_number.errors, 0);
+    assert_eq!(block_number.buckets.iter().sum::<u64>(), 3);
+    assert!(block_number.p50().unwrap() <= block_number.p95().unwrap());
+    assert_eq!(metrics.snapshot().len(), 2);
+    assert_eq!(metrics.method("eth_chainId").unwrap().errors, 1);
+
+    let exposition = metrics.to_string();
+    assert!(exposition.contains("ethrs_rpc_requests_total{method=\"eth_blockNumber\"} 3\n"));
+    assert!(exposition.contains(
+        "ethrs_rpc_request_duration_seconds_bucket{method=\"eth_blockNumber\",le=\"+Inf\"} 3\n"
+    ));
+    assert!(
+        exposition.contains("ethrs_rpc_request_duration_seconds_count{method=\"eth_chainId\"} 1\n")
+    );
+
+    metrics.reset();
+    assert!(provider.metrics().snapshot().is_empty());
+}
+
+#[test]
+fn test_provider_metrics_transport_errors() {
+    let url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let provider = Provider::new(&url);
+    assert!(provider.block_number().is_err());
+    let block_number = provider.metrics().method("eth_blockNumber").unwrap();
+    assert_eq!((block_number.requests, block_number.errors), (1, 1));
+}
+
+#[test]
+fn test_method_metrics_quantiles() {
+    let mut metrics = MethodMetrics::default();
+    assert_eq!(metrics.p50(), None);
+    // 10 requests under 5ms and 10 between 100ms and 250ms
+    metrics.buckets[0] = 10;
+    metrics.buckets[5] = 10;
+    metrics.requests = 20;
+    assert_eq!(
+        metrics.quantile(0.25),
+        Some(Duration::from_secs_f64(0.0025))
+    );
+    assert_eq!(metrics.p50(), Some(Duration::from_secs_f64(0.005)));
+    assert_eq!(
+        metrics.p95(),
+        Some(Duration::from_secs_f64(0.1 + 0.15 * 0.9))
+    );
+
+    metrics.buckets[LATENCY_BUCKETS.len()] = 20;
+    metrics.requests = 40;
+    assert_eq!(metrics.p95(), Some(Duration::from_secs(10)));
+}
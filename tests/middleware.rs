@@ -0,0 +1,144 @@
+mod common;
+
+use common::{rpc, serve_requests, serve_with};
+use ethrs::middleware::{Middleware, RpcRequest, RpcResponse};
+use ethrs::provider::Provider;
+use serde_json::json;
+
+use std::error::Error;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone)]
+struct Recorder {
+    name: &'static str,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware for Recorder {
+    fn on_request(&self, request: &mut RpcRequest) -> Result<(), Box<dyn Error>> {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("{} request {}", self.name, request.method));
+        Ok(())
+    }
+
+    fn on_response(&self, _request: &RpcRequest, response: &mut RpcResponse) {
+        self.events.lock().unwrap().push(format!(
+            "{} response {}",
+            self.name, response.body["result"]
+        ));
+    }
+
+    fn on_error(&self, request: &RpcRequest, _error: &dyn Error) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("{} error {}", self.name, request.method));
+    }
+}
+
+#[derive(Debug)]
+struct Signer;
+
+impl Middleware for Signer {
+    fn on_request(&self, request: &mut RpcRequest) -> Result<(), Box<dyn Error>> {
+        request.id = json!(7);
+        let signature = format!("len={}", request.body().len());
+        request.headers.insert("x-signature", signature.parse()?);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Rewriter;
+
+impl Middleware for Rewriter {
+    fn on_request(&self, request: &mut RpcRequest) -> Result<(), Box<dyn Error>> {
+        match request.method.as_str() {
+            "eth_sendTransaction" => Err("Blocked eth_sendTransaction".into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn on_response(&self, _request: &RpcRequest, response: &mut RpcResponse) {
+        if response.result() == Some(&json!("0x1")) {
+            response.body["result"] = json!("0x2");
+        }
+    }
+}
+
+#[test]
+fn test_middleware_order() -> Result<(), Box<dyn Error>> {
+    let url = serve_with(|_, _| json!("0x10"));
+    let events = Arc::new(Mutex::new(vec![]));
+    let provider = Provider::new(&url)
+        .with_middleware(Recorder {
+            name: "outer",
+            events: events.clone(),
+        })
+        .with_middleware(Recorder {
+            name: "inner",
+            events: events.clone(),
+        });
+    assert_eq!(provider.block_number()?, 16);
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "outer request eth_blockNumber",
+            "inner request eth_blockNumber",
+            "inner response \"0x10\"",
+            "outer response \"0x10\"",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_middleware_headers() -> Result<(), Box<dyn Error>> {
+    let (url, handle) = serve_requests(vec![rpc(json!("0x1"))]);
+    let provider = Provider::new(&url).with_middleware(Signer);
+    assert_eq!(provider.chain_id()?, 1);
+    let (head, body) = handle.join().unwrap().remove(0);
+    assert!(head
+        .to_lowercase()
+        .contains(&format!("x-signature: len={}", body.len())));
+    let body: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(body["id"], 7);
+    assert_eq!(body["method"], "eth_chainId");
+    Ok(())
+}
+
+#[test]
+fn test_middleware_rewrites() -> Result<(), Box<dyn Error>> {
+    let url = serve_with(|_, _| json!("0x1"));
+    let provider = Provider::new(&url).with_middleware(Rewriter);
+    assert_eq!(provider.chain_id()?, 2);
+    let err = provider
+        .request::<String>("eth_sendTransaction", json!([{}]))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Blocked eth_sendTransaction");
+    Ok(())
+}
+
+#[test]
+fn test_middleware_errors() {
+    let url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let events = Arc::new(Mutex::new(vec![]));
+    let provider = Provider::new(&url).with_middleware(Recorder {
+        name: "recorder",
+        events: events.clone(),
+    });
+    assert!(provider.block_number().is_err());
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "recorder request eth_blockNumber",
+            "recorder error eth_blockNumber"
+        ]
+    );
+}
@@ -0,0 +1,74 @@
+use ethrs::mock::{MockProvider, MockResponse};
+use ethrs::provider::CallInput;
+use serde_json::json;
+
+use std::error::Error;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_mock_provider() -> Result<(), Box<dyn Error>> {
+    let mock = MockProvider::new();
+    mock.on("eth_blockNumber", MockResponse::Result(json!("0x10")))
+        .once("eth_blockNumber", MockResponse::Result(json!("0x1")))
+        .once(
+            "eth_call",
+            MockResponse::Error {
+                code: 3,
+                message: "execution reverted".to_owned(),
+            },
+        );
+    let provider = mock.provider();
+    assert_eq!(provider.block_number()?, 1);
+    assert_eq!(provider.block_number()?, 16);
+    assert_eq!(provider.block_number()?, 16);
+
+    let tx = CallInput {
+        from: None,
+        to: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?,
+        gas: None,
+        gas_price: None,
+        value: None,
+        data: Some("0x18160ddd".to_owned()),
+    };
+    assert_eq!(
+        provider.call(tx.clone(), None).unwrap_err().to_string(),
+        "execution reverted"
+    );
+    assert_eq!(
+        provider.call(tx, None).unwrap_err().to_string(),
+        "Method not found: eth_call"
+    );
+    let calls = mock.requests_for("eth_call");
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0][0]["data"], "0x18160ddd");
+    assert_eq!(calls[0][1], "latest");
+    assert_eq!(mock.requests().len(), 5);
+    mock.clear_requests();
+    assert!(mock.requests().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_mock_provider_failures() {
+    let mock = MockProvider::new();
+    mock.on(
+        "eth_chainId",
+        MockResponse::Failure("connection refused".to_owned()),
+    )
+    .with_latency(Duration::from_millis(100));
+    let provider = mock.provider();
+    let start = Instant::now();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let provider = provider.clone();
+            thread::spawn(move || provider.chain_id().unwrap_err().to_string())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "connection refused");
+    }
+    // concurrent requests are delayed concurrently
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(100) && elapsed < Duration::from_millis(300));
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::serve_with;
+use ethrs::abi::{decode, encode, Abi, ParamType, Token};
+use ethrs::multicall::{Multicall, MULTICALL3};
+use ethrs::provider::Provider;
+use ethrs::types::{Address, Bytes, U256};
+use ethrs::utils::function_selector;
+use serde_json::json;
+
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const TOKEN: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const BROKEN: &str = "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512";
+
+fn erc20() -> Abi {
+    serde_json::from_value(json!([{
+        "type": "function",
+        "name": "balanceOf",
+        "inputs": [{"name": "owner", "type": "address"}],
+        "outputs": [{"name": "", "type": "uint256"}],
+        "stateMutability": "view"
+    }]))
+    .unwrap()
+}
+
+///Serves a Multicall3 answering `balanceOf(owner)` of `TOKEN` with the last byte of the owner, and reverting calls to
+///`BROKEN`, counting the `eth_call` requests.
+fn node() -> (String, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let url = serve_with(move |method, params| {
+        assert_eq!(method, "eth_call");
+        counter.fetch_add(1, Ordering::SeqCst);
+        assert_eq!(params[0]["to"], json!(MULTICALL3));
+        let calldata: Bytes = params[0]["data"].as_str().unwrap().parse().unwrap();
+        assert_eq!(
+            calldata.0[..4],
+            function_selector("aggregate3((address,bool,bytes)[])")
+        );
+        let calls = decode(
+            &["(address,bool,bytes)[]".parse().unwrap()],
+            &calldata.0[4..],
+        )
+        .unwrap();
+        let Token::Array(calls) = &calls[0] else {
+            panic!("unexpected calls {calls:?}");
+        };
+        let results = calls
+            .iter()
+            .map(|call| {
+                let Token::Tuple(call) = call else {
+                    panic!("unexpected call {call:?}");
+                };
+                let (Token::Address(target), Token::Bytes(data)) = (&call[0], &call[2]) else {
+                    panic!("unexpected call {call:?}");
+                };
+                match format!("{target:x}") == BROKEN.trim_start_matches("0x") {
+                    true => {
+                        let mut revert = vec![0x08, 0xc3, 0x79, 0xa0];
+                        revert.extend(encode(&[Token::String("broken".to_owned())]));
+                        Token::Tuple(vec![Token::Bool(false), Token::Bytes(revert)])
+                    }
+                    false => Token::Tuple(vec![
+                        Token::Bool(true),
+                        Token::Bytes(encode(&[Token::Uint(U256::from(data[35]))])),
+                    ]),
+                }
+            })
+            .collect();
+        json!(Bytes(encode(&[Token::Array(results)])))
+    });
+    (url, requests)
+}
+
+fn owner_address(last_byte: u8) -> Address {
+    let mut address = [0u8; 20];
+    address[19] = last_byte;
+    Address(address)
+}
+
+#[test]
+fn test_multicall() -> Result<(), Box<dyn Error>> {
+    let (url, requests) = node();
+    let abi = erc20();
+    let balance_of = abi.function("balanceOf").unwrap();
+    let mut multicall = Multicall::new(Provider::new(&url));
+    let token: Address = TOKEN.parse()?;
+    for owner in 1..=200u64 {
+        let owner = owner_address(owner as u8);
+        multicall.add_call(token, balance_of, &[Token::Address(owner)], false)?;
+    }
+    multicall.add_call(BROKEN.parse()?, balance_of, &[Token::Address(token)], true)?;
+    multicall.add_raw_call(
+        token,
+        Bytes(balance_of.encode_input(&[Token::Address(owner_address(7))])?),
+        vec![ParamType::Uint(256)],
+        false,
+    );
+    assert_eq!(multicall.len(), 202);
+    let results = multicall.call()?;
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(results.len(), 202);
+    for (owner, result) in (1..=200u64).zip(&results) {
+        assert!(result.success);
+        assert_eq!(result.decode::<U256>()?, U256::from(owner));
+    }
+    assert!(!results[200].success);
+    assert_eq!(
+        results[200].decode::<U256>().unwrap_err().to_string(),
+        "Call reverted: broken"
+    );
+    assert_eq!(results[201].tokens()?, vec![Token::Uint(U256::from(7))]);
+
+    multicall.clear();
+    assert!(multicall.is_empty());
+    assert!(multicall.call()?.is_empty());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    Ok(())
+}
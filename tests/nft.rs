@@ -0,0 +1,288 @@
+mod common;
+
+use common::serve_with;
+use ethrs::abi::{encode, Token};
+use ethrs::indexer::{Batch, Phase};
+use ethrs::nft::{decode_transfers, Erc1155, Erc721, NftIndexer, Ownership};
+use ethrs::provider::{Log, Provider};
+use ethrs::types::{Address, BlockId, Bytes, H256, U256};
+use ethrs::utils::function_selector;
+use serde_json::json;
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+const COLLECTION: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const ALICE: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+const BOB: &str = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+const ZERO: &str = "0x0000000000000000000000000000000000000000";
+const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const TRANSFER_SINGLE: &str = "0xc3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62";
+const TRANSFER_BATCH: &str = "0x4a39dc06d4c0dbc64b70af90fd698a233a518aa5d07e595d983b8c0526c8f7fb";
+
+fn topic(value: &str) -> String {
+    format!("0x{:0>64}", value.trim_start_matches("0x"))
+}
+
+fn log(block_number: u64, topics: Vec<String>, data: Vec<Token>) -> serde_json::Value {
+    json!({
+        "removed": false,
+        "logIndex": "0x0",
+        "transactionIndex": "0x0",
+        "transactionHash": "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8",
+        "blockHash": "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6",
+        "blockNumber": format!("0x{block_number:x}"),
+        "address": COLLECTION,
+        "data": format!("0x{}", hex::encode(encode(&data))),
+        "topics": topics,
+    })
+}
+
+fn erc721(block_number: u64, from: &str, to: &str, id: u64) -> serde_json::Value {
+    log(
+        block_number,
+        vec![
+            TRANSFER.to_owned(),
+            topic(from),
+            topic(to),
+            topic(&format!("{id:x}")),
+        ],
+        vec![],
+    )
+}
+
+fn erc1155(
+    block_number: u64,
+    from: &str,
+    to: &str,
+    ids: &[u64],
+    amounts: &[u64],
+) -> serde_json::Value {
+    let uints = |values: &[u64]| {
+        values
+            .iter()
+            .map(|value| Token::Uint((*value).into()))
+            .collect()
+    };
+    let (topic0, data) = match ids.len() {
+        1 => (
+            TRANSFER_SINGLE,
+            vec![Token::Uint(ids[0].into()), Token::Uint(amounts[0].into())],
+        ),
+        _ => (
+            TRANSFER_BATCH,
+            vec![Token::Array(uints(ids)), Token::Array(uints(amounts))],
+        ),
+    };
+    log(
+        block_number,
+        vec![topic0.to_owned(), topic(ALICE), topic(from), topic(to)],
+        data,
+    )
+}
+
+#[test]
+fn test_decode_transfers() -> Result<(), Box<dyn Error>> {
+    let single: Log = serde_json::from_value(erc1155(1, ZERO, ALICE, &[3], &[10]))?;
+    let transfers = decode_transfers(&single)?;
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].from, Address::default());
+    assert_eq!(transfers[0].to, ALICE.parse()?);
+    assert_eq!(transfers[0].id, 3.into());
+    assert_eq!(transfers[0].amount, 10.into());
+
+    let batch: Log = serde_json::from_value(erc1155(1, ALICE, BOB, &[3, 4], &[1, 2]))?;
+    let transfers = decode_transfers(&batch)?;
+    assert_eq!(
+        transfers
+            .iter()
+            .map(|transfer| (transfer.id, transfer.amount))
+            .collect::<Vec<_>>(),
+        [(3.into(), 1.into()), (4.into(), 2.into())]
+    );
+
+    // an ERC-20 transfer has the same topic, with the amount in the data
+    let mut erc20: Log = serde_json::from_value(erc721(1, ALICE, BOB, 1))?;
+    erc20.topics.pop();
+    assert!(decode_transfers(&erc20)?.is_empty());
+
+    let mut removed: Log = serde_json::from_value(erc721(1, ALICE, BOB, 1))?;
+    removed.removed = true;
+    assert!(decode_transfers(&removed)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_ownership() -> Result<(), Box<dyn Error>> {
+    let (alice, bob): (Address, Address) = (ALICE.parse()?, BOB.parse()?);
+    let logs = [
+        erc721(5, ZERO, ALICE, 1),
+        erc721(5, ZERO, ALICE, 2),
+        erc721(6, ALICE, BOB, 1),
+        erc721(6, ALICE, ZERO, 2),
+        erc1155(6, ZERO, ALICE, &[3], &[10]),
+        erc1155(7, ALICE, BOB, &[3], &[4]),
+    ];
+    let mut ownership = Ownership::new(5);
+    let batch = Batch {
+        from_block: 5,
+        to_block: 7,
+        phase: Phase::Backfill,
+        logs: logs
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<_, _>>()?,
+    };
+    assert_eq!(ownership.apply_batch(&batch)?.len(), 6);
+    assert_eq!(ownership.next_block(), 8);
+    assert_eq!(ownership.owner_of(1.into()), Some(bob));
+    assert_eq!(ownership.owner_of(2.into()), None);
+    assert_eq!(ownership.owner_of(3.into()), None);
+    assert_eq!(ownership.balance_of(alice, 3.into()), 6.into());
+    assert_eq!(
+        ownership.holders(3.into()),
+        [(bob, 4.into()), (alice, 6.into())]
+    );
+    assert_eq!(
+        ownership.tokens_of(bob),
+        [(1.into(), 1.into()), (3.into(), 4.into())]
+    );
+    assert_eq!(
+        ownership.ids().collect::<Vec<_>>(),
+        [&U256::from(1), &3.into()]
+    );
+
+    // the same batch cannot be applied twice
+    assert_eq!(
+        ownership.apply_batch(&batch).unwrap_err().to_string(),
+        "Batch 5..=7 does not start at checkpoint block 8"
+    );
+
+    let path = env::temp_dir().join(format!("ethrs-ownership-{}.json", std::process::id()));
+    ownership.save(&path)?;
+    let loaded = Ownership::load(&path)?;
+    fs::remove_file(&path)?;
+    assert_eq!(loaded, Some(ownership));
+    assert_eq!(Ownership::load(&path)?, None);
+    Ok(())
+}
+
+#[test]
+fn test_nft_indexer() -> Result<(), Box<dyn Error>> {
+    let url = serve_with(|method, params| match method {
+        "eth_blockNumber" => json!("0x1e"),
+        "eth_getLogs" => {
+            assert_eq!(params[0]["topics"][0][1], TRANSFER_SINGLE);
+            match params[0]["fromBlock"].as_str().unwrap() {
+                "0xa" => json!([erc721(12, ZERO, ALICE, 1)]),
+                _ => json!([erc721(18, ALICE, BOB, 1)]),
+            }
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let stop = AtomicBool::new(false);
+    let mut updates = vec![];
+    let mut indexer = NftIndexer::new(Provider::new(&url), COLLECTION.parse()?, Ownership::new(10))
+        .with_batch_size(6)
+        .with_confirmations(10)
+        .with_poll_interval(Duration::from_millis(10));
+    indexer.run(&stop, |update| {
+        updates.push((update.from_block, update.to_block, update.transfers.len()));
+        if update.to_block == 20 {
+            assert_eq!(update.ownership.owner_of(1.into()), Some(BOB.parse()?));
+            stop.store(true, Ordering::SeqCst);
+        }
+        Ok(())
+    })?;
+    assert_eq!(updates, [(10, 15, 1), (16, 20, 1)]);
+    assert_eq!(indexer.ownership().next_block(), 21);
+    Ok(())
+}
+
+///Serves a collection answering its reads, recording the blocks they are made at.
+fn collection() -> String {
+    serve_with(|method, params| {
+        assert_eq!(method, "eth_call");
+        let data: Bytes = params[0]["data"].as_str().unwrap().parse().unwrap();
+        let (selector, args) = data.0.split_at(4);
+        let call = |signature: &str| selector == function_selector(signature);
+        let output = if call("ownerOf(uint256)") {
+            assert_eq!(args[31], 7);
+            vec![Token::Address(ALICE.parse().unwrap())]
+        } else if call("tokenURI(uint256)") {
+            vec![Token::String("ipfs://token/7".to_owned())]
+        } else if call("balanceOf(address)") {
+            assert_eq!(params[1], "0x10");
+            vec![Token::Uint(U256::from(3))]
+        } else if call("isApprovedForAll(address,address)") {
+            vec![Token::Bool(true)]
+        } else if call("balanceOf(address,uint256)") {
+            vec![Token::Uint(U256::from(args[63]) * 10)]
+        } else if call("balanceOfBatch(address[],uint256[])") {
+            // the ids are the last words of the calldata
+            let ids = args[args.len() - 64..]
+                .chunks(32)
+                .map(|id| Token::Uint(U256::from(id[31]) * 10))
+                .collect();
+            vec![Token::Array(ids)]
+        } else if call("uri(uint256)") {
+            vec![Token::String("https://token/{id}.json".to_owned())]
+        } else {
+            return json!("0x");
+        };
+        json!(Bytes(encode(&output)))
+    })
+}
+
+#[test]
+fn test_erc721_reads() -> Result<(), Box<dyn Error>> {
+    let erc721 = Erc721::new(COLLECTION.parse()?, Provider::new(&collection()));
+    assert_eq!(erc721.owner_of(U256::from(7))?, ALICE.parse()?);
+    assert_eq!(erc721.token_uri(U256::from(7))?, "ipfs://token/7");
+    assert!(erc721.is_approved_for_all(ALICE.parse()?, BOB.parse()?)?);
+    let erc721 = erc721.with_block(BlockId::Number(16));
+    assert_eq!(erc721.balance_of(ALICE.parse()?)?, U256::from(3));
+    Ok(())
+}
+
+#[test]
+fn test_erc1155_reads() -> Result<(), Box<dyn Error>> {
+    let erc1155 = Erc1155::new(COLLECTION.parse()?, Provider::new(&collection()));
+    let alice: Address = ALICE.parse()?;
+    assert_eq!(erc1155.balance_of(alice, U256::from(2))?, U256::from(20));
+    assert_eq!(
+        erc1155.balance_of_batch(&[alice, alice], &[U256::from(1), U256::from(4)])?,
+        vec![U256::from(10), U256::from(40)]
+    );
+    assert!(erc1155.balance_of_batch(&[alice], &[]).is_err());
+    assert_eq!(erc1155.uri(U256::from(1))?, "https://token/{id}.json");
+    Ok(())
+}
+
+#[test]
+fn test_transfer_filters() -> Result<(), Box<dyn Error>> {
+    let provider = Provider::new("http://localhost:8545");
+    let alice: H256 = topic(ALICE).parse()?;
+    let filter = Erc721::new(COLLECTION.parse()?, provider.clone())
+        .transfer_filter(None, Some(ALICE.parse()?));
+    assert_eq!(filter.address, vec![COLLECTION.parse()?]);
+    assert_eq!(
+        filter.topics,
+        vec![Some(vec![TRANSFER.parse()?]), None, Some(vec![alice])]
+    );
+    let filter =
+        Erc1155::new(COLLECTION.parse()?, provider).transfer_filter(Some(ALICE.parse()?), None);
+    assert_eq!(
+        filter.topics,
+        vec![
+            Some(vec![TRANSFER_SINGLE.parse()?, TRANSFER_BATCH.parse()?]),
+            None,
+            Some(vec![alice]),
+            None
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{rpc, serve, serve_with};
+use ethrs::nonce::NonceManager;
+use ethrs::provider::{Provider, TransactionInput};
+use serde_json::json;
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+const FROM: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+const TXHASH: &str = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8";
+
+fn transfer() -> TransactionInput {
+    TransactionInput {
+        from: FROM.parse().unwrap(),
+        to: Some(FROM.parse().unwrap()),
+        gas: None,
+        gas_price: None,
+        value: None,
+        data: None,
+        nonce: None,
+        access_list: None,
+    }
+}
+
+fn error(message: &str) -> (&'static str, String) {
+    (
+        "200 OK",
+        json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -32000, "message": message}})
+            .to_string(),
+    )
+}
+
+#[test]
+fn test_next_nonce() -> Result<(), Box<dyn Error>> {
+    let (url, handle) = serve(vec![rpc(json!("0x5")), rpc(json!("0x9"))]);
+    let nonces = NonceManager::new(Provider::new(&url), FROM.parse()?);
+    assert_eq!(nonces.next_nonce()?, 5);
+    assert_eq!(nonces.clone().next_nonce()?, 6);
+    assert_eq!(nonces.resync()?, 9);
+    assert_eq!(nonces.next_nonce()?, 9);
+    let requests = handle.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    let request: serde_json::Value = serde_json::from_str(&requests[0])?;
+    assert_eq!(request["method"], "eth_getTransactionCount");
+    assert_eq!(request["params"], json!([FROM, "pending"]));
+    Ok(())
+}
+
+#[test]
+fn test_resync_on_nonce_too_low() -> Result<(), Box<dyn Error>> {
+    let (url, handle) = serve(vec![
+        rpc(json!("0x1")),
+        error("nonce too low: next nonce 3, tx nonce 1"),
+        rpc(json!("0x3")),
+        rpc(json!(TXHASH)),
+        error("insufficient funds for gas * price + value"),
+        rpc(json!(TXHASH)),
+    ]);
+    let nonces = NonceManager::new(Provider::new(&url), FROM.parse()?);
+    assert_eq!(nonces.send_transaction(transfer())?, TXHASH.parse()?);
+    // other errors do not use up the nonce
+    assert!(nonces.send_transaction(transfer()).is_err());
+    assert_eq!(nonces.send_transaction(transfer())?, TXHASH.parse()?);
+    let sent: Vec<serde_json::Value> = handle
+        .join()
+        .unwrap()
+        .iter()
+        .map(|body| serde_json::from_str::<serde_json::Value>(body).unwrap())
+        .filter(|request| request["method"] == "eth_sendTransaction")
+        .map(|request| request["params"][0]["nonce"].clone())
+        .collect();
+    assert_eq!(
+        sent,
+        vec![json!("0x1"), json!("0x3"), json!("0x4"), json!("0x4")]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_concurrent_senders() -> Result<(), Box<dyn Error>> {
+    let sent = Arc::new(Mutex::new(vec![]));
+    let recorded = sent.clone();
+    let url = serve_with(move |method, params| match method {
+        "eth_getTransactionCount" => json!("0x0"),
+        _ => {
+            recorded
+                .lock()
+                .unwrap()
+                .push(params[0]["nonce"].as_str().unwrap().to_owned());
+            json!(TXHASH)
+        }
+    });
+    let nonces = NonceManager::new(Provider::new(&url), FROM.parse()?);
+    let senders: Vec<_> = (0..8)
+        .map(|_| {
+            let nonces = nonces.clone();
+            thread::spawn(move || nonces.send_transaction(transfer()).is_ok())
+        })
+        .collect();
+    for sender in senders {
+        assert!(sender.join().unwrap());
+    }
+    let sent = sent.lock().unwrap();
+    // nonces are sent in order, without gaps or duplicates
+    let expected: Vec<String> = (0..8).map(|nonce| format!("0x{nonce:x}")).collect();
+    assert_eq!(*sent, expected);
+    assert_eq!(sent.iter().collect::<BTreeSet<_>>().len(), 8);
+    assert_eq!(nonces.next_nonce()?, 8);
+    Ok(())
+}
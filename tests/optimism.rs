@@ -0,0 +1,98 @@
+mod common;
+
+use ethrs::optimism::{output_at_block, OutputRootProof, Withdrawal};
+use ethrs::provider::Provider;
+use ethrs::types::{H256, U256};
+
+use std::error::Error;
+
+const STATE_ROOT: &str = "0x0c8f0d6c3bd23a7ef0b5e2e8a06b1f5bfc3ae9d3a8d1b0e6c2f4a9b7e3d5c1a0";
+const MESSAGE_PASSER_STORAGE_ROOT: &str =
+    "0x8ed4baae3a927be3dea54996b4d5899f8c01e7594bf50b17dc1e741388ce3d12";
+const LATEST_BLOCKHASH: &str = "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6";
+const OUTPUT_ROOT: &str = "0x585a81bc7d1bc84e6b3e5ec50c24267be0cfce83bd792f30fa91c4ad2667988e";
+
+// a withdrawal of 0.1 ether sent through the L2CrossDomainMessenger, as emitted by `MessagePassed`
+fn withdrawal() -> Result<Withdrawal, Box<dyn Error>> {
+    Ok(Withdrawal {
+        nonce: U256::from("0x0001000000000000000000000000000000000000000000000000000000005d21"),
+        sender: "0x4200000000000000000000000000000000000007".parse()?,
+        target: "0x25ace71c97B33Cc4729CF772ae268934F7ab5fA1".parse()?,
+        value: U256::exp10(17),
+        gas_limit: U256::from(287306),
+        data: hex::decode(
+            "d764ad0b0001000000000000000000000000000000000000000000000000000000002f3e0000000000000000000000009c2d4a1b7e0f\
+             3a5d6c8b1e2f4a7d9c0b3e5f6a8d0000000000000000000000009c2d4a1b7e0f3a5d6c8b1e2f4a7d9c0b3e5f6a8d00000000000000\
+             0000000000000000000000000000000000016345785d8a00000000000000000000000000000000000000000000000000000000000000\
+             00000000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000\
+             00000000000000000000000000",
+        )?,
+    })
+}
+
+fn output_root_proof() -> Result<OutputRootProof, Box<dyn Error>> {
+    Ok(OutputRootProof {
+        version: H256::ZERO,
+        state_root: STATE_ROOT.parse()?,
+        message_passer_storage_root: MESSAGE_PASSER_STORAGE_ROOT.parse()?,
+        latest_blockhash: LATEST_BLOCKHASH.parse()?,
+    })
+}
+
+fn output(output_root: &str) -> serde_json::Value {
+    serde_json::json!({
+        "version": H256::ZERO.to_string(),
+        "outputRoot": output_root,
+        "stateRoot": STATE_ROOT,
+        "withdrawalStorageRoot": MESSAGE_PASSER_STORAGE_ROOT,
+        "blockRef": {
+            "hash": LATEST_BLOCKHASH,
+            "number": 12000000,
+            "parentHash": "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8",
+            "timestamp": 1700000000,
+        },
+    })
+}
+
+#[test]
+fn test_withdrawal_hash() -> Result<(), Box<dyn Error>> {
+    let withdrawal = withdrawal()?;
+    assert_eq!(
+        withdrawal.hash(),
+        "0xa196608ac60d8dbe65c9774a27246d80c54ee84d85953dba3bb359285db64573".parse()?
+    );
+    // sentMessages is the first slot of the L2ToL1MessagePasser
+    assert_eq!(
+        withdrawal.storage_slot(),
+        "0xe3f696c526034f4a19815f5e812a38ab7268f3bc0d465a7050a8d25b676993db".parse()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_output_root() -> Result<(), Box<dyn Error>> {
+    assert_eq!(output_root_proof()?.output_root(), OUTPUT_ROOT.parse()?);
+    Ok(())
+}
+
+#[test]
+fn test_output_at_block() -> Result<(), Box<dyn Error>> {
+    let (url, server) = common::serve(vec![
+        common::rpc(output(OUTPUT_ROOT)),
+        common::rpc(output(LATEST_BLOCKHASH)),
+    ]);
+    let provider = Provider::new(&url);
+    let (output_root, proof) = output_at_block(&provider, 12000000)?;
+    assert_eq!(output_root, OUTPUT_ROOT.parse()?);
+    assert_eq!(proof, output_root_proof()?);
+    assert_eq!(
+        output_at_block(&provider, 12000000)
+            .unwrap_err()
+            .to_string(),
+        "Output root does not match its preimage"
+    );
+    let request: serde_json::Value = serde_json::from_str(&server.join().unwrap()[0])?;
+    assert_eq!(request["method"], "optimism_outputAtBlock");
+    assert_eq!(request["params"], serde_json::json!(["0xb71b00"]));
+    Ok(())
+}
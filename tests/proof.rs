@@ -0,0 +1,351 @@
+use ethrs::proof::{
+    ordered_trie_root, receipts_root, transactions_root, trie_root, verify_account_proof,
+    verify_proof, EMPTY_CODE_HASH, EMPTY_ROOT,
+};
+use ethrs::provider::{AccountProof, BlockWithTx, StorageProof, Transaction, TransactionReceipt};
+use ethrs::types::{Address, Bytes, H256, U256};
+use tiny_keccak::{Hasher, Keccak};
+
+use std::error::Error;
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut hash = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+fn rlp_string(data: &[u8]) -> Vec<u8> {
+    match data.len() {
+        1 if data[0] < 0x80 => data.to_vec(),
+        0..=55 => [vec![0x80 + data.len() as u8], data.to_vec()].concat(),
+        56..=255 => [vec![0xb8, data.len() as u8], data.to_vec()].concat(),
+        _ => [
+            vec![0xb9, (data.len() >> 8) as u8, data.len() as u8],
+            data.to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    match payload.len() {
+        0..=55 => [vec![0xc0 + payload.len() as u8], payload].concat(),
+        56..=255 => [vec![0xf8, payload.len() as u8], payload].concat(),
+        _ => [
+            vec![0xf9, (payload.len() >> 8) as u8, payload.len() as u8],
+            payload,
+        ]
+        .concat(),
+    }
+}
+
+// a leaf holding the last 63 nibbles of `path`, i.e. a child of the root branch node
+fn leaf(path: &[u8; 32], value: Vec<u8>) -> Vec<u8> {
+    let mut encoded_path = vec![0x30 | (path[0] & 0x0f)];
+    encoded_path.extend_from_slice(&path[1..]);
+    rlp_list(&[rlp_string(&encoded_path), rlp_string(&value)])
+}
+
+fn trimmed(value: u64) -> Vec<u8> {
+    value
+        .to_be_bytes()
+        .into_iter()
+        .skip_while(|byte| *byte == 0)
+        .collect()
+}
+
+fn address(seed: u8) -> Address {
+    let mut address = [0u8; 20];
+    address[19] = seed;
+    Address(address)
+}
+
+struct Fixture {
+    state_root: H256,
+    proof: AccountProof,
+    absent: Address,
+    absent_proof: Vec<Bytes>,
+}
+
+fn fixture() -> Fixture {
+    // storage trie with a single slot, `0 => 42`
+    let slot_leaf = rlp_list(&[
+        rlp_string(&[vec![0x20], keccak(&[0u8; 32]).to_vec()].concat()),
+        rlp_string(&rlp_string(&[42])),
+    ]);
+    let storage_root = H256(keccak(&slot_leaf));
+
+    // state trie with two accounts diverging at the first nibble
+    let first = address(1);
+    let second = (2..)
+        .map(address)
+        .find(|other| keccak(other.as_bytes())[0] >> 4 != keccak(first.as_bytes())[0] >> 4)
+        .unwrap();
+    let absent = (3..)
+        .map(address)
+        .find(|other| {
+            let nibble = keccak(other.as_bytes())[0] >> 4;
+            nibble != keccak(first.as_bytes())[0] >> 4
+                && nibble != keccak(second.as_bytes())[0] >> 4
+        })
+        .unwrap();
+    let first_leaf = leaf(
+        &keccak(first.as_bytes()),
+        rlp_list(&[
+            rlp_string(&trimmed(1)),
+            rlp_string(&trimmed(1000)),
+            rlp_string(storage_root.as_bytes()),
+            rlp_string(&keccak(b"code")),
+        ]),
+    );
+    let second_leaf = leaf(
+        &keccak(second.as_bytes()),
+        rlp_list(&[
+            rlp_string(&[]),
+            rlp_string(&trimmed(5)),
+            rlp_string(EMPTY_ROOT.as_bytes()),
+            rlp_string(EMPTY_CODE_HASH.as_bytes()),
+        ]),
+    );
+    let mut children = vec![rlp_string(&[]); 17];
+    children[(keccak(first.as_bytes())[0] >> 4) as usize] = rlp_string(&keccak(&first_leaf));
+    children[(keccak(second.as_bytes())[0] >> 4) as usize] = rlp_string(&keccak(&second_leaf));
+    let branch = rlp_list(&children);
+
+    Fixture {
+        state_root: H256(keccak(&branch)),
+        proof: AccountProof {
+            address: first,
+            balance: U256::from(1000),
+            nonce: U256::one(),
+            code_hash: H256(keccak(b"code")),
+            storage_hash: storage_root,
+            account_proof: vec![Bytes(branch.clone()), Bytes(first_leaf)],
+            storage_proof: vec![StorageProof {
+                key: U256::zero(),
+                value: U256::from(42),
+                proof: vec![Bytes(slot_leaf)],
+            }],
+        },
+        absent,
+        absent_proof: vec![Bytes(branch)],
+    }
+}
+
+#[test]
+fn test_verify_account_proof() -> Result<(), Box<dyn Error>> {
+    let fixture = fixture();
+    verify_account_proof(fixture.state_root, &fixture.proof)?;
+
+    let mut tampered = fixture.proof.clone();
+    tampered.balance = U256::from(1001);
+    assert!(verify_account_proof(fixture.state_root, &tampered).is_err());
+
+    let mut tampered = fixture.proof.clone();
+    tampered.storage_proof[0].value = U256::from(43);
+    assert!(verify_account_proof(fixture.state_root, &tampered).is_err());
+
+    let mut tampered = fixture.proof;
+    tampered.account_proof[1].0[5] ^= 1;
+    assert!(verify_account_proof(fixture.state_root, &tampered).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_verify_exclusion_proof() -> Result<(), Box<dyn Error>> {
+    let fixture = fixture();
+    assert_eq!(
+        verify_proof(
+            fixture.state_root,
+            fixture.absent.as_bytes(),
+            &fixture.absent_proof
+        )?,
+        None
+    );
+    let empty = AccountProof {
+        address: fixture.absent,
+        balance: U256::zero(),
+        nonce: U256::zero(),
+        code_hash: EMPTY_CODE_HASH,
+        storage_hash: EMPTY_ROOT,
+        account_proof: fixture.absent_proof.clone(),
+        storage_proof: vec![StorageProof {
+            key: U256::one(),
+            value: U256::zero(),
+            proof: vec![],
+        }],
+    };
+    verify_account_proof(fixture.state_root, &empty)?;
+
+    let mut tampered = empty;
+    tampered.balance = U256::one();
+    assert!(verify_account_proof(fixture.state_root, &tampered).is_err());
+    assert_eq!(verify_proof(EMPTY_ROOT, &[0u8; 32], &[])?, None);
+    Ok(())
+}
+
+#[test]
+fn test_trie_root() -> Result<(), Box<dyn Error>> {
+    // vectors of the trieanyorder tests of ethereum/tests
+    assert_eq!(trie_root(Vec::<(&[u8], &[u8])>::new()), EMPTY_ROOT);
+    assert_eq!(
+        trie_root([
+            ("dog", "puppy"),
+            ("do", "verb"),
+            ("horse", "stallion"),
+            ("doge", "coin")
+        ]),
+        "0x5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84".parse()?
+    );
+    assert_eq!(
+        trie_root([("foo", "bar"), ("food", "bass")]),
+        "0x17beaa1648bafa633cda809c90c04af50fc8aed3cb40d16efbddee6fdf63c4c3".parse()?
+    );
+    assert_eq!(ordered_trie_root(Vec::<Vec<u8>>::new()), EMPTY_ROOT);
+    Ok(())
+}
+
+fn first_transaction() -> serde_json::Value {
+    // the first transaction of mainnet, mined in block 46147
+    serde_json::json!({
+        "blockHash": null,
+        "blockNumber": "0xb443",
+        "from": "0xa1e4380a3b1f749673e270229993ee55f35663b4",
+        "gas": "0x5208",
+        "gasPrice": "0x2d79883d2000",
+        "hash": "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+        "input": "0x",
+        "nonce": "0x0",
+        "to": "0x5df9b87991262f6ba471f09758cde1c0fc1de734",
+        "transactionIndex": "0x0",
+        "value": "0x7a69",
+        "v": "0x1c",
+        "r": "0x88ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0",
+        "s": "0x45e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a"
+    })
+}
+
+#[test]
+fn test_transactions_root() -> Result<(), Box<dyn Error>> {
+    let root: H256 =
+        "0x4513310fcb9f6f616972a3b948dc5d547f280849a87ebb5af0191f98b87be598".parse()?;
+    let tx: Transaction = serde_json::from_value(first_transaction())?;
+    assert_eq!(transactions_root(&[tx])?, root);
+    assert_eq!(transactions_root(&[])?, EMPTY_ROOT);
+    // the chain id some nodes return for transactions without EIP-155 is not part of their encoding
+    let mut tx = first_transaction();
+    tx["chainId"] = serde_json::json!("0x1");
+    assert_eq!(transactions_root(&[serde_json::from_value(tx)?])?, root);
+
+    let mut block = serde_json::json!({
+        "number": "0xb443",
+        "hash": null,
+        "parentHash": H256::ZERO,
+        "nonce": null,
+        "sha3Uncles": H256::ZERO,
+        "logsBloom": null,
+        "transactionsRoot": root,
+        "stateRoot": H256::ZERO,
+        "receiptsRoot": H256::ZERO,
+        "miner": null,
+        "difficulty": "0x0",
+        "totalDifficulty": null,
+        "extraData": "0x",
+        "size": "0x0",
+        "gasLimit": "0x5208",
+        "gasUsed": "0x5208",
+        "timestamp": "0x0",
+        "transactions": [first_transaction()],
+        "uncles": []
+    });
+    let verified: BlockWithTx = serde_json::from_value(block.clone())?;
+    verified.verify_transactions_root()?;
+    // a transaction that does not match its hash
+    block["transactions"][0]["value"] = serde_json::json!("0x7a6a");
+    let tampered: BlockWithTx = serde_json::from_value(block.clone())?;
+    assert!(tampered.verify_transactions_root().is_err());
+    // transactions missing from the block
+    block["transactions"] = serde_json::json!([]);
+    let truncated: BlockWithTx = serde_json::from_value(block)?;
+    assert!(truncated
+        .verify_transactions_root()
+        .unwrap_err()
+        .to_string()
+        .contains("mismatch"));
+    Ok(())
+}
+
+#[test]
+fn test_receipts_root() -> Result<(), Box<dyn Error>> {
+    let tx: Transaction = serde_json::from_value(first_transaction())?;
+    let bloom = [0u8; 256];
+    let receipt = |status: serde_json::Value, tx_type: Option<&str>| -> TransactionReceipt {
+        let mut receipt = serde_json::json!({
+            "transactionHash": tx.hash,
+            "transactionIndex": "0x0",
+            "blockHash": H256::ZERO,
+            "blockNumber": "0xb443",
+            "from": tx.from,
+            "to": tx.to,
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "gasUsed": "0x5208",
+            "contractAddress": null,
+            "logs": [],
+            "logsBloom": Bytes(bloom.to_vec()),
+            "type": tx_type,
+        });
+        receipt
+            .as_object_mut()
+            .unwrap()
+            .extend(status.as_object().unwrap().clone());
+        serde_json::from_value(receipt).unwrap()
+    };
+    let fields = |outcome: Vec<u8>| {
+        vec![
+            outcome,
+            rlp_string(&trimmed(0x5208)),
+            rlp_string(&bloom),
+            rlp_list(&[]),
+        ]
+    };
+
+    let state_root = H256([7; 32]);
+    let legacy = [receipt(serde_json::json!({ "root": state_root }), None)];
+    let expected = rlp_list(&fields(rlp_string(&state_root.0)));
+    assert_eq!(receipts_root(&legacy)?, ordered_trie_root([expected]));
+
+    let failed = receipt(serde_json::json!({ "status": "0x0" }), Some("0x2"));
+    let expected = [vec![2], rlp_list(&fields(rlp_string(&[])))].concat();
+    assert_eq!(receipts_root(&[failed])?, ordered_trie_root([expected]));
+
+    let mut block: BlockWithTx = serde_json::from_value(serde_json::json!({
+        "number": "0xb443",
+        "hash": null,
+        "parentHash": H256::ZERO,
+        "nonce": null,
+        "sha3Uncles": H256::ZERO,
+        "logsBloom": null,
+        "transactionsRoot": H256::ZERO,
+        "stateRoot": H256::ZERO,
+        "receiptsRoot": receipts_root(&legacy)?,
+        "miner": null,
+        "difficulty": "0x0",
+        "totalDifficulty": null,
+        "extraData": "0x",
+        "size": "0x0",
+        "gasLimit": "0x5208",
+        "gasUsed": "0x5208",
+        "timestamp": "0x0",
+        "transactions": [first_transaction()],
+        "uncles": []
+    }))?;
+    block.verify_receipts_root(&legacy)?;
+    assert!(block.verify_receipts_root(&[]).is_err());
+    block.receipts_root = H256::ZERO;
+    assert!(block.verify_receipts_root(&legacy).is_err());
+    Ok(())
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use common::{receipt, rpc, serve};
+use ethrs::provider::{Provider, TransactionReceipt};
+use ethrs::quirks::{NodeClient, QuirkProfile};
+use serde_json::json;
+
+use std::error::Error;
+use std::net::TcpListener;
+
+fn error(code: i64, message: &str, data: &str) -> (&'static str, String) {
+    (
+        "200 OK",
+        json!({"jsonrpc": "2.0", "id": 1, "error": {"code": code, "message": message, "data": data}})
+            .to_string(),
+    )
+}
+
+#[test]
+fn test_detect_client() {
+    for (client_version, client) in [
+        (
+            "Geth/v1.13.14-stable-2bd6bd01/linux-amd64/go1.21.7",
+            NodeClient::Geth,
+        ),
+        ("erigon/2.59.3/linux-amd64/go1.21.5", NodeClient::Erigon),
+        (
+            "Nethermind/v1.25.4+20b10b35/linux-x64/dotnet8.0.2",
+            NodeClient::Nethermind,
+        ),
+        (
+            "besu/v24.1.2/linux-x86_64/openjdk-java-17",
+            NodeClient::Besu,
+        ),
+        (
+            "reth/v0.2.0-beta.5-54f75cdcc/x86_64-unknown-linux-gnu",
+            NodeClient::Reth,
+        ),
+    ] {
+        assert_eq!(NodeClient::detect(client_version), Some(client));
+    }
+    assert_eq!(NodeClient::detect("anvil/v0.2.0"), None);
+    assert_eq!(NodeClient::detect(""), None);
+}
+
+#[test]
+fn test_connect_detects_quirks() -> Result<(), Box<dyn Error>> {
+    let (url, server) = serve(vec![
+        rpc(json!("0x1")),
+        rpc(json!("besu/v24.1.2/linux-x86_64/openjdk-java-17")),
+        rpc(json!("0x1")),
+        rpc(json!("Geth/v1.13.0")),
+    ]);
+    let provider = Provider::connect(&url)?;
+    assert_eq!(
+        provider.quirks(),
+        Some(&QuirkProfile::for_client(NodeClient::Besu))
+    );
+    // a profile set explicitly is kept
+    let provider = Provider::new(&url)
+        .with_quirks(QuirkProfile::for_client(NodeClient::Erigon))
+        .preflight()?;
+    assert_eq!(provider.quirks().unwrap().client, NodeClient::Erigon);
+    server.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_methods() -> Result<(), Box<dyn Error>> {
+    // nothing listens on the port, so requests fail only if they are sent
+    let url = format!("http://{}", TcpListener::bind("127.0.0.1:0")?.local_addr()?);
+    let provider = Provider::new(&url).with_quirks(QuirkProfile::for_client(NodeClient::Besu));
+    let err = provider
+        .request::<serde_json::Value>("txpool_content", json!([]))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Method txpool_content is not supported by Besu"
+    );
+
+    let provider = Provider::new(&url).with_quirks(
+        QuirkProfile::for_client(NodeClient::Geth).with_unsupported_method("eth_getProof"),
+    );
+    let err = provider
+        .request::<serde_json::Value>("eth_getProof", json!([]))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Method eth_getProof is not supported by Geth"
+    );
+
+    let (url, server) = serve(vec![error(
+        -32601,
+        "the method debug_traceCall does not exist/is not available",
+        "",
+    )]);
+    let provider = Provider::new(&url).with_quirks(QuirkProfile::for_client(NodeClient::Erigon));
+    let err = provider
+        .request::<serde_json::Value>("debug_traceCall", json!([]))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Method debug_traceCall is not supported by Erigon"
+    );
+    server.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_revert_reasons() -> Result<(), Box<dyn Error>> {
+    let (url, server) = serve(vec![
+        error(-32015, "VM execution error.", "revert: not owner"),
+        error(-32000, "Execution reverted", "0x08c379a0"),
+        error(-32015, "VM execution error.", "revert: not owner"),
+    ]);
+    let provider =
+        Provider::new(&url).with_quirks(QuirkProfile::for_client(NodeClient::Nethermind));
+    let call = |provider: &Provider| {
+        provider
+            .request::<String>("eth_call", json!([{}, "latest"]))
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(call(&provider), "execution reverted: not owner");
+    assert_eq!(call(&provider), "execution reverted");
+    // without a profile, errors are returned as the client sends them
+    assert_eq!(call(&Provider::new(&url)), "VM execution error.");
+    server.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_null_fields() -> Result<(), Box<dyn Error>> {
+    let txhash = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+    let mut null_logs = receipt(txhash, None);
+    null_logs["logs"] = serde_json::Value::Null;
+    let (url, server) = serve(vec![
+        rpc(null_logs.clone()),
+        rpc(null_logs.clone()),
+        rpc(null_logs),
+    ]);
+    assert!(Provider::new(&url)
+        .request::<TransactionReceipt>("eth_getTransactionReceipt", json!([txhash]))
+        .is_err());
+    let provider = Provider::new(&url).with_quirks(QuirkProfile::for_client(NodeClient::Erigon));
+    let receipt: TransactionReceipt =
+        provider.request("eth_getTransactionReceipt", json!([txhash]))?;
+    assert!(receipt.logs.is_empty());
+    assert_eq!(receipt.to, None);
+    let receipt: serde_json::Value =
+        provider.request("eth_getTransactionReceipt", json!([txhash]))?;
+    assert!(receipt.get("to").is_none());
+    server.join().unwrap();
+    Ok(())
+}
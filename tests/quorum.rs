@@ -0,0 +1,58 @@
+mod common;
+
+use common::{rpc, serve};
+use ethrs::provider::Provider;
+use ethrs::quorum::QuorumProvider;
+
+use std::error::Error;
+
+#[test]
+fn test_quorum_provider() -> Result<(), Box<dyn Error>> {
+    let address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    let (honest, honest_server) = serve(vec![
+        rpc(serde_json::json!("0x64")),
+        rpc(serde_json::json!("0x64")),
+    ]);
+    let (lying, lying_server) = serve(vec![
+        rpc(serde_json::json!("0xffff")),
+        rpc(serde_json::json!("0xffff")),
+    ]);
+    let (flaky, flaky_server) = serve(vec![
+        rpc(serde_json::json!("0x64")),
+        ("502 Bad Gateway", "<html>bad gateway</html>".to_owned()),
+    ]);
+    let quorum = QuorumProvider::new(&[&honest, &lying, &flaky], 2);
+    let provider = quorum.provider();
+    assert_eq!(provider.get_balance(address, None)?, 100);
+    // only one endpoint answers 0x64 once the flaky one fails
+    let err = provider.get_balance(address, None).unwrap_err().to_string();
+    assert!(err.starts_with("No quorum: 1 of 3 endpoints agreed, 2 required"));
+    assert!(err.contains(&flaky));
+    honest_server.join().unwrap();
+    lying_server.join().unwrap();
+    flaky_server.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_quorum_provider_errors() -> Result<(), Box<dyn Error>> {
+    let reverted = || {
+        (
+            "200 OK",
+            serde_json::json!({"jsonrpc": "2.0", "id": 1, "error": {"code": 3, "message": "execution reverted"}})
+                .to_string(),
+        )
+    };
+    let (first, first_server) = serve(vec![reverted()]);
+    let (second, second_server) = serve(vec![reverted()]);
+    let quorum = QuorumProvider::majority(vec![Provider::new(&first), Provider::new(&second)]);
+    assert_eq!(quorum.quorum(), 2);
+    // JSON-RPC errors agreed on are returned as such
+    assert_eq!(
+        quorum.provider().chain_id().unwrap_err().to_string(),
+        "execution reverted"
+    );
+    first_server.join().unwrap();
+    second_server.join().unwrap();
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use ethrs::provider::AccessListItem;
+use ethrs::rlp::{decode, encode, Header, RlpItem, MAX_DEPTH};
+use ethrs::types::{Address, Bytes, H256, U256};
+
+use std::error::Error;
+
+fn string(data: &[u8]) -> RlpItem {
+    RlpItem::String(data.to_vec())
+}
+
+// `lists` empty lists nested in one another, e.g. `c1c0` for two
+fn nested(lists: usize) -> Vec<u8> {
+    let mut prefixes = vec![];
+    let mut length = 0;
+    for _ in 0..lists {
+        let prefix = match length {
+            0..=55 => vec![0xc0 + length as u8],
+            _ => {
+                let bytes = (length as u64).to_be_bytes();
+                let trimmed = &bytes[bytes.iter().position(|byte| *byte != 0).unwrap()..];
+                let mut prefix = vec![0xf7 + trimmed.len() as u8];
+                prefix.extend_from_slice(trimmed);
+                prefix
+            }
+        };
+        length += prefix.len();
+        prefixes.push(prefix);
+    }
+    prefixes.into_iter().rev().flatten().collect()
+}
+
+#[test]
+fn test_rlp_items() -> Result<(), Box<dyn Error>> {
+    let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+    let mut long = vec![0xb8, 0x38];
+    long.extend_from_slice(lorem);
+    let empty = RlpItem::List(vec![]);
+    // the set theoretical representation of three
+    let three = RlpItem::List(vec![
+        empty.clone(),
+        RlpItem::List(vec![empty.clone()]),
+        RlpItem::List(vec![empty.clone(), RlpItem::List(vec![empty.clone()])]),
+    ]);
+    for (item, encoded) in [
+        (string(b"dog"), hex::decode("83646f67")?),
+        (string(b""), vec![0x80]),
+        (string(&[0x00]), vec![0x00]),
+        (string(&[0x7f]), vec![0x7f]),
+        (string(&[0x80]), vec![0x81, 0x80]),
+        (empty.clone(), vec![0xc0]),
+        (
+            RlpItem::List(vec![string(b"cat"), string(b"dog")]),
+            hex::decode("c88363617483646f67")?,
+        ),
+        (three, hex::decode("c7c0c1c0c3c0c1c0")?),
+        (string(lorem), long),
+    ] {
+        assert_eq!(item.encode(), encoded);
+        assert_eq!(RlpItem::decode(&encoded)?, item);
+    }
+
+    for invalid in [
+        "",
+        "8100",
+        "817f",
+        "b800",
+        "b90038",
+        "83646f",
+        "83646f6700",
+        "c883636174",
+        "f800",
+    ] {
+        assert!(
+            RlpItem::decode(&hex::decode(invalid)?).is_err(),
+            "{invalid}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rlp_values() -> Result<(), Box<dyn Error>> {
+    assert_eq!(encode(&0u64), [0x80]);
+    assert_eq!(encode(&15u8), [0x0f]);
+    assert_eq!(encode(&1024u64), [0x82, 0x04, 0x00]);
+    assert_eq!(encode(&U256::zero()), [0x80]);
+    assert_eq!(encode(&U256::from(1024)), [0x82, 0x04, 0x00]);
+    assert_eq!(decode::<U256>(&encode(&U256::MAX))?, U256::MAX);
+    assert_eq!(decode::<u64>(&encode(&u64::MAX))?, u64::MAX);
+    assert!(decode::<u8>(&encode(&256u64)).is_err());
+    assert!(decode::<u64>(&[0x82, 0x00, 0x01]).is_err());
+    assert!(decode::<bool>(&encode(&true))?);
+
+    let address: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse()?;
+    assert_eq!(decode::<Address>(&encode(&address))?, address);
+    assert_eq!(encode(&None::<Address>), [0x80]);
+    assert_eq!(decode::<Option<Address>>(&[0x80])?, None);
+    assert!(decode::<Address>(&encode(&H256::ZERO)).is_err());
+
+    let item = AccessListItem {
+        address,
+        storage_keys: vec![H256::ZERO, H256([1; 32])],
+    };
+    let decoded: Vec<AccessListItem> = decode(&encode(&vec![item.clone()]))?;
+    assert_eq!(decoded, vec![item]);
+    assert_eq!(
+        decode::<Vec<Bytes>>(&hex::decode("c88363617483646f67")?)?,
+        vec![Bytes(b"cat".to_vec()), Bytes(b"dog".to_vec())]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_header_hash() -> Result<(), Box<dyn Error>> {
+    // the mainnet genesis block, as returned by eth_getBlockByNumber
+    let genesis: Header = serde_json::from_value(serde_json::json!({
+        "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+        "miner": "0x0000000000000000000000000000000000000000",
+        "stateRoot": "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+        "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+        "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+        "logsBloom": format!("0x{}", "0".repeat(512)),
+        "difficulty": "0x400000000",
+        "number": "0x0",
+        "gasLimit": "0x1388",
+        "gasUsed": "0x0",
+        "timestamp": "0x0",
+        "extraData": "0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+        "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "nonce": "0x0000000000000042",
+        "hash": "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+        "size": "0x21c",
+        "transactions": [],
+        "uncles": []
+    }))?;
+    assert_eq!(
+        genesis.hash(),
+        "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3".parse()?
+    );
+    assert_eq!(decode::<Header>(&encode(&genesis))?, genesis);
+
+    // fork fields are appended in order
+    let mut cancun = genesis.clone();
+    cancun.base_fee_per_gas = Some(U256::from(7));
+    cancun.withdrawals_root = Some(H256::ZERO);
+    cancun.blob_gas_used = Some(0);
+    cancun.excess_blob_gas = Some(0);
+    cancun.parent_beacon_block_root = Some(H256::ZERO);
+    let encoded = encode(&cancun);
+    assert_eq!(RlpItem::decode(&encoded)?.as_list()?.len(), 20);
+    assert_eq!(decode::<Header>(&encoded)?, cancun);
+    assert_ne!(cancun.hash(), genesis.hash());
+    Ok(())
+}
+
+#[test]
+fn test_rlp_nesting_limit() -> Result<(), Box<dyn Error>> {
+    assert_eq!(nested(2), [0xc1, 0xc0]);
+    assert!(RlpItem::decode(&nested(MAX_DEPTH)).is_ok());
+    for lists in [MAX_DEPTH + 1, 200_000] {
+        let err = RlpItem::decode(&nested(lists)).unwrap_err();
+        assert_eq!(err.to_string(), "RLP nesting too deep");
+    }
+    // typed decoding goes through the same limit
+    assert!(decode::<Vec<u64>>(&nested(200_000)).is_err());
+    Ok(())
+}
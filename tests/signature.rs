@@ -0,0 +1,186 @@
+use ethrs::signature::Signature;
+use ethrs::transaction::{Eip1559Transaction, LegacyTransaction, TypedTransaction};
+use ethrs::types::{Address, Bytes, H256, U256};
+use ethrs::utils::hash_message;
+use ethrs::wallet::Wallet;
+
+use std::error::Error;
+
+const ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+// the example transaction of EIP-155
+fn eip155() -> (TypedTransaction, Signature) {
+    let transaction = TypedTransaction::Legacy(LegacyTransaction {
+        chain_id: Some(1),
+        nonce: 9,
+        gas_price: U256::from(20_000_000_000u64),
+        gas_limit: 21000,
+        to: Some(Address([0x35; 20])),
+        value: U256::from(1_000_000_000_000_000_000u64),
+        input: Bytes::default(),
+    });
+    let signature = Signature {
+        r: U256::from_str_radix(
+            "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+            16,
+        )
+        .unwrap(),
+        s: U256::from_str_radix(
+            "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+            16,
+        )
+        .unwrap(),
+        v: 37,
+    };
+    (transaction, signature)
+}
+
+fn signer() -> Address {
+    "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_recover() -> Result<(), Box<dyn Error>> {
+    let (transaction, signature) = eip155();
+    let hash = transaction.signing_hash();
+    assert_eq!(signature.recover(hash)?, signer());
+    assert!(signature.verify(hash, signer()));
+    assert!(!signature.verify(hash, Address([0x35; 20])));
+    assert!(!signature.verify(H256([0x01; 32]), signer()));
+    // every encoding of v recovers the same signer
+    for v in [0, 27] {
+        assert_eq!(Signature { v, ..signature }.recover(hash)?, signer());
+    }
+    assert!(Signature {
+        r: U256::zero(),
+        ..signature
+    }
+    .recover(hash)
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_recover_high_s() -> Result<(), Box<dyn Error>> {
+    let (transaction, signature) = eip155();
+    assert!(signature.is_low_s());
+    let high = Signature {
+        s: U256::from_str_radix(ORDER, 16).unwrap() - signature.s,
+        v: 28,
+        ..signature
+    };
+    assert!(!high.is_low_s());
+    assert_eq!(high.recover(transaction.signing_hash())?, signer());
+    // transactions must be signed with a low s
+    let mut raw = transaction.encode_signed(&signature)?;
+    let offset = raw.len() - 32;
+    high.s.to_big_endian(&mut raw[offset..]);
+    raw[offset - 35] = 0x26;
+    assert_eq!(
+        TypedTransaction::decode_bytes(&raw)
+            .unwrap_err()
+            .to_string(),
+        "Invalid transaction signature: s is above half the curve order"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_eip155_v() -> Result<(), Box<dyn Error>> {
+    let (_, signature) = eip155();
+    assert_eq!(signature.y_parity()?, 0);
+    assert_eq!(signature.chain_id(), Some(1));
+    assert_eq!(signature.with_chain_id(None)?.v, 27);
+    assert_eq!(signature.with_chain_id(Some(11155111))?.v, 22310257);
+    assert_eq!(
+        Signature {
+            v: 22310258,
+            ..signature
+        }
+        .y_parity()?,
+        1
+    );
+    assert_eq!(Signature { v: 28, ..signature }.chain_id(), None);
+    // the largest chain id whose v fits in 64 bits with a y parity of 0
+    let largest = (u64::MAX - 35) / 2;
+    assert_eq!(signature.with_chain_id(Some(largest))?.v, u64::MAX);
+    assert_eq!(
+        signature
+            .with_chain_id(Some(largest + 1))
+            .unwrap_err()
+            .to_string(),
+        format!("Chain id {} is too large for EIP-155", largest + 1)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_v() -> Result<(), Box<dyn Error>> {
+    let (transaction, signature) = eip155();
+    let hash = transaction.signing_hash();
+    for v in (2..27).chain(29..35) {
+        let invalid = Signature { v, ..signature };
+        let error = format!("Invalid signature v: {v}");
+        assert_eq!(invalid.y_parity().unwrap_err().to_string(), error);
+        assert_eq!(invalid.recover(hash).unwrap_err().to_string(), error);
+        assert!(!invalid.verify(hash, signer()));
+        assert!(invalid.with_chain_id(Some(1)).is_err());
+        assert!(transaction.encode_signed(&invalid).is_err());
+        // the invalid v is kept, and rejected when parsed back
+        assert_eq!(invalid.to_bytes()[64], v as u8);
+        assert!(Signature::from_bytes(&invalid.to_bytes()).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_signature_bytes() -> Result<(), Box<dyn Error>> {
+    let (_, signature) = eip155();
+    let hex = "0x28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276\
+               67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d831b";
+    assert_eq!(signature.to_string(), hex);
+    assert_eq!(hex.parse::<Signature>()?, signature.with_chain_id(None)?);
+    assert_eq!(Signature::from_bytes(&signature.to_bytes())?.v, 27);
+    assert_eq!(
+        Signature::from_bytes(&[0; 64]).unwrap_err().to_string(),
+        "Expected a 65-byte signature, found 64 bytes"
+    );
+    assert_eq!(
+        Signature::from_bytes(&[0; 65]).map(|signature| signature.v)?,
+        0
+    );
+    assert_eq!(
+        Signature::from_bytes(&[2; 65]).unwrap_err().to_string(),
+        "Invalid signature v: 2"
+    );
+    assert!("0xzz".parse::<Signature>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_wallet_signatures() -> Result<(), Box<dyn Error>> {
+    let wallet = Wallet::from_private_key(&[0x46; 32])?;
+    let signature = wallet.sign_message(b"hello");
+    assert!(signature.is_low_s());
+    assert!(signature.v == 27 || signature.v == 28);
+    assert!(signature.verify(hash_message(b"hello"), wallet.address()));
+    assert!(!signature.verify(hash_message(b"hello!"), wallet.address()));
+    // signatures are deterministic, as specified by RFC 6979
+    let (transaction, expected) = eip155();
+    let signed = wallet.sign_transaction(transaction.clone())?;
+    assert_eq!(signed.signature, expected);
+    assert_eq!(signed.from, signer());
+    assert_eq!(TypedTransaction::decode_bytes(&signed.encode()?)?, signed);
+    let signed = wallet.sign_transaction(
+        Eip1559Transaction {
+            chain_id: 1,
+            ..Default::default()
+        }
+        .into(),
+    )?;
+    assert!(signed.signature.v <= 1);
+    assert_eq!(TypedTransaction::decode_bytes(&signed.encode()?)?, signed);
+    Ok(())
+}
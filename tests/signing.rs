@@ -0,0 +1,59 @@
+mod common;
+
+use common::{rpc, serve_requests};
+use ethrs::provider::Provider;
+use ethrs::signing::HmacSigner;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use std::error::Error;
+
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+#[test]
+fn test_hmac_signer() -> Result<(), Box<dyn Error>> {
+    let (url, handle) = serve_requests(vec![rpc(json!("0x10"))]);
+    let signer = HmacSigner::new(b"gateway secret")
+        .with_headers("x-gateway-timestamp", "x-gateway-signature")?
+        .with_key_id("x-gateway-key", "key-1")?;
+    assert!(!format!("{signer:?}").contains("gateway secret"));
+    let provider = Provider::builder(&url).signer(signer).build()?;
+    provider.block_number()?;
+
+    let (head, body) = handle.join().unwrap().remove(0);
+    let timestamp = header(&head, "x-gateway-timestamp").unwrap();
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"gateway secret")?;
+    mac.update(format!("{timestamp}.{}", hex::encode(Sha256::digest(&body))).as_bytes());
+    assert_eq!(
+        header(&head, "x-gateway-signature"),
+        Some(hex::encode(mac.finalize().into_bytes()).as_str())
+    );
+    assert_eq!(header(&head, "x-gateway-key"), Some("key-1"));
+    assert_eq!(header(&head, "x-timestamp"), None);
+    Ok(())
+}
+
+#[test]
+fn test_hmac_signer_defaults() -> Result<(), Box<dyn Error>> {
+    let (url, handle) = serve_requests(vec![rpc(json!("0x10"))]);
+    let signer = HmacSigner::new(b"secret");
+    let provider = Provider::builder(&url).signer(signer.clone()).build()?;
+    provider.block_number()?;
+
+    let (head, body) = handle.join().unwrap().remove(0);
+    let timestamp: u64 = header(&head, "x-timestamp").unwrap().parse()?;
+    assert_eq!(
+        header(&head, "x-signature"),
+        Some(signer.signature(timestamp, body.as_bytes()).as_str())
+    );
+    assert!(HmacSigner::new(b"secret")
+        .with_headers("x timestamp", "x-signature")
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use ethrs::provider::Log;
+use ethrs::sink::{CallbackSink, ChannelSink, Encoding, Event, Sink, WebhookSink};
+use ethrs::types::{Address, H256, U256};
+
+use std::error::Error;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+mod common;
+
+fn log() -> Log {
+    Log {
+        removed: false,
+        log_index: U256::zero(),
+        transaction_index: U256::zero(),
+        transaction_hash: H256::ZERO,
+        block_hash: H256::ZERO,
+        block_number: 1,
+        address: Address::ZERO,
+        data: "0x".to_owned(),
+        topics: vec![],
+    }
+}
+
+#[test]
+fn test_channel_and_callback_sinks() -> Result<(), Box<dyn Error>> {
+    let (sender, receiver) = channel();
+    ChannelSink::new(sender).send(&Event::Log(log()))?;
+    assert!(matches!(receiver.recv()?, Event::Log(_)));
+
+    let sink = CallbackSink::new(|event: &Event| match event {
+        Event::Log(log) if log.removed => Err("removed log".into()),
+        _ => Ok(()),
+    });
+    assert!(sink.send(&Event::Log(log())).is_ok());
+    let mut removed = log();
+    removed.removed = true;
+    assert!(sink.send(&Event::Log(removed)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_webhook_sink_retries() -> Result<(), Box<dyn Error>> {
+    let (url, server) = common::serve(vec![
+        ("500 Internal Server Error", String::new()),
+        ("200 OK", String::new()),
+    ]);
+
+    WebhookSink::new(&url)
+        .with_retries(1)
+        .with_backoff(Duration::from_millis(10))
+        .send(&Event::Log(log()))?;
+    let bodies = server.join().unwrap();
+    assert_eq!(bodies.len(), 2);
+    assert!(bodies[1].starts_with("{\"type\":\"log\",\"data\":{"));
+    Ok(())
+}
+
+#[test]
+fn test_encoding() -> Result<(), Box<dyn Error>> {
+    let event = Event::Log(log());
+    assert_eq!(event.kind(), "log");
+    assert_eq!(Encoding::Json.encode(&event)?, serde_json::to_vec(&event)?);
+    Ok(())
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn test_protobuf_encoding() -> Result<(), Box<dyn Error>> {
+    use ethrs::proto;
+    use prost::Message;
+
+    let mut log = log();
+    log.data = "0x2a".to_owned();
+    log.topics = vec![H256([1; 32])];
+    let encoded = Encoding::Protobuf.encode(&Event::Log(log))?;
+    match proto::Event::decode(encoded.as_slice())?.event {
+        Some(proto::event::Event::Log(log)) => {
+            assert_eq!(log.data, vec![0x2a]);
+            assert_eq!(log.topics, vec![vec![1; 32]]);
+            assert_eq!(log.block_number, 1);
+        }
+        other => panic!("unexpected event {other:?}"),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "nats")]
+#[test]
+fn test_nats_sink() -> Result<(), Box<dyn Error>> {
+    use ethrs::sink::NatsSink;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream
+            .write_all(b"INFO {\"server_id\":\"test\"}\r\n")
+            .unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut published = vec![];
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0 {
+            if let Some(publish) = line.strip_prefix("PUB ") {
+                let (subject, length) = publish.trim_end().split_once(' ').unwrap();
+                let mut payload = vec![0u8; length.parse::<usize>().unwrap() + 2];
+                reader.read_exact(&mut payload).unwrap();
+                published.push((subject.to_owned(), payload));
+            } else if line == "PING\r\n" {
+                stream.write_all(b"PONG\r\n").unwrap();
+                if published.len() == 2 {
+                    break;
+                }
+            }
+            line.clear();
+        }
+        published
+    });
+
+    let sink = NatsSink::new(&format!("nats://{address}"), "eth");
+    sink.send(&Event::Log(log()))?;
+    sink.send(&Event::Log(log()))?;
+    let published = server.join().unwrap();
+    assert_eq!(published[0].0, "eth.log");
+    assert!(published[1].1.starts_with(b"{\"type\":\"log\""));
+    assert!(published[1].1.ends_with(b"}\r\n"));
+    Ok(())
+}
+
+#[cfg(feature = "kafka")]
+#[test]
+fn test_kafka_sink() -> Result<(), Box<dyn Error>> {
+    use ethrs::sink::KafkaSink;
+
+    let (url, server) = common::serve(vec![
+        (
+            "200 OK",
+            r#"{"offsets":[{"partition":0,"offset":7,"error_code":null,"error":null}]}"#.to_owned(),
+        ),
+        (
+            "200 OK",
+            r#"{"offsets":[{"partition":null,"offset":null,"error_code":40403,"error":"Topic not found"}]}"#
+                .to_owned(),
+        ),
+    ]);
+    let sink = KafkaSink::new(&format!("{url}/"), "events");
+    sink.send(&Event::Log(log()))?;
+    assert!(sink.send(&Event::Log(log())).is_err());
+    let request: serde_json::Value = serde_json::from_str(&server.join().unwrap()[0])?;
+    assert_eq!(request["records"][0]["key"], "log");
+    assert_eq!(request["records"][0]["value"]["type"], "log");
+
+    #[cfg(feature = "protobuf")]
+    {
+        let (url, server) = common::serve(vec![(
+            "200 OK",
+            r#"{"offsets":[{"partition":0,"offset":8}]}"#.to_owned(),
+        )]);
+        KafkaSink::new(&url, "events")
+            .with_encoding(Encoding::Protobuf)
+            .send(&Event::Log(log()))?;
+        let request: serde_json::Value = serde_json::from_str(&server.join().unwrap()[0])?;
+        assert_eq!(request["records"][0]["key"], "bG9n");
+        assert!(request["records"][0]["value"]
+            .as_str()
+            .unwrap()
+            .ends_with('='));
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use ethrs::provider::{CallInput, Provider};
+use ethrs::snapshot::{GasChange, GasSnapshot, Tolerance};
+
+use std::env;
+use std::error::Error;
+
+mod common;
+
+#[test]
+fn test_snapshot_diff() -> Result<(), Box<dyn Error>> {
+    let previous: GasSnapshot = "Counter:increment() (gas: 43482)\nCounter:reset() (gas: 21300)\nCounter:set(uint256) (gas: 26000)\n".parse()?;
+    let mut snapshot = GasSnapshot::new();
+    snapshot.record("Counter:increment()", 43500);
+    snapshot.record("Counter:set(uint256)", 25000);
+    snapshot.record("Counter:number()", 2400);
+
+    let diff = snapshot.diff(&previous, Tolerance::Absolute(10));
+    assert_eq!(diff.changes.len(), 4);
+    assert_eq!(diff.changes["Counter:increment()"].delta(), Some(18));
+    assert_eq!(
+        diff.changes["Counter:reset()"],
+        GasChange {
+            before: Some(21300),
+            after: None
+        }
+    );
+    assert_eq!(
+        diff.regressions().map(|(name, _)| name).collect::<Vec<_>>(),
+        vec!["Counter:increment()"]
+    );
+    assert!(diff.check().is_err());
+    assert!(snapshot
+        .diff(&previous, Tolerance::Relative(0.001))
+        .check()
+        .is_ok());
+    assert!(diff
+        .to_string()
+        .contains("Counter:set(uint256) (gas: 26000 -> 25000 | -1000 -3.846%)"));
+
+    let path = env::temp_dir().join(format!("ethrs-gas-snapshot-{}", std::process::id()));
+    snapshot.save(&path)?;
+    assert_eq!(GasSnapshot::load(&path)?, snapshot);
+    std::fs::remove_file(&path)?;
+    assert!(GasSnapshot::load(&path)?.is_empty());
+    assert!("Counter:increment() 43482".parse::<GasSnapshot>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_record() -> Result<(), Box<dyn Error>> {
+    let (url, server) = common::serve(vec![
+        common::rpc("0x6b3a".into()),
+        common::rpc(serde_json::json!({
+            "type": "CALL",
+            "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "to": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+            "gas": "0x13880",
+            "gasUsed": "0xa9da",
+            "input": "0xd09de08a"
+        })),
+    ]);
+    let provider = Provider::new(&url);
+    let mut snapshot = GasSnapshot::new();
+    let tx = CallInput {
+        from: None,
+        to: "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?,
+        gas: None,
+        gas_price: None,
+        value: None,
+        data: Some("0xd09de08a".to_owned()),
+    };
+    assert_eq!(snapshot.estimate(&provider, "estimate", tx)?, 27450);
+    assert_eq!(
+        snapshot.trace(
+            &provider,
+            "trace",
+            "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8".parse()?
+        )?,
+        43482
+    );
+    assert_eq!(
+        snapshot.to_string(),
+        "estimate (gas: 27450)\ntrace (gas: 43482)\n"
+    );
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].contains("eth_estimateGas"));
+    assert!(requests[1].contains("\"onlyTopCall\":true"));
+    Ok(())
+}
@@ -0,0 +1,389 @@
+mod common;
+
+use common::serve_with;
+use ethrs::bloom::Bloom;
+use ethrs::provider::{Filter, Log, Provider};
+use ethrs::stream::{BlockEvent, BlockStream, Finality, LogStream, LogStreamExt, StreamExt};
+use serde_json::json;
+use std::error::Error;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+fn quantity(value: &serde_json::Value) -> u64 {
+    u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+}
+
+fn log(block_number: u64, tx: u8) -> serde_json::Value {
+    json!({
+        "removed": false,
+        "logIndex": "0x0",
+        "transactionIndex": "0x0",
+        "transactionHash": format!("0x{}", format!("{tx:02x}").repeat(32)),
+        "blockHash": format!("0x{:064x}", block_number),
+        "blockNumber": format!("0x{block_number:x}"),
+        "address": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+        "data": "0x",
+        "topics": []
+    })
+}
+
+///Serves a chain up to `head` whose blocks from `fork` onwards are on a second branch.
+fn forked_chain(state: Arc<Mutex<(u64, u64)>>) -> String {
+    let hash = |number: u64, fork: u64| {
+        let branch = (number >= fork) as u8;
+        format!("0x{branch:032x}{number:032x}")
+    };
+    serve_with(move |method, params| {
+        assert_eq!(method, "eth_getBlockByNumber");
+        let (head, fork) = *state.lock().unwrap();
+        let number = quantity(&params[0]);
+        if number > head {
+            return json!(null);
+        }
+        let mut block = common::block(number, 0);
+        block["hash"] = json!(hash(number, fork));
+        block["parentHash"] = json!(hash(number - 1, fork));
+        block
+    })
+}
+
+fn logs(logs: &[serde_json::Value]) -> impl Iterator<Item = Result<Log, Box<dyn Error>>> {
+    logs.iter()
+        .map(|log| Ok(serde_json::from_value(log.clone()).unwrap()))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+#[test]
+fn test_log_stream_group_by_block() {
+    let ranges = Arc::new(AtomicUsize::new(0));
+    let requests = ranges.clone();
+    let url = serve_with(move |method, params| match method {
+        "eth_blockNumber" => json!("0x6"),
+        "eth_getLogs" => {
+            requests.fetch_add(1, Ordering::SeqCst);
+            [log(2, 1), log(2, 2), log(4, 3), log(6, 4)]
+                .into_iter()
+                .filter(|log| {
+                    let number = quantity(&log["blockNumber"]);
+                    number >= quantity(&params[0]["fromBlock"])
+                        && number <= quantity(&params[0]["toBlock"])
+                })
+                .collect()
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let groups: Vec<(u64, usize)> = LogStream::new(Provider::new(&url), Filter::default(), 1)
+        .with_batch_size(3)
+        .group_by_block()
+        .take(2)
+        .map(|group| {
+            let group = group.unwrap();
+            (group.block_number, group.logs.len())
+        })
+        .collect();
+    assert_eq!(groups, [(2, 2), (4, 1)]);
+    // the group of block 4 is complete once the log of block 6 arrives, in the second range
+    assert_eq!(ranges.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_block_stream() {
+    let head = Arc::new(AtomicU64::new(2));
+    let chain = head.clone();
+    let url = serve_with(move |method, params| match method {
+        "eth_getBlockByNumber" => {
+            let number = quantity(&params[0]);
+            // a new block is mined whenever the stream waits for one
+            match number <= chain.load(Ordering::SeqCst) {
+                true => common::block(number, 0),
+                false => {
+                    chain.fetch_add(1, Ordering::SeqCst);
+                    serde_json::Value::Null
+                }
+            }
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let numbers: Vec<u64> = BlockStream::new(Provider::new(&url), 1)
+        .with_poll_interval(Duration::from_millis(10))
+        .take(4)
+        .map(|block| block.unwrap().number.unwrap())
+        .collect();
+    assert_eq!(numbers, [1, 2, 3, 4]);
+    assert_eq!(head.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn test_confirmed() {
+    let head = Arc::new(AtomicU64::new(4));
+    let polls = head.clone();
+    let url = serve_with(move |method, _| match method {
+        "eth_blockNumber" => json!(format!("0x{:x}", polls.fetch_add(1, Ordering::SeqCst))),
+        _ => panic!("unexpected method {method}"),
+    });
+    let confirmed: Vec<u64> = logs(&[log(3, 1), log(3, 2), log(5, 3)])
+        .confirmed(Provider::new(&url), 2, Duration::from_millis(10))
+        .map(|log| log.unwrap().block_number)
+        .collect();
+    assert_eq!(confirmed, [3, 3, 5]);
+    // heads 4, then 5 and 6 for block 5, the second log of block 3 being confirmed by the known head
+    assert_eq!(head.load(Ordering::SeqCst), 7);
+}
+
+#[test]
+fn test_with_transactions() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let served = requests.clone();
+    let url = serve_with(move |method, params| match method {
+        "eth_getTransactionByHash" => {
+            served.fetch_add(1, Ordering::SeqCst);
+            json!({
+                "blockHash": null,
+                "blockNumber": null,
+                "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "gas": "0x5208",
+                "gasPrice": "0x1",
+                "hash": params[0],
+                "input": "0x",
+                "nonce": "0x0",
+                "to": null,
+                "transactionIndex": null,
+                "value": "0x0",
+                "v": "0x0",
+                "r": "0x0",
+                "s": "0x0",
+            })
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let joined: Vec<bool> = logs(&[log(1, 1), log(1, 1), log(1, 2)])
+        .with_transactions(Provider::new(&url))
+        .map(|result| {
+            let (log, tx) = result.unwrap();
+            log.transaction_hash == tx.hash
+        })
+        .collect();
+    assert_eq!(joined, [true, true, true]);
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_throttle() {
+    let start = Instant::now();
+    let count = logs(&[log(1, 1), log(2, 2), log(3, 3)])
+        .throttle(Duration::from_millis(50))
+        .count();
+    assert_eq!(count, 3);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn test_watch_blocks() -> Result<(), Box<dyn Error>> {
+    let head = Arc::new(AtomicU64::new(10));
+    let chain = head.clone();
+    let url = serve_with(move |method, params| match method {
+        "eth_blockNumber" => json!(format!("0x{:x}", chain.load(Ordering::SeqCst))),
+        "eth_getBlockByNumber" => {
+            let number = quantity(&params[0]);
+            match number <= chain.load(Ordering::SeqCst) {
+                true => common::block(number, 0),
+                false => {
+                    chain.fetch_add(1, Ordering::SeqCst);
+                    serde_json::Value::Null
+                }
+            }
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let numbers: Vec<u64> = Provider::new(&url)
+        .watch_blocks(Duration::from_millis(10))?
+        .take(2)
+        .map(|block| block.unwrap().number.unwrap())
+        .collect();
+    // blocks mined before watching are skipped
+    assert_eq!(numbers, [11, 12]);
+    assert_eq!(head.load(Ordering::SeqCst), 12);
+    Ok(())
+}
+
+#[test]
+fn test_watch_logs() -> Result<(), Box<dyn Error>> {
+    let url = serve_with(move |method, params| match method {
+        "eth_blockNumber" => json!("0x6"),
+        "eth_getLogs" => [log(2, 1), log(5, 2), log(6, 3)]
+            .into_iter()
+            .filter(|log| {
+                let number = quantity(&log["blockNumber"]);
+                number >= quantity(&params[0]["fromBlock"])
+                    && number <= quantity(&params[0]["toBlock"])
+            })
+            .collect(),
+        _ => panic!("unexpected method {method}"),
+    });
+    let provider = Provider::new(&url);
+    let filter = Filter {
+        from_block: Some(5.into()),
+        ..Default::default()
+    };
+    let blocks: Vec<u64> = provider
+        .watch_logs(filter)?
+        .take(2)
+        .map(|log| log.unwrap().block_number)
+        .collect();
+    assert_eq!(blocks, [5, 6]);
+    let mut stream = provider
+        .watch_logs(Filter::default())?
+        .with_poll_interval(Duration::from_millis(10));
+    // no block is mined after the head, so the stream waits
+    let waiter = std::thread::spawn(move || stream.next().is_some());
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!waiter.is_finished());
+    Ok(())
+}
+
+#[test]
+fn test_track_reorgs() -> Result<(), Box<dyn Error>> {
+    let state = Arc::new(Mutex::new((5, u64::MAX)));
+    let url = forked_chain(state.clone());
+    let mut stream = BlockStream::new(Provider::new(&url), 1)
+        .with_poll_interval(Duration::from_millis(10))
+        .track_reorgs(8);
+    let number = |event: Option<Result<BlockEvent, Box<dyn Error>>>| match event.unwrap() {
+        Ok(BlockEvent::NewBlock(block)) => block.number.unwrap(),
+        event => panic!("unexpected event {event:?}"),
+    };
+    for expected in 1..=5 {
+        assert_eq!(number(stream.next()), expected);
+    }
+    *state.lock().unwrap() = (7, 4);
+    match stream.next().unwrap()? {
+        BlockEvent::Reorg {
+            common_ancestor,
+            dropped,
+            new,
+        } => {
+            assert_eq!(common_ancestor.number, Some(3));
+            let numbers = |blocks: &[ethrs::provider::Block]| {
+                blocks
+                    .iter()
+                    .map(|block| block.number.unwrap())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(numbers(&dropped), [4, 5]);
+            assert_eq!(numbers(&new), [4, 5, 6]);
+            assert!(dropped
+                .iter()
+                .zip(&new)
+                .all(|(old, new)| old.hash != new.hash));
+            assert_eq!(new[0].parent_hash, common_ancestor.hash.unwrap());
+        }
+        event => panic!("unexpected event {event:?}"),
+    }
+    assert_eq!(number(stream.next()), 7);
+
+    // a reorg deeper than the tracked blocks
+    let state = Arc::new(Mutex::new((5, u64::MAX)));
+    let url = forked_chain(state.clone());
+    let mut stream = BlockStream::new(Provider::new(&url), 1).track_reorgs(2);
+    for expected in 1..=5 {
+        assert_eq!(number(stream.next()), expected);
+    }
+    *state.lock().unwrap() = (7, 2);
+    assert!(stream.next().unwrap().is_err());
+    assert_eq!(number(stream.next()), 7);
+    Ok(())
+}
+
+#[test]
+fn test_finality() -> Result<(), Box<dyn Error>> {
+    let head = Arc::new(AtomicU64::new(8));
+    let chain = head.clone();
+    let url = serve_with(move |method, params| match method {
+        // a new block is mined whenever the head is polled
+        "eth_blockNumber" => json!(format!("0x{:x}", chain.fetch_add(1, Ordering::SeqCst))),
+        "eth_getBlockByNumber" if params[0] == "finalized" => common::block(4, 0),
+        "eth_getBlockByNumber" => {
+            let number = quantity(&params[0]);
+            assert!(number <= chain.load(Ordering::SeqCst));
+            common::block(number, 0)
+        }
+        "eth_getLogs" => {
+            assert_eq!(params[0]["toBlock"], "0x4");
+            [log(2, 1), log(4, 2)].into_iter().collect()
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let provider = Provider::new(&url);
+    let numbers: Vec<u64> = BlockStream::new(provider.clone(), 5)
+        .with_poll_interval(Duration::from_millis(10))
+        .with_finality(Finality::Confirmations(3))
+        .take(3)
+        .map(|block| block.unwrap().number.unwrap())
+        .collect();
+    assert_eq!(numbers, [5, 6, 7]);
+    // blocks 5 and 6 are confirmed by the first head polled, block 7 by the next one
+    assert_eq!(head.load(Ordering::SeqCst), 10);
+
+    let blocks: Vec<u64> = LogStream::new(provider.clone(), Filter::default(), 1)
+        .with_finality(Finality::Finalized)
+        .take(2)
+        .map(|log| log.unwrap().block_number)
+        .collect();
+    assert_eq!(blocks, [2, 4]);
+    let mut stream = BlockStream::new(provider, 5)
+        .with_poll_interval(Duration::from_millis(10))
+        .with_finality(Finality::Finalized);
+    let waiter = std::thread::spawn(move || stream.next().is_some());
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!waiter.is_finished());
+    Ok(())
+}
+
+#[test]
+fn test_bloom_filter() -> Result<(), Box<dyn Error>> {
+    let token = "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?;
+    let mut bloom = Bloom::default();
+    bloom.accrue_address(&token);
+    let requests = Arc::new(Mutex::new(vec![]));
+    let log_requests = requests.clone();
+    let url = serve_with(move |method, params| match method {
+        "eth_blockNumber" => json!("0x3"),
+        "eth_getBlockByNumber" => {
+            let number = quantity(&params[0]);
+            let mut block = common::block(number, 0);
+            block["hash"] = json!(format!("0x{number:064x}"));
+            // block 1 has no logs of the token, block 3 has a malformed bloom
+            block["logsBloom"] = match number {
+                1 => json!(Bloom::default()),
+                2 => json!(bloom),
+                _ => json!("0x"),
+            };
+            block
+        }
+        "eth_getLogs" => {
+            let block_hash = params[0]["blockHash"].as_str().unwrap().to_owned();
+            assert!(params[0].get("fromBlock").is_none());
+            log_requests.lock().unwrap().push(block_hash.clone());
+            let number = u64::from_str_radix(&block_hash[2..], 16).unwrap();
+            json!([log(number, number as u8)])
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let filter = Filter {
+        address: vec![token],
+        ..Default::default()
+    };
+    let blocks: Vec<u64> = LogStream::new(Provider::new(&url), filter, 1)
+        .with_bloom_filter(true)
+        .take(2)
+        .map(|log| log.unwrap().block_number)
+        .collect();
+    assert_eq!(blocks, [2, 3]);
+    assert_eq!(
+        *requests.lock().unwrap(),
+        [format!("0x{:064x}", 2), format!("0x{:064x}", 3)]
+    );
+    Ok(())
+}
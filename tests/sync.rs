@@ -0,0 +1,139 @@
+mod common;
+
+use common::{block, receipt, serve_with};
+use ethrs::provider::Provider;
+use ethrs::sync::BlockFetcher;
+use serde_json::json;
+
+use std::error::Error;
+
+///Returns block `number` with one transaction, with a hash starting with `0xb` for the canonical block or with `0xf`
+///for one that is reorganized out.
+fn block_with_tx(number: u64, prefix: char) -> serde_json::Value {
+    let txhash = format!("0x{number:064x}");
+    let mut block = block(number, 1700000000 + number * 12);
+    block["hash"] = json!(format!("0x{prefix}{number:063x}"));
+    block["transactions"] = json!([{
+        "blockHash": block["hash"],
+        "blockNumber": format!("0x{number:x}"),
+        "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+        "gas": "0x5208",
+        "gasPrice": "0x3b9aca00",
+        "hash": txhash,
+        "input": "0x",
+        "nonce": format!("0x{number:x}"),
+        "to": "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+        "transactionIndex": "0x0",
+        "value": "0x1",
+        "v": "0x1",
+        "r": "0x1",
+        "s": "0x1",
+    }]);
+    block
+}
+
+///Returns the block number encoded in a block number or EIP-1898 block hash param.
+fn number(param: &serde_json::Value) -> u64 {
+    let param = param.get("blockHash").unwrap_or(param).as_str().unwrap();
+    let digits = match param.len() {
+        66 => &param[3..],
+        _ => &param[2..],
+    };
+    u64::from_str_radix(digits, 16).unwrap()
+}
+
+///Serves blocks up to 100 with one transaction each, block 50 being missing.
+fn node() -> String {
+    serve_with(|method, params| {
+        let number = number(&params[0]);
+        match method {
+            "eth_getBlockByNumber" if number > 100 || number == 50 => json!(null),
+            "eth_getBlockByNumber" => {
+                assert_eq!(params[1], true);
+                block_with_tx(number, 'b')
+            }
+            "eth_getBlockReceipts" => {
+                assert!(params[0]["blockHash"].as_str().unwrap().starts_with("0xb"));
+                json!([receipt(&format!("0x{number:064x}"), None)])
+            }
+            _ => panic!("unexpected method {method}"),
+        }
+    })
+}
+
+#[test]
+fn test_fetch_in_order() -> Result<(), Box<dyn Error>> {
+    let fetcher = BlockFetcher::new(Provider::new(&node()))
+        .with_concurrency(8)
+        .with_receipts(true);
+    let fetched = fetcher.fetch(1..=40).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        fetched
+            .iter()
+            .map(|fetched| fetched.block.number.unwrap())
+            .collect::<Vec<_>>(),
+        (1..=40).collect::<Vec<_>>()
+    );
+    for fetched in &fetched {
+        assert_eq!(fetched.receipts.len(), 1);
+        assert_eq!(
+            fetched.receipts[0].transaction_hash,
+            fetched.block.transactions[0].hash
+        );
+    }
+
+    let fetched = BlockFetcher::new(Provider::new(&node()))
+        .fetch(7..=7)
+        .next()
+        .unwrap()?;
+    assert_eq!(fetched.block.number, Some(7));
+    assert!(fetched.receipts.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_fetch_errors() -> Result<(), Box<dyn Error>> {
+    let fetcher = BlockFetcher::new(Provider::new(&node())).with_concurrency(3);
+    let mut fetched = fetcher.fetch(45..=60);
+    for number in 45..50 {
+        assert_eq!(fetched.next().unwrap()?.block.number, Some(number));
+    }
+    assert!(fetched.next().unwrap().is_err());
+    // the fetching stops at the first error
+    assert!(fetched.next().is_none());
+
+    let empty = std::ops::RangeInclusive::new(10, 9);
+    assert_eq!(fetcher.fetch(empty).count(), 0);
+    // dropping the iterator early stops the fetching
+    assert_eq!(fetcher.fetch(1..=u64::MAX).take(3).count(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_fetch_reorg() -> Result<(), Box<dyn Error>> {
+    let calls = std::sync::atomic::AtomicUsize::new(0);
+    let url = serve_with(move |method, params| match method {
+        // the block is reorganized out between the first two calls
+        "eth_getBlockByNumber" => match calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+            0 => block_with_tx(7, 'f'),
+            _ => block_with_tx(7, 'b'),
+        },
+        "eth_getBlockReceipts" if params[0]["blockHash"].as_str().unwrap().starts_with("0xf") => {
+            json!(null)
+        }
+        "eth_getBlockReceipts" => {
+            let mut receipt = receipt(&format!("0x{:064x}", 7), None);
+            receipt["blockHash"] = params[0]["blockHash"].clone();
+            json!([receipt])
+        }
+        _ => panic!("unexpected method {method}"),
+    });
+    let fetched = BlockFetcher::new(Provider::new(&url))
+        .with_receipts(true)
+        .fetch(7..=7)
+        .next()
+        .unwrap()?;
+    assert_eq!(fetched.block.hash, Some(format!("0xb{:063x}", 7).parse()?));
+    assert_eq!(Some(fetched.receipts[0].block_hash), fetched.block.hash);
+    Ok(())
+}
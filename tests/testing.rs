@@ -0,0 +1,91 @@
+mod common;
+
+use common::{rpc, serve};
+use ethrs::testing::{TestEndpoints, REQUIRE_ENDPOINTS_VAR};
+
+use std::env;
+use std::error::Error;
+use std::panic;
+use std::time::Duration;
+
+#[test]
+fn test_endpoints_from_env() {
+    let var = "ETHRS_TEST_ENDPOINTS_FROM_ENV";
+    let defaults = ["http://127.0.0.1:1"];
+    assert_eq!(TestEndpoints::from_env(var, &defaults).urls(), defaults);
+    env::set_var(var, " http://127.0.0.1:2, ,http://127.0.0.1:3");
+    assert_eq!(
+        TestEndpoints::from_env(var, &defaults).urls(),
+        ["http://127.0.0.1:2", "http://127.0.0.1:3"]
+    );
+}
+
+#[test]
+fn test_endpoints_provider() -> Result<(), Box<dyn Error>> {
+    // nothing listens on port 1
+    let down = "http://127.0.0.1:1";
+    let (wrong_chain, wrong_chain_server) = serve(vec![rpc(serde_json::json!("0x1"))]);
+    let (flaky, flaky_server) = serve(vec![
+        rpc(serde_json::json!("0xaa36a7")),
+        ("502 Bad Gateway", "<html>bad gateway</html>".to_owned()),
+        rpc(serde_json::json!("0x10")),
+    ]);
+    let endpoints = TestEndpoints::new(&[down, &wrong_chain, &flaky])
+        .with_chain_id(11155111)
+        .with_backoff(Duration::from_millis(10));
+    let provider = endpoints.provider().unwrap();
+    // the failed request is retried
+    assert_eq!(provider.block_number()?, 16);
+    wrong_chain_server.join().unwrap();
+    flaky_server.join().unwrap();
+
+    // the probe error of every endpoint is returned when none is reachable
+    let (wrong_chain, wrong_chain_server) = serve(vec![rpc(serde_json::json!("0x1"))]);
+    let errors = TestEndpoints::new(&[down, &wrong_chain])
+        .with_chain_id(11155111)
+        .provider()
+        .unwrap_err();
+    wrong_chain_server.join().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, down);
+    assert_eq!(
+        errors[1].1.to_string(),
+        format!("{wrong_chain} serves chain 1 instead of 11155111")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_endpoints_provider_or_skip() {
+    let down = TestEndpoints::new(&["http://127.0.0.1:1"]);
+    env::remove_var(REQUIRE_ENDPOINTS_VAR);
+    assert!(down.provider_or_skip().is_none());
+    env::set_var(REQUIRE_ENDPOINTS_VAR, "1");
+    let result = panic::catch_unwind(|| down.provider_or_skip());
+    env::remove_var(REQUIRE_ENDPOINTS_VAR);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_endpoints_retry() {
+    let endpoints = TestEndpoints::new(&[])
+        .with_attempts(3)
+        .with_backoff(Duration::ZERO);
+    let mut calls = 0;
+    let result = endpoints.retry(|| {
+        calls += 1;
+        match calls {
+            3 => Ok(calls),
+            _ => Err("flaky".into()),
+        }
+    });
+    assert_eq!(result.unwrap(), 3);
+    calls = 0;
+    assert!(endpoints
+        .retry(|| -> Result<(), _> {
+            calls += 1;
+            Err("down".into())
+        })
+        .is_err());
+    assert_eq!(calls, 3);
+}
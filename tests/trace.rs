@@ -0,0 +1,332 @@
+use ethrs::provider::{CallInput, Provider};
+use ethrs::trace::{
+    debug_trace_block_by_hash, debug_trace_block_by_number, debug_trace_call,
+    debug_trace_transaction, trace_block, trace_call, trace_filter, trace_transaction, Action,
+    GethTrace, PrestateFrame, TraceFilter, TraceOutput, TraceType, Tracer, TracerOptions,
+};
+use ethrs::types::{Address, BlockId, H256, U256};
+
+use std::error::Error;
+
+mod common;
+
+const TXHASH: &str = "0x14c2bae040612f036c032f7f0eccf9b3389cd8c30d810df69abdf772f7acf6d8";
+
+#[test]
+fn test_struct_logger() -> Result<(), Box<dyn Error>> {
+    let (url, server) = common::serve(vec![common::rpc(serde_json::json!({
+        "failed": false,
+        "gas": 21164,
+        "returnValue": "",
+        "structLogs": [{
+            "pc": 0,
+            "op": "PUSH1",
+            "gas": 78936,
+            "gasCost": 3,
+            "depth": 1,
+            "stack": [],
+            "storage": {}
+        }, {
+            "pc": 2,
+            "op": "SLOAD",
+            "gas": 78933,
+            "gasCost": 2100,
+            "depth": 1,
+            "stack": ["0x0"],
+            "storage": {"0000000000000000000000000000000000000000000000000000000000000000": "000000000000000000000000000000000000000000000000000000000000002a"}
+        }]
+    }))]);
+    let options = TracerOptions {
+        enable_memory: true,
+        ..Default::default()
+    };
+    let trace = debug_trace_transaction(&Provider::new(&url), TXHASH.parse()?, &options)?;
+    let frame = match trace {
+        GethTrace::Default(frame) => frame,
+        other => panic!("unexpected trace {other:?}"),
+    };
+    assert!(!frame.failed);
+    assert_eq!(frame.struct_logs[1].op, "SLOAD");
+    assert_eq!(frame.struct_logs[1].stack, Some(vec![U256::zero()]));
+    assert_eq!(
+        frame.struct_logs[1].storage.as_ref().unwrap()[&H256::ZERO],
+        "0x000000000000000000000000000000000000000000000000000000000000002a".parse()?
+    );
+
+    let request: serde_json::Value = serde_json::from_str(&server.join().unwrap()[0])?;
+    assert_eq!(request["method"], "debug_traceTransaction");
+    assert_eq!(
+        request["params"][1],
+        serde_json::json!({"enableMemory": true})
+    );
+    Ok(())
+}
+
+#[test]
+fn test_call_tracer() -> Result<(), Box<dyn Error>> {
+    let (url, server) = common::serve(vec![common::rpc(serde_json::json!({
+        "type": "CALL",
+        "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+        "to": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+        "value": "0x0",
+        "gas": "0x13880",
+        "gasUsed": "0x5a3c",
+        "input": "0xd09de08a",
+        "output": "0x08c379a0",
+        "error": "execution reverted",
+        "revertReason": "not allowed",
+        "calls": [{
+            "type": "STATICCALL",
+            "from": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+            "to": "0x0000000000000000000000000000000000000001",
+            "gas": "0xbb8",
+            "gasUsed": "0xbb8",
+            "input": "0x"
+        }]
+    }))]);
+    let options = TracerOptions::call_tracer(false, true);
+    let frame = match debug_trace_transaction(&Provider::new(&url), TXHASH.parse()?, &options)? {
+        GethTrace::Call(frame) => frame,
+        other => panic!("unexpected trace {other:?}"),
+    };
+    assert_eq!(frame.kind, "CALL");
+    assert_eq!(frame.revert_reason.as_deref(), Some("not allowed"));
+    assert_eq!(frame.calls.len(), 1);
+    assert_eq!(frame.calls[0].kind, "STATICCALL");
+    assert_eq!(frame.calls[0].value, None);
+    assert!(frame.calls[0].calls.is_empty());
+
+    let request: serde_json::Value = serde_json::from_str(&server.join().unwrap()[0])?;
+    assert_eq!(
+        request["params"][1],
+        serde_json::json!({"tracer": "callTracer", "tracerConfig": {"onlyTopCall": false, "withLog": true}})
+    );
+    Ok(())
+}
+
+#[test]
+fn test_prestate_tracer() -> Result<(), Box<dyn Error>> {
+    let account = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    let (url, _) = common::serve(vec![
+        common::rpc(serde_json::json!({
+            account: {"balance": "0xde0b6b3a7640000", "nonce": 1}
+        })),
+        common::rpc(serde_json::json!({
+            "pre": {account: {"balance": "0xde0b6b3a7640000", "nonce": 1}},
+            "post": {account: {"balance": "0x0", "nonce": 2}}
+        })),
+        common::rpc(serde_json::json!({"custom": true})),
+    ]);
+    let provider = Provider::new(&url);
+    match debug_trace_transaction(
+        &provider,
+        TXHASH.parse()?,
+        &TracerOptions::prestate_tracer(false),
+    )? {
+        GethTrace::Prestate(PrestateFrame::Default(accounts)) => {
+            assert_eq!(accounts[&account.parse()?].nonce, Some(1))
+        }
+        other => panic!("unexpected trace {other:?}"),
+    }
+    match debug_trace_transaction(
+        &provider,
+        TXHASH.parse()?,
+        &TracerOptions::prestate_tracer(true),
+    )? {
+        GethTrace::Prestate(PrestateFrame::Diff { pre, post }) => {
+            assert_eq!(pre[&account.parse()?].balance, Some(U256::exp10(18)));
+            assert_eq!(post[&account.parse()?].nonce, Some(2));
+        }
+        other => panic!("unexpected trace {other:?}"),
+    }
+    let options = TracerOptions {
+        tracer: Some(Tracer::Custom("4byteTracer".to_owned())),
+        ..Default::default()
+    };
+    assert_eq!(
+        debug_trace_transaction(&provider, TXHASH.parse()?, &options)?,
+        GethTrace::Custom(serde_json::json!({"custom": true}))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_trace_call_and_block() -> Result<(), Box<dyn Error>> {
+    let frame = serde_json::json!({
+        "type": "CALL",
+        "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+        "to": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+        "gas": "0x13880",
+        "gasUsed": "0xa9da",
+        "input": "0xd09de08a"
+    });
+    let (url, server) = common::serve(vec![
+        common::rpc(frame.clone()),
+        common::rpc(serde_json::json!([
+            {"txHash": TXHASH, "result": frame},
+            {"txHash": TXHASH, "error": "execution timeout"}
+        ])),
+        common::rpc(serde_json::json!([])),
+    ]);
+    let provider = Provider::new(&url);
+    let options = TracerOptions::call_tracer(true, false);
+    let tx = CallInput {
+        from: None,
+        to: "0x5fbdb2315678afecb367f032d93f642f64180aa3".parse()?,
+        gas: None,
+        gas_price: None,
+        value: None,
+        data: Some("0xd09de08a".to_owned()),
+    };
+    match debug_trace_call(&provider, tx, Some(BlockId::Number(1)), &options)? {
+        GethTrace::Call(frame) => assert_eq!(frame.gas_used, U256::from(0xa9da)),
+        other => panic!("unexpected trace {other:?}"),
+    }
+
+    let traces = debug_trace_block_by_number(&provider, BlockId::Latest, &options)?;
+    assert_eq!(traces.len(), 2);
+    assert_eq!(traces[0].tx_hash, Some(TXHASH.parse()?));
+    assert!(matches!(traces[0].result, Some(GethTrace::Call(_))));
+    assert_eq!(traces[1].result, None);
+    assert_eq!(traces[1].error.as_deref(), Some("execution timeout"));
+    assert!(debug_trace_block_by_hash(&provider, H256::ZERO, &options)?.is_empty());
+
+    let requests: Vec<serde_json::Value> = server
+        .join()
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_str(request).unwrap())
+        .collect();
+    assert_eq!(requests[0]["method"], "debug_traceCall");
+    assert_eq!(requests[0]["params"][1], "0x1");
+    assert_eq!(requests[1]["method"], "debug_traceBlockByNumber");
+    assert_eq!(requests[1]["params"][0], "latest");
+    assert_eq!(requests[2]["method"], "debug_traceBlockByHash");
+    Ok(())
+}
+
+#[test]
+fn test_parity_traces() -> Result<(), Box<dyn Error>> {
+    let sender = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    let contract = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+    let located = serde_json::json!({
+        "blockHash": "0x6b7b2f1ad3b6f8a0e1c7d2e8f4b5a9c3d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6",
+        "blockNumber": 17000000,
+        "transactionHash": TXHASH,
+        "transactionPosition": 3,
+    });
+    let mut call = serde_json::json!({
+        "type": "call",
+        "action": {"from": sender, "to": contract, "value": "0x0", "gas": "0x13880", "input": "0xd09de08a", "callType": "call"},
+        "result": {"gasUsed": "0x5a3c", "output": "0x"},
+        "subtraces": 1,
+        "traceAddress": [],
+    });
+    let mut create = serde_json::json!({
+        "type": "create",
+        "action": {"from": contract, "value": "0x0", "gas": "0xbb80", "init": "0x6080"},
+        "result": {"gasUsed": "0x7d0", "code": "0x60", "address": "0x0000000000000000000000000000000000000001"},
+        "subtraces": 0,
+        "traceAddress": [0],
+    });
+    let mut reverted = serde_json::json!({
+        "type": "call",
+        "action": {"from": sender, "to": contract, "value": "0x0", "gas": "0x5208", "input": "0x", "callType": "staticcall"},
+        "error": "Reverted",
+        "subtraces": 0,
+        "traceAddress": [],
+    });
+    let mut selfdestruct = serde_json::json!({
+        "type": "suicide",
+        "action": {"address": contract, "refundAddress": sender, "balance": "0x1"},
+        "result": null,
+        "subtraces": 0,
+        "traceAddress": [1],
+    });
+    let mut reward = serde_json::json!({
+        "type": "reward",
+        "action": {"author": sender, "value": "0x1bc16d674ec80000", "rewardType": "block"},
+        "result": null,
+        "subtraces": 0,
+        "traceAddress": [],
+    });
+    for trace in [
+        &mut call,
+        &mut create,
+        &mut reverted,
+        &mut selfdestruct,
+        &mut reward,
+    ] {
+        trace
+            .as_object_mut()
+            .unwrap()
+            .extend(located.as_object().unwrap().clone());
+    }
+    let (url, server) = common::serve(vec![
+        common::rpc(serde_json::json!([call, create, selfdestruct])),
+        common::rpc(serde_json::json!([reverted, reward])),
+        common::rpc(serde_json::Value::Null),
+        common::rpc(serde_json::json!([call])),
+        common::rpc(
+            serde_json::json!({"output": "0x2a", "trace": [call], "vmTrace": null, "stateDiff": null}),
+        ),
+    ]);
+    let provider = Provider::new(&url);
+
+    let traces = trace_transaction(&provider, TXHASH.parse()?)?.unwrap();
+    assert!(matches!(&traces[0].action, Action::Call(call) if call.call_type == "call"));
+    assert!(matches!(traces[0].result, Some(TraceOutput::Call(_))));
+    match &traces[1].result {
+        Some(TraceOutput::Create(output)) => assert_eq!(output.code.as_ref(), &[0x60]),
+        other => panic!("unexpected result {other:?}"),
+    }
+    assert_eq!(traces[1].trace_address, vec![0]);
+    assert!(matches!(traces[2].action, Action::Suicide(_)));
+    assert_eq!(traces[2].block_number, Some(17000000));
+
+    let traces = trace_block(&provider, BlockId::Number(17000000))?.unwrap();
+    assert_eq!(traces[0].error.as_deref(), Some("Reverted"));
+    assert_eq!(traces[0].result, None);
+    assert!(matches!(&traces[1].action, Action::Reward(reward) if reward.reward_type == "block"));
+    assert!(trace_block(&provider, BlockId::Number(0))?.is_none());
+
+    let filter = TraceFilter {
+        from_block: Some(BlockId::Number(17000000)),
+        to_address: vec![contract.parse()?],
+        count: Some(10),
+        ..Default::default()
+    };
+    assert_eq!(trace_filter(&provider, &filter)?.len(), 1);
+    let tx = CallInput {
+        from: None,
+        to: contract.parse()?,
+        gas: None,
+        gas_price: None,
+        value: None,
+        data: Some("0xd09de08a".to_owned()),
+    };
+    let results = trace_call(
+        &provider,
+        tx,
+        &[TraceType::Trace, TraceType::StateDiff],
+        None,
+    )?;
+    assert_eq!(results.output.as_ref(), &[0x2a]);
+    assert_eq!(results.trace.len(), 1);
+
+    let requests: Vec<serde_json::Value> = server
+        .join()
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_str(request).unwrap())
+        .collect();
+    assert_eq!(
+        requests[3]["params"][0],
+        serde_json::json!({"fromBlock": "0x1036640", "toAddress": [contract.parse::<Address>()?], "count": 10})
+    );
+    assert_eq!(
+        requests[4]["params"][1],
+        serde_json::json!(["trace", "stateDiff"])
+    );
+    Ok(())
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::{rpc, serve};
+use ethrs::provider::Provider;
+use serde_json::json;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+///Records the fields of every span and event as strings.
+#[derive(Default)]
+struct Fields(BTreeMap<String, String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_owned(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.to_owned());
+    }
+}
+
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<BTreeMap<String, String>>>>,
+    events: Arc<Mutex<Vec<BTreeMap<String, String>>>>,
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields::default();
+        span.record(&mut fields);
+        fields
+            .0
+            .insert("name".to_owned(), span.metadata().name().to_owned());
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(fields.0);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        fields
+            .0
+            .insert("level".to_owned(), event.metadata().level().to_string());
+        self.events.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_tracing_spans() {
+    let (url, _) = serve(vec![
+        rpc(json!("0x10")),
+        (
+            "200 OK",
+            json!({"jsonrpc": "2.0", "id": 1, "error": {"code": 3, "message": "execution reverted"}})
+                .to_string(),
+        ),
+    ]);
+    let closed = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let provider = Provider::new(&format!("{url}/?apikey=secret"));
+        assert_eq!(provider.block_number().unwrap(), 16);
+        assert!(provider.chain_id().is_err());
+        assert!(Provider::new(&closed).block_number().is_err());
+    });
+
+    let spans = recorder.spans.lock().unwrap();
+    assert_eq!(spans.len(), 3);
+    assert_eq!(spans[0]["name"], "rpc");
+    assert_eq!(spans[0]["method"], "eth_blockNumber");
+    assert!(!spans[0]["endpoint"].contains("secret"));
+    assert_eq!(spans[1]["method"], "eth_chainId");
+    assert_eq!(spans[2]["endpoint"], format!("{closed}/"));
+
+    let events = recorder.events.lock().unwrap();
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        events[0]["response_size"],
+        r#"{"id":1,"jsonrpc":"2.0","result":"0x10"}"#.len().to_string()
+    );
+    assert!(events[0].contains_key("duration_ms"));
+    assert_eq!(events[1]["error"], "execution reverted");
+    assert_eq!(events[2]["level"], "WARN");
+    assert!(events[2].contains_key("error"));
+}
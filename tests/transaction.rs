@@ -0,0 +1,415 @@
+use ethrs::provider::{AccessListItem, Transaction};
+use ethrs::rlp::RlpItem;
+use ethrs::signature::Signature;
+use ethrs::transaction::{
+    Authorization, Eip1559Transaction, Eip2930Transaction, Eip4844Transaction, Eip7702Transaction,
+    LegacyTransaction, TypedTransaction,
+};
+use ethrs::types::{Address, Bytes, H256, U256};
+use ethrs::utils::keccak256;
+use ethrs::wallet::Wallet;
+use k256::ecdsa::SigningKey;
+use serde_json::json;
+
+use std::error::Error;
+
+fn recipient() -> Address {
+    Address([0x35; 20])
+}
+
+// the example transaction of EIP-155
+fn eip155() -> (TypedTransaction, Signature) {
+    let transaction = TypedTransaction::Legacy(LegacyTransaction {
+        chain_id: Some(1),
+        nonce: 9,
+        gas_price: U256::from(20_000_000_000u64),
+        gas_limit: 21000,
+        to: Some(recipient()),
+        value: U256::from(1_000_000_000_000_000_000u64),
+        input: Bytes::default(),
+    });
+    let signature = Signature {
+        r: U256::from_str_radix(
+            "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+            16,
+        )
+        .unwrap(),
+        s: U256::from_str_radix(
+            "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+            16,
+        )
+        .unwrap(),
+        v: 37,
+    };
+    (transaction, signature)
+}
+
+#[test]
+fn test_legacy_transaction() -> Result<(), Box<dyn Error>> {
+    let (transaction, signature) = eip155();
+    assert_eq!(transaction.tx_type(), 0);
+    assert_eq!(
+        hex::encode(transaction.encode_unsigned()),
+        "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+    );
+    assert_eq!(
+        transaction.signing_hash(),
+        "0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53".parse()?
+    );
+    let signed = transaction.encode_signed(&signature)?;
+    assert_eq!(
+        hex::encode(&signed),
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a0\
+         28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b\
+         3800ccf555c9f3dc64214b297fb1966a3b6d83"
+    );
+    assert_eq!(transaction.hash(&signature)?, H256(keccak256(&signed)));
+    // the v of the signature is re-encoded for the transaction
+    assert_eq!(
+        transaction.encode_signed(&Signature { v: 0, ..signature })?,
+        signed
+    );
+    Ok(())
+}
+
+#[test]
+fn test_legacy_transaction_without_chain_id() {
+    let transaction = TypedTransaction::Legacy(LegacyTransaction {
+        to: Some(recipient()),
+        ..Default::default()
+    });
+    assert_eq!(transaction.chain_id(), None);
+    assert_eq!(
+        hex::encode(transaction.encode_unsigned()),
+        format!("da80808094{}8080", "35".repeat(20))
+    );
+    let signature = Signature {
+        r: U256::one(),
+        s: U256::from(2),
+        v: 1,
+    };
+    assert_eq!(
+        hex::encode(transaction.encode_signed(&signature).unwrap()),
+        format!("dd80808094{}80801c0102", "35".repeat(20))
+    );
+}
+
+#[test]
+fn test_legacy_transaction_chain_id_overflow() {
+    let signature = Signature {
+        r: U256::one(),
+        s: U256::from(2),
+        v: 1,
+    };
+    let transaction = |chain_id| {
+        TypedTransaction::Legacy(LegacyTransaction {
+            chain_id: Some(chain_id),
+            ..Default::default()
+        })
+    };
+    // the largest chain id whose v fits in 64 bits with a y parity of 1
+    let largest = (u64::MAX - 36) / 2;
+    assert!(transaction(largest).encode_signed(&signature).is_ok());
+    for chain_id in [largest + 1, u64::MAX] {
+        let transaction = transaction(chain_id);
+        assert_eq!(
+            transaction
+                .encode_signed(&signature)
+                .unwrap_err()
+                .to_string(),
+            format!("Chain id {chain_id} is too large for EIP-155")
+        );
+        assert!(transaction.hash(&signature).is_err());
+        assert!(transaction.into_rpc(&signature, Address::ZERO).is_err());
+    }
+}
+
+#[test]
+fn test_eip1559_transaction() {
+    let transaction = TypedTransaction::Eip1559(Eip1559Transaction {
+        chain_id: 1,
+        max_priority_fee_per_gas: U256::one(),
+        max_fee_per_gas: U256::from(2),
+        gas_limit: 21000,
+        to: Some(recipient()),
+        ..Default::default()
+    });
+    assert_eq!(transaction.tx_type(), 2);
+    assert_eq!(
+        hex::encode(transaction.encode_unsigned()),
+        format!("02df0180010282520894{}8080c0", "35".repeat(20))
+    );
+    let signature = Signature {
+        r: U256::one(),
+        s: U256::from(2),
+        v: 28,
+    };
+    assert_eq!(
+        hex::encode(transaction.encode_signed(&signature).unwrap()),
+        format!("02e20180010282520894{}8080c0010102", "35".repeat(20))
+    );
+}
+
+#[test]
+fn test_authorization_signing_hash() {
+    let authorization = Authorization {
+        chain_id: U256::one(),
+        address: recipient(),
+        nonce: 0,
+        ..Default::default()
+    };
+    let payload = hex::decode(format!("05d70194{}80", "35".repeat(20))).unwrap();
+    assert_eq!(authorization.signing_hash(), H256(keccak256(&payload)));
+}
+
+fn typed_transactions() -> Vec<TypedTransaction> {
+    let access_list = vec![AccessListItem {
+        address: recipient(),
+        storage_keys: vec![H256([0x01; 32])],
+    }];
+    vec![
+        eip155().0,
+        Eip2930Transaction {
+            chain_id: 11155111,
+            nonce: 1,
+            gas_price: U256::from(1_000_000_000u64),
+            gas_limit: 30000,
+            to: None,
+            value: U256::zero(),
+            input: Bytes(vec![0x60, 0x00]),
+            access_list: access_list.clone(),
+        }
+        .into(),
+        Eip1559Transaction {
+            chain_id: 1,
+            nonce: 2,
+            max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+            max_fee_per_gas: U256::from(30_000_000_000u64),
+            gas_limit: 50000,
+            to: Some(recipient()),
+            value: U256::from(7),
+            input: Bytes(vec![0xde, 0xad]),
+            access_list: access_list.clone(),
+        }
+        .into(),
+        Eip4844Transaction {
+            chain_id: 1,
+            nonce: 3,
+            max_priority_fee_per_gas: U256::one(),
+            max_fee_per_gas: U256::from(2),
+            gas_limit: 21000,
+            to: recipient(),
+            max_fee_per_blob_gas: U256::from(3),
+            blob_versioned_hashes: vec![H256([0x01; 32])],
+            ..Default::default()
+        }
+        .into(),
+        Eip7702Transaction {
+            chain_id: 1,
+            nonce: 4,
+            max_priority_fee_per_gas: U256::one(),
+            max_fee_per_gas: U256::from(2),
+            gas_limit: 60000,
+            to: recipient(),
+            authorization_list: vec![Authorization {
+                chain_id: U256::zero(),
+                address: recipient(),
+                nonce: 5,
+                y_parity: 1,
+                r: U256::from(6),
+                s: U256::from(7),
+            }],
+            ..Default::default()
+        }
+        .into(),
+    ]
+}
+
+#[test]
+fn test_rpc_transaction_round_trip() -> Result<(), Box<dyn Error>> {
+    let from = Address([0x11; 20]);
+    for (i, transaction) in typed_transactions().into_iter().enumerate() {
+        let signature = match transaction.tx_type() {
+            0 => eip155().1,
+            _ => Signature {
+                r: U256::from(8),
+                s: U256::from(9),
+                v: 1,
+            },
+        };
+        let hash = transaction.hash(&signature)?;
+        let rpc = transaction.clone().into_rpc(&signature, from)?;
+        assert_eq!(rpc.hash, hash);
+        assert_eq!(rpc.from, from);
+        let value = serde_json::to_value(&rpc)?;
+        assert_eq!(value["type"], json!(format!("0x{i}")));
+        let rpc: Transaction = serde_json::from_value(value)?;
+        assert_eq!(TypedTransaction::try_from(&rpc)?, transaction);
+        assert_eq!(Signature::try_from(&rpc)?, signature);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rpc_transaction_fields() -> Result<(), Box<dyn Error>> {
+    let mut value = json!({
+        "blockHash": null,
+        "blockNumber": null,
+        "from": "0x1111111111111111111111111111111111111111",
+        "gas": "0x5208",
+        "gasPrice": "0x2",
+        "hash": format!("0x{}", "00".repeat(32)),
+        "input": "0x",
+        "nonce": "0x0",
+        "to": "0x3535353535353535353535353535353535353535",
+        "transactionIndex": null,
+        "value": "0x0",
+        "v": "0x25",
+        "r": "0x1",
+        "s": "0x2",
+    });
+    // legacy transactions without a type read their chain id from v
+    let transaction: Transaction = serde_json::from_value(value.clone())?;
+    assert_eq!(
+        TypedTransaction::try_from(&transaction)?.chain_id(),
+        Some(1)
+    );
+    value["type"] = json!("0x2");
+    value["chainId"] = json!("0x1");
+    value["maxPriorityFeePerGas"] = json!("0x1");
+    let transaction: Transaction = serde_json::from_value(value.clone())?;
+    assert_eq!(
+        TypedTransaction::try_from(&transaction)
+            .unwrap_err()
+            .to_string(),
+        "Missing maxFeePerGas in type 2 transaction"
+    );
+    value["type"] = json!("0x7e");
+    let transaction: Transaction = serde_json::from_value(value)?;
+    assert_eq!(
+        TypedTransaction::try_from(&transaction)
+            .unwrap_err()
+            .to_string(),
+        "Unsupported transaction type 0x7e"
+    );
+    Ok(())
+}
+
+fn sign(transaction: &TypedTransaction) -> Signature {
+    let key = SigningKey::from_slice(&[0x46; 32]).unwrap();
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(&transaction.signing_hash().0)
+        .unwrap();
+    Signature {
+        r: U256::from_big_endian(&signature.r().to_bytes()),
+        s: U256::from_big_endian(&signature.s().to_bytes()),
+        v: u64::from(recovery_id.to_byte()),
+    }
+}
+
+#[test]
+fn test_decode_raw_transactions() -> Result<(), Box<dyn Error>> {
+    let sender = Wallet::from_private_key(&[0x46; 32])?.address();
+    let (transaction, signature) = eip155();
+    let signed = TypedTransaction::decode(&format!(
+        "0x{}",
+        hex::encode(transaction.encode_signed(&signature)?)
+    ))?;
+    assert_eq!(signed.transaction, transaction);
+    assert_eq!(signed.signature, signature);
+    assert_eq!(signed.from, sender);
+    for transaction in typed_transactions().into_iter().skip(1) {
+        let signature = sign(&transaction);
+        let raw = transaction.encode_signed(&signature)?;
+        let signed = TypedTransaction::decode_bytes(&raw)?;
+        assert_eq!(signed.transaction, transaction);
+        assert_eq!(signed.signature, signature);
+        assert_eq!(signed.from, sender);
+        assert_eq!(signed.hash, H256(keccak256(&raw)));
+        assert_eq!(signed.encode()?, raw);
+        assert_eq!(signed.into_rpc()?.from, sender);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_decode_blob_transaction_with_blobs() -> Result<(), Box<dyn Error>> {
+    let transaction = typed_transactions().remove(3);
+    let signature = sign(&transaction);
+    let raw = transaction.encode_signed(&signature)?;
+    let blobs = RlpItem::List(vec![RlpItem::String(vec![0; 64])]);
+    let network = [
+        vec![3],
+        RlpItem::List(vec![
+            RlpItem::decode(&raw[1..])?,
+            blobs.clone(),
+            blobs.clone(),
+            blobs,
+        ])
+        .encode(),
+    ]
+    .concat();
+    let signed = TypedTransaction::decode_bytes(&network)?;
+    assert_eq!(signed.transaction, transaction);
+    assert_eq!(signed.hash, H256(keccak256(&raw)));
+    Ok(())
+}
+
+#[test]
+fn test_decode_invalid_raw_transactions() {
+    let transaction = typed_transactions().remove(2);
+    let signature = sign(&transaction);
+    let raw = transaction.encode_signed(&signature).unwrap();
+    let mut fields = RlpItem::decode(&raw[1..])
+        .unwrap()
+        .as_list()
+        .unwrap()
+        .to_vec();
+    fields[9] = RlpItem::String(vec![2]);
+    for (raw, error) in [
+        (vec![], "Empty raw transaction".to_owned()),
+        (vec![0x80], "Invalid raw transaction prefix 0x80".to_owned()),
+        (
+            [&[0x05], &raw[1..]].concat(),
+            "Unsupported transaction type 0x5".to_owned(),
+        ),
+        (
+            [vec![2], RlpItem::List(fields).encode()].concat(),
+            "Invalid y parity: 2".to_owned(),
+        ),
+    ] {
+        assert_eq!(
+            TypedTransaction::decode_bytes(&raw)
+                .unwrap_err()
+                .to_string(),
+            error
+        );
+    }
+    assert!(TypedTransaction::decode_bytes(&[&raw[..], &[0x00]].concat()).is_err());
+    assert!(TypedTransaction::decode("0xzz").is_err());
+}
+
+#[test]
+fn test_decode_deeply_nested_raw_transactions() {
+    // a 70000 byte string wrapped in 200000 lists, each with a 3 byte long form length
+    let mut length = 70_000u32;
+    let mut headers = vec![[&[0xba], &length.to_be_bytes()[1..]].concat()];
+    for _ in 0..200_000 {
+        length += 4;
+        headers.push([&[0xfa], &length.to_be_bytes()[1..]].concat());
+    }
+    let nested = headers
+        .into_iter()
+        .rev()
+        .flatten()
+        .chain(std::iter::repeat(0x00).take(70_000))
+        .collect::<Vec<_>>();
+    for raw in [nested.clone(), [&[0x02], &nested[..]].concat()] {
+        assert_eq!(
+            TypedTransaction::decode_bytes(&raw)
+                .unwrap_err()
+                .to_string(),
+            "RLP nesting too deep"
+        );
+    }
+}
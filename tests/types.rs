@@ -0,0 +1,230 @@
+use ethrs::types::{
+    format_u256, parse_u256, Address, BlockId, Bytes, FromHexError, Wei, H256, U256,
+};
+
+use std::error::Error;
+
+#[test]
+fn test_address_checksum() -> Result<(), Box<dyn Error>> {
+    for checksummed in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        let address: Address = checksummed.to_lowercase().parse()?;
+        assert_eq!(address.to_string(), checksummed);
+        assert_eq!(checksummed.parse::<Address>()?, address);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_address_parse_errors() {
+    assert_eq!(
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".parse::<Address>(),
+        Err(FromHexError::InvalidChecksum)
+    );
+    assert_eq!(
+        "0x6c4925c897c45d377d8fb3ef59df7e0cf97604fc85b909bb806818368fdc6b07".parse::<Address>(),
+        Err(FromHexError::InvalidLength {
+            expected: 40,
+            found: 64
+        })
+    );
+    assert_eq!(
+        "0xzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz".parse::<Address>(),
+        Err(FromHexError::InvalidCharacter)
+    );
+    assert!("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"
+        .parse::<Address>()
+        .is_ok());
+}
+
+#[test]
+fn test_address_from_checksummed() -> Result<(), Box<dyn Error>> {
+    let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    assert_eq!(
+        Address::from_checksummed(checksummed)?,
+        checksummed.parse()?
+    );
+    assert_eq!(
+        Address::from_checksummed(&checksummed[2..])?,
+        checksummed.parse()?
+    );
+    for unchecked in [
+        checksummed.to_lowercase(),
+        checksummed.to_uppercase().replace("0X", "0x"),
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".to_owned(),
+    ] {
+        assert_eq!(
+            Address::from_checksummed(&unchecked),
+            Err(FromHexError::InvalidChecksum)
+        );
+    }
+    // addresses without letters have no casing to check
+    assert_eq!(
+        Address::from_checksummed("0x0000000000000000000000000000000000000000")?,
+        Address::ZERO
+    );
+    assert_eq!(
+        Address::from_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"),
+        Err(FromHexError::InvalidLength {
+            expected: 40,
+            found: 38
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_address_serde() -> Result<(), Box<dyn Error>> {
+    let address: Address = serde_json::from_str("\"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\"")?;
+    assert_eq!(
+        serde_json::to_string(&address)?,
+        "\"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\""
+    );
+    assert_eq!(
+        format!("{address:#x}"),
+        "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_h256() -> Result<(), Box<dyn Error>> {
+    let hash: H256 =
+        "0x6C4925C897C45D377D8FB3EF59DF7E0CF97604FC85B909BB806818368FDC6B07".parse()?;
+    assert_eq!(
+        hash.to_string(),
+        "0x6c4925c897c45d377d8fb3ef59df7e0cf97604fc85b909bb806818368fdc6b07"
+    );
+    assert_eq!(
+        serde_json::from_str::<H256>(&serde_json::to_string(&hash)?)?,
+        hash
+    );
+    assert_eq!(
+        "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse::<H256>(),
+        Err(FromHexError::InvalidLength {
+            expected: 64,
+            found: 40
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_block_id_serde() -> Result<(), Box<dyn Error>> {
+    assert_eq!(serde_json::to_string(&BlockId::default())?, "\"latest\"");
+    assert_eq!(serde_json::to_string(&BlockId::Pending)?, "\"pending\"");
+    assert_eq!(
+        serde_json::to_string(&BlockId::from(3347000))?,
+        "\"0x331238\""
+    );
+    assert_eq!(
+        serde_json::to_string(&BlockId::from(H256::ZERO))?,
+        "{\"blockHash\":\"0x0000000000000000000000000000000000000000000000000000000000000000\"}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_block_id_try_from() -> Result<(), Box<dyn Error>> {
+    assert_eq!(BlockId::try_from(3347000u128)?, BlockId::Number(3347000));
+    assert_eq!(
+        BlockId::try_from(U256::from(u64::MAX))?,
+        BlockId::Number(u64::MAX)
+    );
+    assert!(BlockId::try_from(u64::MAX as u128 + 1).is_err());
+    assert!(BlockId::try_from(U256::from(u64::MAX) + 1).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_bytes() -> Result<(), Box<dyn Error>> {
+    let bytes: Bytes = serde_json::from_str("\"0xDEADbeef\"")?;
+    assert_eq!(bytes, Bytes(vec![0xde, 0xad, 0xbe, 0xef]));
+    assert_eq!(serde_json::to_string(&bytes)?, "\"0xdeadbeef\"");
+    assert_eq!("0x".parse::<Bytes>()?, Bytes::default());
+    assert_eq!(
+        "0xabc".parse::<Bytes>(),
+        Err(FromHexError::InvalidLength {
+            expected: 4,
+            found: 3
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_wei() {
+    assert_eq!(Wei::ZERO.to_ether(), "0");
+    assert_eq!(Wei::from(1u128).to_ether(), "0.000000000000000001");
+    assert_eq!(Wei::from_ether(1234).to_ether(), "1234");
+    assert_eq!(Wei::from_gwei(30).to_gwei(), "30");
+    assert_eq!(Wei::from(12_345_678u128).format_units(6), "12.345678");
+    assert_eq!(
+        Wei::from_ether(1).checked_add(Wei::from_gwei(1)),
+        Some(Wei::from(1_000_000_001_000_000_000u128))
+    );
+    assert_eq!(Wei::ZERO.checked_sub(Wei::from(1u128)), None);
+    assert_eq!(
+        serde_json::to_string(&Wei::from_gwei(1)).unwrap(),
+        "\"0x3b9aca00\""
+    );
+    assert_eq!(
+        serde_json::from_str::<Wei>("\"0x3b9aca00\"").unwrap(),
+        Wei::from_gwei(1)
+    );
+    assert_eq!(U256::from(Wei::from_gwei(1)), U256::exp10(9));
+}
+
+#[test]
+fn test_u256_strings() -> Result<(), Box<dyn Error>> {
+    assert_eq!(parse_u256("0")?, U256::zero());
+    assert_eq!(parse_u256("1000000")?, U256::from(1_000_000));
+    assert_eq!(parse_u256("0xF4240")?, U256::from(1_000_000));
+    assert_eq!(parse_u256(&U256::MAX.to_string())?, U256::MAX);
+    for invalid in ["", "0x", "-1", "1.5", "0xg", "1_000", " 1"] {
+        assert!(parse_u256(invalid).is_err(), "{invalid}");
+    }
+    assert!(parse_u256(&format!("{}0", U256::MAX)).is_err());
+
+    assert_eq!(format_u256(U256::zero(), ','), "0");
+    assert_eq!(format_u256(U256::from(100), ','), "100");
+    assert_eq!(format_u256(U256::from(1000), ','), "1,000");
+    assert_eq!(format_u256(U256::from(123_456_789), '_'), "123_456_789");
+    assert_eq!(
+        format_u256(U256::MAX, ',').replace(',', ""),
+        U256::MAX.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_u256_serde() -> Result<(), Box<dyn Error>> {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Amounts {
+        #[serde(with = "ethrs::types::u256_quantity")]
+        quantity: U256,
+        #[serde(with = "ethrs::types::u256_decimal")]
+        decimal: U256,
+    }
+
+    for json in [
+        r#"{"quantity": "0xf4240", "decimal": "0xf4240"}"#,
+        r#"{"quantity": "1000000", "decimal": "1000000"}"#,
+        r#"{"quantity": 1000000, "decimal": 1000000}"#,
+    ] {
+        let amounts: Amounts = serde_json::from_str(json)?;
+        assert_eq!(amounts.quantity, U256::from(1_000_000));
+        assert_eq!(amounts.decimal, U256::from(1_000_000));
+        assert_eq!(
+            serde_json::to_value(&amounts)?,
+            serde_json::json!({"quantity": "0xf4240", "decimal": "1000000"})
+        );
+    }
+    assert!(serde_json::from_str::<Amounts>(r#"{"quantity": -1, "decimal": "1"}"#).is_err());
+    assert!(serde_json::from_str::<Amounts>(r#"{"quantity": 1.5, "decimal": "1"}"#).is_err());
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use ethrs::types::{Wei, U256};
+use ethrs::units::{format_ether, format_gwei, format_units, parse_ether, parse_gwei, parse_units};
+
+use std::error::Error;
+
+#[test]
+fn test_parse_units() -> Result<(), Box<dyn Error>> {
+    let ether = U256::exp10(18);
+    assert_eq!(parse_ether("1")?, ether);
+    assert_eq!(parse_ether("1.5")?, ether * 3 / 2);
+    assert_eq!(parse_ether("0.1")?, U256::exp10(17));
+    assert_eq!(parse_ether(".1")?, U256::exp10(17));
+    assert_eq!(parse_ether("1.")?, ether);
+    assert_eq!(parse_ether("0.000000000000000001")?, U256::one());
+    assert_eq!(parse_ether("1.100000000000000000000")?, ether * 11 / 10);
+    assert_eq!(parse_ether("000")?, U256::zero());
+    assert_eq!(parse_gwei("30.5")?, U256::from(30_500_000_000u64));
+    assert_eq!(parse_units("123", 0)?, U256::from(123));
+    // floating point would round this amount
+    assert_eq!(
+        parse_ether("0.3")?,
+        parse_ether("0.1")? + parse_ether("0.2")?
+    );
+
+    for invalid in ["", ".", "-1", "1e18", "1.2.3", " 1", "0x1", "1,5"] {
+        assert!(parse_ether(invalid).is_err(), "{invalid}");
+    }
+    assert_eq!(
+        parse_ether("0.0000000000000000001")
+            .unwrap_err()
+            .to_string(),
+        "0.0000000000000000001 has more than 18 decimals"
+    );
+    assert!(parse_units("1.5", 0).is_err());
+    assert!(parse_ether(&U256::MAX.to_string()).is_err());
+    assert_eq!(parse_units(&U256::MAX.to_string(), 0)?, U256::MAX);
+    Ok(())
+}
+
+#[test]
+fn test_format_units() -> Result<(), Box<dyn Error>> {
+    assert_eq!(format_ether(U256::exp10(18)), "1");
+    assert_eq!(format_ether(U256::exp10(18) * 3 / 2), "1.5");
+    assert_eq!(format_ether(U256::one()), "0.000000000000000001");
+    assert_eq!(format_ether(U256::zero()), "0");
+    assert_eq!(format_gwei(U256::from(30_500_000_000u64)), "30.5");
+    assert_eq!(format_units(U256::from(1234), 2), "12.34");
+    assert_eq!(format_units(U256::MAX, 0), U256::MAX.to_string());
+    assert_eq!(Wei::from_gwei(1).to_ether(), "0.000000001");
+
+    for amount in [U256::one(), U256::from(123_456_789), U256::MAX] {
+        for decimals in [0, 6, 18, 77, 80] {
+            assert_eq!(
+                parse_units(&format_units(amount, decimals), decimals)?,
+                amount
+            );
+        }
+    }
+    Ok(())
+}
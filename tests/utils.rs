@@ -0,0 +1,103 @@
+use ethrs::abi::Abi;
+use ethrs::types::H256;
+use ethrs::utils::{
+    event_topic, function_selector, is_checksummed, keccak256, to_checksum_address,
+};
+
+use std::error::Error;
+
+#[test]
+fn test_keccak256() {
+    assert_eq!(
+        hex::encode(keccak256(b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_eq!(
+        hex::encode(keccak256(b"hello world")),
+        "47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad"
+    );
+}
+
+#[test]
+fn test_function_selector() {
+    assert_eq!(
+        function_selector("transfer(address,uint256)"),
+        [0xa9, 0x05, 0x9c, 0xbb]
+    );
+    assert_eq!(
+        function_selector("balanceOf(address)"),
+        [0x70, 0xa0, 0x82, 0x31]
+    );
+    assert_eq!(
+        function_selector(" approve( address, uint256 ) "),
+        [0x09, 0x5e, 0xa7, 0xb3]
+    );
+}
+
+#[test]
+fn test_event_topic() -> Result<(), Box<dyn Error>> {
+    let transfer: H256 =
+        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse()?;
+    assert_eq!(event_topic("Transfer(address,address,uint256)"), transfer);
+    assert_eq!(event_topic("Transfer(address, address, uint256)"), transfer);
+
+    // matches the topics and selectors computed from a JSON ABI
+    let abi: Abi = serde_json::from_value(serde_json::json!([
+        {
+            "type": "event",
+            "name": "Transfer",
+            "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ],
+            "anonymous": false
+        },
+        {
+            "type": "function",
+            "name": "transfer",
+            "inputs": [{"name": "to", "type": "address"}, {"name": "value", "type": "uint256"}],
+            "outputs": [{"name": "", "type": "bool"}],
+            "stateMutability": "nonpayable"
+        }
+    ]))?;
+    assert_eq!(abi.event("Transfer").unwrap().topic()?, transfer);
+    assert_eq!(
+        abi.function("transfer").unwrap().selector()?,
+        function_selector("transfer(address,uint256)")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_checksums() -> Result<(), Box<dyn Error>> {
+    // test vectors from EIP-55
+    for checksummed in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        "0x52908400098527886E0F7030069857D2E4169EE7",
+        "0xde709f2102306220921060314715629080e2fb77",
+    ] {
+        assert!(is_checksummed(checksummed));
+        assert_eq!(
+            to_checksum_address(&checksummed.to_lowercase())?,
+            checksummed
+        );
+        assert_eq!(
+            to_checksum_address(&checksummed.to_uppercase().replace("0X", "0x"))?,
+            checksummed
+        );
+    }
+    assert!(!is_checksummed(
+        "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+    ));
+    assert!(!is_checksummed(
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"
+    ));
+    assert!(!is_checksummed("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+    assert!(!is_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"));
+    assert!(to_checksum_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea").is_err());
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use ethrs::types::{public_key_to_address, Address};
+use ethrs::wallet::{PublicKey, VanitySearch, Wallet};
+
+use std::error::Error;
+
+const UNCOMPRESSED: &str = "048318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed753547f11ca8696646f2f3acb08e31016afac23e630c5d11f59f61fef57b0d2aa5";
+const COMPRESSED: &str = "038318535b54105d4a7aae60c08fc45f9687181b4fdfc625bd1a753fa7397fed75";
+
+#[test]
+fn test_public_key_to_address() -> Result<(), Box<dyn Error>> {
+    let address: Address = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?;
+    let uncompressed = hex::decode(UNCOMPRESSED)?;
+    assert_eq!(public_key_to_address(&uncompressed)?, address);
+    assert_eq!(public_key_to_address(&uncompressed[1..])?, address);
+    assert_eq!(public_key_to_address(&hex::decode(COMPRESSED)?)?, address);
+    // not a point on the curve
+    let mut invalid = hex::decode(COMPRESSED)?;
+    invalid[32] ^= 1;
+    assert!(public_key_to_address(&invalid).is_err());
+    assert!(public_key_to_address(&[4u8; 20]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_public_key() -> Result<(), Box<dyn Error>> {
+    let key: PublicKey = COMPRESSED.parse()?;
+    assert_eq!(hex::encode(key.to_uncompressed()), UNCOMPRESSED);
+    assert_eq!(key, format!("0x{UNCOMPRESSED}").parse()?);
+    assert_eq!(key.to_string(), format!("0x{COMPRESSED}"));
+    assert_eq!(
+        key.address().to_string(),
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_wallet() -> Result<(), Box<dyn Error>> {
+    let private_key = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let wallet: Wallet = private_key.parse()?;
+    assert_eq!(hex::encode(wallet.private_key()), private_key);
+    assert_eq!(hex::encode(wallet.public_key().to_compressed()), COMPRESSED);
+    assert!(!format!("{wallet:?}").contains(private_key));
+    assert!(Wallet::from_private_key(&[0u8; 32]).is_err());
+    assert!("0x1234".parse::<Wallet>().is_err());
+    assert_ne!(Wallet::random().address(), Wallet::random().address());
+    Ok(())
+}
+
+#[test]
+fn test_vanity_search() -> Result<(), Box<dyn Error>> {
+    let wallet = VanitySearch::new()
+        .with_prefix("0xa")
+        .with_suffix("b")
+        .with_threads(2)
+        .run()?
+        .unwrap();
+    let hex = format!("{:x}", wallet.address());
+    assert!(hex.starts_with('a') && hex.ends_with('b'));
+
+    let search = VanitySearch::new().with_prefix("A");
+    let wallet = search.run()?.unwrap();
+    assert!(wallet.address().to_string().starts_with("0xA"));
+    assert!(search.matches(&wallet.address()));
+
+    assert!(VanitySearch::new()
+        .with_prefix("0000000000")
+        .with_max_attempts(100)
+        .run()?
+        .is_none());
+    assert!(VanitySearch::new().with_prefix("xyz").run().is_err());
+    Ok(())
+}
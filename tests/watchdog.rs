@@ -0,0 +1,78 @@
+mod common;
+
+use common::{block, serve_with};
+use ethrs::provider::Provider;
+use ethrs::watchdog::{expected_block_time, Health, Watchdog};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn serve_head(age: u64) -> String {
+    serve_with(move |method, _| match method {
+        "eth_chainId" => json!("0xaa36a7"),
+        "eth_getBlockByNumber" => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            block(100, now.as_secs() - age)
+        }
+        _ => panic!("unexpected method {method}"),
+    })
+}
+
+#[test]
+fn test_expected_block_time() {
+    assert_eq!(expected_block_time(1), Some(Duration::from_secs(12)));
+    assert_eq!(expected_block_time(10), Some(Duration::from_secs(2)));
+    assert_eq!(expected_block_time(31337), None);
+}
+
+#[test]
+fn test_watchdog_failover() {
+    // the API keys in the endpoint URLs are redacted before reaching the callbacks
+    let stale = format!("{}/v3/0123456789abcdef0123456789abcdef", serve_head(600));
+    let fresh = format!("{}/v3/fedcba9876543210fedcba9876543210", serve_head(0));
+    let redact = |url: &str| url.replace(&url[url.len() - 32..], "REDACTED");
+    let alarms = Arc::new(Mutex::new(vec![]));
+    let failovers = Arc::new(Mutex::new(vec![]));
+    let (alarms_, failovers_) = (alarms.clone(), failovers.clone());
+    let mut watchdog = Watchdog::for_chain(Provider::new(&stale))
+        .unwrap()
+        .with_fallback(Provider::new(&fresh))
+        .on_alarm(move |url, health| {
+            alarms_
+                .lock()
+                .unwrap()
+                .push((url.to_owned(), health.clone()))
+        })
+        .on_failover(move |from, to| {
+            failovers_
+                .lock()
+                .unwrap()
+                .push((from.to_owned(), to.to_owned()))
+        });
+    assert_eq!(watchdog.max_lag(), Duration::from_secs(60));
+
+    match watchdog.check() {
+        Health::Lagging(head) => {
+            assert_eq!(head.number, 100);
+            assert!(head.age >= Duration::from_secs(600));
+        }
+        health => panic!("unexpected health {health:?}"),
+    }
+    assert_eq!(watchdog.provider().url(), fresh);
+    assert_eq!(alarms.lock().unwrap()[0].0, redact(&stale));
+    assert_eq!(
+        *failovers.lock().unwrap(),
+        vec![(redact(&stale), redact(&fresh))]
+    );
+
+    assert!(watchdog.check().is_healthy());
+    assert_eq!(watchdog.provider().url(), fresh);
+    assert_eq!(alarms.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_watchdog_unreachable() {
+    let mut watchdog = Watchdog::new(Provider::new("http://127.0.0.1:1"), Duration::from_secs(60));
+    assert!(matches!(watchdog.check(), Health::Unreachable(_)));
+    assert_eq!(watchdog.provider().url(), "http://127.0.0.1:1");
+}